@@ -174,6 +174,13 @@ impl Message for SetFinished {
     type Result = Result<(), ()>;
 }
 
+/// Persist the database, resolving once it has been written
+pub struct PersistDrDatabase;
+
+impl Message for PersistDrDatabase {
+    type Result = ();
+}
+
 impl Handler<SetDrInfoBridge> for DrDatabase {
     type Result = ();
 
@@ -183,7 +190,7 @@ impl Handler<SetDrInfoBridge> for DrDatabase {
         self.dr.insert(dr_id, dr_info);
 
         self.max_dr_id = cmp::max(self.max_dr_id, dr_id);
-        log::debug!("Data request #{} inserted with state {}", dr_id, dr_state);
+        dr_log!(debug, dr_id, "inserted with state {}", dr_state);
 
         // Persist Data Request Database
         ctx.spawn(self.persist().into_actor(self));
@@ -239,6 +246,14 @@ impl Handler<GetLastDrId> for DrDatabase {
     }
 }
 
+impl Handler<PersistDrDatabase> for DrDatabase {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, _msg: PersistDrDatabase, _ctx: &mut Self::Context) -> Self::Result {
+        Box::pin(self.persist())
+    }
+}
+
 impl Handler<SetFinished> for DrDatabase {
     type Result = Result<(), ()>;
 
@@ -247,11 +262,7 @@ impl Handler<SetFinished> for DrDatabase {
         match self.dr.entry(dr_id) {
             Entry::Occupied(entry) => {
                 entry.into_mut().dr_state = DrState::Finished;
-                log::debug!(
-                    "Data request #{} updated to state {}",
-                    dr_id,
-                    DrState::Finished
-                );
+                dr_log!(debug, dr_id, "updated to state {}", DrState::Finished);
             }
             Entry::Vacant(entry) => {
                 entry.insert(DrInfoBridge {
@@ -260,11 +271,7 @@ impl Handler<SetFinished> for DrDatabase {
                     dr_tx_hash: None,
                     dr_tx_creation_timestamp: None,
                 });
-                log::debug!(
-                    "Data request #{} inserted with state {}",
-                    dr_id,
-                    DrState::Finished
-                );
+                dr_log!(debug, dr_id, "inserted with state {}", DrState::Finished);
             }
         }
 
@@ -2,6 +2,8 @@ use crate::{
     actors::dr_database::{DrDatabase, DrId, SetFinished, WitnetQueryStatus},
     config::Config,
     handle_receipt,
+    nonce_manager::NonceManager,
+    shutdown::SHUTDOWN,
 };
 use actix::prelude::*;
 use std::{collections::HashSet, sync::Arc, time::Duration};
@@ -9,7 +11,7 @@ use web3::{
     contract::{self, Contract},
     ethabi::{ethereum_types::H256, Token},
     transports::Http,
-    types::{H160, U256},
+    types::{BlockNumber, H160, U256},
     Web3,
 };
 use witnet_data_structures::{chain::Hash, radon_error::RadonErrors};
@@ -38,6 +40,12 @@ pub struct DrReporter {
     /// Max ratio between the gas price recommended by the provider and the gas price of the requests in the WRB
     /// That is, the bridge will refrain from paying more than these times the gas price originally set forth by the requesters.
     pub report_result_max_network_gas_price_ratio: f64,
+    /// Minimum gas price of the reportResult transactions, in wei
+    pub min_gas_price: Option<U256>,
+    /// Maximum gas price of the reportResult transactions, in wei
+    pub max_gas_price: Option<U256>,
+    /// Assigns the nonces of the reportResult transactions, which may be sent concurrently
+    pub nonce_manager: NonceManager,
 }
 
 impl Drop for DrReporter {
@@ -82,10 +90,36 @@ impl DrReporter {
             num_confirmations: config.num_confirmations,
             report_result_max_network_gas_price_ratio: config
                 .report_result_max_network_gas_price_ratio,
+            min_gas_price: config.min_gas_price.map(Into::into),
+            max_gas_price: config.max_gas_price.map(Into::into),
+            nonce_manager: Default::default(),
         }
     }
 }
 
+impl DrReporter {
+    /// Remove all the reports whose reporting transaction is still pending, and mark the remaining
+    /// ones as pending. A data request that appears more than once in `reports` is only kept once,
+    /// so it can never be reported twice at the same time.
+    fn retain_new_reports(&mut self, reports: &mut Vec<Report>) {
+        let pending_report_result = &mut self.pending_report_result;
+        reports.retain(|report| {
+            if pending_report_result.insert(report.dr_id) {
+                true
+            } else {
+                // Timeout is not over yet, no action is needed
+                dr_log!(
+                    debug,
+                    report.dr_id,
+                    "is already being resolved, ignoring DrReporterMsg"
+                );
+
+                false
+            }
+        });
+    }
+}
+
 /// Report the results of these data requests to Ethereum
 pub struct DrReporterMsg {
     /// Reports
@@ -112,20 +146,14 @@ impl Handler<DrReporterMsg> for DrReporter {
     type Result = ();
 
     fn handle(&mut self, mut msg: DrReporterMsg, ctx: &mut Self::Context) -> Self::Result {
-        // Remove all reports that have already been reported, but whose reporting transaction is still pending
-        msg.reports.retain(|report| {
-            if self.pending_report_result.contains(&report.dr_id) {
-                // Timeout is not over yet, no action is needed
-                log::debug!(
-                    "Request [{}] is already being resolved, ignoring DrReporterMsg",
-                    report.dr_id
-                );
-
-                false
-            } else {
-                true
+        let in_flight = match SHUTDOWN.start_operation() {
+            Some(in_flight) => in_flight,
+            None => {
+                log::debug!("The bridge is shutting down, ignoring DrReporterMsg");
+                return;
             }
-        });
+        };
+        self.retain_new_reports(&mut msg.reports);
 
         if msg.reports.is_empty() {
             // Nothing to report
@@ -147,16 +175,16 @@ impl Handler<DrReporterMsg> for DrReporter {
             }
         }
 
-        // New request or timeout elapsed, save dr_id
-        for report in &msg.reports {
-            self.pending_report_result.insert(report.dr_id);
-        }
-
         let eth = self.web3.as_ref().unwrap().eth();
         let report_result_max_network_gas_price_ratio =
             self.report_result_max_network_gas_price_ratio;
+        let min_gas_price = self.min_gas_price;
+        let max_gas_price = self.max_gas_price;
+        let nonce_manager = self.nonce_manager.clone();
 
         let fut = async move {
+            // Keep the reportResult transactions in flight until they are confirmed or time out
+            let _in_flight = in_flight;
             // Check if the request has already been resolved by some old pending transaction
             // that got confirmed after the eth_confirmation_timeout has elapsed
             let mut reports = vec![];
@@ -216,6 +244,15 @@ impl Handler<DrReporterMsg> for DrReporter {
                 log::warn!("Network gas price is {}x higher than request's gas price. Capping report gas price to {}", ratio, max_report_gas_price);
                 report_gas_price = max_report_gas_price;
             }
+            let clamped_gas_price = clamp_gas_price(report_gas_price, min_gas_price, max_gas_price);
+            if clamped_gas_price != report_gas_price {
+                log::debug!(
+                    "Report gas price {} is out of the configured bounds. Setting report gas price to {}",
+                    report_gas_price,
+                    clamped_gas_price
+                );
+                report_gas_price = clamped_gas_price;
+            }
 
             let batch_results: Vec<_> = msg
                 .reports
@@ -257,6 +294,20 @@ impl Handler<DrReporterMsg> for DrReporter {
                 } else {
                     log::debug!("Executing reportResult {:?}", batch_results);
                 }
+                let nonce = match nonce_manager
+                    .next_nonce(|| eth.transaction_count(eth_account, Some(BlockNumber::Pending)))
+                    .await
+                {
+                    Ok(nonce) => nonce,
+                    Err(e) => {
+                        log::error!(
+                            "Error fetching the nonce of the reportResult transaction: {}",
+                            e
+                        );
+
+                        break;
+                    }
+                };
                 let params_str;
                 let only_1_batch = batch_results.len() == 1;
                 let receipt = if only_1_batch {
@@ -274,6 +325,7 @@ impl Handler<DrReporterMsg> for DrReporter {
                         contract::Options::with(|opt| {
                             opt.gas = Some(estimated_gas_limit);
                             opt.gas_price = Some(report_gas_price);
+                            opt.nonce = Some(nonce);
                         }),
                         num_confirmations,
                     );
@@ -288,6 +340,7 @@ impl Handler<DrReporterMsg> for DrReporter {
                         contract::Options::with(|opt| {
                             opt.gas = Some(estimated_gas_limit);
                             opt.gas_price = Some(report_gas_price);
+                            opt.nonce = Some(nonce);
                         }),
                         num_confirmations,
                     );
@@ -327,10 +380,15 @@ impl Handler<DrReporterMsg> for DrReporter {
                     Ok(Err(e)) => {
                         // Error in call_with_confirmations
                         log::error!("{}: {:?}", params_str, e);
+                        // The nonce may not have been used, fetch it again for the next transaction
+                        nonce_manager.resync().await;
                     }
                     Err(_e) => {
                         // Timeout is over
                         log::warn!("{}: timeout is over", params_str);
+                        // The transaction may have been dropped, fetch the nonce again for the
+                        // next transaction
+                        nonce_manager.resync().await;
                     }
                 }
             }
@@ -362,16 +420,16 @@ async fn read_resolved_request_from_contract(
 
     match query_status {
         Ok(status) => match WitnetQueryStatus::from_code(status) {
-            WitnetQueryStatus::Unknown => log::debug!("[{}] does not exist, skipping", dr_id),
+            WitnetQueryStatus::Unknown => dr_log!(debug, dr_id, "does not exist, skipping"),
             WitnetQueryStatus::Posted => {
-                log::debug!("[{}] has not got a result yet, skipping", dr_id)
+                dr_log!(debug, dr_id, "has not got a result yet, skipping")
             }
             WitnetQueryStatus::Reported => {
-                log::debug!("[{}] already reported", dr_id);
+                dr_log!(debug, dr_id, "already reported");
                 return Some(SetFinished { dr_id });
             }
             WitnetQueryStatus::Deleted => {
-                log::debug!("[{}] already reported and deleted", dr_id);
+                dr_log!(debug, dr_id, "already reported and deleted");
                 return Some(SetFinished { dr_id });
             }
         },
@@ -409,7 +467,7 @@ async fn get_max_gas_price(msg: &DrReporterMsg, wrb_contract: &Contract<Http>) -
                 }
             }
             Err(e) => {
-                log::error!("[{}] ReadGasPrice {:?}", report.dr_id, e);
+                dr_log!(error, report.dr_id, "ReadGasPrice {:?}", e);
                 continue;
             }
         }
@@ -514,6 +572,20 @@ fn parse_posted_result_event(
     }
 }
 
+/// Returns `estimated` clamped between the optional `floor` and `cap`. The floor takes precedence
+/// if it is higher than the cap, although `Config::validate` rejects that case.
+fn clamp_gas_price(estimated: U256, floor: Option<U256>, cap: Option<U256>) -> U256 {
+    let gas_price = match cap {
+        Some(cap) => std::cmp::min(estimated, cap),
+        None => estimated,
+    };
+
+    match floor {
+        Some(floor) => std::cmp::max(gas_price, floor),
+        None => gas_price,
+    }
+}
+
 /// Returns `a / b`, as f64
 fn u256_div_as_f64(a: U256, b: U256) -> f64 {
     u256_to_f64(a) / u256_to_f64(b)
@@ -525,7 +597,7 @@ fn u256_to_f64(a: U256) -> f64 {
 }
 
 /// Returns `a * b` as U256, saturating on overflow
-fn u256_saturating_mul_f64(a: U256, b: f64) -> U256 {
+pub(crate) fn u256_saturating_mul_f64(a: U256, b: f64) -> U256 {
     assert!(
         b >= 0.0,
         "u256_mul_f64 only supports positive floating point values, got {}",
@@ -575,6 +647,72 @@ mod tests {
     use crate::hack_fix_functions_with_multiple_definitions;
     use web3::contract::tokens::Tokenize;
 
+    fn example_report(dr_id: u64) -> Report {
+        Report {
+            dr_id: DrId::from(dr_id),
+            timestamp: 0,
+            dr_tx_hash: Hash::default(),
+            result: vec![],
+        }
+    }
+
+    #[test]
+    fn overlapping_reports_are_only_sent_once() {
+        let mut dr_reporter = DrReporter::default();
+
+        // The first message marks the data request as pending
+        let mut reports = vec![example_report(1)];
+        dr_reporter.retain_new_reports(&mut reports);
+        assert_eq!(reports.len(), 1);
+
+        // A message arriving while the first one is still being reported is ignored
+        let mut reports = vec![example_report(1), example_report(2)];
+        dr_reporter.retain_new_reports(&mut reports);
+        let dr_ids: Vec<_> = reports.iter().map(|report| report.dr_id).collect();
+        assert_eq!(dr_ids, vec![DrId::from(2)]);
+
+        // Once the report finishes, the data request can be reported again
+        dr_reporter.pending_report_result.remove(&DrId::from(1));
+        let mut reports = vec![example_report(1)];
+        dr_reporter.retain_new_reports(&mut reports);
+        assert_eq!(reports.len(), 1);
+    }
+
+    #[test]
+    fn duplicated_reports_in_one_message_are_only_sent_once() {
+        let mut dr_reporter = DrReporter::default();
+
+        let mut reports = vec![example_report(3), example_report(3)];
+        dr_reporter.retain_new_reports(&mut reports);
+        assert_eq!(reports.len(), 1);
+    }
+
+    #[test]
+    fn gas_price_is_clamped_between_floor_and_cap() {
+        let floor = Some(U256::from(10));
+        let cap = Some(U256::from(100));
+
+        assert_eq!(clamp_gas_price(U256::from(5), floor, cap), U256::from(10));
+        assert_eq!(clamp_gas_price(U256::from(10), floor, cap), U256::from(10));
+        assert_eq!(clamp_gas_price(U256::from(50), floor, cap), U256::from(50));
+        assert_eq!(
+            clamp_gas_price(U256::from(100), floor, cap),
+            U256::from(100)
+        );
+        assert_eq!(
+            clamp_gas_price(U256::from(500), floor, cap),
+            U256::from(100)
+        );
+
+        // Without bounds, the estimated gas price is used as is
+        assert_eq!(clamp_gas_price(U256::from(5), None, cap), U256::from(5));
+        assert_eq!(
+            clamp_gas_price(U256::from(500), floor, None),
+            U256::from(500)
+        );
+        assert_eq!(clamp_gas_price(U256::MAX, None, None), U256::MAX);
+    }
+
     #[test]
     fn report_result_type_check() {
         let wrb_contract_abi_json: &[u8] = include_bytes!("../../wrb_abi.json");
@@ -4,6 +4,8 @@ use crate::{
         dr_reporter::{DrReporter, DrReporterMsg, Report},
     },
     config::Config,
+    logging::DrTag,
+    shutdown::SHUTDOWN,
 };
 use actix::prelude::*;
 use serde_json::json;
@@ -79,11 +81,20 @@ impl DrSender {
     }
 
     fn check_new_drs(&self, ctx: &mut Context<Self>, period: Duration) {
+        let in_flight = match SHUTDOWN.start_operation() {
+            Some(in_flight) => in_flight,
+            None => {
+                log::debug!("DrSender stopped: the bridge is shutting down");
+                return;
+            }
+        };
         let witnet_client = self.witnet_client.clone().unwrap();
         let max_dr_value_nanowits = self.max_dr_value_nanowits;
         let dr_fee_nanowits = self.dr_fee_nanowits;
 
         let fut = async move {
+            // Keep the operation in flight until the future finishes
+            let _in_flight = in_flight;
             let dr_database_addr = DrDatabase::from_registry();
             let dr_reporter_addr = DrReporter::from_registry();
 
@@ -126,16 +137,17 @@ impl DrSender {
                                     }
                                     Err(e) => {
                                         // Unexpected error deserializing hash
-                                        panic!("[{}] error deserializing dr_tx: {}", dr_id, e);
+                                        panic!("{} error deserializing dr_tx: {}", DrTag(dr_id), e);
                                     }
                                 }
                             }
                             Err(e) => {
                                 // Error sending transaction: node not synced, not enough balance, etc.
                                 // Do nothing, will retry later.
-                                log::error!(
-                                    "[{}] error creating data request transaction: {}",
+                                dr_log!(
+                                    error,
                                     dr_id,
+                                    "error creating data request transaction: {}",
                                     e
                                 );
                                 continue;
@@ -145,7 +157,7 @@ impl DrSender {
                     Err(err) => {
                         // Error deserializing or validating data request: mark data request as
                         // error and report error as result to ethereum.
-                        log::error!("[{}] error: {}", dr_id, err);
+                        dr_log!(error, dr_id, "error: {}", err);
                         let result = err.encode_cbor();
                         // In this case there is no data request transaction, so the dr_tx_hash
                         // field can be set to anything.
@@ -1,8 +1,10 @@
 use crate::{
-    actors::dr_database::{
-        DrDatabase, DrInfoBridge, GetLastDrId, SetDrInfoBridge, WitnetQueryStatus,
+    actors::{
+        dr_database::{DrDatabase, DrInfoBridge, GetLastDrId, SetDrInfoBridge, WitnetQueryStatus},
+        dr_reporter::u256_saturating_mul_f64,
     },
     config::Config,
+    shutdown::SHUTDOWN,
 };
 use actix::prelude::*;
 use std::{convert::TryFrom, sync::Arc, time::Duration};
@@ -24,6 +26,11 @@ pub struct EthPoller {
     pub eth_new_dr_polling_rate_ms: u64,
     /// Skip first requests up to index n when updating database
     pub skip_first: u64,
+    /// If set, skip data requests whose reward does not cover the estimated cost of reporting
+    /// their result plus this margin
+    pub min_report_profit_margin: Option<f64>,
+    /// Gas that is expected to be spent when reporting the result of one data request
+    pub report_result_estimated_gas: u64,
 }
 
 impl Drop for EthPoller {
@@ -62,16 +69,29 @@ impl EthPoller {
             wrb_contract: Some(wrb_contract),
             eth_new_dr_polling_rate_ms: config.eth_new_dr_polling_rate_ms,
             skip_first: config.skip_first.unwrap_or(0),
+            min_report_profit_margin: config.min_report_profit_margin,
+            report_result_estimated_gas: config.report_result_estimated_gas,
         }
     }
 
     fn check_new_requests_from_ethereum(&self, ctx: &mut Context<Self>, period: Duration) {
+        let in_flight = match SHUTDOWN.start_operation() {
+            Some(in_flight) => in_flight,
+            None => {
+                log::debug!("EthPoller stopped: the bridge is shutting down");
+                return;
+            }
+        };
         log::debug!("Checking new DRs from Ethereum contract...");
 
         let wrb_contract = self.wrb_contract.clone().unwrap();
         let skip_first = U256::from(self.skip_first);
+        let min_report_profit_margin = self.min_report_profit_margin;
+        let report_result_estimated_gas = self.report_result_estimated_gas;
         // Check requests
         let fut = async move {
+            // Keep the operation in flight until the future finishes
+            let _in_flight = in_flight;
             let total_requests_count: Result<U256, web3::contract::Error> = wrb_contract
                 .query(
                     "getNextQueryId",
@@ -108,7 +128,7 @@ impl EthPoller {
                     let last_index = usize::try_from(total_requests_count).unwrap();
 
                     for i in init_index..last_index {
-                        log::debug!("[{}] checking dr in wrb", i);
+                        dr_log!(debug, i, "checking dr in wrb");
 
                         let query_status: Result<u8, web3::contract::Error> = wrb_contract
                             .query(
@@ -123,10 +143,32 @@ impl EthPoller {
                         match query_status {
                             Ok(status) => match WitnetQueryStatus::from_code(status) {
                                 WitnetQueryStatus::Unknown => {
-                                    log::debug!("[{}] has not exist, skipping", i)
+                                    dr_log!(debug, i, "has not exist, skipping")
                                 }
                                 WitnetQueryStatus::Posted => {
-                                    log::info!("[{}] new dr in wrb", i);
+                                    dr_log!(info, i, "new dr in wrb");
+                                    if let Some(margin) = min_report_profit_margin {
+                                        match check_profitability(
+                                            i.into(),
+                                            &wrb_contract,
+                                            report_result_estimated_gas,
+                                            margin,
+                                        )
+                                        .await
+                                        {
+                                            Ok(true) => {}
+                                            Ok(false) => continue,
+                                            Err(err) => {
+                                                dr_log!(
+                                                    error,
+                                                    i,
+                                                    "fail to estimate profitability: {:?}",
+                                                    err.to_string()
+                                                );
+                                                break;
+                                            }
+                                        }
+                                    }
                                     if let Ok(set_dr_info_bridge) =
                                         process_posted_request(i.into(), &wrb_contract).await
                                     {
@@ -136,7 +178,7 @@ impl EthPoller {
                                     }
                                 }
                                 WitnetQueryStatus::Reported => {
-                                    log::debug!("[{}] already reported", i);
+                                    dr_log!(debug, i, "already reported");
                                     if let Ok(set_dr_info_bridge) =
                                         process_posted_request(i.into(), &wrb_contract).await
                                     {
@@ -146,7 +188,7 @@ impl EthPoller {
                                     }
                                 }
                                 WitnetQueryStatus::Deleted => {
-                                    log::debug!("[{}] has been deleted, skipping", i)
+                                    dr_log!(debug, i, "has been deleted, skipping")
                                 }
                             },
                             Err(err) => {
@@ -219,3 +261,81 @@ async fn process_posted_request(
         },
     ))
 }
+
+/// Auxiliary function that checks whether the reward of a posted request covers the estimated cost
+/// of reporting its result
+async fn check_profitability(
+    query_id: U256,
+    wrb_contract: &Contract<web3::transports::Http>,
+    report_result_estimated_gas: u64,
+    margin: f64,
+) -> Result<bool, web3::contract::Error> {
+    let reward: U256 = wrb_contract
+        .query(
+            "readRequestReward",
+            (query_id,),
+            None,
+            contract::Options::default(),
+            None,
+        )
+        .await?;
+    let gas_price: U256 = wrb_contract
+        .query(
+            "readRequestGasPrice",
+            (query_id,),
+            None,
+            contract::Options::default(),
+            None,
+        )
+        .await?;
+
+    let profitable = is_profitable(reward, gas_price, report_result_estimated_gas, margin);
+    if !profitable {
+        dr_log!(
+            info,
+            query_id,
+            "skipping unprofitable dr: reward {} does not cover the estimated cost of {} gas at gas price {} plus a margin of {}",
+            reward,
+            report_result_estimated_gas,
+            gas_price,
+            margin
+        );
+    }
+
+    Ok(profitable)
+}
+
+/// Returns true if `reward` is at least the cost of spending `gas` at `gas_price`, increased by
+/// `margin`
+fn is_profitable(reward: U256, gas_price: U256, gas: u64, margin: f64) -> bool {
+    let cost = gas_price.saturating_mul(U256::from(gas));
+    let min_reward = u256_saturating_mul_f64(cost, (1.0 + margin).max(0.0));
+
+    reward >= min_reward
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_reward_dr_is_skipped() {
+        // 100_000 gas at 10 gwei cost 0.001 ETH, and the reward is just below that
+        let gas_price = U256::from(10_000_000_000u64);
+        let reward = U256::from(999_999_999_999_999u64);
+
+        assert!(!is_profitable(reward, gas_price, 100_000, 0.0));
+    }
+
+    #[test]
+    fn profitable_dr_proceeds() {
+        // 100_000 gas at 10 gwei cost 0.001 ETH, and the reward is 0.0015 ETH
+        let gas_price = U256::from(10_000_000_000u64);
+        let reward = U256::from(1_500_000_000_000_000u64);
+
+        assert!(is_profitable(reward, gas_price, 100_000, 0.0));
+        assert!(is_profitable(reward, gas_price, 100_000, 0.5));
+        // Requiring a 60% margin makes this data request unprofitable
+        assert!(!is_profitable(reward, gas_price, 100_000, 0.6));
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
         dr_reporter::{DrReporter, DrReporterMsg, Report},
     },
     config::Config,
+    shutdown::SHUTDOWN,
 };
 use actix::prelude::*;
 use serde_json::json;
@@ -65,10 +66,19 @@ impl WitPoller {
     }
 
     fn check_tally_pending_drs(&self, ctx: &mut Context<Self>, period: Duration) {
+        let in_flight = match SHUTDOWN.start_operation() {
+            Some(in_flight) => in_flight,
+            None => {
+                log::debug!("WitPoller stopped: the bridge is shutting down");
+                return;
+            }
+        };
         let witnet_client = self.witnet_client.clone().unwrap();
         let dr_tx_unresolved_timeout_ms = self.dr_tx_unresolved_timeout_ms;
 
         let fut = async move {
+            // Keep the operation in flight until the future finishes
+            let _in_flight = in_flight;
             let dr_database_addr = DrDatabase::from_registry();
             let dr_reporter_addr = DrReporter::from_registry();
             let pending_drs = dr_database_addr
@@ -99,11 +109,7 @@ impl WitPoller {
                     Ok(report) => report,
 
                     Err(e) => {
-                        log::debug!(
-                            "[{}] dataRequestReport call error: {}",
-                            dr_id,
-                            e.to_string()
-                        );
+                        dr_log!(debug, dr_id, "dataRequestReport call error: {}", e);
 
                         if let Some(dr_timeout_ms) = dr_tx_unresolved_timeout_ms {
                             // In case of error, if the data request has been unresolved for more than
@@ -111,7 +117,12 @@ impl WitPoller {
                             if (current_timestamp - dr_tx_creation_timestamp)
                                 > i64::try_from(dr_timeout_ms / 1000).unwrap()
                             {
-                                log::debug!("[{}] has been unresolved after more than {} ms, setting to New", dr_id, dr_timeout_ms);
+                                dr_log!(
+                                    debug,
+                                    dr_id,
+                                    "has been unresolved after more than {} ms, setting to New",
+                                    dr_timeout_ms
+                                );
                                 dr_database_addr
                                     .send(SetDrInfoBridge(
                                         dr_id,
@@ -136,9 +147,10 @@ impl WitPoller {
                         block_hash_dr_tx: Some(dr_block_hash),
                         ..
                     })) => {
-                        log::info!(
-                            "[{}] Found possible tally to be reported for dr_tx_hash {}",
+                        dr_log!(
+                            info,
                             dr_id,
+                            "Found possible tally to be reported for dr_tx_hash {}",
                             dr_tx_hash
                         );
 
@@ -163,11 +175,11 @@ impl WitPoller {
                     }
                     Ok(..) => {
                         // No problem, this means the data request has not been resolved yet
-                        log::debug!("[{}] Data request not resolved yet", dr_id);
+                        dr_log!(debug, dr_id, "Data request not resolved yet");
                         continue;
                     }
                     Err(e) => {
-                        log::error!("[{}] dataRequestReport deserialize error: {:?}", dr_id, e);
+                        dr_log!(error, dr_id, "dataRequestReport deserialize error: {:?}", e);
                         continue;
                     }
                 };
@@ -19,6 +19,10 @@ pub struct Config {
     pub eth_client_url: String,
     /// Address of the WitnetRequestsBoard deployed contract
     pub wrb_contract_addr: H160,
+    /// Path to a JSON file with the ABI of the WitnetRequestsBoard contract. If missing, or if the
+    /// file does not exist, the ABI embedded in the bridge at compile time is used
+    #[serde(default)]
+    pub wrb_contract_abi_path: Option<PathBuf>,
     /// Address of a Request example deployed contract
     pub request_example_contract_addr: H160,
     /// Ethereum account used to create the transactions
@@ -56,6 +60,63 @@ pub struct Config {
     /// That is, the bridge will refrain from paying more than these times the gas price originally set forth by the requesters.
     #[serde(default = "one_f64")]
     pub report_result_max_network_gas_price_ratio: f64,
+    /// Minimum gas price (in wei) of the reportResult transactions, so that they do not get stuck
+    /// during congestion. If missing, there is no floor
+    #[serde(default)]
+    pub min_gas_price: Option<u64>,
+    /// Maximum gas price (in wei) of the reportResult transactions, so that the bridge does not
+    /// overpay during gas price spikes. If missing, there is no cap
+    #[serde(default)]
+    pub max_gas_price: Option<u64>,
+    /// If set, the bridge will not relay data requests whose reward is lower than the estimated
+    /// gas cost of reporting their result, increased by this margin. For example, 0.1 requires the
+    /// reward to be at least 10% higher than the cost. If missing, all data requests are relayed.
+    #[serde(default)]
+    pub min_report_profit_margin: Option<f64>,
+    /// Gas that is expected to be spent when reporting the result of one data request. Only used to
+    /// estimate the profitability of data requests.
+    #[serde(default = "default_report_result_estimated_gas")]
+    pub report_result_estimated_gas: u64,
+    /// Max time to wait for the in-flight operations to finish when the bridge is shutting down
+    #[serde(default = "default_shutdown_grace_period_ms")]
+    pub shutdown_grace_period_ms: u64,
+}
+
+impl Config {
+    /// Check that the values of the configuration can be used by the bridge.
+    ///
+    /// The polling rates are the periods of the loops of the bridge actors, so they cannot be zero.
+    /// The minimum gas price cannot be higher than the maximum gas price.
+    pub fn validate(&self) -> Result<(), String> {
+        let polling_rates = [
+            (
+                "eth_new_dr_polling_rate_ms",
+                self.eth_new_dr_polling_rate_ms,
+            ),
+            ("wit_tally_polling_rate_ms", self.wit_tally_polling_rate_ms),
+            (
+                "wit_dr_sender_polling_rate_ms",
+                self.wit_dr_sender_polling_rate_ms,
+            ),
+        ];
+        for (name, value) in polling_rates {
+            if value == 0 {
+                return Err(format!("{} must be greater than zero", name));
+            }
+        }
+
+        if let (Some(min_gas_price), Some(max_gas_price)) = (self.min_gas_price, self.max_gas_price)
+        {
+            if min_gas_price > max_gas_price {
+                return Err(format!(
+                    "min_gas_price ({}) cannot be higher than max_gas_price ({})",
+                    min_gas_price, max_gas_price
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn one() -> usize {
@@ -66,6 +127,14 @@ fn one_f64() -> f64 {
     1.0
 }
 
+fn default_report_result_estimated_gas() -> u64 {
+    150_000
+}
+
+fn default_shutdown_grace_period_ms() -> u64 {
+    60_000
+}
+
 /// Gas limits for some methods. If missing, let the client estimate
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -154,6 +223,42 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn polling_rates_read_from_config() {
+        let example = include_str!("../../../witnet_centralized_ethereum_bridge.toml");
+        let mut config: Config = toml::from_str(example).unwrap();
+
+        assert_eq!(config.eth_new_dr_polling_rate_ms, 45_000);
+        assert_eq!(config.wit_tally_polling_rate_ms, 45_000);
+        assert_eq!(config.wit_dr_sender_polling_rate_ms, 45_000);
+        assert_eq!(config.validate(), Ok(()));
+
+        config.wit_dr_sender_polling_rate_ms = 0;
+        assert_eq!(
+            config.validate(),
+            Err("wit_dr_sender_polling_rate_ms must be greater than zero".to_string())
+        );
+    }
+
+    #[test]
+    fn min_gas_price_cannot_exceed_max_gas_price() {
+        let example = include_str!("../../../witnet_centralized_ethereum_bridge.toml");
+        let mut config: Config = toml::from_str(example).unwrap();
+
+        config.min_gas_price = Some(20_000_000_000);
+        config.max_gas_price = Some(20_000_000_000);
+        assert_eq!(config.validate(), Ok(()));
+
+        config.max_gas_price = Some(10_000_000_000);
+        assert_eq!(
+            config.validate(),
+            Err(
+                "min_gas_price (20000000000) cannot be higher than max_gas_price (10000000000)"
+                    .to_string()
+            )
+        );
+    }
+
     #[test]
     fn envy_deserialize_nested_toml() {
         // The envy crate does not support deserializing nested structs, such as the `Gas` struct
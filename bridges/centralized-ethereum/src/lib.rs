@@ -9,7 +9,7 @@
 use async_jsonrpc_client::{transports::tcp::TcpSocket, Transport};
 use futures_util::compat::Compat01As03;
 use serde_json::json;
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
 use web3::{
     contract::Contract,
     transports::Http,
@@ -17,33 +17,111 @@ use web3::{
     Web3,
 };
 
+/// Logging helpers
+#[macro_use]
+pub mod logging;
 /// Actors
 pub mod actors;
 /// Configuration
 pub mod config;
+/// Nonces of the ethereum transactions
+pub mod nonce_manager;
+/// Graceful shutdown
+pub mod shutdown;
+
+/// Functions of the Witnet Request Board contract that are called by the bridge
+const WRB_REQUIRED_FUNCTIONS: &[&str] = &[
+    "getNextQueryId",
+    "getQueryStatus",
+    "readRequestBytecode",
+    "readRequestGasPrice",
+    "readRequestReward",
+    "reportResult",
+    "reportResultBatch",
+];
+
+/// Events of the Witnet Request Board contract that are parsed by the bridge
+const WRB_REQUIRED_EVENTS: &[&str] = &["PostedResult"];
 
 /// Creates a Witnet Request Board contract from Config information
 pub fn create_wrb_contract(
     eth_client_url: &str,
     wrb_contract_addr: H160,
+    wrb_contract_abi_path: Option<&Path>,
 ) -> (Web3<Http>, Contract<Http>) {
     let web3_http = web3::transports::Http::new(eth_client_url)
         .map_err(|e| format!("Failed to connect to Ethereum client.\nError: {:?}", e))
         .unwrap();
     let web3 = web3::Web3::new(web3_http);
-    // Why read files at runtime when you can read files at compile time
-    let wrb_contract_abi_json: &[u8] = include_bytes!("../wrb_abi.json");
-    let mut wrb_contract_abi = web3::ethabi::Contract::load(wrb_contract_abi_json)
-        .map_err(|e| format!("Unable to load WRB contract from ABI: {:?}", e))
-        .unwrap();
+    let wrb_contract_abi = load_wrb_contract_abi(wrb_contract_abi_path).unwrap();
+
+    let wrb_contract = Contract::new(web3.eth(), wrb_contract_addr, wrb_contract_abi);
+
+    (web3, wrb_contract)
+}
+
+/// Loads the ABI of the Witnet Request Board contract.
+///
+/// If `abi_path` points to an existing file, the ABI is read from that file, so upgrading the
+/// contract does not require rebuilding the bridge. Otherwise, the ABI embedded at compile time
+/// is used. In both cases, the ABI must contain all the functions and events used by the bridge.
+pub fn load_wrb_contract_abi(abi_path: Option<&Path>) -> Result<web3::ethabi::Contract, String> {
+    let mut wrb_contract_abi = match abi_path {
+        Some(path) if path.exists() => {
+            log::info!("Loading WRB contract ABI from {}", path.display());
+            let file = std::fs::File::open(path).map_err(|e| {
+                format!(
+                    "Unable to open WRB contract ABI file {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            web3::ethabi::Contract::load(file)
+                .map_err(|e| format!("Unable to load WRB contract from ABI: {:?}", e))?
+        }
+        _ => {
+            if let Some(path) = abi_path {
+                log::warn!(
+                    "WRB contract ABI file {} not found, using the embedded ABI",
+                    path.display()
+                );
+            }
+            // Why read files at runtime when you can read files at compile time
+            let wrb_contract_abi_json: &[u8] = include_bytes!("../wrb_abi.json");
+            web3::ethabi::Contract::load(wrb_contract_abi_json)
+                .map_err(|e| format!("Unable to load WRB contract from ABI: {:?}", e))?
+        }
+    };
+
+    validate_wrb_contract_abi(&wrb_contract_abi)?;
 
     // Fix issue #2046, manually select the desired function when multiple candidates have the same name
     // https://github.com/witnet/witnet-rust/issues/2046
     hack_fix_functions_with_multiple_definitions(&mut wrb_contract_abi);
 
-    let wrb_contract = Contract::new(web3.eth(), wrb_contract_addr, wrb_contract_abi);
+    Ok(wrb_contract_abi)
+}
 
-    (web3, wrb_contract)
+/// Check that the WRB contract ABI contains all the functions and events used by the bridge
+fn validate_wrb_contract_abi(wrb_contract_abi: &web3::ethabi::Contract) -> Result<(), String> {
+    for function_name in WRB_REQUIRED_FUNCTIONS {
+        if !wrb_contract_abi.functions.contains_key(*function_name) {
+            return Err(format!(
+                "WRB contract ABI is missing the {} function",
+                function_name
+            ));
+        }
+    }
+    for event_name in WRB_REQUIRED_EVENTS {
+        if !wrb_contract_abi.events.contains_key(*event_name) {
+            return Err(format!(
+                "WRB contract ABI is missing the {} event",
+                event_name
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 // The web3 library does not properly support overloaded functions yet, so here we ensure that there
@@ -185,4 +263,63 @@ mod tests {
             .unwrap();
         hack_fix_functions_with_multiple_definitions(&mut wrb_contract_abi);
     }
+
+    /// Writes `abi` into a new file in the temporary directory and returns its path
+    fn write_temp_abi(file_name: &str, abi: &serde_json::Value) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{}", std::process::id(), file_name));
+        std::fs::write(&path, abi.to_string()).unwrap();
+
+        path
+    }
+
+    fn embedded_abi_json() -> Vec<serde_json::Value> {
+        serde_json::from_slice(include_bytes!("../wrb_abi.json")).unwrap()
+    }
+
+    #[test]
+    fn runtime_abi_overrides_embedded_abi() {
+        let mut abi = embedded_abi_json();
+        abi.push(json!({
+            "inputs": [],
+            "name": "newMethod",
+            "outputs": [],
+            "stateMutability": "view",
+            "type": "function"
+        }));
+        let path = write_temp_abi("wrb_abi_override.json", &json!(abi));
+
+        let runtime_abi = load_wrb_contract_abi(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(runtime_abi.unwrap().function("newMethod").is_ok());
+        assert!(load_wrb_contract_abi(None)
+            .unwrap()
+            .function("newMethod")
+            .is_err());
+    }
+
+    #[test]
+    fn missing_runtime_abi_falls_back_to_embedded_abi() {
+        let path = std::env::temp_dir().join("this_wrb_abi_does_not_exist.json");
+
+        assert_eq!(
+            load_wrb_contract_abi(Some(&path)).unwrap(),
+            load_wrb_contract_abi(None).unwrap()
+        );
+    }
+
+    #[test]
+    fn runtime_abi_without_required_methods_is_rejected() {
+        let mut abi = embedded_abi_json();
+        abi.retain(|item| item["name"] != "readRequestReward");
+        let path = write_temp_abi("wrb_abi_missing_method.json", &json!(abi));
+
+        let runtime_abi = load_wrb_contract_abi(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            runtime_abi.unwrap_err(),
+            "WRB contract ABI is missing the readRequestReward function"
+        );
+    }
 }
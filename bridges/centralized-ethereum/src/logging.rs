@@ -0,0 +1,91 @@
+//! Helpers for tagging log messages with the data request they refer to.
+//!
+//! A data request goes through several actors while being claimed, included and resolved. Every
+//! message logged along the way starts with the same `[dr_id=<id>]` tag, so that the whole
+//! lifecycle of a data request can be followed by filtering the logs by that tag.
+
+use std::fmt;
+
+use crate::actors::dr_database::DrId;
+
+/// Tag identifying the data request that a log message refers to. It is displayed as
+/// `[dr_id=<id>]`, with the id written in decimal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DrTag(pub DrId);
+
+impl fmt::Display for DrTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[dr_id={}]", self.0)
+    }
+}
+
+/// Log a message tagged with the id of the data request it refers to.
+///
+/// The first argument is the log level macro to use (`error`, `warn`, `info`, `debug` or `trace`)
+/// and the second one is the data request id, which can be anything that converts into a `DrId`.
+/// The rest of arguments are those of the `format!` macro:
+///
+/// ```
+/// # use witnet_centralized_ethereum_bridge::dr_log;
+/// dr_log!(info, 42u64, "new dr in wrb");
+/// // Logs "[dr_id=42] new dr in wrb"
+/// ```
+#[macro_export]
+macro_rules! dr_log {
+    ($level:ident, $dr_id:expr, $($arg:tt)+) => {
+        ::log::$level!("{}", $crate::dr_format_args!($dr_id, $($arg)+))
+    };
+}
+
+/// Format a message tagged with the id of the data request it refers to, as logged by `dr_log!`.
+///
+/// Like `format_args!`, it does not allocate, so the message is only formatted if it is used:
+///
+/// ```
+/// # use witnet_centralized_ethereum_bridge::dr_format_args;
+/// assert_eq!(
+///     format!("{}", dr_format_args!(42u64, "new dr in wrb")),
+///     "[dr_id=42] new dr in wrb"
+/// );
+/// ```
+#[macro_export]
+macro_rules! dr_format_args {
+    ($dr_id:expr, $($arg:tt)+) => {
+        format_args!(
+            "{} {}",
+            $crate::logging::DrTag(::std::convert::Into::into($dr_id)),
+            format_args!($($arg)+)
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dr_tag_display() {
+        assert_eq!(DrTag(DrId::from(0)).to_string(), "[dr_id=0]");
+        assert_eq!(DrTag(DrId::from(1234567)).to_string(), "[dr_id=1234567]");
+    }
+
+    #[test]
+    fn test_dr_log_output_format() {
+        let dr_id = DrId::from(42);
+        assert_eq!(
+            format!("{}", dr_format_args!(dr_id, "new dr in wrb")),
+            "[dr_id=42] new dr in wrb"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                dr_format_args!(
+                    7usize,
+                    "error creating data request transaction: {}",
+                    "timeout"
+                )
+            ),
+            "[dr_id=7] error creating data request transaction: timeout"
+        );
+    }
+}
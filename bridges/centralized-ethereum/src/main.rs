@@ -1,7 +1,7 @@
 //! Witnet <> Ethereum bridge
 
-use actix::{Actor, System, SystemRegistry};
-use std::{path::PathBuf, process::exit, sync::Arc};
+use actix::{Actor, Addr, System, SystemRegistry};
+use std::{path::PathBuf, process::exit, sync::Arc, time::Duration};
 use structopt::StructOpt;
 
 use web3::{contract, types::U256};
@@ -11,6 +11,7 @@ use witnet_centralized_ethereum_bridge::{
         eth_poller::EthPoller, wit_poller::WitPoller,
     },
     check_ethereum_node_running, check_witnet_node_running, config, create_wrb_contract,
+    shutdown::{graceful_shutdown, SHUTDOWN},
 };
 use witnet_config::config::Config as NodeConfig;
 use witnet_net::client::tcp::JsonRpcClient;
@@ -46,8 +47,11 @@ fn init_logger() {
 
 async fn post_example_dr(config: Arc<config::Config>) {
     log::info!("Posting an example of Data Request");
-    let (_web3, wrb_contract) =
-        create_wrb_contract(&config.eth_client_url, config.wrb_contract_addr);
+    let (_web3, wrb_contract) = create_wrb_contract(
+        &config.eth_client_url,
+        config.wrb_contract_addr,
+        config.wrb_contract_abi_path.as_deref(),
+    );
 
     log::info!("calling postDataRequest");
 
@@ -71,10 +75,18 @@ async fn post_example_dr(config: Arc<config::Config>) {
 fn main() {
     init_logger();
 
-    if let Err(err) = run(|| {
-        // FIXME(#72): decide what to do when interrupt signals are received
+    if let Err(err) = run(|grace_period, dr_database| {
+        let system = System::current();
         ctrlc::set_handler(move || {
-            close();
+            if SHUTDOWN.request() {
+                log::info!("Shutting down the bridge, send the signal again to exit immediately");
+                system
+                    .arbiter()
+                    .spawn(graceful_shutdown(grace_period, dr_database.clone()));
+            } else {
+                log::warn!("Exiting without waiting for the in-flight operations");
+                exit(1);
+            }
         })
         .expect("Error setting handler for both SIGINT (Ctrl+C) and SIGTERM (kill)");
     }) {
@@ -84,7 +96,7 @@ fn main() {
 }
 
 /// Function to run the main system
-fn run(callback: fn()) -> Result<(), String> {
+fn run(callback: fn(Duration, Option<Addr<DrDatabase>>)) -> Result<(), String> {
     let app = App::from_args();
     let config = if app.env {
         config::from_env()
@@ -98,19 +110,22 @@ fn run(callback: fn()) -> Result<(), String> {
         .map(Arc::new)
         .map_err(|e| format!("Error reading configuration file: {}", e))?
     };
+    config
+        .validate()
+        .map_err(|e| format!("Invalid configuration: {}", e))?;
 
     // Init system
     let system = System::new();
     let condition = app.post_dr;
+    let shutdown_grace_period = Duration::from_millis(config.shutdown_grace_period_ms);
 
     // Init actors
     system.block_on(async {
-        // Call cb function (register interrupt handlers)
-        callback();
-
-        if condition {
+        let dr_database_addr = if condition {
             post_example_dr(config).await;
             log::info!("post post_example DR");
+
+            None
         } else {
             let witnet_client_url = config.witnet_jsonrpc_addr.to_string();
 
@@ -123,13 +138,16 @@ fn run(callback: fn()) -> Result<(), String> {
                 .expect("witnet node not running");
 
             // Web3 contract using HTTP transport with an Ethereum client
-            let (web3, wrb_contract) =
-                create_wrb_contract(&config.eth_client_url, config.wrb_contract_addr);
+            let (web3, wrb_contract) = create_wrb_contract(
+                &config.eth_client_url,
+                config.wrb_contract_addr,
+                config.wrb_contract_abi_path.as_deref(),
+            );
             let wrb_contract = Arc::new(wrb_contract);
 
             // Start DrDatabase actor
             let dr_database_addr = DrDatabase::default().start();
-            SystemRegistry::set(dr_database_addr);
+            SystemRegistry::set(dr_database_addr.clone());
 
             // Start Json-RPC actor connected to Witnet node
             let node_client = JsonRpcClient::start(&witnet_client_url)
@@ -155,20 +173,14 @@ fn run(callback: fn()) -> Result<(), String> {
             let mut node_config = NodeConfig::default();
             node_config.storage.db_path = config.storage.db_path.clone();
             storage_mngr::start_from_config(node_config);
-        }
+
+            Some(dr_database_addr)
+        };
+
+        // Call cb function (register interrupt handlers)
+        callback(shutdown_grace_period, dr_database_addr);
     });
 
     // Run system
     system.run().map_err(|error| error.to_string())
 }
-
-/// Function to close the main system
-pub fn close() {
-    log::info!("Closing bridge");
-
-    // FIXME(#72): find out how to gracefully stop the system
-    // System::current().stop();
-
-    // Process exit
-    exit(0);
-}
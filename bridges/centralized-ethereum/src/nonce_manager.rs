@@ -0,0 +1,81 @@
+//! Nonce management of the ethereum transactions sent by the bridge.
+//!
+//! Several `reportResult` transactions can be in flight at the same time, and letting the ethereum
+//! client pick their nonces results in different transactions using the same nonce ("nonce too
+//! low" errors). Instead, the pending nonce of the account is fetched once and every outgoing
+//! transaction is assigned the next one.
+
+use futures_util::lock::Mutex;
+use std::{future::Future, sync::Arc};
+use web3::types::U256;
+
+/// Assigns increasing nonces to the transactions sent from the bridge account
+#[derive(Clone, Debug, Default)]
+pub struct NonceManager {
+    /// Nonce of the next transaction, or `None` if it must be fetched from the ethereum client
+    next_nonce: Arc<Mutex<Option<U256>>>,
+}
+
+impl NonceManager {
+    /// Returns the nonce that must be used by the next transaction.
+    ///
+    /// If the nonce is not known yet, it is fetched using `fetch_pending_nonce`, which should
+    /// return the transaction count of the account including the pending transactions.
+    pub async fn next_nonce<F, Fut, E>(&self, fetch_pending_nonce: F) -> Result<U256, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<U256, E>>,
+    {
+        // Keep the lock while fetching, so that concurrent callers wait for the fetched nonce
+        // instead of fetching the same one again
+        let mut next_nonce = self.next_nonce.lock().await;
+        let nonce = match *next_nonce {
+            Some(nonce) => nonce,
+            None => fetch_pending_nonce().await?,
+        };
+        *next_nonce = Some(nonce + 1);
+
+        Ok(nonce)
+    }
+
+    /// Forget the next nonce, so it is fetched again from the ethereum client.
+    ///
+    /// This must be called when a transaction fails, because its nonce may not have been used.
+    pub async fn resync(&self) {
+        *self.next_nonce.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::future::join_all;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn concurrent_transactions_get_distinct_increasing_nonces() {
+        let nonce_manager = NonceManager::default();
+        let fetch_count = AtomicUsize::new(0);
+        let fetch_count = &fetch_count;
+        let fetch_pending_nonce = move || async move {
+            fetch_count.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(U256::from(7))
+        };
+
+        let (first_nonces, resynced_nonce) = actix::System::new().block_on(async {
+            let first_nonces =
+                join_all((0..10).map(|_| nonce_manager.next_nonce(fetch_pending_nonce))).await;
+            nonce_manager.resync().await;
+            let resynced_nonce = nonce_manager.next_nonce(fetch_pending_nonce).await;
+
+            (first_nonces, resynced_nonce)
+        });
+
+        let first_nonces: Vec<U256> = first_nonces.into_iter().map(Result::unwrap).collect();
+        let expected: Vec<U256> = (7..17).map(U256::from).collect();
+        assert_eq!(first_nonces, expected);
+        // After a resync, the nonce is fetched again from the ethereum client
+        assert_eq!(resynced_nonce, Ok(U256::from(7)));
+        assert_eq!(fetch_count.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,165 @@
+//! Graceful shutdown of the bridge.
+//!
+//! When the bridge receives SIGINT or SIGTERM it stops starting new work, waits for the work that
+//! is already in flight (such as `reportResult` transactions waiting for confirmations) to finish,
+//! persists the database and then stops the actor system.
+
+use actix::prelude::*;
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::actors::dr_database::{DrDatabase, PersistDrDatabase};
+
+/// Period to check whether the in-flight operations have finished
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shutdown state of the bridge process
+pub static SHUTDOWN: Shutdown = Shutdown::new();
+
+/// Tracks whether a shutdown has been requested and how many operations are still in flight
+#[derive(Debug, Default)]
+pub struct Shutdown {
+    requested: AtomicBool,
+    in_flight: AtomicUsize,
+}
+
+impl Shutdown {
+    /// Create a new `Shutdown` with no operations in flight
+    pub const fn new() -> Self {
+        Self {
+            requested: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Request a shutdown. Returns `false` if the shutdown had already been requested.
+    pub fn request(&self) -> bool {
+        !self.requested.swap(true, Ordering::SeqCst)
+    }
+
+    /// Whether a shutdown has been requested
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// Number of operations currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Mark a new operation as in flight until the returned guard is dropped.
+    ///
+    /// Returns `None` if a shutdown has been requested, in which case the operation must not be
+    /// started.
+    pub fn start_operation(&self) -> Option<InFlight<'_>> {
+        // Increment the counter before checking the flag, so that `drain` cannot miss an operation
+        // started right before the shutdown was requested
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let in_flight = InFlight(self);
+
+        if self.is_requested() {
+            None
+        } else {
+            Some(in_flight)
+        }
+    }
+
+    /// Wait until there are no operations in flight, or until `grace_period` has elapsed.
+    /// Returns `true` if all the operations finished in time.
+    pub async fn drain(&self, grace_period: Duration) -> bool {
+        let deadline = Instant::now() + grace_period;
+        loop {
+            if self.in_flight() == 0 {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Guard of an in-flight operation, see `Shutdown::start_operation`
+#[derive(Debug)]
+pub struct InFlight<'a>(&'a Shutdown);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Wait for the in-flight operations to finish, persist the database and stop the system.
+///
+/// `SHUTDOWN.request()` must have been called before, so that no new operations are started.
+pub async fn graceful_shutdown(grace_period: Duration, dr_database: Option<Addr<DrDatabase>>) {
+    log::info!(
+        "Waiting up to {} ms for {} in-flight operations to finish",
+        grace_period.as_millis(),
+        SHUTDOWN.in_flight()
+    );
+    if !SHUTDOWN.drain(grace_period).await {
+        log::warn!(
+            "Grace period is over, stopping with {} operations still in flight",
+            SHUTDOWN.in_flight()
+        );
+    }
+
+    if let Some(dr_database) = dr_database {
+        dr_database.send(PersistDrDatabase).await.ok();
+    }
+
+    log::info!("Closing bridge");
+    System::current().stop();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn shutdown_stops_new_operations_and_waits_for_in_flight_ones() {
+        static TEST_SHUTDOWN: Shutdown = Shutdown::new();
+
+        System::new().block_on(async {
+            let in_flight = TEST_SHUTDOWN.start_operation().unwrap();
+            let completed = Arc::new(AtomicBool::new(false));
+            let completed2 = completed.clone();
+            actix::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                completed2.store(true, Ordering::SeqCst);
+                drop(in_flight);
+            });
+
+            assert!(TEST_SHUTDOWN.request());
+            // Requesting the shutdown twice has no effect
+            assert!(!TEST_SHUTDOWN.request());
+            // The next tick does not start a new operation
+            assert!(TEST_SHUTDOWN.start_operation().is_none());
+            assert_eq!(TEST_SHUTDOWN.in_flight(), 1);
+
+            // But the operation that was already in flight is allowed to complete
+            assert!(TEST_SHUTDOWN.drain(Duration::from_secs(10)).await);
+            assert!(completed.load(Ordering::SeqCst));
+            assert_eq!(TEST_SHUTDOWN.in_flight(), 0);
+        });
+    }
+
+    #[test]
+    fn drain_gives_up_after_grace_period() {
+        static TEST_SHUTDOWN: Shutdown = Shutdown::new();
+
+        System::new().block_on(async {
+            let _in_flight = TEST_SHUTDOWN.start_operation().unwrap();
+            TEST_SHUTDOWN.request();
+
+            assert!(!TEST_SHUTDOWN.drain(Duration::from_millis(10)).await);
+            assert_eq!(TEST_SHUTDOWN.in_flight(), 1);
+        });
+    }
+}
@@ -139,6 +139,18 @@ pub struct Log {
     pub level: log::LevelFilter,
     /// Automated bug reporting (helps the community improve the software)
     pub sentry_telemetry: bool,
+    /// Whether to use ANSI colors when logging data request results and consolidated blocks.
+    /// If not set, colors are used only if the logs are written to a terminal.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub color: Option<bool>,
+    /// Whether to include the result of completed data requests in the logs
+    pub dr_results: bool,
+    /// Whether to log decorative banners, like the one shown when the node gets synced.
+    /// If not set, banners are shown only if the logs are written to a terminal.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub banners: Option<bool>,
 }
 
 /// Connection-specific configuration.
@@ -231,9 +243,14 @@ pub struct Connections {
     /// Number of seconds before giving up waiting for requested blocks
     pub blocks_timeout: i64,
 
-    /// Constant to specify when consensus is achieved (in %)
+    /// Constant to specify when consensus is achieved (in %) while the node is synced or almost
+    /// synced
     pub consensus_c: u32,
 
+    /// Constant to specify when consensus is achieved (in %) while the node is waiting for
+    /// consensus or synchronizing. Defaults to `consensus_c` when not set
+    pub consensus_c_syncing: u32,
+
     /// Period that indicate the validity of a checked peer
     pub bucketing_update_period: i64,
 
@@ -247,6 +264,14 @@ pub struct Connections {
     ))]
     pub bucketing_ice_period: Duration,
 
+    /// Maximum number of addresses kept in the new addresses bucket. When the bucket is full, the
+    /// oldest addresses are evicted to make room for new ones.
+    pub bucketing_new_capacity: usize,
+
+    /// Maximum number of bucket slots that addresses sharing the same IP should take before a
+    /// warning is logged. Zero disables the warning.
+    pub bucketing_max_slots_per_host: usize,
+
     /// Reject (tarpit) inbound connections coming from addresses that are alike, so as
     /// to prevent sybil peers from monopolizing our inbound capacity.
     pub reject_sybil_inbounds: bool,
@@ -257,6 +282,14 @@ pub struct Connections {
 
     /// Limit the number of requested blocks that will be processed as one batch
     pub requested_blocks_batch_limit: u32,
+
+    /// Number of epochs to wait for a requested batch of blocks while synchronizing before
+    /// requesting it again
+    pub sync_blocks_batch_timeout_epochs: u32,
+
+    /// Maximum number of epochs that the node will roll back in order to follow a fork. Deeper
+    /// forks are refused and need operator intervention. Set to 0 to follow forks of any depth
+    pub max_reorg_depth: u32,
 }
 
 /// Witnessing-specific configuration.
@@ -280,6 +313,28 @@ pub struct Witnessing {
     /// and we are taking as small of a risk as possible when committing to specially crafted data
     /// requests that may be potentially ill-intended.
     pub proxies: Vec<String>,
+
+    /// Value of the `User-Agent` header sent along every HTTP retrieval. It is the same for all
+    /// nodes by default, so that data sources cannot tell node versions apart.
+    pub user_agent: String,
+
+    /// Maximum size in bytes of the body of an HTTP retrieval response. Retrievals are aborted as
+    /// soon as this limit is exceeded.
+    pub max_response_size: usize,
+
+    /// Number of times that the HTTP request of a retrieval is attempted before giving up. Only
+    /// network errors are retried, never HTTP error status codes. The default of 1 disables
+    /// retries.
+    pub retrieval_attempts: u8,
+
+    /// Delay before the first retry of a failed HTTP request. It doubles with every new attempt.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "retrieval_retry_delay_milliseconds"
+    ))]
+    pub retrieval_retry_delay: Duration,
 }
 
 /// Available storage backends
@@ -311,6 +366,19 @@ pub struct Storage {
     pub utxos_in_memory: bool,
     /// RocksDB option max_open_files. -1 means unlimited.
     pub max_open_files: i32,
+    /// How long the reports of resolved data requests read from the database are kept in memory,
+    /// so that polling the same data request does not read the database every time.
+    /// Set to 0 to disable the cache.
+    #[partial_struct(serde(
+        default,
+        serialize_with = "to_secs",
+        deserialize_with = "from_secs",
+        rename = "data_request_report_cache_ttl_seconds"
+    ))]
+    pub data_request_report_cache_ttl: Duration,
+    /// Allow rewinding the chain state back to a given epoch. This is destructive: the chain
+    /// state is deleted and rebuilt from the blocks in storage, so it is disabled by default.
+    pub allow_rewind: bool,
 }
 
 /// JsonRPC API configuration
@@ -331,6 +399,9 @@ pub struct JsonRPC {
     /// JSON-RPC WebSockets server address, that is, the socket address (interface ip and port) for
     /// the JSON-RPC server to listen over WebSockets
     pub ws_address: Option<SocketAddr>,
+    /// Maximum number of block hashes returned by a single `getBlockChain` request. Requests with
+    /// a greater or unlimited `limit` are truncated to this amount. Set to 0 to remove the cap.
+    pub max_block_chain_limit: u32,
 }
 
 /// Mining-related configuration
@@ -344,6 +415,14 @@ pub struct Mining {
     /// bad condition if hitting bandwidth or CPU bottlenecks.
     /// Set to 0 totally disable participation in resolving data requests.
     pub data_request_max_retrievals_per_epoch: u16,
+    /// Limits the number of retrievals that can be executed concurrently across all the data
+    /// requests being resolved by this node. Retrievals exceeding this limit are queued until
+    /// others finish. Set to 0 to remove the limit.
+    pub data_request_max_concurrent_retrievals: u16,
+    /// What to do when the aggregation script of a data request fails while this node is resolving
+    /// it as a witness. Note that tally script failures are not affected by this setting: tallies
+    /// are part of consensus, so they always resolve with a canonical `RadonError`.
+    pub data_request_aggregation_failure_policy: ScriptFailurePolicy,
     /// Timeout for data request retrieval and aggregation execution.
     /// This should usually be slightly below half the checkpoints period.
     /// Set to 0 to disable timeouts.
@@ -369,6 +448,19 @@ pub struct Mining {
     pub minimum_vtt_fee: u64,
 }
 
+/// Available policies for handling failures of data request scripts
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum ScriptFailurePolicy {
+    /// Commit a canonical `RadonError` as the result, so that the data request can resolve as a
+    /// failure
+    #[default]
+    #[serde(rename = "error")]
+    CommitError,
+    /// Do not commit any result, leaving the data request unresolved by this node
+    #[serde(rename = "unresolved")]
+    Unresolved,
+}
+
 /// NTP-related configuration
 #[derive(PartialStruct, Debug, Clone, PartialEq, Eq)]
 #[partial_struct(derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq, Eq))]
@@ -387,6 +479,16 @@ pub struct Ntp {
 
     /// Enable NTP for clock synchronization
     pub enabled: bool,
+
+    /// Maximum difference between the system clock and the NTP time before warning about the
+    /// system clock being off. Zero disables the warning.
+    #[partial_struct(serde(
+        default,
+        serialize_with = "to_secs",
+        deserialize_with = "from_secs",
+        rename = "max_drift_seconds"
+    ))]
+    pub max_drift: Duration,
 }
 
 /// Mempool-related configuration
@@ -397,6 +499,9 @@ pub struct Mempool {
     pub tx_pending_timeout: u64,
     /// Maximum number of recovered transactions to include by epoch
     pub max_reinserted_transactions: u32,
+    /// Allow building transactions while the node is `AlmostSynced`, and not only when it is
+    /// `Synced`. The UTXO set is already up to date in that state
+    pub build_transactions_while_almost_synced: bool,
 }
 
 /// Threshold Activation of Protocol Improvements
@@ -624,6 +729,12 @@ impl Log {
                 .to_owned()
                 .unwrap_or_else(|| defaults.log_level()),
             sentry_telemetry: config.sentry_telemetry.unwrap_or(false),
+            color: config.color,
+            dr_results: config
+                .dr_results
+                .to_owned()
+                .unwrap_or_else(|| defaults.log_dr_results()),
+            banners: config.banners,
         }
     }
 
@@ -631,6 +742,9 @@ impl Log {
         PartialLog {
             level: Some(self.level),
             sentry_telemetry: Some(self.sentry_telemetry),
+            color: self.color,
+            dr_results: Some(self.dr_results),
+            banners: self.banners,
         }
     }
 }
@@ -691,6 +805,10 @@ impl Connections {
                 .consensus_c
                 .to_owned()
                 .unwrap_or_else(|| defaults.connections_consensus_c()),
+            consensus_c_syncing: config
+                .consensus_c_syncing
+                .or(config.consensus_c)
+                .unwrap_or_else(|| defaults.connections_consensus_c()),
             bucketing_ice_period: config
                 .bucketing_ice_period
                 .to_owned()
@@ -699,6 +817,14 @@ impl Connections {
                 .bucketing_update_period
                 .to_owned()
                 .unwrap_or_else(|| defaults.connections_bucketing_update_period()),
+            bucketing_new_capacity: config
+                .bucketing_new_capacity
+                .to_owned()
+                .unwrap_or_else(|| defaults.connections_bucketing_new_capacity()),
+            bucketing_max_slots_per_host: config
+                .bucketing_max_slots_per_host
+                .to_owned()
+                .unwrap_or_else(|| defaults.connections_bucketing_max_slots_per_host()),
             reject_sybil_inbounds: config
                 .reject_sybil_inbounds
                 .to_owned()
@@ -711,6 +837,14 @@ impl Connections {
                 .requested_blocks_batch_limit
                 .to_owned()
                 .unwrap_or_else(|| defaults.connections_requested_blocks_batch_limit()),
+            sync_blocks_batch_timeout_epochs: config
+                .sync_blocks_batch_timeout_epochs
+                .to_owned()
+                .unwrap_or_else(|| defaults.connections_sync_blocks_batch_timeout_epochs()),
+            max_reorg_depth: config
+                .max_reorg_depth
+                .to_owned()
+                .unwrap_or_else(|| defaults.connections_max_reorg_depth()),
         }
     }
 
@@ -730,11 +864,16 @@ impl Connections {
             handshake_max_ts_diff: Some(self.handshake_max_ts_diff),
             blocks_timeout: Some(self.blocks_timeout),
             consensus_c: Some(self.consensus_c),
+            consensus_c_syncing: Some(self.consensus_c_syncing),
             bucketing_ice_period: Some(self.bucketing_ice_period),
             bucketing_update_period: Some(self.bucketing_update_period),
+            bucketing_new_capacity: Some(self.bucketing_new_capacity),
+            bucketing_max_slots_per_host: Some(self.bucketing_max_slots_per_host),
             reject_sybil_inbounds: Some(self.reject_sybil_inbounds),
             reject_sybil_inbounds_range_limit: Some(self.reject_sybil_inbounds_range_limit),
             requested_blocks_batch_limit: Some(self.requested_blocks_batch_limit),
+            sync_blocks_batch_timeout_epochs: Some(self.sync_blocks_batch_timeout_epochs),
+            max_reorg_depth: Some(self.max_reorg_depth),
         }
     }
 }
@@ -755,6 +894,12 @@ impl Storage {
             max_open_files: config
                 .max_open_files
                 .unwrap_or_else(|| defaults.storage_max_open_files()),
+            data_request_report_cache_ttl: config
+                .data_request_report_cache_ttl
+                .unwrap_or_else(|| defaults.storage_data_request_report_cache_ttl()),
+            allow_rewind: config
+                .allow_rewind
+                .unwrap_or_else(|| defaults.storage_allow_rewind()),
         }
     }
 
@@ -765,6 +910,8 @@ impl Storage {
             master_key_import_path: self.master_key_import_path.clone(),
             utxos_in_memory: Some(self.utxos_in_memory),
             max_open_files: Some(self.max_open_files),
+            data_request_report_cache_ttl: Some(self.data_request_report_cache_ttl),
+            allow_rewind: Some(self.allow_rewind),
         }
     }
 }
@@ -792,6 +939,10 @@ impl JsonRPC {
                 .enable_sensitive_methods
                 .to_owned()
                 .unwrap_or_else(|| defaults.jsonrpc_enable_sensitive_methods()),
+            max_block_chain_limit: config
+                .max_block_chain_limit
+                .to_owned()
+                .unwrap_or_else(|| defaults.jsonrpc_max_block_chain_limit()),
         }
     }
 
@@ -802,6 +953,7 @@ impl JsonRPC {
             tcp_address: Some(self.tcp_address),
             ws_address: Some(self.ws_address),
             enable_sensitive_methods: Some(self.enable_sensitive_methods),
+            max_block_chain_limit: Some(self.max_block_chain_limit),
         }
     }
 }
@@ -821,6 +973,14 @@ impl Mining {
                 .data_request_max_retrievals_per_epoch
                 .to_owned()
                 .unwrap_or_else(|| defaults.mining_data_request_max_retrievals_per_epoch()),
+            data_request_max_concurrent_retrievals: config
+                .data_request_max_concurrent_retrievals
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_max_concurrent_retrievals()),
+            data_request_aggregation_failure_policy: config
+                .data_request_aggregation_failure_policy
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_aggregation_failure_policy()),
             genesis_path: config
                 .genesis_path
                 .clone()
@@ -846,6 +1006,12 @@ impl Mining {
             enabled: Some(self.enabled),
             data_request_timeout: Some(self.data_request_timeout),
             data_request_max_retrievals_per_epoch: Some(self.data_request_max_retrievals_per_epoch),
+            data_request_max_concurrent_retrievals: Some(
+                self.data_request_max_concurrent_retrievals,
+            ),
+            data_request_aggregation_failure_policy: Some(
+                self.data_request_aggregation_failure_policy,
+            ),
             genesis_path: Some(self.genesis_path.clone()),
             mint_external_percentage: Some(self.mint_external_percentage),
             mint_external_address: self.mint_external_address.clone(),
@@ -870,6 +1036,10 @@ impl Ntp {
                 .enabled
                 .to_owned()
                 .unwrap_or_else(|| defaults.ntp_enabled()),
+            max_drift: config
+                .max_drift
+                .to_owned()
+                .unwrap_or_else(|| defaults.ntp_max_drift()),
         }
     }
 
@@ -878,6 +1048,7 @@ impl Ntp {
             update_period: Some(self.update_period),
             servers: Some(self.servers.clone()),
             enabled: Some(self.enabled),
+            max_drift: Some(self.max_drift),
         }
     }
 }
@@ -893,6 +1064,9 @@ impl Mempool {
                 .max_reinserted_transactions
                 .to_owned()
                 .unwrap_or_else(|| defaults.mempool_max_reinserted_transactions()),
+            build_transactions_while_almost_synced: config
+                .build_transactions_while_almost_synced
+                .unwrap_or_else(|| defaults.mempool_build_transactions_while_almost_synced()),
         }
     }
 
@@ -900,6 +1074,9 @@ impl Mempool {
         PartialMempool {
             tx_pending_timeout: Some(self.tx_pending_timeout),
             max_reinserted_transactions: Some(self.max_reinserted_transactions),
+            build_transactions_while_almost_synced: Some(
+                self.build_transactions_while_almost_synced,
+            ),
         }
     }
 }
@@ -930,12 +1107,20 @@ pub struct Wallet {
     pub db_path: PathBuf,
     /// Database file name.
     pub db_file_name: String,
-    /// Database hash iterations when encrypting.
+    /// Directory where the files exported by the wallet, like the CSV exports of transactions,
+    /// are written. Clients can only choose file names inside this directory. Defaults to the
+    /// `exports` directory inside `db_path`.
+    pub export_path: PathBuf,
+    /// Database hash iterations when encrypting. Wallets encrypted with fewer iterations are
+    /// re-encrypted with this count the next time they are unlocked.
     pub db_encrypt_hash_iterations: u32,
     /// Database init-vector-length when encrypting.
     pub db_encrypt_iv_length: usize,
     /// Database random salt length when encrypting.
     pub db_encrypt_salt_length: usize,
+    /// Whether to encrypt the database values of new wallets with AES GCM, which detects corrupted
+    /// or tampered data, instead of AES CBC. Existing wallets keep the mode they were created with.
+    pub db_encrypt_authenticated: bool,
     /// Master Key-generation seed password. Default empty `""`.
     pub seed_password: ProtectedString,
     /// Master Key-generation salt. Default `Bitcoin seed`.
@@ -956,10 +1141,34 @@ pub struct Wallet {
     /// If a transaction has not been included in a block for this amount of seconds, mark the UTXOs
     /// as available again.
     pub pending_transactions_timeout_seconds: u64,
+    /// Period in seconds between automatic compactions of the database. Zero disables the
+    /// periodic compaction.
+    pub db_compaction_interval_seconds: u64,
+    /// Compact the database right after resynchronizing a wallet, so as to reclaim the space used
+    /// by the chain data that was cleared.
+    pub db_compact_on_resync: bool,
+    /// Number of blocks that need to be built on top of a block confirmed by a superblock before
+    /// its balance movements are marked as confirmed.
+    pub confirmations_required: u32,
+    /// Enable the administrative methods (e.g. listing the open sessions), which are meant for
+    /// operators debugging a running wallet.
+    pub enable_admin_methods: bool,
+    /// Maximum number of requests that each session can forward to the node in a burst. Zero
+    /// (the default) disables the rate limiting of forwarded requests.
+    pub forward_rate_limit_capacity: u32,
+    /// Number of requests per second that can be forwarded to the node once the burst capacity
+    /// has been exhausted.
+    pub forward_rate_limit_refill_rate: u32,
 }
 
 impl Wallet {
     pub fn from_partial(config: &PartialWallet, defaults: &dyn Defaults) -> Self {
+        let db_path = config.db_path.clone().unwrap_or_else(dirs::data_dir);
+        let export_path = config
+            .export_path
+            .clone()
+            .unwrap_or_else(|| db_path.join("exports"));
+
         Wallet {
             testnet: config.testnet,
             session_expires_in: config.session_expires_in.unwrap_or(900),
@@ -970,11 +1179,12 @@ impl Wallet {
             node_url: config.node_url.clone(),
             node_sync_batch_size: config.node_sync_batch_size.unwrap_or(50),
             concurrency: config.concurrency,
-            db_path: config.db_path.clone().unwrap_or_else(dirs::data_dir),
+            db_path,
             db_file_name: config
                 .db_file_name
                 .clone()
                 .unwrap_or_else(|| defaults.wallet_db_file_name()),
+            export_path,
             db_encrypt_hash_iterations: config
                 .db_encrypt_hash_iterations
                 .unwrap_or_else(|| defaults.wallet_db_encrypt_hash_iterations()),
@@ -984,6 +1194,9 @@ impl Wallet {
             db_encrypt_salt_length: config
                 .db_encrypt_salt_length
                 .unwrap_or_else(|| defaults.wallet_db_encrypt_salt_length()),
+            db_encrypt_authenticated: config
+                .db_encrypt_authenticated
+                .unwrap_or_else(|| defaults.wallet_db_encrypt_authenticated()),
             seed_password: config
                 .seed_password
                 .clone()
@@ -1008,6 +1221,24 @@ impl Wallet {
             pending_transactions_timeout_seconds: config
                 .pending_transactions_timeout_seconds
                 .unwrap_or_else(|| defaults.wallet_pending_transactions_timeout_seconds()),
+            db_compaction_interval_seconds: config
+                .db_compaction_interval_seconds
+                .unwrap_or_else(|| defaults.wallet_db_compaction_interval_seconds()),
+            db_compact_on_resync: config
+                .db_compact_on_resync
+                .unwrap_or_else(|| defaults.wallet_db_compact_on_resync()),
+            confirmations_required: config
+                .confirmations_required
+                .unwrap_or_else(|| defaults.wallet_confirmations_required()),
+            enable_admin_methods: config
+                .enable_admin_methods
+                .unwrap_or_else(|| defaults.wallet_enable_admin_methods()),
+            forward_rate_limit_capacity: config
+                .forward_rate_limit_capacity
+                .unwrap_or_else(|| defaults.wallet_forward_rate_limit_capacity()),
+            forward_rate_limit_refill_rate: config
+                .forward_rate_limit_refill_rate
+                .unwrap_or_else(|| defaults.wallet_forward_rate_limit_refill_rate()),
         }
     }
 
@@ -1020,9 +1251,11 @@ impl Wallet {
             concurrency: self.concurrency,
             db_path: Some(self.db_path.clone()),
             db_file_name: Some(self.db_file_name.clone()),
+            export_path: Some(self.export_path.clone()),
             db_encrypt_hash_iterations: Some(self.db_encrypt_hash_iterations),
             db_encrypt_iv_length: Some(self.db_encrypt_iv_length),
             db_encrypt_salt_length: Some(self.db_encrypt_salt_length),
+            db_encrypt_authenticated: Some(self.db_encrypt_authenticated),
             seed_password: None,   // seed_password should not be exported
             master_key_salt: None, // master_key_salt should not be exported
             id_hash_iterations: Some(self.id_hash_iterations),
@@ -1032,6 +1265,12 @@ impl Wallet {
             sync_address_batch_length: Some(self.sync_address_batch_length),
             use_unconfirmed_utxos: Some(self.use_unconfirmed_utxos),
             pending_transactions_timeout_seconds: Some(self.pending_transactions_timeout_seconds),
+            db_compaction_interval_seconds: Some(self.db_compaction_interval_seconds),
+            db_compact_on_resync: Some(self.db_compact_on_resync),
+            confirmations_required: Some(self.confirmations_required),
+            enable_admin_methods: Some(self.enable_admin_methods),
+            forward_rate_limit_capacity: Some(self.forward_rate_limit_capacity),
+            forward_rate_limit_refill_rate: Some(self.forward_rate_limit_refill_rate),
         }
     }
 }
@@ -1120,6 +1359,19 @@ impl Witnessing {
                 .proxies
                 .clone()
                 .unwrap_or_else(|| defaults.witnessing_proxies()),
+            user_agent: config
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| defaults.witnessing_user_agent()),
+            max_response_size: config
+                .max_response_size
+                .unwrap_or_else(|| defaults.witnessing_max_response_size()),
+            retrieval_attempts: config
+                .retrieval_attempts
+                .unwrap_or_else(|| defaults.witnessing_retrieval_attempts()),
+            retrieval_retry_delay: config
+                .retrieval_retry_delay
+                .unwrap_or_else(|| defaults.witnessing_retrieval_retry_delay()),
         }
     }
 
@@ -1128,6 +1380,10 @@ impl Witnessing {
             allow_unproxied: Some(self.allow_unproxied),
             paranoid_percentage: Some(self.paranoid_percentage),
             proxies: Some(self.proxies.clone()),
+            user_agent: Some(self.user_agent.clone()),
+            max_response_size: Some(self.max_response_size),
+            retrieval_attempts: Some(self.retrieval_attempts),
+            retrieval_retry_delay: Some(self.retrieval_retry_delay),
         }
     }
 
@@ -1167,6 +1423,10 @@ impl Witnessing {
         WitnessingConfig {
             paranoid_threshold: paranoid,
             transports,
+            user_agent: self.user_agent,
+            max_response_size: self.max_response_size,
+            retrieval_attempts: self.retrieval_attempts,
+            retrieval_retry_delay: self.retrieval_retry_delay,
         }
     }
 }
@@ -1308,10 +1568,14 @@ mod tests {
             master_key_import_path: None,
             utxos_in_memory: None,
             max_open_files: None,
+            data_request_report_cache_ttl: Some(Duration::from_secs(5)),
+            allow_rewind: Some(true),
         };
         let config = Storage::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.db_path.to_str(), Some("other"));
+        assert_eq!(config.data_request_report_cache_ttl, Duration::from_secs(5));
+        assert!(config.allow_rewind);
     }
 
     #[test]
@@ -1361,11 +1625,16 @@ mod tests {
             handshake_max_ts_diff: Some(17),
             blocks_timeout: Some(5),
             consensus_c: Some(51),
+            consensus_c_syncing: Some(75),
             bucketing_ice_period: Some(Duration::from_secs(13200)),
             bucketing_update_period: Some(200),
+            bucketing_new_capacity: Some(1000),
+            bucketing_max_slots_per_host: Some(2),
             reject_sybil_inbounds: Some(true),
             reject_sybil_inbounds_range_limit: Some(14),
             requested_blocks_batch_limit: Some(99),
+            sync_blocks_batch_timeout_epochs: Some(3),
+            max_reorg_depth: Some(50),
         };
         let config = Connections::from_partial(&partial_config, &Testnet);
 
@@ -1383,11 +1652,16 @@ mod tests {
         assert_eq!(config.blocks_timeout, 5);
         assert_eq!(config.handshake_max_ts_diff, 17);
         assert_eq!(config.consensus_c, 51);
+        assert_eq!(config.consensus_c_syncing, 75);
         assert_eq!(config.bucketing_ice_period, Duration::from_secs(13200));
         assert_eq!(config.bucketing_update_period, 200);
+        assert_eq!(config.bucketing_new_capacity, 1000);
+        assert_eq!(config.bucketing_max_slots_per_host, 2);
         assert!(config.reject_sybil_inbounds);
         assert_eq!(config.reject_sybil_inbounds_range_limit, 14);
         assert_eq!(config.requested_blocks_batch_limit, 99);
+        assert_eq!(config.sync_blocks_batch_timeout_epochs, 3);
+        assert_eq!(config.max_reorg_depth, 50);
     }
 
     #[test]
@@ -1396,12 +1670,20 @@ mod tests {
             allow_unproxied: Some(true),
             paranoid_percentage: Some(51),
             proxies: Some(Vec::<String>::new()),
+            user_agent: None,
+            max_response_size: Some(4096),
+            retrieval_attempts: Some(3),
+            retrieval_retry_delay: None,
         };
         let config = Witnessing::from_partial(&partial, &Testnet);
 
         assert!(config.allow_unproxied);
         assert_eq!(config.paranoid_percentage, 51);
         assert_eq!(config.proxies, Vec::<String>::new());
+        assert_eq!(config.user_agent, "witnet-rad");
+        assert_eq!(config.max_response_size, 4096);
+        assert_eq!(config.retrieval_attempts, 3);
+        assert_eq!(config.retrieval_retry_delay, Duration::from_millis(500));
     }
 
     #[test]
@@ -1421,10 +1703,12 @@ mod tests {
             http_address: None,
             tcp_address: Some(Some(addr)),
             ws_address: None,
+            max_block_chain_limit: Some(100),
         };
         let config = JsonRPC::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.tcp_address, Some(addr));
+        assert_eq!(config.max_block_chain_limit, 100);
     }
 
     #[test]
@@ -1481,6 +1765,10 @@ mod tests {
             config.connections.consensus_c,
             Mainnet.connections_consensus_c()
         );
+        assert_eq!(
+            config.connections.consensus_c_syncing,
+            Mainnet.connections_consensus_c()
+        );
         assert_eq!(
             config.connections.bucketing_update_period,
             Mainnet.connections_bucketing_update_period()
@@ -8,9 +8,17 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use witnet_crypto::hash::HashFunction;
-use witnet_data_structures::chain::Hash;
+use witnet_data_structures::{
+    chain::Hash,
+    witnessing::{
+        DEFAULT_MAX_RESPONSE_SIZE, DEFAULT_RETRIEVAL_ATTEMPTS, DEFAULT_RETRIEVAL_RETRY_DELAY,
+        DEFAULT_USER_AGENT,
+    },
+};
 use witnet_protected::ProtectedString;
 
+use crate::config::ScriptFailurePolicy;
+
 // When changing the defaults, remember to update the documentation!
 // https://github.com/witnet/witnet-rust/blob/master/docs/configuration/toml-file.md
 // https://github.com/witnet/witnet-rust/blob/master/docs/configuration/environment.md
@@ -24,6 +32,11 @@ pub trait Defaults {
         log::LevelFilter::Info
     }
 
+    /// Log the result of completed data requests by default
+    fn log_dr_results(&self) -> bool {
+        true
+    }
+
     /// Default server addr
     fn connections_server_addr(&self) -> SocketAddr {
         SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 21337)
@@ -59,6 +72,16 @@ pub trait Defaults {
         -1
     }
 
+    /// Keep the data request reports read from the database in memory for one minute
+    fn storage_data_request_report_cache_ttl(&self) -> Duration {
+        Duration::from_secs(60)
+    }
+
+    /// Rewinding the chain state is destructive, so it must be explicitly enabled
+    fn storage_allow_rewind(&self) -> bool {
+        false
+    }
+
     /// Default period for bootstrap peers
     fn connections_bootstrap_peers_period(&self) -> Duration {
         Duration::from_secs(5)
@@ -106,6 +129,16 @@ pub trait Defaults {
         300
     }
 
+    /// Maximum number of addresses kept in the new addresses bucket
+    fn connections_bucketing_new_capacity(&self) -> usize {
+        4096
+    }
+
+    /// Maximum number of bucket slots that addresses sharing the same IP should take
+    fn connections_bucketing_max_slots_per_host(&self) -> usize {
+        4
+    }
+
     /// Reject (tarpit) inbound connections coming from addresses that are alike, so as
     /// to prevent sybil peers from monopolizing our inbound capacity.
     fn connections_reject_sybil_inbounds(&self) -> bool {
@@ -124,6 +157,16 @@ pub trait Defaults {
         500
     }
 
+    /// Number of epochs to wait for a requested batch of blocks before requesting it again
+    fn connections_sync_blocks_batch_timeout_epochs(&self) -> u32 {
+        10
+    }
+
+    /// Maximum number of epochs rolled back to follow a fork (0 means no limit)
+    fn connections_max_reorg_depth(&self) -> u32 {
+        0
+    }
+
     /// Let the default unproxied HTTP transport be enabled by default
     fn witnessing_allow_unproxied(&self) -> bool {
         true
@@ -141,6 +184,27 @@ pub trait Defaults {
         vec![]
     }
 
+    /// User-Agent header sent when retrieving data sources. It is fixed so that it does not
+    /// change across node versions.
+    fn witnessing_user_agent(&self) -> String {
+        String::from(DEFAULT_USER_AGENT)
+    }
+
+    /// Retrieval responses larger than 1 MiB are rejected by default
+    fn witnessing_max_response_size(&self) -> usize {
+        DEFAULT_MAX_RESPONSE_SIZE
+    }
+
+    /// HTTP requests of retrievals are not retried by default
+    fn witnessing_retrieval_attempts(&self) -> u8 {
+        DEFAULT_RETRIEVAL_ATTEMPTS
+    }
+
+    /// Wait 500 milliseconds before retrying a failed HTTP request for the first time
+    fn witnessing_retrieval_retry_delay(&self) -> Duration {
+        DEFAULT_RETRIEVAL_RETRY_DELAY
+    }
+
     /// Timestamp at the start of epoch 0
     fn consensus_constants_checkpoint_zero_timestamp(&self) -> i64;
 
@@ -228,6 +292,11 @@ pub trait Defaults {
         true
     }
 
+    /// Return at most 1000 block hashes in a single getBlockChain request by default
+    fn jsonrpc_max_block_chain_limit(&self) -> u32 {
+        1000
+    }
+
     /// MiningManager, enabled by default
     fn mining_enabled(&self) -> bool {
         true
@@ -244,6 +313,16 @@ pub trait Defaults {
         core::u16::MAX
     }
 
+    /// Do not limit the number of concurrent retrievals by default
+    fn mining_data_request_max_concurrent_retrievals(&self) -> u16 {
+        0
+    }
+
+    /// Commit an error when the aggregation script of a data request fails
+    fn mining_data_request_aggregation_failure_policy(&self) -> ScriptFailurePolicy {
+        ScriptFailurePolicy::CommitError
+    }
+
     /// Genesis block path, "./genesis_block.json" by default
     fn mining_genesis_path(&self) -> String {
         "genesis_block.json".to_string()
@@ -406,6 +485,10 @@ pub trait Defaults {
         32
     }
 
+    fn wallet_db_encrypt_authenticated(&self) -> bool {
+        false
+    }
+
     fn wallet_seed_password(&self) -> ProtectedString {
         "".into()
     }
@@ -435,6 +518,33 @@ pub trait Defaults {
         10 * u64::from(self.consensus_constants_checkpoints_period())
     }
 
+    fn wallet_db_compaction_interval_seconds(&self) -> u64 {
+        // Default: disabled
+        0
+    }
+
+    fn wallet_db_compact_on_resync(&self) -> bool {
+        true
+    }
+
+    fn wallet_confirmations_required(&self) -> u32 {
+        // Default: confirm blocks as soon as they are consolidated by a superblock
+        0
+    }
+
+    fn wallet_enable_admin_methods(&self) -> bool {
+        false
+    }
+
+    fn wallet_forward_rate_limit_capacity(&self) -> u32 {
+        // Default: do not rate limit the requests forwarded to the node
+        0
+    }
+
+    fn wallet_forward_rate_limit_refill_rate(&self) -> u32 {
+        10
+    }
+
     fn rocksdb_create_if_missing(&self) -> bool {
         true
     }
@@ -468,6 +578,10 @@ pub trait Defaults {
         true
     }
 
+    fn ntp_max_drift(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
     fn mempool_tx_pending_timeout(&self) -> u64 {
         u64::from(self.consensus_constants_checkpoints_period()) * 10
     }
@@ -475,6 +589,11 @@ pub trait Defaults {
     fn mempool_max_reinserted_transactions(&self) -> u32 {
         100
     }
+
+    /// Only build transactions when the node is synced by default
+    fn mempool_build_transactions_while_almost_synced(&self) -> bool {
+        false
+    }
 }
 
 /// Allow setting a reward to collateral percentage for a data request to be included in a block
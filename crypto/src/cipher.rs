@@ -3,6 +3,10 @@ use aes::Aes256;
 use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
 use failure::Fail;
 use rand::{rngs::OsRng, RngCore};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+
+/// Length in bytes of the nonces used by AES GCM
+pub const GCM_NONCE_LENGTH: usize = ring::aead::NONCE_LEN;
 
 type Aes256Cbc = Cbc<Aes256, Pkcs7>;
 
@@ -18,6 +22,12 @@ pub enum Error {
     /// Wrapper for random generation errors
     #[fail(display = "Random generation error")]
     Rng(rand::Error),
+    /// Invalid key or nonce for AES GCM
+    #[fail(display = "Invalid key or nonce length")]
+    InvalidKeyNonceLength,
+    /// The ciphertext does not match its authentication tag
+    #[fail(display = "Authentication tag mismatch")]
+    AuthenticationFailed,
 }
 
 /// Encrypt data with AES CBC using the supplied secret
@@ -38,6 +48,43 @@ pub fn decrypt_aes_cbc(secret: &[u8], ciphertext: &[u8], iv: &[u8]) -> Result<Ve
     Ok(plaintext)
 }
 
+fn aes_gcm_key(secret: &[u8]) -> Result<LessSafeKey, Error> {
+    let key = UnboundKey::new(&AES_256_GCM, secret).map_err(|_| Error::InvalidKeyNonceLength)?;
+
+    Ok(LessSafeKey::new(key))
+}
+
+/// Encrypt data with AES GCM using the supplied secret. The authentication tag is appended to the
+/// ciphertext.
+///
+/// A nonce must never be reused with the same secret.
+pub fn encrypt_aes_gcm(secret: &[u8], plaintext: &[u8], nonce: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = aes_gcm_key(secret)?;
+    let nonce =
+        Nonce::try_assume_unique_for_key(nonce).map_err(|_| Error::InvalidKeyNonceLength)?;
+    let mut ciphertext = plaintext.to_vec();
+    key.seal_in_place_append_tag(nonce, Aad::empty(), &mut ciphertext)
+        .map_err(|_| Error::InvalidKeyNonceLength)?;
+
+    Ok(ciphertext)
+}
+
+/// Decrypt data with AES GCM using the supplied secret, failing if the authentication tag appended
+/// to the ciphertext does not match
+pub fn decrypt_aes_gcm(secret: &[u8], ciphertext: &[u8], nonce: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = aes_gcm_key(secret)?;
+    let nonce =
+        Nonce::try_assume_unique_for_key(nonce).map_err(|_| Error::InvalidKeyNonceLength)?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext_len = key
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| Error::AuthenticationFailed)?
+        .len();
+    in_out.truncate(plaintext_len);
+
+    Ok(in_out)
+}
+
 /// Generate a random initialization vector of the given size in bytes
 pub fn generate_random(size: usize) -> Result<Vec<u8>, Error> {
     let mut iv = vec![0u8; size];
@@ -152,6 +152,9 @@ pub enum KeyDerivationError {
     /// Secp256k1 internal error
     #[fail(display = "Error in secp256k1 crate")]
     Secp256k1Error(secp256k1::Error),
+    /// Hardened keys can only be derived from a private key
+    #[fail(display = "Hardened keys cannot be derived from a public key")]
+    HardenedFromPublicKey,
 }
 
 /// Secret Key
@@ -342,6 +345,111 @@ impl ExtendedPK {
             chain_code: chain_code.clone(),
         }
     }
+
+    /// Create a new extended public key from the given slip32-encoded string.
+    pub fn from_slip32(slip32: &str) -> Result<(Self, KeyPath), KeyError> {
+        let (hrp, data) = bech32::decode(slip32).map_err(KeyError::deserialization_err)?;
+
+        if hrp.as_str() != "xpub" {
+            return Err(KeyError::Deserialization(failure::format_err!(
+                "prefix is not \"xpub\""
+            )));
+        }
+
+        let bytes: Vec<u8> =
+            FromBase32::from_base32(&data).map_err(KeyError::deserialization_err)?;
+        let actual_len = bytes.len();
+        let mut cursor = io::Cursor::new(bytes);
+        let depth = cursor.read_u8()? as usize;
+        let len = depth * 4;
+        let expected_len = len + 66; // 66 = 1 (depth) 32 (chain code) + 33 (public key)
+
+        if expected_len != actual_len {
+            return Err(KeyError::Deserialization(failure::format_err!(
+                "invalid data length, expected: {}, got: {}",
+                expected_len,
+                actual_len
+            )));
+        }
+
+        let mut path = vec![0; depth];
+        cursor.read_u32_into::<BigEndian>(path.as_mut())?;
+
+        let mut chain_code = Protected::new(vec![0; 32]);
+        cursor.read_exact(chain_code.as_mut())?;
+
+        let mut public = [0; 33];
+        cursor.read_exact(&mut public)?;
+
+        let key = PK::from_slice(&public)?;
+        let extended_pk = Self { key, chain_code };
+
+        Ok((extended_pk, path.into()))
+    }
+
+    /// Serialize the key following the SLIP32 spec.
+    ///
+    /// See https://github.com/satoshilabs/slips/blob/master/slip-0032.md#serialization-format
+    pub fn to_slip32(&self, path: &KeyPath) -> Result<String, KeyError> {
+        let depth = path.depth();
+        let depth = u8::try_from(depth).map_err(|_| {
+            KeyError::Serialization(failure::format_err!(
+                "path depth '{}' is greater than 255",
+                depth,
+            ))
+        })?;
+
+        let capacity = 1     // 1 byte for depth
+            + 4 * depth      // 4 * depth bytes for path
+            + 32             // 32 bytes for chain code
+            + 33             // 33 bytes for the compressed public key
+            ;
+        let mut bytes = vec![0; usize::from(capacity)];
+        let mut slice = bytes.as_mut_slice();
+
+        slice.write_all(&[depth])?;
+        for index in path.iter() {
+            slice.write_all(&index.as_ref().to_be_bytes())?;
+        }
+        slice.write_all(self.chain_code.as_ref())?;
+        slice.write_all(&self.key.serialize())?;
+
+        let encoded =
+            bech32::encode("xpub", bytes.to_base32()).map_err(KeyError::serialization_err)?;
+
+        Ok(encoded)
+    }
+
+    /// Try to derive an extended public key from a given path. Only non-hardened paths can be
+    /// derived from a public key.
+    pub fn derive(&self, path: &KeyPath) -> Result<ExtendedPK, KeyDerivationError> {
+        let mut extended_pk = self.clone();
+        for index in path.iter() {
+            extended_pk = extended_pk.child(index)?
+        }
+
+        Ok(extended_pk)
+    }
+
+    /// Try to get a public child key from parent
+    pub fn child(&self, index: &KeyPathIndex) -> Result<ExtendedPK, KeyDerivationError> {
+        if index.is_hardened() {
+            return Err(KeyDerivationError::HardenedFromPublicKey);
+        }
+
+        let mut hmac512: Hmac<sha2::Sha512> =
+            Hmac::new_varkey(&self.chain_code).map_err(|_| KeyDerivationError::InvalidKeyLength)?;
+        let index_bytes = index.as_ref().to_be_bytes();
+        hmac512.input(&self.key.serialize());
+
+        let (chain_code, tweak) = get_chain_code_and_secret(&index_bytes, hmac512)?;
+
+        let mut key = self.key;
+        key.add_exp_assign(secp256k1::SECP256K1, &tweak[..])
+            .map_err(KeyDerivationError::Secp256k1Error)?;
+
+        Ok(ExtendedPK { key, chain_code })
+    }
 }
 
 impl From<ExtendedPK> for PK {
@@ -586,4 +694,43 @@ mod tests {
             assert_eq!(key, recovered_key);
         }
     }
+
+    #[test]
+    fn test_public_key_derivation() {
+        let seed = [0; 32];
+        let master_key = MasterKeyGen::new(seed).generate().unwrap();
+        let account_path = KeyPath::default().hardened(3).hardened(4919).hardened(0);
+        let account_key = master_key.derive(&account_path).unwrap();
+        let account_pk = ExtendedPK::from_secret_key(&account_key);
+
+        // Deriving non-hardened children from the public key matches the private derivation
+        let path = KeyPath::default().index(0).index(7);
+        let expected = ExtendedPK::from_secret_key(&account_key.derive(&path).unwrap());
+        assert_eq!(account_pk.derive(&path).unwrap(), expected);
+
+        // Hardened children cannot be derived from a public key
+        assert_eq!(
+            account_pk.derive(&KeyPath::default().hardened(0)),
+            Err(KeyDerivationError::HardenedFromPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_public_key_slip32() {
+        let seed = [0; 32];
+        let master_key = MasterKeyGen::new(seed).generate().unwrap();
+        let path = KeyPath::default().hardened(3).hardened(4919).hardened(0);
+        let key = ExtendedPK::from_secret_key(&master_key.derive(&path).unwrap());
+
+        let slip32 = key.to_slip32(&path).unwrap();
+        assert!(slip32.starts_with("xpub1"));
+
+        let (recovered_key, recovered_path) = ExtendedPK::from_slip32(&slip32).unwrap();
+        assert_eq!(recovered_key, key);
+        assert_eq!(recovered_path, path);
+
+        // Extended private keys are not valid extended public keys
+        let xprv = master_key.to_slip32(&KeyPath::default()).unwrap();
+        assert!(ExtendedPK::from_slip32(&xprv).is_err());
+    }
 }
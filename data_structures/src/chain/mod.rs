@@ -19,6 +19,7 @@ use bech32::{FromBase32, ToBase32};
 use bls_signatures_rs::{bn256, bn256::Bn256, MultiSignature};
 use failure::Fail;
 use futures::future::BoxFuture;
+use itertools::Either;
 use ordered_float::OrderedFloat;
 use partial_struct::PartialStruct;
 use serde::{Deserialize, Serialize};
@@ -97,7 +98,7 @@ pub enum StateMachine {
 }
 
 /// Node synchronization status
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SyncStatus {
     /// The hash of the top consolidated block and the epoch of that block
     pub chain_beacon: CheckpointBeacon,
@@ -1303,6 +1304,22 @@ impl PublicKeyHash {
     }
 }
 
+/// Validate a bech32 Witnet address for the given environment and return its `PublicKeyHash`.
+///
+/// Surrounding whitespace is ignored, and so is the case of the address as long as it is not
+/// mixed, so `pkh_to_address` can be used on the result to obtain the normalized address.
+pub fn address_to_pkh(
+    environment: Environment,
+    address: &str,
+) -> Result<PublicKeyHash, PublicKeyHashParseError> {
+    PublicKeyHash::from_bech32(environment, address.trim())
+}
+
+/// Encode a `PublicKeyHash` as a bech32 Witnet address for the given environment.
+pub fn pkh_to_address(environment: Environment, pkh: &PublicKeyHash) -> String {
+    pkh.bech32(environment)
+}
+
 /// Input data structure
 #[derive(
     Debug, Default, Eq, PartialEq, Copy, Clone, Serialize, Deserialize, ProtobufConvert, Hash,
@@ -1645,11 +1662,17 @@ pub enum RADType {
     /// HTTP POST request
     #[serde(rename = "HTTP-POST")]
     HttpPost,
+    /// HTTP HEAD request, whose script gets the response headers as input
+    #[serde(rename = "HTTP-HEAD")]
+    HttpHead,
 }
 
 impl RADType {
     pub fn is_http(&self) -> bool {
-        matches!(self, RADType::HttpGet | RADType::HttpPost)
+        matches!(
+            self,
+            RADType::HttpGet | RADType::HttpPost | RADType::HttpHead
+        )
     }
 }
 
@@ -1701,7 +1724,7 @@ pub struct RADRetrieve {
     pub script: Vec<u8>,
     /// Body of a HTTP-POST request
     pub body: Vec<u8>,
-    /// Extra headers of a HTTP-GET or HTTP-POST request
+    /// Extra headers of a HTTP-GET, HTTP-POST or HTTP-HEAD request
     pub headers: Vec<(String, String)>,
 }
 
@@ -1810,6 +1833,9 @@ impl RADRetrieve {
                     &[Field::Body, Field::Headers],
                 )
             }
+            RADType::HttpHead => {
+                check(&[Field::Kind, Field::Url, Field::Script], &[Field::Headers])
+            }
         }
     }
 
@@ -2785,6 +2811,39 @@ impl TransactionsPool {
             .filter_map(move |(_, h)| self.dr_transactions.get(h).map(|(_, t)| t))
     }
 
+    /// List all the value transfer and data request transactions in the pool along with their
+    /// priority (fee per weight unit), sorted by priority in descending order. Transactions with
+    /// the same priority are sorted by hash.
+    ///
+    /// Only references are sorted, and each transaction is cloned when the iterator reaches it, so
+    /// taking the first few transactions does not clone the whole pool.
+    pub fn sorted_by_priority(&self) -> impl Iterator<Item = (f64, Transaction)> + '_ {
+        let mut prioritized: Vec<(
+            OrderedFloat<f64>,
+            &Hash,
+            Either<&VTTransaction, &DRTransaction>,
+        )> = self
+            .vt_transactions
+            .iter()
+            .map(|(h, (priority, t))| (*priority, h, Either::Left(t)))
+            .chain(
+                self.dr_transactions
+                    .iter()
+                    .map(|(h, (priority, t))| (*priority, h, Either::Right(t))),
+            )
+            .collect();
+        prioritized.sort_unstable_by(|(p1, h1, _), (p2, h2, _)| p2.cmp(p1).then(h1.cmp(h2)));
+
+        prioritized.into_iter().map(|(priority, _, t)| {
+            let transaction = match t {
+                Either::Left(vt) => Transaction::ValueTransfer(vt.clone()),
+                Either::Right(dr) => Transaction::DataRequest(dr.clone()),
+            };
+
+            (priority.into_inner(), transaction)
+        })
+    }
+
     /// Returns a reference to the value corresponding to the key.
     ///
     /// Examples:
@@ -4567,6 +4626,45 @@ mod tests {
         assert!(PublicKeyHash::from_bech32(Environment::Mainnet, addr_testnet).is_err());
     }
 
+    #[test]
+    fn address_to_pkh_valid() {
+        let addr = "wit1gdm8mqlz8lxtj05w05mw63jvecyenvua7ajdk5";
+        let pkh = PublicKeyHash::from_hex("43767d83e23fccb93e8e7d36ed464cce0999b39d").unwrap();
+
+        assert_eq!(address_to_pkh(Environment::Mainnet, addr).unwrap(), pkh);
+        assert_eq!(pkh_to_address(Environment::Mainnet, &pkh), addr);
+
+        // Uppercase addresses and surrounding whitespace are normalized
+        let upper = format!("  {}\n", addr.to_uppercase());
+        let normalized = pkh_to_address(
+            Environment::Mainnet,
+            &address_to_pkh(Environment::Mainnet, &upper).unwrap(),
+        );
+        assert_eq!(normalized, addr);
+    }
+
+    #[test]
+    fn address_to_pkh_wrong_checksum() {
+        let addr = "wit1gdm8mqlz8lxtj05w05mw63jvecyenvua7ajdk4";
+
+        assert!(matches!(
+            address_to_pkh(Environment::Mainnet, addr),
+            Err(PublicKeyHashParseError::Bech32(
+                bech32::Error::InvalidChecksum
+            ))
+        ));
+    }
+
+    #[test]
+    fn address_to_pkh_wrong_prefix() {
+        let addr_testnet = "twit1gdm8mqlz8lxtj05w05mw63jvecyenvuasgmfk9";
+
+        assert!(matches!(
+            address_to_pkh(Environment::Mainnet, addr_testnet),
+            Err(PublicKeyHashParseError::WrongEnvironment { prefix, .. }) if prefix == "twit"
+        ));
+    }
+
     #[test]
     fn transactions_pool_contains_commit_no_signatures() {
         let transactions_pool = TransactionsPool::default();
@@ -5016,6 +5114,50 @@ mod tests {
         assert!(!transactions_pool.contains(&vt2).unwrap());
     }
 
+    #[test]
+    fn transactions_pool_size_limit_evicts_lowest_priority() {
+        // Transactions with the same weight, spending different inputs
+        let vts: Vec<Transaction> = (0..5)
+            .map(|i| {
+                Transaction::ValueTransfer(VTTransaction::new(
+                    VTTransactionBody::new(
+                        vec![Input::new(OutputPointer {
+                            transaction_id: Hash::default(),
+                            output_index: i,
+                        })],
+                        vec![ValueTransferOutput::default()],
+                    ),
+                    vec![],
+                ))
+            })
+            .collect();
+        let vt_size = match &vts[0] {
+            Transaction::ValueTransfer(vt) => u64::from(vt.weight()),
+            _ => unreachable!(),
+        };
+
+        // Room for 3 transactions
+        let mut transactions_pool = TransactionsPool::default();
+        let _removed = transactions_pool.set_total_weight_limit(3 * vt_size, 1.0);
+        let fees = [30, 10, 50, 20, 40];
+        let mut removed = vec![];
+        for (vt, fee) in vts.iter().zip(fees) {
+            removed.extend(transactions_pool.insert(vt.clone(), fee));
+        }
+
+        // The cheapest transactions are evicted, in the order they became the cheapest
+        assert_eq!(removed, vec![vts[1].clone(), vts[3].clone()]);
+        assert_eq!(transactions_pool.vt_len(), 3);
+        for i in [0, 2, 4] {
+            assert!(transactions_pool.contains(&vts[i]).unwrap());
+        }
+
+        // A transaction cheaper than all the pooled ones is rejected
+        let removed = transactions_pool.insert(vts[1].clone(), 1);
+        assert_eq!(removed, vec![vts[1].clone()]);
+        assert_eq!(transactions_pool.vt_len(), 3);
+    }
+
     #[allow(clippy::cast_precision_loss)]
     #[test]
     fn transactions_pool_size_limit_vt_to_dr_ratio() {
@@ -5137,6 +5279,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transactions_pool_sorted_by_priority() {
+        let output = |value| ValueTransferOutput {
+            value,
+            ..Default::default()
+        };
+        let vt1 = Transaction::ValueTransfer(VTTransaction::new(
+            VTTransactionBody::new(vec![Input::default()], vec![output(1)]),
+            vec![],
+        ));
+        // vt2 weighs the same as vt1, while the extra output makes vt3 heavier
+        let vt2 = Transaction::ValueTransfer(VTTransaction::new(
+            VTTransactionBody::new(vec![Input::default()], vec![output(2)]),
+            vec![],
+        ));
+        let vt3 = Transaction::ValueTransfer(VTTransaction::new(
+            VTTransactionBody::new(vec![Input::default()], vec![output(3), output(3)]),
+            vec![],
+        ));
+        let dr1 = Transaction::DataRequest(DRTransaction::new(
+            DRTransactionBody::new(vec![], vec![output(1)], DataRequestOutput::default()),
+            vec![],
+        ));
+        let weight = |t: &Transaction| match t {
+            Transaction::ValueTransfer(vt) => vt.weight(),
+            Transaction::DataRequest(dr) => dr.weight(),
+            _ => unreachable!(),
+        };
+        assert!(weight(&vt3) > weight(&vt1));
+
+        let mut transactions_pool = TransactionsPool::default();
+        // vt3 pays the highest absolute fee but, being heavier, has a lower priority than vt2
+        for (t, fee) in [
+            (vt1.clone(), 100),
+            (vt2.clone(), 1_000),
+            (vt3.clone(), 1_001),
+            (dr1.clone(), 1_000_000),
+        ] {
+            assert!(transactions_pool.insert(t, fee).is_empty());
+        }
+
+        let sorted: Vec<_> = transactions_pool.sorted_by_priority().collect();
+        let sorted_transactions: Vec<_> = sorted.iter().map(|(_, t)| t.clone()).collect();
+        assert_eq!(
+            sorted_transactions,
+            vec![dr1.clone(), vt2, vt3, vt1.clone()]
+        );
+        // The priority is the fee per weight unit, in descending order
+        assert_eq!(sorted[0].0, 1_000_000.0 / f64::from(weight(&dr1)));
+        assert_eq!(sorted[3].0, 100.0 / f64::from(weight(&vt1)));
+        assert!(sorted.windows(2).all(|w| w[0].0 >= w[1].0));
+    }
+
     #[test]
     fn transactions_pool_minimum_vtt_fee() {
         let input = Input::default();
@@ -124,6 +124,7 @@ pub fn all_wips_active() -> ActiveWips {
     active_wips.active_wips.insert("WIP0025".to_string(), 0);
     active_wips.active_wips.insert("WIP0026".to_string(), 0);
     active_wips.active_wips.insert("WIP0027".to_string(), 0);
+    active_wips.active_wips.insert("WIP0028".to_string(), 0);
 
     active_wips
 }
@@ -577,6 +578,12 @@ impl ActiveWips {
         self.wip_active("WIP0027")
     }
 
+    // WIP0028 enables the RADON extensions added after WIP0027. It has not been scheduled for
+    // activation yet, so it is only active in tests
+    pub fn wip0028(&self) -> bool {
+        self.wip_active("WIP0028")
+    }
+
     /// Convenience method for inserting WIPs.
     pub fn insert_wip(&mut self, wip: &str, activation_epoch: Epoch) {
         self.active_wips.insert(String::from(wip), activation_epoch);
@@ -368,6 +368,36 @@ impl DataRequestPool {
         self.data_request_pool.get(dr_pointer)
     }
 
+    /// Get the pointers and current stages of all the active data requests in which the identity
+    /// `pkh` has committed or revealed, sorted by data request pointer.
+    pub fn data_requests_by_witness(&self, pkh: &PublicKeyHash) -> Vec<(Hash, DataRequestStage)> {
+        let mut data_requests: Vec<_> = self
+            .data_request_pool
+            .iter()
+            .filter(|(_dr_pointer, dr_state)| {
+                dr_state.info.commits.contains_key(pkh) || dr_state.info.reveals.contains_key(pkh)
+            })
+            .map(|(dr_pointer, dr_state)| (*dr_pointer, dr_state.stage))
+            .collect();
+        data_requests.sort_unstable_by_key(|(dr_pointer, _stage)| *dr_pointer);
+
+        data_requests
+    }
+
+    /// Count the active data requests in each stage, returned as `(commit, reveal, tally)`.
+    pub fn count_by_stage(&self) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for dr_state in self.data_request_pool.values() {
+            match dr_state.stage {
+                DataRequestStage::COMMIT => counts.0 += 1,
+                DataRequestStage::REVEAL => counts.1 += 1,
+                DataRequestStage::TALLY => counts.2 += 1,
+            }
+        }
+
+        counts
+    }
+
     /// Get the data request info of the finished data requests, to be persisted to the storage
     pub fn finished_data_requests(&mut self) -> Vec<DataRequestInfo> {
         std::mem::take(&mut self.to_be_stored)
@@ -1415,4 +1445,120 @@ mod tests {
             DataRequestStage::REVEAL
         );
     }
+
+    #[test]
+    fn data_requests_by_witness() {
+        let fake_block_hash = Hash::SHA256([1; 32]);
+        let epoch = 0;
+        let witness_signature = KeyedSignature {
+            public_key: PublicKey {
+                compressed: 2,
+                bytes: [7; 32],
+            },
+            ..KeyedSignature::default()
+        };
+        let witness_pkh = witness_signature.public_key.pkh();
+
+        let mut p = DataRequestPool::default();
+        let dr_pointers: Vec<Hash> = (1..=3)
+            .map(|witnesses| {
+                let dr_output = DataRequestOutput {
+                    witnesses,
+                    ..DataRequestOutput::default()
+                };
+                let dr_transaction = DRTransaction::new(
+                    DRTransactionBody::new(vec![Input::default()], vec![], dr_output),
+                    vec![KeyedSignature::default()],
+                );
+                p.process_data_request(&dr_transaction, epoch, &fake_block_hash)
+                    .unwrap();
+
+                dr_transaction.hash()
+            })
+            .collect();
+
+        // Nothing committed yet
+        assert!(p.data_requests_by_witness(&witness_pkh).is_empty());
+
+        // Our witness commits to the first two data requests, someone else commits to the third
+        for (dr_pointer, signature) in dr_pointers.iter().zip(vec![
+            witness_signature.clone(),
+            witness_signature,
+            KeyedSignature::default(),
+        ]) {
+            let commit_transaction = CommitTransaction::new(
+                CommitTransactionBody::without_collateral(
+                    *dr_pointer,
+                    Hash::default(),
+                    DataRequestEligibilityClaim::default(),
+                ),
+                vec![signature],
+            );
+            p.process_commit(&commit_transaction, &fake_block_hash)
+                .unwrap();
+        }
+
+        let mut expected = vec![
+            (dr_pointers[0], DataRequestStage::COMMIT),
+            (dr_pointers[1], DataRequestStage::COMMIT),
+        ];
+        expected.sort_unstable_by_key(|(dr_pointer, _stage)| *dr_pointer);
+        assert_eq!(p.data_requests_by_witness(&witness_pkh), expected);
+    }
+
+    #[test]
+    fn count_by_stage() {
+        let fake_block_hash = Hash::SHA256([1; 32]);
+        let epoch = 0;
+        let new_data_request = |witnesses| {
+            let dr_output = DataRequestOutput {
+                witnesses,
+                ..DataRequestOutput::default()
+            };
+            DRTransaction::new(
+                DRTransactionBody::new(vec![Input::default()], vec![], dr_output),
+                vec![KeyedSignature::default()],
+            )
+        };
+
+        let mut p = DataRequestPool::default();
+        assert_eq!(p.count_by_stage(), (0, 0, 0));
+
+        let dr_pointers: Vec<Hash> = (1..=3)
+            .map(|witnesses| {
+                let dr_transaction = new_data_request(witnesses);
+                p.process_data_request(&dr_transaction, epoch, &fake_block_hash)
+                    .unwrap();
+
+                dr_transaction.hash()
+            })
+            .collect();
+        assert_eq!(p.count_by_stage(), (3, 0, 0));
+
+        // The first two data requests get one commit each, the third one gets no commits
+        for dr_pointer in &dr_pointers[..2] {
+            let commit_transaction = CommitTransaction::new(
+                CommitTransactionBody::without_collateral(
+                    *dr_pointer,
+                    Hash::default(),
+                    DataRequestEligibilityClaim::default(),
+                ),
+                vec![KeyedSignature::default()],
+            );
+            p.process_commit(&commit_transaction, &fake_block_hash)
+                .unwrap();
+        }
+        p.update_data_request_stages();
+        // A new data request in the next block starts in commit stage
+        p.process_data_request(&new_data_request(4), epoch + 1, &fake_block_hash)
+            .unwrap();
+        assert_eq!(p.count_by_stage(), (1, 2, 1));
+
+        // Resolved data requests are removed from the pool
+        let tally_transaction =
+            TallyTransaction::new(dr_pointers[2], vec![], vec![], vec![], vec![]);
+        p.process_tally(&tally_transaction, &fake_block_hash)
+            .unwrap();
+        assert_eq!(p.count_by_stage(), (1, 2, 0));
+    }
 }
@@ -129,6 +129,33 @@ pub enum TransactionError {
         tx_hash, output_id
     )]
     ZeroValueOutput { tx_hash: Hash, output_id: usize },
+    /// A transaction cannot be built because one of the requested outputs has zero value
+    #[fail(
+        display = "Cannot build a transaction. The output at index {} has zero value",
+        output_id
+    )]
+    ZeroValueOutputRequested { output_id: usize },
+    /// A transaction cannot be built because one of the requested outputs is worth less than the
+    /// fee needed to spend it
+    #[fail(
+        display = "Cannot build a transaction. The output at index {} has value {}, which is lower than the fee needed to spend it ({})",
+        output_id, value, dust_threshold
+    )]
+    DustOutputRequested {
+        output_id: usize,
+        value: u64,
+        dust_threshold: u64,
+    },
+    /// A transaction cannot be built because the inputs needed to pay for it exceed the weight limit
+    #[fail(
+        display = "Cannot build a transaction. The {} inputs needed to pay for it make its weight ({}) exceed the limit ({})",
+        inputs, weight, max_weight
+    )]
+    TooManyInputs {
+        inputs: usize,
+        weight: u32,
+        max_weight: u32,
+    },
     /// A dishonest witness has been rewarded
     #[fail(display = "A dishonest witness has been rewarded")]
     DishonestReward,
@@ -9,6 +9,11 @@ use crate::{chain::priority::Priority, wit::Wit};
 pub struct AbsoluteFee(Wit);
 
 impl AbsoluteFee {
+    #[inline]
+    pub fn from_nanowits(nanowits: u64) -> Self {
+        Self(Wit::from_nanowits(nanowits))
+    }
+
     #[inline]
     pub fn as_nanowits(&self) -> u64 {
         self.0.nanowits()
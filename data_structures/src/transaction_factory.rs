@@ -4,6 +4,7 @@ use std::{
     ops::AddAssign,
 };
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -108,6 +109,7 @@ pub trait OutputsCollection {
         let mut total: u64 = 0;
         let mut outputs = vec![];
         let mut pointers = vec![];
+        let minimize_change = matches!(utxo_strategy, UtxoSelectionStrategy::MinimizeChange { .. });
 
         let utxo_iter = self.sort_by(utxo_strategy);
 
@@ -140,11 +142,26 @@ pub trait OutputsCollection {
             pointers.push(*pointer);
             outputs.push(output);
 
-            if total_value >= amount {
+            // The minimize change strategy needs to know all the available UTXOs
+            if total_value >= amount && !minimize_change {
                 break;
             }
         }
 
+        if total_value >= amount && minimize_change {
+            let candidates: Vec<(OutputPointer, u64)> = pointers
+                .iter()
+                .zip(outputs.iter())
+                .map(|(pointer, output)| (*pointer, output.value))
+                .collect();
+            let selected = minimize_change_selection(&candidates, amount)
+                .expect("the available UTXOs are enough to cover the amount");
+
+            pointers = selected.iter().map(|i| candidates[*i].0).collect();
+            outputs = selected.iter().map(|i| outputs[*i].clone()).collect();
+            total_value = selected.iter().map(|i| candidates[*i].1).sum();
+        }
+
         if total_value >= amount {
             Ok(CollectedOutputs {
                 resolved: outputs,
@@ -196,6 +213,12 @@ pub trait OutputsCollection {
 
                 let inputs =
                     self.take_enough_utxos(amount, timestamp, block_number_limit, utxo_strategy)?;
+                inputs_weight(
+                    inputs.pointers.len(),
+                    outputs.len() + 1,
+                    dr_output,
+                    max_weight,
+                )?;
 
                 Ok(TransactionInfo {
                     fee: absolute_fee,
@@ -219,7 +242,7 @@ pub trait OutputsCollection {
                         utxo_strategy,
                     )?;
 
-                    let new_weight = calculate_weight(
+                    let new_weight = inputs_weight(
                         inputs.pointers.len(),
                         outputs.len() + 1,
                         dr_output,
@@ -243,6 +266,89 @@ pub trait OutputsCollection {
     }
 }
 
+/// Maximum number of steps of the search performed by `minimize_change_selection`
+const MINIMIZE_CHANGE_MAX_STEPS: u32 = 100_000;
+
+/// Select the subset of `candidates` whose sum is the closest to `amount` without going below it,
+/// preferring the subset with less inputs in case of tie.
+///
+/// The candidates are explored in descending order of value, with ties broken by output pointer,
+/// so the result does not depend on the order of `candidates`. The first subset found is the one
+/// the `BigFirst` strategy would select, and the search is stopped after
+/// `MINIMIZE_CHANGE_MAX_STEPS` steps, keeping the best subset found until then.
+///
+/// Returns the indexes of the selected candidates, or None if the sum of all the candidates is
+/// lower than `amount`.
+pub fn minimize_change_selection(
+    candidates: &[(OutputPointer, u64)],
+    amount: u64,
+) -> Option<Vec<usize>> {
+    let order: Vec<usize> = (0..candidates.len())
+        .sorted_by(|a, b| {
+            let (pointer_a, value_a) = &candidates[*a];
+            let (pointer_b, value_b) = &candidates[*b];
+
+            value_b.cmp(value_a).then(pointer_a.cmp(pointer_b))
+        })
+        .collect();
+    let values: Vec<u64> = order.iter().map(|i| candidates[*i].1).collect();
+    // remaining[i] is the sum of all the values starting from position i
+    let mut remaining = vec![0; values.len() + 1];
+    for i in (0..values.len()).rev() {
+        remaining[i] = remaining[i + 1] + values[i];
+    }
+
+    // Depth-first search where each candidate is first included and then excluded
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut selected: Vec<usize> = vec![];
+    let mut sum = 0;
+    let mut position = 0;
+    for _ in 0..MINIMIZE_CHANGE_MAX_STEPS {
+        let backtrack = if sum >= amount {
+            let change = sum - amount;
+            let improves = match &best {
+                None => true,
+                Some((best_change, best_selected)) => {
+                    change < *best_change
+                        || (change == *best_change && selected.len() < best_selected.len())
+                }
+            };
+            if improves {
+                best = Some((change, selected.clone()));
+            }
+
+            true
+        } else if position >= values.len() || sum + remaining[position] < amount {
+            true
+        } else {
+            // At least one more input is needed, so this branch cannot improve an exact match
+            // with the same number of inputs
+            match &best {
+                Some((0, best_selected)) if selected.len() + 1 >= best_selected.len() => true,
+                _ => {
+                    selected.push(position);
+                    sum += values[position];
+                    position += 1;
+
+                    false
+                }
+            }
+        };
+
+        if backtrack {
+            match selected.pop() {
+                Some(last) => {
+                    sum -= values[last];
+                    position = last + 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    best.map(|(_change, selected)| selected.into_iter().map(|i| order[i]).collect())
+}
+
 /// Calculate weight from inputs and outputs information
 pub fn calculate_weight(
     inputs_count: usize,
@@ -281,6 +387,28 @@ pub fn calculate_weight(
     Ok(weight)
 }
 
+/// Calculate the weight of a transaction once its inputs have been selected.
+///
+/// Exceeding the value transfer weight limit at this point means that too many inputs are needed
+/// to pay for the transaction, so that error is reported as `TooManyInputs`.
+fn inputs_weight(
+    inputs_count: usize,
+    outputs_count: usize,
+    dro: Option<&DataRequestOutput>,
+    max_weight: u32,
+) -> Result<u32, TransactionError> {
+    calculate_weight(inputs_count, outputs_count, dro, max_weight).map_err(|e| match e {
+        TransactionError::ValueTransferWeightLimitExceeded { weight, max_weight } => {
+            TransactionError::TooManyInputs {
+                inputs: inputs_count,
+                weight,
+                max_weight,
+            }
+        }
+        e => e,
+    })
+}
+
 /// Get total balance
 pub fn get_total_balance(
     all_utxos: &UnspentOutputsPool,
@@ -346,6 +474,29 @@ pub fn build_vtt(
     max_weight: u32,
     dry_run: bool,
 ) -> Result<VTTransactionBody, TransactionError> {
+    // The outputs are validated before selecting any UTXOs, so that an invalid output is reported
+    // even if the balance is not enough to pay for it.
+    // Outputs with zero value are invalid, so the transaction would be rejected by other nodes
+    if let Some(output_id) = outputs.iter().position(|output| output.value == 0) {
+        return Err(TransactionError::ZeroValueOutputRequested { output_id });
+    }
+    // With a relative fee, outputs worth less than the fee needed to spend them are dust: they
+    // could never be used as inputs of a transaction with the same priority
+    if let Fee::Relative(priority) = fee {
+        let dust_threshold = priority.into_absolute(INPUT_SIZE).as_nanowits();
+        if let Some((output_id, output)) = outputs
+            .iter()
+            .enumerate()
+            .find(|(_, output)| output.value < dust_threshold)
+        {
+            return Err(TransactionError::DustOutputRequested {
+                output_id,
+                value: output.value,
+                dust_threshold,
+            });
+        }
+    }
+
     let mut utxos = NodeUtxos {
         all_utxos,
         own_utxos,
@@ -1075,6 +1226,242 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_vtt_typed_errors() {
+        let own_pkh = my_pkh();
+        let outputs = vec![pay_me(1000)];
+        let (mut own_utxos, all_utxos) = build_utxo_set(outputs, None, vec![]);
+
+        // Insufficient balance reports the needed and available amounts
+        assert_eq!(
+            build_vtt_tx(
+                vec![pay_bob(900)],
+                Fee::absolute_from_nanowits(200),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos
+            )
+            .unwrap_err(),
+            TransactionError::NoMoney {
+                total_balance: 1000,
+                available_balance: 1000,
+                transaction_value: 1100
+            }
+        );
+
+        // Zero value outputs are rejected, and the UTXOs are not marked as used
+        assert_eq!(
+            build_vtt_tx(
+                vec![pay_bob(500), pay_alice(0)],
+                Fee::absolute_from_nanowits(10),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos
+            )
+            .unwrap_err(),
+            TransactionError::ZeroValueOutputRequested { output_id: 1 }
+        );
+        // Invalid outputs are reported even if the balance is not enough
+        assert_eq!(
+            build_vtt_tx(
+                vec![pay_bob(5000), pay_alice(0)],
+                Fee::absolute_from_nanowits(10),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos
+            )
+            .unwrap_err(),
+            TransactionError::ZeroValueOutputRequested { output_id: 1 }
+        );
+        assert!(build_vtt_tx(
+            vec![pay_bob(500)],
+            Fee::absolute_from_nanowits(10),
+            &mut own_utxos,
+            own_pkh,
+            &all_utxos
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn build_vtt_dust_output() {
+        let own_pkh = my_pkh();
+        let outputs = vec![pay_me(10_000)];
+        let (mut own_utxos, all_utxos) = build_utxo_set(outputs, None, vec![]);
+
+        // With a priority of 1 nanowit per weight unit, spending an output costs INPUT_SIZE
+        assert_eq!(
+            build_vtt_tx(
+                vec![pay_bob(500), pay_alice(u64::from(INPUT_SIZE) - 1)],
+                Fee::relative_from_float(1),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos
+            )
+            .unwrap_err(),
+            TransactionError::DustOutputRequested {
+                output_id: 1,
+                value: u64::from(INPUT_SIZE) - 1,
+                dust_threshold: u64::from(INPUT_SIZE),
+            }
+        );
+        // Dust outputs are reported even if the balance is not enough
+        assert_eq!(
+            build_vtt_tx(
+                vec![pay_bob(50_000), pay_alice(u64::from(INPUT_SIZE) - 1)],
+                Fee::relative_from_float(1),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos
+            )
+            .unwrap_err(),
+            TransactionError::DustOutputRequested {
+                output_id: 1,
+                value: u64::from(INPUT_SIZE) - 1,
+                dust_threshold: u64::from(INPUT_SIZE),
+            }
+        );
+        // Outputs reaching the threshold are not dust, and with an absolute fee there is no
+        // threshold
+        assert!(build_vtt_tx(
+            vec![pay_bob(500), pay_alice(u64::from(INPUT_SIZE))],
+            Fee::relative_from_float(1),
+            &mut own_utxos,
+            own_pkh,
+            &all_utxos
+        )
+        .is_ok());
+        assert!(build_vtt_tx(
+            vec![pay_bob(500), pay_alice(u64::from(INPUT_SIZE) - 1)],
+            Fee::absolute_from_nanowits(10),
+            &mut own_utxos,
+            own_pkh,
+            &all_utxos
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn build_vtt_too_many_inputs() {
+        let own_pkh = my_pkh();
+        let outputs = vec![pay_me(10); 200];
+        let (mut own_utxos, all_utxos) = build_utxo_set(outputs, None, vec![]);
+
+        // Paying 1800 nanowits needs 180 inputs, which exceed the weight limit
+        let err = build_vtt_tx(
+            vec![pay_bob(1800)],
+            Fee::absolute_from_nanowits(0),
+            &mut own_utxos,
+            own_pkh,
+            &all_utxos,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TransactionError::TooManyInputs {
+                inputs: 180,
+                weight: 180 * INPUT_SIZE + 2 * OUTPUT_SIZE * GAMMA,
+                max_weight: MAX_VT_WEIGHT,
+            }
+        );
+
+        // The UTXOs are not marked as used, so a smaller transaction can still be built
+        assert!(build_vtt_tx(
+            vec![pay_bob(1000)],
+            Fee::absolute_from_nanowits(0),
+            &mut own_utxos,
+            own_pkh,
+            &all_utxos
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn minimize_change_strategy() {
+        let own_pkh = my_pkh();
+        let outputs = vec![
+            pay_me(1000),
+            pay_me(600),
+            pay_me(500),
+            pay_me(300),
+            pay_me(250),
+        ];
+        let (mut own_utxos, all_utxos) = build_utxo_set(outputs, None, vec![]);
+
+        let mut build_with_strategy = |utxo_strategy: UtxoSelectionStrategy| {
+            let vtt = build_vtt(
+                vec![pay_bob(850)],
+                Fee::absolute_from_nanowits(10),
+                &mut own_utxos,
+                own_pkh,
+                &all_utxos,
+                777,
+                100,
+                &utxo_strategy,
+                MAX_VT_WEIGHT,
+                true,
+            )
+            .unwrap();
+            let change: u64 = vtt
+                .outputs
+                .iter()
+                .filter(|output| output.pkh == own_pkh)
+                .map(|output| output.value)
+                .sum();
+
+            (vtt, change)
+        };
+
+        let (_, big_first_change) =
+            build_with_strategy(UtxoSelectionStrategy::BigFirst { from: None });
+        let (_, small_first_change) =
+            build_with_strategy(UtxoSelectionStrategy::SmallFirst { from: None });
+        let (vtt, minimize_change) =
+            build_with_strategy(UtxoSelectionStrategy::MinimizeChange { from: None });
+        assert_eq!(big_first_change, 140);
+        assert_eq!(small_first_change, 190);
+        // 600 + 300 is the closest sum to 850 + 10
+        assert_eq!(minimize_change, 40);
+        assert_eq!(vtt.inputs.len(), 2);
+
+        // The selection is deterministic
+        let (vtt2, _) = build_with_strategy(UtxoSelectionStrategy::MinimizeChange { from: None });
+        assert_eq!(vtt.inputs, vtt2.inputs);
+    }
+
+    #[test]
+    fn minimize_change_selection_prefers_exact_match_with_less_inputs() {
+        let candidates: Vec<(OutputPointer, u64)> = [100, 50, 40, 30, 20, 10]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                (
+                    OutputPointer {
+                        transaction_id: Hash::default(),
+                        output_index: i as u32,
+                    },
+                    *value,
+                )
+            })
+            .collect();
+
+        // 50 + 40 and 50 + 30 + 10 are both exact, but the first one needs less inputs
+        assert_eq!(minimize_change_selection(&candidates, 90), Some(vec![1, 2]));
+        // Not enough value, or all the candidates are needed
+        assert_eq!(minimize_change_selection(&candidates, 251), None);
+        assert_eq!(
+            minimize_change_selection(&candidates, 250),
+            Some(vec![0, 1, 2, 3, 4, 5])
+        );
+        // Changing the order of the candidates does not change the selected UTXOs
+        let mut reversed = candidates.clone();
+        reversed.reverse();
+        let selected = minimize_change_selection(&reversed, 90).unwrap();
+        let mut values: Vec<u64> = selected.iter().map(|i| reversed[*i].1).collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![40, 50]);
+    }
+
     #[test]
     fn poor_utxo() {
         let own_pkh = my_pkh();
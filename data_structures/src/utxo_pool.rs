@@ -410,7 +410,8 @@ impl<'a> OutputsCollection for NodeUtxosRef<'a> {
         }
 
         match strategy {
-            UtxoSelectionStrategy::BigFirst { from: _ } => {
+            UtxoSelectionStrategy::BigFirst { from: _ }
+            | UtxoSelectionStrategy::MinimizeChange { from: _ } => {
                 self.own_utxos.sort(self.all_utxos, true)
             }
             UtxoSelectionStrategy::SmallFirst { from: _ } => {
@@ -503,11 +504,15 @@ impl<'a> OutputsCollection for NodeUtxos<'a> {
 }
 
 /// Strategy to sort our own unspent outputs pool
+///
+/// `MinimizeChange` selects the UTXOs whose sum is the closest to the transaction value, in order
+/// to minimize the change output
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum UtxoSelectionStrategy {
     Random { from: Option<PublicKeyHash> },
     BigFirst { from: Option<PublicKeyHash> },
     SmallFirst { from: Option<PublicKeyHash> },
+    MinimizeChange { from: Option<PublicKeyHash> },
 }
 
 impl Default for UtxoSelectionStrategy {
@@ -525,6 +530,7 @@ impl UtxoSelectionStrategy {
             UtxoSelectionStrategy::Random { from } => from,
             UtxoSelectionStrategy::BigFirst { from } => from,
             UtxoSelectionStrategy::SmallFirst { from } => from,
+            UtxoSelectionStrategy::MinimizeChange { from } => from,
         }
     }
 
@@ -535,6 +541,7 @@ impl UtxoSelectionStrategy {
             UtxoSelectionStrategy::Random { from } => from,
             UtxoSelectionStrategy::BigFirst { from } => from,
             UtxoSelectionStrategy::SmallFirst { from } => from,
+            UtxoSelectionStrategy::MinimizeChange { from } => from,
         }
     }
 
@@ -629,6 +636,48 @@ pub fn get_utxo_info(
     }
 }
 
+/// Balance of an address, split according to which part of it can be spent or used as collateral
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BalanceBreakdown {
+    /// Sum of the values of all the UTXOs
+    pub total: u64,
+    /// Value that can be spent right now because it is not time-locked
+    pub available: u64,
+    /// Value that is still time-locked
+    pub locked: u64,
+    /// Value that can be used as collateral right now: it is not time-locked and it was included in
+    /// a block old enough according to the collateral age
+    pub collateralizable: u64,
+}
+
+/// Get the balance breakdown of `pkh`. The UTXOs included in blocks after `block_number_limit` are
+/// still maturing, so they cannot be used as collateral yet.
+pub fn get_balance_breakdown(
+    pkh: PublicKeyHash,
+    all_utxos: &UnspentOutputsPool,
+    block_number_limit: u32,
+    now: u64,
+) -> BalanceBreakdown {
+    let mut breakdown = BalanceBreakdown::default();
+    all_utxos.visit_with_pkh(
+        pkh,
+        |_| {},
+        |(_o, (vto, block_number))| {
+            breakdown.total += vto.value;
+            if vto.time_lock > now {
+                breakdown.locked += vto.value;
+            } else {
+                breakdown.available += vto.value;
+                if *block_number <= block_number_limit {
+                    breakdown.collateralizable += vto.value;
+                }
+            }
+        },
+    );
+
+    breakdown
+}
+
 /// Diffs to apply to an utxo set. This type does not contains a
 /// reference to the original utxo set.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -1,3 +1,21 @@
+use std::time::Duration;
+
+/// User-Agent presented to data sources when retrieving them over HTTP, unless overridden through
+/// configuration. It does not depend on the node version so that every node is seen the same way.
+pub const DEFAULT_USER_AGENT: &str = "witnet-rad";
+
+/// Maximum size in bytes of the body of an HTTP retrieval response, unless overridden through
+/// configuration (1 MiB).
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// Number of times that an HTTP request is attempted when retrieving a data source, unless
+/// overridden through configuration. Only network errors are retried.
+pub const DEFAULT_RETRIEVAL_ATTEMPTS: u8 = 1;
+
+/// Delay before retrying a failed HTTP request for the first time, unless overridden through
+/// configuration. It doubles with every new attempt.
+pub const DEFAULT_RETRIEVAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
 /// Holds witnessing configuration after it has been validated.
 ///
 /// This is ready to use with `witnet_node::actors::RadManager::from_config` or in
@@ -9,6 +27,10 @@ where
 {
     pub transports: Vec<Option<T>>,
     pub paranoid_threshold: f32,
+    pub user_agent: String,
+    pub max_response_size: usize,
+    pub retrieval_attempts: u8,
+    pub retrieval_retry_delay: Duration,
 }
 
 impl<T> Default for WitnessingConfig<T>
@@ -19,6 +41,10 @@ where
         Self {
             transports: vec![None],
             paranoid_threshold: 0.51,
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            retrieval_attempts: DEFAULT_RETRIEVAL_ATTEMPTS,
+            retrieval_retry_delay: DEFAULT_RETRIEVAL_RETRY_DELAY,
         }
     }
 }
@@ -28,6 +28,10 @@ fn serialize_utxo_selection_strategy_no_from() {
         UtxoSelectionStrategy::SmallFirst { from: None },
         r#""small_first""#,
     );
+    test_json_serialization(
+        UtxoSelectionStrategy::MinimizeChange { from: None },
+        r#""minimize_change""#,
+    );
 }
 #[test]
 fn serialize_utxo_selection_strategy_with_from() {
@@ -48,6 +52,10 @@ fn serialize_utxo_selection_strategy_with_from() {
         UtxoSelectionStrategy::SmallFirst { from: Some(my_pkh) },
         r#"{"strategy":"small_first","from":"wit1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqwrt3a4"}"#,
     );
+    test_json_serialization(
+        UtxoSelectionStrategy::MinimizeChange { from: Some(my_pkh) },
+        r#"{"strategy":"minimize_change","from":"wit1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqwrt3a4"}"#,
+    );
 }
 
 #[test]
@@ -3,8 +3,8 @@ use witnet_data_structures::{
     chain::{Hash, Hashable, Input, OutputPointer, PublicKeyHash, ValueTransferOutput},
     transaction::{Transaction, VTTransaction, VTTransactionBody},
     utxo_pool::{
-        CacheUtxosByPkh, OwnUnspentOutputsPool, UnspentOutputsPool, UtxoDb, UtxoDbWrapStorage,
-        UtxoWriteBatch,
+        get_balance_breakdown, BalanceBreakdown, CacheUtxosByPkh, OwnUnspentOutputsPool,
+        UnspentOutputsPool, UtxoDb, UtxoDbWrapStorage, UtxoWriteBatch,
     },
 };
 use witnet_storage::storage::Storage;
@@ -132,6 +132,49 @@ fn utxo_set_coin_age() {
     assert_eq!(p.iter().count(), 0);
 }
 
+#[test]
+fn utxo_set_balance_breakdown() {
+    let pkh = PublicKeyHash::from_bytes(&[1; 20]).unwrap();
+    let other_pkh = PublicKeyHash::from_bytes(&[2; 20]).unwrap();
+    let now = 1_000;
+    let mut p = UnspentOutputsPool::default();
+    let mut insert = |index: u32, value, time_lock, pkh, block_number| {
+        let output_pointer = OutputPointer {
+            transaction_id: Hash::SHA256([1; 32]),
+            output_index: index,
+        };
+        let vto = ValueTransferOutput {
+            pkh,
+            value,
+            time_lock,
+        };
+        p.insert(output_pointer, vto, block_number);
+    };
+
+    // Old enough to be used as collateral
+    insert(0, 1, 0, pkh, 5);
+    // Exactly at the collateral age limit
+    insert(1, 10, 0, pkh, 10);
+    // Still maturing
+    insert(2, 100, 0, pkh, 11);
+    // Old, but time-locked
+    insert(3, 1_000, now + 1, pkh, 5);
+    // Time lock already expired
+    insert(4, 10_000, now, pkh, 5);
+    // Belongs to another address
+    insert(5, 100_000, 0, other_pkh, 5);
+
+    assert_eq!(
+        get_balance_breakdown(pkh, &p, 10, now),
+        BalanceBreakdown {
+            total: 11_111,
+            available: 10_111,
+            locked: 1_000,
+            collateralizable: 10_011,
+        }
+    );
+}
+
 #[test]
 #[should_panic = "UTXO did already exist"]
 fn utxo_set_insert_twice() {
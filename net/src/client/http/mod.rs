@@ -94,6 +94,9 @@ pub enum WitnetHttpError {
 
 impl WitnetHttpClient {
     /// Create a new `WitnetHttpClient`
+    ///
+    /// If a proxy URI is provided, requests are routed through it. The proxy only affects the
+    /// transport: the method, URI, headers and body of every request remain the same.
     pub fn new(
         proxy: impl Into<Option<isahc::http::Uri>>,
         follow_redirects: bool,
@@ -201,3 +204,59 @@ impl From<WitnetHttpVersion> for isahc::http::version::Version {
         version.version
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn requests_are_routed_through_proxy() {
+        // A mock proxy that answers a single request and returns the raw bytes it received
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_address = listener.local_addr().unwrap();
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !received.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                received.extend_from_slice(&buffer[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+
+            String::from_utf8(received).unwrap()
+        });
+
+        let proxy_uri = format!("http://{}", proxy_address)
+            .parse::<http::Uri>()
+            .unwrap();
+        let client = WitnetHttpClient::new(proxy_uri, false).unwrap();
+        let request = WitnetHttpRequest::build(|builder| {
+            builder
+                .method("GET")
+                .uri("http://example.invalid/data")
+                .header("X-Witnet-Test", "1")
+                .body(WitnetHttpBody::empty())
+        })
+        .unwrap();
+
+        let response = futures::executor::block_on(client.send(request))
+            .unwrap()
+            .inner();
+        assert!(response.status().is_success());
+
+        // The proxy receives the original request, with the absolute URI of the data source
+        let received = proxy.join().unwrap();
+        assert!(received.starts_with("GET http://example.invalid/data HTTP/1.1\r\n"));
+        assert!(received.to_lowercase().contains("x-witnet-test: 1\r\n"));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{path::PathBuf, pin::Pin, str::FromStr, time::Duration};
+use std::{io::IsTerminal, path::PathBuf, pin::Pin, str::FromStr, time::Duration};
 
 use actix::prelude::*;
 use actix::{ActorTryFutureExt, ContextFutureSpawner, WrapFuture};
@@ -9,7 +9,7 @@ use witnet_config::defaults::PSEUDO_CONSENSUS_CONSTANTS_WIP0022_REWARD_COLLATERA
 use witnet_data_structures::{
     chain::{
         Block, ChainImport, ChainInfo, ChainState, CheckpointBeacon, CheckpointVRF,
-        GenesisBlockInfo, PublicKeyHash, ReputationEngine, StateMachine, SuperBlock,
+        GenesisBlockInfo, Hashable, PublicKeyHash, ReputationEngine, StateMachine, SuperBlock,
     },
     data_request::DataRequestPool,
     get_environment,
@@ -23,7 +23,7 @@ use witnet_util::timestamp::pretty_print;
 use crate::{
     actors::{
         chain_manager::{
-            handlers::EveryEpochPayload, ChainManager, ChainManagerError, ImportError,
+            handlers::EveryEpochPayload, ChainManager, ChainManagerError, DrLogOptions, ImportError,
         },
         epoch_manager::{EpochManager, EpochManagerError::CheckpointZeroInTheFuture},
         inventory_manager::InventoryManager,
@@ -51,10 +51,19 @@ impl Actor for ChainManager {
 
         ctx.wait(
             self.initialize_from_storage_fut(false)
+                .and_then(|_res, act, _ctx| {
+                    // Restore the transactions that were pending before the node was stopped
+                    act.restore_transactions_pool()
+                })
                 .and_then(|_res, act, _ctx| {
                     // If we are importing a chain snapshot, it is time to load and store all the relevant data,
                     // before the actor starts to communicate with any other actors
-                    act.snapshot_import().map_err(|_, _, _| ())
+                    act.snapshot_import().then(|_res, act, _ctx| {
+                        // Resume synchronizing towards the target that was set before the node
+                        // was stopped, if it is still valid. This is checked against the chain
+                        // state that results from the import, if any
+                        act.restore_sync_target()
+                    })
                 })
                 .then(|_res, act, _ctx| {
                     // Export chain snapshot if requested to do so
@@ -118,6 +127,16 @@ impl ChainManager {
 
                 act.tx_pending_timeout = config.mempool.tx_pending_timeout;
 
+                // Whether transactions can be built before the node is completely synced
+                act.build_transactions_while_almost_synced = config.mempool.build_transactions_while_almost_synced;
+
+                // Colored logs are only enabled by default when logging to a terminal
+                act.dr_log_options = DrLogOptions {
+                    color: config.log.color.unwrap_or_else(|| std::io::stderr().is_terminal()),
+                    results: config.log.dr_results,
+                };
+                act.show_banners = config.log.banners.unwrap_or_else(|| std::io::stderr().is_terminal());
+
                 let magic = consensus_constants.get_magic();
                 act.set_magic(magic);
 
@@ -134,12 +153,28 @@ impl ChainManager {
 
                 // Get consensus parameter from config
                 act.consensus_c = config.connections.consensus_c;
+                act.consensus_c_syncing = config.connections.consensus_c_syncing;
+
+                // Number of epochs to wait for a batch of blocks while synchronizing
+                act.sync_blocks_batch_timeout_epochs = config.connections.sync_blocks_batch_timeout_epochs;
+
+                // Maximum number of epochs to roll back in order to follow a fork
+                act.max_reorg_depth = config.connections.max_reorg_depth;
+
+                // Rewinding the chain state is destructive, so it must be enabled explicitly
+                act.allow_rewind = config.storage.allow_rewind;
+
+                // Time to keep the data request reports read from storage in memory
+                act.dr_report_cache.set_ttl(config.storage.data_request_report_cache_ttl);
 
                 act.chain_state_snapshot.superblock_period = consensus_constants.superblock_period;
 
                 // Set weight limit of transactions pool
                 let vt_to_dr_factor = f64::from(config.consensus_constants.max_vt_weight) / f64::from(config.consensus_constants.max_dr_weight);
-                let _removed_transactions = act.transactions_pool.set_total_weight_limit(config.mining.transactions_pool_total_weight_limit, vt_to_dr_factor);
+                let removed_transactions = act.transactions_pool.set_total_weight_limit(config.mining.transactions_pool_total_weight_limit, vt_to_dr_factor);
+                for tx in removed_transactions {
+                    log::debug!("Transaction {} removed from the mempool to fit the weight limit", tx.hash());
+                }
 
                 // Minimum fee required to include a VTT into a block
                 act.transactions_pool.set_minimum_vtt_fee(config.mining.minimum_vtt_fee);
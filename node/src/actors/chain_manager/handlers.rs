@@ -1,25 +1,26 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
     convert::{TryFrom, TryInto},
-    future,
+    fs::File,
+    io::{BufWriter, Write},
     net::SocketAddr,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use actix::{prelude::*, ActorFutureExt, WrapFuture};
 use futures::future::Either;
 
-use witnet_config::defaults::PSEUDO_CONSENSUS_CONSTANTS_WIP0027_COLLATERAL_AGE;
 use witnet_data_structures::{
     chain::{
-        tapi::ActiveWips, Block, ChainState, CheckpointBeacon, DataRequestInfo, Epoch, Hash,
-        Hashable, NodeStats, PublicKeyHash, SuperBlockVote, SupplyInfo,
+        tapi::ActiveWips, Block, Blockchain, ChainState, CheckpointBeacon, DataRequestInfo, Epoch,
+        Hash, Hashable, NodeStats, PublicKeyHash, SuperBlockVote, SupplyInfo, TransactionsPool,
+        ValueTransferOutput,
     },
     error::{ChainInfoError, TransactionError::DataRequestNotFound},
     transaction::{DRTransaction, Transaction, VTTransaction},
     transaction_factory::{self, NodeBalance},
     types::LastBeacon,
-    utxo_pool::{get_utxo_info, UtxoInfo},
+    utxo_pool::{get_balance_breakdown, get_utxo_info, BalanceBreakdown, UtxoInfo},
 };
 use witnet_util::timestamp::get_timestamp;
 use witnet_validations::validations::{block_reward, total_block_reward, validate_rad_request};
@@ -28,14 +29,19 @@ use crate::{
     actors::{
         chain_manager::{handlers::BlockBatches::*, BlockCandidate},
         messages::{
-            AddBlocks, AddCandidates, AddCommitReveal, AddSuperBlock, AddSuperBlockVote,
-            AddTransaction, Broadcast, BuildDrt, BuildVtt, EpochNotification, EstimatePriority,
-            GetBalance, GetBalanceTarget, GetBlocksEpochRange, GetDataRequestInfo,
-            GetHighestCheckpointBeacon, GetMemoryTransaction, GetMempool, GetMempoolResult,
-            GetNodeStats, GetReputation, GetReputationResult, GetSignalingInfo, GetState,
-            GetSuperBlockVotes, GetSupplyInfo, GetUtxoInfo, IsConfirmedBlock, PeersBeacons,
+            ActiveDataRequest, AddBlocks, AddCandidates, AddCommitReveal, AddSuperBlock,
+            AddSuperBlockVote, AddTransaction, BlocksEpochRangePage, Broadcast, BuildDrt, BuildVtt,
+            DataRequestStats, EpochNotification, EstimatePriority, GetBalance, GetBalanceBreakdown,
+            GetBalanceTarget, GetBlock, GetBlocksEpochRange, GetBlocksEpochRangePaged,
+            GetBlocksEpochRangeWithTimestamps, GetDataRequestInfo, GetDataRequestStats,
+            GetHighestCheckpointBeacon, GetMemoryTransaction, GetMempool, GetMempoolByPriority,
+            GetMempoolResult, GetMyActiveDataRequests, GetNodeStats, GetOutput, GetReputation,
+            GetReputationResult, GetSignalingInfo, GetState, GetSuperBlockVotes, GetSupplyInfo,
+            GetTip, GetUtxoInfo, GetUtxoSnapshot, IsActiveIdentity, IsConfirmedBlock,
+            MempoolFilter, PeersBeacons, PersistTransactionsPool, PrioritizedTransaction,
             ReputationStats, Rewind, SendLastBeacon, SessionUnitResult, SetLastBeacon,
             SetPeersLimits, SignalingInfo, SnapshotExport, SnapshotImport, TryMineBlock,
+            UtxoSnapshot, UtxoSnapshotEntry,
         },
         sessions_manager::SessionsManager,
     },
@@ -43,7 +49,10 @@ use crate::{
     utils::mode_consensus,
 };
 
-use super::{ChainManager, ChainManagerError, StateMachine, SyncTarget};
+use super::{
+    ChainManager, ChainManagerError, StateMachine, SyncTarget, UtxoSupply,
+    DATA_REQUEST_REPORT_READ_ATTEMPTS, DATA_REQUEST_REPORT_READ_BACKOFF,
+};
 
 pub const SYNCED_BANNER: &str = r"
 ███████╗██╗   ██╗███╗   ██╗ ██████╗███████╗██████╗ ██╗
@@ -268,6 +277,15 @@ impl Handler<GetHighestCheckpointBeacon> for ChainManager {
     }
 }
 
+/// Handler for GetTip message
+impl Handler<GetTip> for ChainManager {
+    type Result = <GetTip as Message>::Result;
+
+    fn handle(&mut self, _msg: GetTip, _ctx: &mut Context<Self>) -> Self::Result {
+        self.tip()
+    }
+}
+
 /// Handler for GetSuperBlockVotes message
 impl Handler<GetSuperBlockVotes> for ChainManager {
     type Result = Result<HashSet<SuperBlockVote>, failure::Error>;
@@ -702,6 +720,35 @@ impl Handler<GetBlocksEpochRange> for ChainManager {
     }
 }
 
+impl Handler<GetBlocksEpochRangeWithTimestamps> for ChainManager {
+    type Result = Result<Vec<(Epoch, Hash, i64)>, ChainManagerError>;
+
+    fn handle(
+        &mut self,
+        msg: GetBlocksEpochRangeWithTimestamps,
+        _ctx: &mut Context<Self>,
+    ) -> Self::Result {
+        self.get_blocks_epoch_range_with_timestamps(msg)
+    }
+}
+
+impl Handler<GetBlocksEpochRangePaged> for ChainManager {
+    type Result = Result<BlocksEpochRangePage, ChainManagerError>;
+
+    fn handle(&mut self, msg: GetBlocksEpochRangePaged, _ctx: &mut Context<Self>) -> Self::Result {
+        Ok(self.get_blocks_epoch_range_paged(msg))
+    }
+}
+
+/// Handler for GetBlock
+impl Handler<GetBlock> for ChainManager {
+    type Result = ResponseActFuture<Self, Result<Block, failure::Error>>;
+
+    fn handle(&mut self, GetBlock { hash }: GetBlock, _ctx: &mut Context<Self>) -> Self::Result {
+        self.get_block(hash)
+    }
+}
+
 impl PeersBeacons {
     /// Pretty-print a map {beacon: [peers]}
     pub fn pretty_format(&self) -> String {
@@ -770,8 +817,9 @@ impl PeersBeacons {
             // * There is a majority of beacons but it is below consensus_threshold. We will use
             // this majority as the block_consensus, and we will unregister the peers that voted on
             // a different superblock
-            // * There is a tie. Will use any block as the consensus. So if there are 4 votes for
-            // A, 4 votes for B, and 1 vote for C, the consensus can be A, B, or C.
+            // * There is a tie. Will use the tied block with the lowest hash as the consensus, so
+            // that all the nodes resolve the tie in the same way. So if there are 4 votes for A,
+            // 4 votes for B, and 1 vote for C, the consensus will be min(A, B).
             let block_beacons: Vec<_> = self
                 .pb
                 .iter()
@@ -791,17 +839,25 @@ impl PeersBeacons {
                 // Case 1
                 Some(Some(x)) => (*x, true),
                 _ => {
-                    // Case 2
-                    let block_beacons_flatten: Vec<CheckpointBeacon> =
-                        block_beacons.into_iter().flatten().collect();
-                    let first = block_beacons_flatten[0];
-                    match mode_consensus(block_beacons_flatten.iter(), 0) {
-                        Some(x) => (*x, false),
-                        None => {
-                            // Case 3: In case of tie, we can choose one random different to None
-                            (first, false)
-                        }
+                    // Case 2: count the votes of each block
+                    let mut block_votes: HashMap<CheckpointBeacon, usize> = HashMap::new();
+                    for block_beacon in block_beacons.into_iter().flatten() {
+                        *block_votes.entry(block_beacon).or_default() += 1;
                     }
+                    // Case 3: in case of tie, choose the lowest hash. The iteration order of the
+                    // HashMap is random, so it must not be used to break the tie
+                    let most_voted = block_votes
+                        .into_iter()
+                        .max_by(|(a, a_votes), (b, b_votes)| {
+                            a_votes
+                                .cmp(b_votes)
+                                .then_with(|| b.hash_prev_block.cmp(&a.hash_prev_block))
+                                .then_with(|| b.checkpoint.cmp(&a.checkpoint))
+                        })
+                        .map(|(block_beacon, _votes)| block_beacon)
+                        .expect("superblock consensus implies at least one block beacon");
+
+                    (most_voted, false)
                 }
             };
 
@@ -907,8 +963,9 @@ impl Handler<PeersBeacons> for ChainManager {
             return Err(());
         }
 
-        // Calculate the consensus, or None if there is no consensus
-        let consensus_threshold = self.consensus_c as usize;
+        // Calculate the consensus, or None if there is no consensus. The threshold depends on
+        // whether the node is still synchronizing or already synced
+        let consensus_threshold = self.consensus_threshold();
         let beacon_consensus = peers_beacons.superblock_consensus(consensus_threshold);
         let outbound_limit = peers_beacons.outbound_limit;
         let pb_len = peers_beacons.pb.len();
@@ -967,10 +1024,13 @@ impl Handler<PeersBeacons> for ChainManager {
                         },
                         _,
                     )) => {
-                        self.sync_target = Some(SyncTarget {
-                            block: consensus_beacon,
-                            superblock: superblock_consensus,
-                        });
+                        self.set_sync_target(
+                            ctx,
+                            SyncTarget {
+                                block: consensus_beacon,
+                                superblock: superblock_consensus,
+                            },
+                        );
                         log::debug!("Sync target {:?}", self.sync_target);
 
                         let our_beacon = self.get_chain_beacon();
@@ -1002,8 +1062,7 @@ impl Handler<PeersBeacons> for ChainManager {
                                 consensus_beacon
                             );
 
-                            self.initialize_from_storage(ctx);
-                            log::info!("Restored chain state from storage");
+                            self.handle_fork(ctx);
 
                             StateMachine::WaitingConsensus
                         } else {
@@ -1056,7 +1115,20 @@ impl Handler<PeersBeacons> for ChainManager {
                         Ok(peers_to_unregister)
                     }
                     // No consensus: unregister all peers
-                    None => Ok(peers_to_unregister),
+                    None => {
+                        // Until the peers reach a consensus, resume synchronizing towards the
+                        // target that was restored from storage, if any
+                        if let Some(sync_target) = self.restored_sync_target_to_resume() {
+                            log::info!(
+                                "Resuming synchronization towards restored sync target {:?}",
+                                sync_target
+                            );
+                            self.request_blocks_batch(ctx);
+                            self.update_state_machine(StateMachine::Synchronizing, ctx);
+                        }
+
+                        Ok(peers_to_unregister)
+                    }
                 }
             }
             StateMachine::Synchronizing => {
@@ -1068,10 +1140,13 @@ impl Handler<PeersBeacons> for ChainManager {
                         },
                         _,
                     )) => {
-                        self.sync_target = Some(SyncTarget {
-                            block: consensus_beacon,
-                            superblock: superblock_consensus,
-                        });
+                        self.set_sync_target(
+                            ctx,
+                            SyncTarget {
+                                block: consensus_beacon,
+                                superblock: superblock_consensus,
+                            },
+                        );
 
                         let our_beacon = self.get_chain_beacon();
 
@@ -1088,8 +1163,7 @@ impl Handler<PeersBeacons> for ChainManager {
                                 consensus_beacon
                             );
 
-                            self.initialize_from_storage(ctx);
-                            log::info!("Restored chain state from storage");
+                            self.handle_fork(ctx);
 
                             StateMachine::WaitingConsensus
                         } else {
@@ -1102,7 +1176,10 @@ impl Handler<PeersBeacons> for ChainManager {
                     }
                     // No consensus: unregister all peers
                     None => {
-                        self.update_state_machine(StateMachine::WaitingConsensus, ctx);
+                        // A target restored from storage is kept until the peers reach a consensus
+                        if !self.sync_target_restored {
+                            self.update_state_machine(StateMachine::WaitingConsensus, ctx);
+                        }
 
                         Ok(peers_to_unregister)
                     }
@@ -1185,15 +1262,9 @@ impl Handler<PeersBeacons> for ChainManager {
                 .superblock;
             self.request_sync_target_superblock(ctx, superblock_consensus);
 
-            if let Some(sync_start_epoch) = self.sync_waiting_for_add_blocks_since {
-                let current_epoch = self.current_epoch.unwrap();
-                let how_many_epochs_are_we_willing_to_wait_for_one_block_batch = 10;
-                if current_epoch - sync_start_epoch
-                    >= how_many_epochs_are_we_willing_to_wait_for_one_block_batch
-                {
-                    log::warn!("Timeout for waiting for blocks achieved. Requesting blocks again.");
-                    self.request_blocks_batch(ctx);
-                }
+            if self.sync_blocks_batch_timed_out() {
+                log::warn!("Timeout for waiting for blocks achieved. Requesting blocks again.");
+                self.request_blocks_batch(ctx);
             }
         } else if self.sm_state == StateMachine::AlmostSynced
             || self.sm_state == StateMachine::Synced
@@ -1216,13 +1287,8 @@ impl Handler<BuildVtt> for ChainManager {
     type Result = ResponseActFuture<Self, <BuildVtt as Message>::Result>;
 
     fn handle(&mut self, msg: BuildVtt, _ctx: &mut Self::Context) -> Self::Result {
-        if self.sm_state != StateMachine::Synced {
-            return Box::pin(actix::fut::err(
-                ChainManagerError::NotSynced {
-                    current_state: self.sm_state,
-                }
-                .into(),
-            ));
+        if let Err(e) = self.check_can_build_transactions() {
+            return Box::pin(actix::fut::err(e.into()));
         }
         let timestamp = u64::try_from(get_timestamp()).unwrap();
         let max_vt_weight = self.consensus_constants().max_vt_weight;
@@ -1240,7 +1306,22 @@ impl Handler<BuildVtt> for ChainManager {
         ) {
             Err(e) => {
                 log::error!("Error when building value transfer transaction: {}", e);
-                Box::pin(actix::fut::err(e.into()))
+                // Keep the TransactionError as the cause, so that the JSON-RPC server can
+                // downcast it and return a structured error to the client
+                Box::pin(actix::fut::err(failure::Error::from(e)))
+            }
+            Ok(vtt) if msg.dry_run => {
+                // Dry runs are not signed nor added to the mempool, they only show the transaction
+                // that would be built: its hash, weight and selected inputs
+                let vtt = VTTransaction::new(vtt, vec![]);
+                log::debug!(
+                    "Dry run of value transfer transaction {}: weight {}, {} inputs",
+                    vtt.hash(),
+                    vtt.weight(),
+                    vtt.body.inputs.len()
+                );
+
+                Box::pin(actix::fut::ok(vtt))
             }
             Ok(vtt) => {
                 let fut = signature_mngr::sign_transaction(&vtt, vtt.inputs.len())
@@ -1248,22 +1329,18 @@ impl Handler<BuildVtt> for ChainManager {
                     .then(move |s, act, _ctx| match s {
                         Ok(signatures) => {
                             let vtt = VTTransaction::new(vtt, signatures);
-
-                            if msg.dry_run {
-                                Either::Right(actix::fut::result(Ok(vtt)))
-                            } else {
-                                let transaction = Transaction::ValueTransfer(vtt.clone());
-                                Either::Left(
-                                    act.add_transaction(
-                                        AddTransaction {
-                                            transaction,
-                                            broadcast_flag: true,
-                                        },
-                                        get_timestamp(),
-                                    )
-                                    .map_ok(move |_, _, _| vtt),
+                            let transaction = Transaction::ValueTransfer(vtt.clone());
+
+                            Either::Left(
+                                act.add_transaction(
+                                    AddTransaction {
+                                        transaction,
+                                        broadcast_flag: true,
+                                    },
+                                    get_timestamp(),
                                 )
-                            }
+                                .map_ok(move |_, _, _| vtt),
+                            )
                         }
                         Err(e) => {
                             log::error!("Failed to sign value transfer transaction: {}", e);
@@ -1281,13 +1358,8 @@ impl Handler<BuildDrt> for ChainManager {
     type Result = ResponseActFuture<Self, Result<DRTransaction, failure::Error>>;
 
     fn handle(&mut self, msg: BuildDrt, _ctx: &mut Self::Context) -> Self::Result {
-        if self.sm_state != StateMachine::Synced {
-            return Box::pin(actix::fut::err(
-                ChainManagerError::NotSynced {
-                    current_state: self.sm_state,
-                }
-                .into(),
-            ));
+        if let Err(e) = self.check_can_build_transactions() {
+            return Box::pin(actix::fut::err(e.into()));
         }
 
         let active_wips = ActiveWips {
@@ -1316,6 +1388,19 @@ impl Handler<BuildDrt> for ChainManager {
                 log::error!("Error when building data request transaction: {}", e);
                 Box::pin(actix::fut::err(e.into()))
             }
+            Ok(drt) if msg.dry_run => {
+                // Dry runs are not signed nor added to the mempool, they only show the transaction
+                // that would be built: its hash, weight and selected inputs
+                let drt = DRTransaction::new(drt, vec![]);
+                log::debug!(
+                    "Dry run of data request transaction {}: weight {}, {} inputs",
+                    drt.hash(),
+                    drt.weight(),
+                    drt.body.inputs.len()
+                );
+
+                Box::pin(actix::fut::ok(drt))
+            }
             Ok(drt) => {
                 log::debug!("Created drt:\n{:?}", drt);
                 let fut = signature_mngr::sign_transaction(&drt, drt.inputs.len())
@@ -1323,22 +1408,18 @@ impl Handler<BuildDrt> for ChainManager {
                     .then(move |s, act, _ctx| match s {
                         Ok(signatures) => {
                             let drt = DRTransaction::new(drt, signatures);
-
-                            if msg.dry_run {
-                                Either::Right(actix::fut::result(Ok(drt)))
-                            } else {
-                                let transaction = Transaction::DataRequest(drt.clone());
-                                Either::Left(
-                                    act.add_transaction(
-                                        AddTransaction {
-                                            transaction,
-                                            broadcast_flag: true,
-                                        },
-                                        get_timestamp(),
-                                    )
-                                    .map_ok(move |_, _, _| drt),
+                            let transaction = Transaction::DataRequest(drt.clone());
+
+                            Either::Left(
+                                act.add_transaction(
+                                    AddTransaction {
+                                        transaction,
+                                        broadcast_flag: true,
+                                    },
+                                    get_timestamp(),
                                 )
-                            }
+                                .map_ok(move |_, _, _| drt),
+                            )
                         }
                         Err(e) => {
                             log::error!("Failed to sign data request transaction: {}", e);
@@ -1361,7 +1442,7 @@ impl Handler<GetState> for ChainManager {
 }
 
 impl Handler<GetDataRequestInfo> for ChainManager {
-    type Result = ResponseFuture<Result<DataRequestInfo, failure::Error>>;
+    type Result = ResponseActFuture<Self, Result<DataRequestInfo, failure::Error>>;
 
     fn handle(&mut self, msg: GetDataRequestInfo, _ctx: &mut Self::Context) -> Self::Result {
         let dr_pointer = msg.dr_pointer;
@@ -1374,18 +1455,34 @@ impl Handler<GetDataRequestInfo> for ChainManager {
             .get(&dr_pointer)
             .map(|dr_state| dr_state.info.clone())
         {
-            Box::pin(future::ready(Ok(dr_info)))
+            Box::pin(actix::fut::ok(dr_info))
+        } else if let Some(dr_info) = self.dr_report_cache.get(&dr_pointer, Instant::now()) {
+            // Then, from the reports recently read from storage
+            Box::pin(actix::fut::ok(dr_info))
         } else {
             let dr_pointer_string = format!("DR-REPORT-{}", dr_pointer);
             // Otherwise, try to get it from storage
             let fut = async move {
-                let dr_info = storage_mngr::get::<_, DataRequestInfo>(&dr_pointer_string).await?;
+                // Retry the read so that a transient storage error does not fail the request
+                let dr_info = storage_mngr::get_with_retries::<_, DataRequestInfo>(
+                    &dr_pointer_string,
+                    DATA_REQUEST_REPORT_READ_ATTEMPTS,
+                    DATA_REQUEST_REPORT_READ_BACKOFF,
+                )
+                .await?;
 
                 match dr_info {
                     Some(x) => Ok(x),
                     None => Err(DataRequestNotFound { hash: dr_pointer }.into()),
                 }
-            };
+            }
+            .into_actor(self)
+            .map_ok(move |dr_info, act, _ctx| {
+                act.dr_report_cache
+                    .insert(dr_pointer, dr_info.clone(), Instant::now());
+
+                dr_info
+            });
 
             Box::pin(fut)
         }
@@ -1477,16 +1574,21 @@ impl Handler<GetSupplyInfo> for ChainManager {
         let current_epoch = self.current_epoch.unwrap();
         let current_time = u64::try_from(get_timestamp()).unwrap();
 
-        let mut current_unlocked_supply = 0;
-        let mut current_locked_supply = 0;
-        for (_output_pointer, value_transfer_output) in self.chain_state.unspent_outputs_pool.iter()
-        {
-            if value_transfer_output.0.time_lock <= current_time {
-                current_unlocked_supply += value_transfer_output.0.value;
-            } else {
-                current_locked_supply += value_transfer_output.0.value;
-            }
+        // The supply held in UTXOs only changes when a new block is consolidated
+        let chain_beacon = self.chain_state.get_chain_beacon();
+        let is_cached = matches!(
+            &self.utxo_supply_cache,
+            Some((beacon, _utxo_supply)) if *beacon == chain_beacon
+        );
+        if !is_cached {
+            let utxo_supply = UtxoSupply::from_utxo_pool(&self.chain_state.unspent_outputs_pool);
+            self.utxo_supply_cache = Some((chain_beacon, utxo_supply));
         }
+        let (current_unlocked_supply, current_locked_supply) = self
+            .utxo_supply_cache
+            .as_ref()
+            .map(|(_beacon, utxo_supply)| utxo_supply.unlocked_and_locked(current_time))
+            .unwrap();
 
         let in_flight_requests = self
             .chain_state
@@ -1553,19 +1655,7 @@ impl Handler<GetUtxoInfo> for ChainManager {
         }
 
         let chain_info = self.chain_state.chain_info.as_ref().unwrap();
-        let active_wips = ActiveWips {
-            active_wips: self.chain_state.tapi_engine.wip_activation.clone(),
-            block_epoch: self.current_epoch.unwrap(),
-        };
-        let collateral_age = if active_wips.wip0027() {
-            PSEUDO_CONSENSUS_CONSTANTS_WIP0027_COLLATERAL_AGE
-        } else {
-            chain_info.consensus_constants.collateral_age
-        };
-        let block_number_limit = self
-            .chain_state
-            .block_number()
-            .saturating_sub(collateral_age);
+        let block_number_limit = self.collateral_block_number_limit();
 
         let pkh = if self.own_pkh == Some(pkh) {
             None
@@ -1583,6 +1673,150 @@ impl Handler<GetUtxoInfo> for ChainManager {
     }
 }
 
+impl Handler<GetBalanceBreakdown> for ChainManager {
+    type Result = Result<BalanceBreakdown, failure::Error>;
+
+    fn handle(
+        &mut self,
+        GetBalanceBreakdown { target }: GetBalanceBreakdown,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if self.sm_state != StateMachine::Synced {
+            return Err(ChainManagerError::NotSynced {
+                current_state: self.sm_state,
+            }
+            .into());
+        }
+
+        let pkh = match target {
+            GetBalanceTarget::Own => self.own_pkh.unwrap_or_default(),
+            GetBalanceTarget::Address(pkh) => pkh,
+            GetBalanceTarget::All => {
+                return Err(failure::format_err!(
+                    "The balance breakdown is only available for a single address"
+                ));
+            }
+        };
+        let now = u64::try_from(get_timestamp()).unwrap_or_default();
+
+        Ok(get_balance_breakdown(
+            pkh,
+            &self.chain_state.unspent_outputs_pool,
+            self.collateral_block_number_limit(),
+            now,
+        ))
+    }
+}
+
+impl Handler<GetOutput> for ChainManager {
+    type Result = Result<ValueTransferOutput, failure::Error>;
+
+    fn handle(
+        &mut self,
+        GetOutput { output_pointer }: GetOutput,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if self.sm_state != StateMachine::Synced {
+            return Err(ChainManagerError::NotSynced {
+                current_state: self.sm_state,
+            }
+            .into());
+        }
+
+        self.chain_state
+            .unspent_outputs_pool
+            .get(&output_pointer)
+            .ok_or_else(|| ChainManagerError::OutputNotFound { output_pointer }.into())
+    }
+}
+
+impl Handler<GetUtxoSnapshot> for ChainManager {
+    type Result = Result<UtxoSnapshot, failure::Error>;
+
+    fn handle(
+        &mut self,
+        GetUtxoSnapshot { path }: GetUtxoSnapshot,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if self.chain_state.chain_info.is_none() {
+            return Err(ChainManagerError::ChainNotReady.into());
+        }
+
+        let block_number = self.chain_state.block_number();
+        let beacon = self.get_chain_beacon();
+        let utxos = self.chain_state.unspent_outputs_pool.iter().map(
+            |(output_pointer, (output, block_number))| UtxoSnapshotEntry {
+                output_pointer,
+                output,
+                block_number,
+            },
+        );
+
+        match path {
+            Some(path) => {
+                let mut writer = BufWriter::new(File::create(&path)?);
+                let utxos_count = write_utxo_snapshot(&mut writer, block_number, beacon, utxos)?;
+                writer.flush()?;
+                log::info!(
+                    "Wrote snapshot of {} UTXOs at block #{} into {}",
+                    utxos_count,
+                    block_number,
+                    path.display()
+                );
+
+                Ok(UtxoSnapshot {
+                    block_number,
+                    beacon,
+                    utxos_count,
+                    utxos: None,
+                })
+            }
+            None => {
+                let utxos: Vec<_> = utxos.collect();
+
+                Ok(UtxoSnapshot {
+                    block_number,
+                    beacon,
+                    utxos_count: utxos.len(),
+                    utxos: Some(utxos),
+                })
+            }
+        }
+    }
+}
+
+/// Write a UTXO set snapshot as one JSON object per line, so that the whole UTXO set never needs
+/// to be kept in memory. The first line has the block number and the beacon, and each of the
+/// following lines is an unspent output. Returns the number of unspent outputs written.
+fn write_utxo_snapshot<W, I>(
+    mut writer: W,
+    block_number: u32,
+    beacon: CheckpointBeacon,
+    utxos: I,
+) -> Result<usize, failure::Error>
+where
+    W: Write,
+    I: Iterator<Item = UtxoSnapshotEntry>,
+{
+    serde_json::to_writer(
+        &mut writer,
+        &serde_json::json!({
+            "block_number": block_number,
+            "beacon": beacon,
+        }),
+    )?;
+    writer.write_all(b"\n")?;
+
+    let mut utxos_count = 0;
+    for utxo in utxos {
+        serde_json::to_writer(&mut writer, &utxo)?;
+        writer.write_all(b"\n")?;
+        utxos_count += 1;
+    }
+
+    Ok(utxos_count)
+}
+
 impl Handler<GetReputation> for ChainManager {
     type Result = Result<GetReputationResult, failure::Error>;
 
@@ -1644,6 +1878,47 @@ impl Handler<GetReputation> for ChainManager {
     }
 }
 
+impl Handler<IsActiveIdentity> for ChainManager {
+    type Result = Result<bool, failure::Error>;
+
+    fn handle(
+        &mut self,
+        IsActiveIdentity { pkh }: IsActiveIdentity,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        // Unlike GetReputation, this does not require the node to be synced: the active
+        // reputation set is available as soon as the chain state has been loaded
+        match self.chain_state.reputation_engine.as_ref() {
+            Some(rep_eng) => Ok(rep_eng.ars().contains(&pkh)),
+            None => Err(ChainManagerError::ChainNotReady.into()),
+        }
+    }
+}
+
+impl Handler<GetDataRequestStats> for ChainManager {
+    type Result = Result<DataRequestStats, failure::Error>;
+
+    fn handle(&mut self, _msg: GetDataRequestStats, _ctx: &mut Self::Context) -> Self::Result {
+        if self.sm_state != StateMachine::Synced {
+            return Err(ChainManagerError::NotSynced {
+                current_state: self.sm_state,
+            }
+            .into());
+        }
+
+        let (commit_stage, reveal_stage, tally_stage) =
+            self.chain_state.data_request_pool.count_by_stage();
+
+        Ok(DataRequestStats {
+            commit_stage,
+            reveal_stage,
+            tally_stage,
+            resolved: self.resolved_data_requests.count,
+            average_resolution_epochs: self.resolved_data_requests.average_epochs(),
+        })
+    }
+}
+
 impl Handler<TryMineBlock> for ChainManager {
     type Result = ();
 
@@ -1698,16 +1973,101 @@ impl Handler<GetMemoryTransaction> for ChainManager {
 impl Handler<GetMempool> for ChainManager {
     type Result = Result<GetMempoolResult, failure::Error>;
 
-    fn handle(&mut self, _msg: GetMempool, _ctx: &mut Self::Context) -> Self::Result {
-        let res = GetMempoolResult {
-            value_transfer: self.transactions_pool.vt_iter().map(|t| t.hash()).collect(),
-            data_request: self.transactions_pool.dr_iter().map(|t| t.hash()).collect(),
+    fn handle(&mut self, msg: GetMempool, _ctx: &mut Self::Context) -> Self::Result {
+        Ok(mempool_hashes(&self.transactions_pool, msg))
+    }
+}
+
+impl Handler<PersistTransactionsPool> for ChainManager {
+    type Result = ResponseActFuture<Self, Result<(), ()>>;
+
+    fn handle(&mut self, _msg: PersistTransactionsPool, _ctx: &mut Self::Context) -> Self::Result {
+        self.persist_transactions_pool()
+    }
+}
+
+impl Handler<GetMempoolByPriority> for ChainManager {
+    type Result = Result<Vec<PrioritizedTransaction>, failure::Error>;
+
+    fn handle(&mut self, msg: GetMempoolByPriority, _ctx: &mut Self::Context) -> Self::Result {
+        let limit = if msg.limit == 0 {
+            usize::MAX
+        } else {
+            usize::try_from(msg.limit).unwrap_or(usize::MAX)
         };
+        let res = self
+            .transactions_pool
+            .sorted_by_priority()
+            .take(limit)
+            .map(|(priority, transaction)| PrioritizedTransaction {
+                priority,
+                transaction,
+            })
+            .collect();
 
         Ok(res)
     }
 }
 
+/// List the hashes of the pending transactions of the types selected by the filter, returning at
+/// most `limit` hashes of each type
+fn mempool_hashes(
+    transactions_pool: &TransactionsPool,
+    GetMempool { filter, limit }: GetMempool,
+) -> GetMempoolResult {
+    let limit = if limit == 0 {
+        usize::MAX
+    } else {
+        usize::try_from(limit).unwrap_or(usize::MAX)
+    };
+    let (value_transfer, data_request) = match filter {
+        MempoolFilter::All => (true, true),
+        MempoolFilter::ValueTransfer => (true, false),
+        MempoolFilter::DataRequest => (false, true),
+    };
+
+    GetMempoolResult {
+        value_transfer: if value_transfer {
+            transactions_pool
+                .vt_iter()
+                .map(|t| t.hash())
+                .take(limit)
+                .collect()
+        } else {
+            vec![]
+        },
+        data_request: if data_request {
+            transactions_pool
+                .dr_iter()
+                .map(|t| t.hash())
+                .take(limit)
+                .collect()
+        } else {
+            vec![]
+        },
+    }
+}
+
+impl Handler<GetMyActiveDataRequests> for ChainManager {
+    type Result = Result<Vec<ActiveDataRequest>, failure::Error>;
+
+    fn handle(
+        &mut self,
+        GetMyActiveDataRequests { pkh }: GetMyActiveDataRequests,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let active_data_requests = self
+            .chain_state
+            .data_request_pool
+            .data_requests_by_witness(&pkh)
+            .into_iter()
+            .map(|(dr_pointer, stage)| ActiveDataRequest { dr_pointer, stage })
+            .collect();
+
+        Ok(active_data_requests)
+    }
+}
+
 impl Handler<AddSuperBlock> for ChainManager {
     type Result = ();
 
@@ -1769,13 +2129,12 @@ impl Handler<Rewind> for ChainManager {
     type Result = Result<bool, failure::Error>;
 
     fn handle(&mut self, msg: Rewind, ctx: &mut Self::Context) -> Self::Result {
+        if !self.allow_rewind {
+            return Err(ChainManagerError::RewindDisabled.into());
+        }
+
         // Save list of blocks that are known to be valid
-        let old_block_chain: VecDeque<(Epoch, Hash)> = self
-            .chain_state
-            .block_chain
-            .range(0..=msg.epoch)
-            .map(|(k, v)| (*k, *v))
-            .collect();
+        let old_block_chain = blocks_kept_after_rewind(&self.chain_state.block_chain, msg.epoch);
 
         self.delete_chain_state_and_reinitialize()
             .map(|_res, act, ctx| {
@@ -1820,6 +2179,15 @@ impl Handler<Rewind> for ChainManager {
     }
 }
 
+/// List of blocks that survive rewinding the chain to `epoch`: those with an epoch lower or equal
+/// to `epoch`, which will be processed again from storage. Later blocks are dropped.
+fn blocks_kept_after_rewind(block_chain: &Blockchain, epoch: Epoch) -> VecDeque<(Epoch, Hash)> {
+    block_chain
+        .range(0..=epoch)
+        .map(|(k, v)| (*k, *v))
+        .collect()
+}
+
 impl Handler<GetSignalingInfo> for ChainManager {
     type Result = Result<SignalingInfo, failure::Error>;
 
@@ -1997,6 +2365,143 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn utxo_snapshot_matches_pool() {
+        use witnet_data_structures::chain::{ChainInfo, OutputPointer};
+
+        let mut chain_manager = ChainManager::default();
+        chain_manager.chain_state.chain_info = Some(ChainInfo {
+            highest_block_checkpoint: CheckpointBeacon {
+                checkpoint: 7,
+                hash_prev_block: Hash::SHA256([7; 32]),
+            },
+            ..ChainInfo::default()
+        });
+        chain_manager.chain_state.block_chain = (0..3)
+            .map(|epoch| (epoch, Hash::SHA256([epoch as u8; 32])))
+            .collect();
+        let mut expected = HashMap::new();
+        for i in 0..3 {
+            let output_pointer = OutputPointer {
+                transaction_id: Hash::SHA256([i; 32]),
+                output_index: u32::from(i),
+            };
+            let output = ValueTransferOutput {
+                value: u64::from(i) + 1,
+                ..Default::default()
+            };
+            chain_manager.chain_state.unspent_outputs_pool.insert(
+                output_pointer,
+                output.clone(),
+                u32::from(i),
+            );
+            expected.insert(output_pointer, (output, u32::from(i)));
+        }
+        let snapshot_to_map = |utxos: Vec<UtxoSnapshotEntry>| {
+            utxos
+                .into_iter()
+                .map(|utxo| (utxo.output_pointer, (utxo.output, utxo.block_number)))
+                .collect::<HashMap<_, _>>()
+        };
+
+        crate::utils::test_actix_system(|| async move {
+            let mut ctx = Context::new();
+
+            // Snapshot returned in memory
+            let snapshot = chain_manager
+                .handle(GetUtxoSnapshot { path: None }, &mut ctx)
+                .unwrap();
+            assert_eq!(snapshot.block_number, 3);
+            assert_eq!(snapshot.beacon.checkpoint, 7);
+            assert_eq!(snapshot.utxos_count, 3);
+            assert_eq!(snapshot_to_map(snapshot.utxos.unwrap()), expected);
+
+            // Snapshot written into a file
+            let path = std::env::temp_dir().join("witnet_utxo_snapshot_matches_pool.jsonl");
+            let snapshot = chain_manager
+                .handle(
+                    GetUtxoSnapshot {
+                        path: Some(path.clone()),
+                    },
+                    &mut ctx,
+                )
+                .unwrap();
+            assert_eq!(snapshot.utxos_count, 3);
+            assert_eq!(snapshot.utxos, None);
+
+            let contents = std::fs::read_to_string(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            let mut lines = contents.lines();
+            let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+            assert_eq!(header["block_number"], 3);
+            let utxos = lines
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            assert_eq!(snapshot_to_map(utxos), expected);
+        });
+    }
+
+    #[test]
+    fn is_active_identity() {
+        use witnet_data_structures::chain::ReputationEngine;
+
+        let member = PublicKeyHash::from_bytes(&[1; 20]).unwrap();
+        let non_member = PublicKeyHash::from_bytes(&[2; 20]).unwrap();
+
+        crate::utils::test_actix_system(|| async move {
+            let mut chain_manager = ChainManager::default();
+            let mut ctx = Context::new();
+
+            // No reputation engine yet
+            assert!(chain_manager
+                .handle(IsActiveIdentity { pkh: member }, &mut ctx)
+                .is_err());
+
+            let mut rep_eng = ReputationEngine::new(1000);
+            rep_eng.ars_mut().push_activity(vec![member]);
+            chain_manager.chain_state.reputation_engine = Some(rep_eng);
+
+            assert!(chain_manager
+                .handle(IsActiveIdentity { pkh: member }, &mut ctx)
+                .unwrap());
+            assert!(!chain_manager
+                .handle(IsActiveIdentity { pkh: non_member }, &mut ctx)
+                .unwrap());
+        });
+    }
+
+    #[test]
+    fn rewind_drops_later_blocks() {
+        let block_chain: Blockchain = (0..10)
+            .map(|epoch| (epoch, Hash::SHA256([epoch as u8; 32])))
+            .collect();
+
+        let kept = blocks_kept_after_rewind(&block_chain, 4);
+        assert_eq!(
+            kept,
+            (0..=4)
+                .map(|epoch| (epoch, Hash::SHA256([epoch as u8; 32])))
+                .collect::<VecDeque<_>>()
+        );
+
+        // Rewinding past the chain tip keeps all the blocks
+        assert_eq!(blocks_kept_after_rewind(&block_chain, 20).len(), 10);
+    }
+
+    #[test]
+    fn rewind_is_disabled_by_default() {
+        crate::utils::test_actix_system(|| async {
+            let mut chain_manager = ChainManager::default();
+            let mut ctx = Context::new();
+
+            let res = chain_manager.handle(Rewind { epoch: 0 }, &mut ctx);
+            assert_eq!(
+                res.unwrap_err().downcast::<ChainManagerError>().unwrap(),
+                ChainManagerError::RewindDisabled
+            );
+        });
+    }
+
     #[test]
     fn peers_beacons_consensus_less_peers_than_outbound() {
         let beacon1 = LastBeacon {
@@ -2118,6 +2623,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mempool_filter_by_type() {
+        use witnet_data_structures::{
+            chain::DataRequestOutput,
+            transaction::{DRTransactionBody, VTTransactionBody},
+        };
+
+        let output = |value| ValueTransferOutput {
+            value,
+            ..Default::default()
+        };
+        let mut transactions_pool = TransactionsPool::default();
+        let mut vtt_hashes = vec![];
+        let mut dr_hashes = vec![];
+        for value in 1..=3 {
+            let vtt = Transaction::ValueTransfer(VTTransaction::new(
+                VTTransactionBody::new(vec![], vec![output(value)]),
+                vec![],
+            ));
+            let drt = Transaction::DataRequest(DRTransaction::new(
+                DRTransactionBody::new(vec![], vec![output(value)], DataRequestOutput::default()),
+                vec![],
+            ));
+            vtt_hashes.push(vtt.hash());
+            dr_hashes.push(drt.hash());
+            assert!(transactions_pool.insert(vtt, 1).is_empty());
+            assert!(transactions_pool.insert(drt, 1).is_empty());
+        }
+        vtt_hashes.sort();
+        dr_hashes.sort();
+        let sorted = |mut hashes: Vec<Hash>| {
+            hashes.sort();
+            hashes
+        };
+
+        let all = mempool_hashes(&transactions_pool, GetMempool::default());
+        assert_eq!(sorted(all.value_transfer), vtt_hashes);
+        assert_eq!(sorted(all.data_request), dr_hashes);
+
+        let only_vtts = mempool_hashes(
+            &transactions_pool,
+            GetMempool {
+                filter: MempoolFilter::ValueTransfer,
+                limit: 0,
+            },
+        );
+        assert_eq!(sorted(only_vtts.value_transfer), vtt_hashes);
+        assert!(only_vtts.data_request.is_empty());
+
+        let only_drs = mempool_hashes(
+            &transactions_pool,
+            GetMempool {
+                filter: MempoolFilter::DataRequest,
+                limit: 0,
+            },
+        );
+        assert!(only_drs.value_transfer.is_empty());
+        assert_eq!(sorted(only_drs.data_request), dr_hashes);
+
+        let limited = mempool_hashes(
+            &transactions_pool,
+            GetMempool {
+                filter: MempoolFilter::All,
+                limit: 2,
+            },
+        );
+        assert_eq!(limited.value_transfer.len(), 2);
+        assert_eq!(limited.data_request.len(), 2);
+    }
+
+    #[test]
+    fn superblock_consensus_tie_is_deterministic() {
+        let superblock = CheckpointBeacon {
+            checkpoint: 0,
+            hash_prev_block: "6b86b273ff34fce19d6b804eff5a3f5747ada4eaa22f1d49c01e52ddb7875b4b"
+                .parse()
+                .unwrap(),
+        };
+        let last_beacon = |hash: &str| LastBeacon {
+            highest_block_checkpoint: CheckpointBeacon {
+                checkpoint: 1,
+                hash_prev_block: hash.parse().unwrap(),
+            },
+            highest_superblock_checkpoint: superblock,
+        };
+        let beacon_a =
+            last_beacon("2222222222222222222222222222222222222222222222222222222222222222");
+        let beacon_b =
+            last_beacon("1111111111111111111111111111111111111111111111111111111111111111");
+        let beacon_c =
+            last_beacon("0000000000000000000000000000000000000000000000000000000000000000");
+
+        // 9 peers (SB 9/9, B 4/9 for A, 4/9 for B and 1/9 for C)
+        let mut beacons = vec![beacon_a.clone(); 4];
+        beacons.extend(vec![beacon_b.clone(); 4]);
+        beacons.push(beacon_c);
+
+        // The tie between A and B is broken in favor of the lowest hash, regardless of the
+        // order in which the beacons were received
+        let expected = Some((beacon_b, false));
+        for rotation in 0..beacons.len() {
+            for reverse in [false, true] {
+                let mut beacons = beacons.clone();
+                beacons.rotate_left(rotation);
+                if reverse {
+                    beacons.reverse();
+                }
+                let peers_beacons = PeersBeacons {
+                    pb: beacons
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, beacon)| {
+                            (
+                                format!("127.0.0.1:{}", 10001 + i).parse().unwrap(),
+                                Some(beacon),
+                            )
+                        })
+                        .collect(),
+                    outbound_limit: Some(9),
+                };
+                assert_eq!(peers_beacons.superblock_consensus(60), expected);
+            }
+        }
+    }
+
     #[test]
     fn test_superblock_consensus() {
         let hash_1 =
@@ -32,21 +32,26 @@ use std::{
     convert::TryFrom,
     future,
     net::SocketAddr,
+    ops::Bound,
     pin::Pin,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use actix::{
     prelude::*, ActorFutureExt, ActorTryFutureExt, AsyncContext, Context, ContextFutureSpawner,
     Supervised, SystemService, WrapFuture,
 };
-use ansi_term::Color::{Purple, White, Yellow};
+use ansi_term::{
+    Color::{Purple, White, Yellow},
+    Style,
+};
 use derive_more::{Display, Error};
 use failure::Fail;
 use futures::future::{try_join_all, FutureExt};
 use glob::glob;
 use itertools::Itertools;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use witnet_config::{
     config::Tapi,
     defaults::{
@@ -64,10 +69,11 @@ use witnet_data_structures::{
         Alpha, AltKeys, Block, BlockHeader, Bn256PublicKey, ChainImport, ChainInfo, ChainState,
         CheckpointBeacon, CheckpointVRF, ConsensusConstants, DataRequestInfo, DataRequestOutput,
         DataRequestStage, Epoch, EpochConstants, Hash, Hashable, InventoryEntry, InventoryItem,
-        NodeStats, PublicKeyHash, Reputation, ReputationEngine, SignaturesToVerify, StateMachine,
-        SuperBlock, SuperBlockVote, TransactionsPool,
+        NodeStats, OutputPointer, PublicKeyHash, Reputation, ReputationEngine, SignaturesToVerify,
+        StateMachine, SuperBlock, SuperBlockVote, TransactionsPool,
     },
     data_request::DataRequestPool,
+    error::ChainInfoError,
     get_environment,
     radon_report::{RadonReport, ReportContext},
     superblock::{ARSIdentities, AddSuperBlockVote, SuperBlockConsensus},
@@ -92,10 +98,12 @@ use crate::{
         inventory_manager::InventoryManager,
         json_rpc::JsonRpcServer,
         messages::{
-            AddItem, AddItems, AddTransaction, Anycast, BlockNotify, Broadcast, DropOutboundPeers,
-            GetBlocksEpochRange, GetItemBlock, NodeStatusNotify, RemoveAddressesFromTried,
-            SendInventoryItem, SendInventoryRequest, SendLastBeacon, SendSuperBlockVote,
-            SetLastBeacon, SetSuperBlockTargetBeacon, StoreInventoryItem, SuperBlockNotify,
+            AddItem, AddItems, AddTransaction, Anycast, BlockNotify, BlocksEpochRangePage,
+            Broadcast, DataRequestResolved, DropOutboundPeers, GetBlocksEpochRange,
+            GetBlocksEpochRangePaged, GetBlocksEpochRangeWithTimestamps, GetItemBlock,
+            NodeStateChanged, NodeStatusNotify, RemoveAddressesFromTried, SendInventoryItem,
+            SendInventoryRequest, SendLastBeacon, SendSuperBlockVote, SetLastBeacon,
+            SetSuperBlockTargetBeacon, StoreInventoryItem, SuperBlockNotify, TipInfo,
         },
         node::{NodeOps, PutNodeOps},
         peers_manager::PeersManager,
@@ -137,9 +145,24 @@ pub enum ChainManagerError {
         /// not possible at this time.
         current_state: StateMachine,
     },
+    /// The requested output does not exist or has already been spent
+    #[fail(display = "Output not found: {}", output_pointer)]
+    OutputNotFound {
+        /// The output pointer that was looked up
+        output_pointer: OutputPointer,
+    },
+    /// Rewinding the chain state is disabled in the node configuration
+    #[fail(display = "Rewinding the chain state is disabled by node setting storage.allow_rewind")]
+    RewindDisabled,
     /// The node is trying to mine a block so commits are not allowed
     #[fail(display = "Commit received while node is trying to mine a block")]
     TooLateToCommit,
+    /// The timestamp of an epoch does not fit in an i64
+    #[fail(display = "Overflow when calculating the timestamp of epoch {}", epoch)]
+    EpochTimestampOverflow {
+        /// Epoch whose timestamp was calculated
+        epoch: Epoch,
+    },
     /// The node received a batch of blocks that is inconsistent with the current index
     #[fail(
         display = "Wrong number of blocks provided {:?} for superblock index {:?} and epoch {:?})",
@@ -156,7 +179,7 @@ pub enum ChainManagerError {
 }
 
 /// Synchronization target determined by the beacons received from outbound peers
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SyncTarget {
     // TODO: the target block must be set, but the node will not assume that it is valid
     block: CheckpointBeacon,
@@ -187,12 +210,25 @@ pub struct ChainManager {
     sm_state: StateMachine,
     /// The best beacon known to this node—to which it will try to catch up
     sync_target: Option<SyncTarget>,
+    /// Whether `sync_target` was restored from storage and has not been replaced by a peers
+    /// consensus yet
+    sync_target_restored: bool,
     /// The superblock hash and superblock according to a majority of peers
     sync_superblock: Option<(Hash, SuperBlock)>,
     /// The node asked for a batch of blocks on this epoch. This is used to implement a timeout
     /// that will move the node back to WaitingConsensus state if it does not receive any AddBlocks
     /// message after a certain number of epochs
     sync_waiting_for_add_blocks_since: Option<Epoch>,
+    /// Time at which the node started processing blocks in the current synchronization, along
+    /// with the epoch of its chain tip at that moment. Used to estimate the time left to reach
+    /// the sync target
+    sync_progress: Option<(Instant, Epoch)>,
+    /// Number of epochs to wait for a requested batch of blocks before requesting it again
+    sync_blocks_batch_timeout_epochs: u32,
+    /// Maximum number of epochs to roll back in order to follow a fork (0 means no limit)
+    max_reorg_depth: u32,
+    /// Whether the chain state can be rewound through the `Rewind` message
+    allow_rewind: bool,
     /// Map that stores candidate blocks for further validation and consolidation as tip of the blockchain
     /// (block_hash, block))
     candidates: HashMap<Hash, Vec<Block>>,
@@ -210,8 +246,10 @@ pub struct ChainManager {
     vrf_ctx: Option<VrfCtx>,
     /// Peers beacons boolean
     peers_beacons_received: bool,
-    /// Consensus parameter (in %)
+    /// Consensus parameter (in %) used while AlmostSynced or Synced
     consensus_c: u32,
+    /// Consensus parameter (in %) used while WaitingConsensus or Synchronizing
+    consensus_c_syncing: u32,
     /// Constants used to convert between epoch and timestamp
     epoch_constants: Option<EpochConstants>,
     /// Maximum number of sources to retrieve in a single epoch
@@ -220,6 +258,8 @@ pub struct ChainManager {
     data_request_timeout: Option<Duration>,
     /// Pending transaction timeout
     tx_pending_timeout: u64,
+    /// Whether `BuildVtt` and `BuildDrt` are allowed while `AlmostSynced`
+    build_transactions_while_almost_synced: bool,
     /// Magic number from ConsensusConstants
     magic: u16,
     /// External mint address
@@ -246,6 +286,73 @@ pub struct ChainManager {
     import: Force<ChainImport<ImportError>>,
     /// Signals that a chain snapshot export is due.
     export: Force<PathBuf>,
+    /// Options for logging data request results and consolidated blocks
+    dr_log_options: DrLogOptions,
+    /// Whether to log the `SYNCED_BANNER` when the node gets synced
+    show_banners: bool,
+    /// Reports of resolved data requests recently read from storage
+    dr_report_cache: DataRequestReportCache,
+    /// Supply held in the unspent outputs pool at the given chain beacon, used to avoid iterating
+    /// over all the UTXOs on every `GetSupplyInfo` until a new block is consolidated
+    utxo_supply_cache: Option<(CheckpointBeacon, UtxoSupply)>,
+    /// Data requests resolved in the blocks consolidated since the node started
+    resolved_data_requests: ResolvedDataRequests,
+}
+
+/// Counters of resolved data requests, used to answer `GetDataRequestStats`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct ResolvedDataRequests {
+    /// Number of resolved data requests
+    count: u64,
+    /// Sum of the number of epochs that it took to resolve each data request
+    total_epochs: u64,
+}
+
+impl ResolvedDataRequests {
+    /// Count a data request that could start receiving commitments in `dr_epoch` and whose tally
+    /// was included in a block of `tally_epoch`
+    fn insert(&mut self, dr_epoch: Epoch, tally_epoch: Epoch) {
+        self.count += 1;
+        self.total_epochs += u64::from(tally_epoch.saturating_sub(dr_epoch));
+    }
+
+    /// Average number of epochs that it took to resolve a data request
+    fn average_epochs(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.total_epochs as f64 / self.count as f64)
+        }
+    }
+}
+
+/// Options for logging data request results and consolidated blocks
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DrLogOptions {
+    /// Use ANSI colors
+    pub color: bool,
+    /// Include the result of completed data requests
+    pub results: bool,
+}
+
+impl Default for DrLogOptions {
+    fn default() -> Self {
+        Self {
+            color: true,
+            results: true,
+        }
+    }
+}
+
+impl DrLogOptions {
+    /// Paint `text` using `style`, but only if colors are enabled
+    fn paint<T: ToString>(&self, style: Style, text: T) -> String {
+        if self.color {
+            style.paint(text.to_string()).to_string()
+        } else {
+            text.to_string()
+        }
+    }
 }
 
 impl ChainManager {
@@ -500,6 +607,187 @@ impl ChainManager {
         Box::pin(fut)
     }
 
+    /// Persist the pending value transfer and data request transactions into storage, so that
+    /// they can be restored after a restart
+    fn persist_transactions_pool(&self) -> ResponseActFuture<Self, Result<(), ()>> {
+        let transactions: Vec<Transaction> = self
+            .transactions_pool
+            .vt_iter()
+            .cloned()
+            .map(Transaction::ValueTransfer)
+            .chain(
+                self.transactions_pool
+                    .dr_iter()
+                    .cloned()
+                    .map(Transaction::DataRequest),
+            )
+            .collect();
+
+        log::debug!(
+            "Persisting {} transactions from the transactions pool",
+            transactions.len()
+        );
+
+        let fut = storage_mngr::put(
+            &storage_keys::transactions_pool_key(self.get_magic()),
+            &transactions,
+        )
+        .into_actor(self)
+        .map_err(|err, _, _| {
+            log::error!("Failed to persist transactions pool into storage: {}", err)
+        });
+
+        Box::pin(fut)
+    }
+
+    /// Load the transactions pool persisted by `persist_transactions_pool` and queue the
+    /// transactions that are still spendable to be re-added into the mempool
+    fn restore_transactions_pool(&mut self) -> ResponseActFuture<Self, Result<(), ()>> {
+        let fut = storage_mngr::get::<_, Vec<Transaction>>(&storage_keys::transactions_pool_key(
+            self.get_magic(),
+        ))
+        .into_actor(self)
+        .map(|res, act, _ctx| {
+            match res {
+                Ok(Some(transactions)) => {
+                    let total = transactions.len();
+                    let transactions = filter_restored_transactions(
+                        transactions,
+                        &act.chain_state.unspent_outputs_pool,
+                    );
+                    log::debug!(
+                        "Restored {} out of {} persisted transactions into mempool",
+                        transactions.len(),
+                        total
+                    );
+                    act.temp_vts_and_drs.extend(transactions);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::error!("Failed to restore transactions pool from storage: {}", err)
+                }
+            }
+
+            // Failing to restore the transactions pool is not fatal
+            Ok(())
+        });
+
+        Box::pin(fut)
+    }
+
+    /// Set the synchronization target and persist it into storage, so that the node can resume
+    /// synchronizing towards the same target after a restart
+    fn set_sync_target(&mut self, ctx: &mut Context<Self>, sync_target: SyncTarget) {
+        // The first peers consensus always takes precedence over a target restored from storage
+        if std::mem::take(&mut self.sync_target_restored) && self.sync_target != Some(sync_target) {
+            log::info!(
+                "Discarding restored sync target {:?} in favor of the peers consensus {:?}",
+                self.sync_target,
+                sync_target
+            );
+        }
+
+        if self.sync_target == Some(sync_target) {
+            return;
+        }
+
+        self.sync_target = Some(sync_target);
+        ctx.spawn(self.persist_sync_target().map(|_res, _act, _ctx| ()));
+    }
+
+    /// Persist the current synchronization target into storage
+    fn persist_sync_target(&self) -> ResponseActFuture<Self, Result<(), ()>> {
+        let sync_target = match self.sync_target {
+            Some(sync_target) => sync_target,
+            None => return Box::pin(actix::fut::ok(())),
+        };
+
+        let fut = storage_mngr::put(
+            &storage_keys::sync_target_key(self.get_magic()),
+            &sync_target,
+        )
+        .into_actor(self)
+        .map_err(|err, _, _| log::error!("Failed to persist sync target into storage: {}", err));
+
+        Box::pin(fut)
+    }
+
+    /// Load the synchronization target persisted by `persist_sync_target`. The target is discarded
+    /// if it is older than the last superblock consolidated by this node
+    fn restore_sync_target(&mut self) -> ResponseActFuture<Self, Result<(), ()>> {
+        let fut = storage_mngr::get::<_, SyncTarget>(&storage_keys::sync_target_key(
+            self.get_magic(),
+        ))
+        .into_actor(self)
+        .map(|res, act, _ctx| {
+            match res {
+                Ok(Some(sync_target)) => {
+                    let superblock_beacon = act.get_superblock_beacon();
+                    if sync_target.superblock.checkpoint >= superblock_beacon.checkpoint {
+                        log::debug!("Restored sync target {:?}", sync_target);
+                        act.sync_target = Some(sync_target);
+                        act.sync_target_restored = true;
+                    } else {
+                        log::debug!(
+                            "Discarding persisted sync target {:?} because it is older than the current consensus {:?}",
+                            sync_target,
+                            superblock_beacon
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => log::error!("Failed to restore sync target from storage: {}", err),
+            }
+
+            // Failing to restore the sync target is not fatal
+            Ok(())
+        });
+
+        Box::pin(fut)
+    }
+
+    /// Target restored from storage that the node can synchronize towards while waiting for a
+    /// peers consensus, if it is still ahead of our chain
+    fn restored_sync_target_to_resume(&self) -> Option<SyncTarget> {
+        if !self.sync_target_restored {
+            return None;
+        }
+
+        self.sync_target
+            .filter(|sync_target| sync_target.block.checkpoint > self.get_chain_beacon().checkpoint)
+    }
+
+    /// Look up a block by its hash, first among the block candidates kept in memory and then in
+    /// the storage, where the consolidated blocks are persisted
+    fn get_block(&self, hash: Hash) -> ResponseActFuture<Self, Result<Block, failure::Error>> {
+        let candidate = self
+            .best_candidate
+            .iter()
+            .map(|candidate| &candidate.block)
+            .chain(self.candidates.values().flatten())
+            .find(|block| block.hash() == hash);
+        if let Some(block) = candidate {
+            return Box::pin(actix::fut::ok(block.clone()));
+        }
+
+        let key = match hash {
+            Hash::SHA256(x) => x.to_vec(),
+        };
+        let fut =
+            storage_mngr::get::<_, Block>(&key)
+                .into_actor(self)
+                .map(move |res, _act, _ctx| match res {
+                    Ok(Some(block)) => Ok(block),
+                    Ok(None) => Err(ChainManagerError::BlockDoesNotExist.into()),
+                    Err(e) => {
+                        log::error!("Couldn't get block {} from storage: {}", hash, e);
+                        Err(e)
+                    }
+                });
+
+        Box::pin(fut)
+    }
+
     /// Persist an empty `ChainState` to the storage and set the node to `WaitingConsensus`.
     /// This can be used to recover from a forked chain without manually deleting the storage.
     fn delete_chain_state_and_reinitialize(&mut self) -> ResponseActFuture<Self, Result<(), ()>> {
@@ -963,6 +1251,7 @@ impl ChainManager {
                     own_pkh,
                     &mut self.chain_state.own_utxos,
                     &mut self.chain_state.node_stats,
+                    &mut self.resolved_data_requests,
                     self.sm_state,
                 );
 
@@ -1043,8 +1332,14 @@ impl ChainManager {
                         let to_be_stored =
                             self.chain_state.data_request_pool.finished_data_requests();
                         for dr_info in &to_be_stored {
-                            show_tally_info(dr_info.tally.as_ref().unwrap(), block_epoch);
+                            show_tally_info(
+                                dr_info.tally.as_ref().unwrap(),
+                                block_epoch,
+                                self.dr_log_options,
+                            );
                         }
+                        let resolved_data_requests =
+                            data_requests_resolved(&to_be_stored, block_hash, block_epoch);
 
                         if !resynchronizing {
                             self.persist_data_requests(ctx, to_be_stored);
@@ -1055,7 +1350,11 @@ impl ChainManager {
                             .data_request_pool
                             .update_data_request_stages();
 
-                        show_info_dr(&self.chain_state.data_request_pool, &block);
+                        show_info_dr(
+                            &self.chain_state.data_request_pool,
+                            &block,
+                            self.dr_log_options,
+                        );
 
                         for reveal in reveals {
                             // Send AddTransaction message to self
@@ -1081,8 +1380,11 @@ impl ChainManager {
                             );
                         }
 
-                        // Send notification to JsonRpcServer
-                        JsonRpcServer::from_registry().do_send(BlockNotify { block })
+                        // Send notifications to JsonRpcServer
+                        JsonRpcServer::from_registry().do_send(BlockNotify { block });
+                        for resolved in resolved_data_requests {
+                            JsonRpcServer::from_registry().do_send(resolved);
+                        }
                     }
                 }
 
@@ -1198,6 +1500,22 @@ impl ChainManager {
         self.chain_state.get_consensus_constants()
     }
 
+    /// Retrieve the highest block checkpoint along with the current state of the node, regardless
+    /// of whether the node is synced.
+    fn tip(&self) -> Result<TipInfo, failure::Error> {
+        let chain_info = self
+            .chain_state
+            .chain_info
+            .as_ref()
+            .ok_or(ChainInfoError::ChainInfoNotFound)?;
+
+        Ok(TipInfo {
+            beacon: chain_info.highest_block_checkpoint,
+            node_state: self.sm_state,
+            synced: self.sm_state == StateMachine::Synced,
+        })
+    }
+
     fn add_temp_superblock_votes(&mut self, ctx: &mut Context<Self>) {
         let consensus_constants = self.consensus_constants();
 
@@ -1735,8 +2053,11 @@ impl ChainManager {
 
                         let fut: Pin<Box<dyn ActorFuture<Self, Output = Result<_, ()>>>> = if act.sm_state == StateMachine::Synced || act.sm_state == StateMachine::AlmostSynced {
                             // Persist previous_chain_state with current superblock_state
-                            Box::pin(act.persist_chain_state(Some(voted_superblock_beacon.checkpoint)).map(move |_res: Result<(), ()>, act, _ctx| {
+                            Box::pin(act.persist_chain_state(Some(voted_superblock_beacon.checkpoint)).map(move |_res: Result<(), ()>, act, ctx| {
                                 act.move_chain_state_forward(superblock_index);
+                                act.persist_transactions_pool()
+                                    .map(|_res: Result<(), ()>, _act, _ctx| ())
+                                    .spawn(ctx);
 
                                 Ok((block_headers, last_hash))
                             }))
@@ -2017,26 +2338,24 @@ impl ChainManager {
     /// and to ensure that every transition gets logged in a predictable format.
     fn update_state_machine(&mut self, next_state: StateMachine, ctx: &mut Context<Self>) {
         let same_state = self.sm_state == next_state;
-        match (&self.sm_state, &next_state) {
-            (old, _new) if same_state => log::debug!("State machine staying in state {:?}", old),
-            (_, StateMachine::Synced) => log::debug!(
-                "State machine is transitioning from {:?} into {:?}\n{}",
-                self.sm_state,
-                next_state,
-                SYNCED_BANNER
-            ),
-            _ => log::debug!(
-                "State machine is transitioning from {:?} into {:?}",
-                self.sm_state,
-                next_state
-            ),
-        }
+        log::debug!(
+            "{}",
+            state_transition_message(self.sm_state, next_state, self.show_banners)
+        );
 
         if !same_state && next_state == StateMachine::AlmostSynced {
             self.add_temp_superblock_votes(ctx)
         }
 
+        // The progress of a previous synchronization must not be used to estimate the time left
+        if !same_state && next_state == StateMachine::Synchronizing {
+            self.sync_progress = None;
+        }
+
         self.notify_node_status(next_state);
+        if let Some(state_changed) = node_state_changed(self.sm_state, next_state) {
+            JsonRpcServer::from_registry().do_send(state_changed);
+        }
         self.sm_state = next_state
     }
 
@@ -2071,6 +2390,102 @@ impl ChainManager {
         self.sync_waiting_for_add_blocks_since = Some(epoch);
     }
 
+    /// Number of epochs that would be rolled back by restoring the chain state of the last
+    /// consolidated superblock, as done when the node finds itself on a fork
+    fn reorg_depth(&self) -> u32 {
+        let superblock_period = u32::from(self.consensus_constants().superblock_period);
+        let consolidated_epoch = self
+            .get_superblock_beacon()
+            .checkpoint
+            .saturating_mul(superblock_period);
+
+        self.get_chain_beacon()
+            .checkpoint
+            .saturating_sub(consolidated_epoch)
+    }
+
+    /// Consensus threshold (in %) that applies to the current state: bootstrapping and steady
+    /// state have different risk profiles, so each of them has its own threshold
+    fn consensus_threshold(&self) -> usize {
+        let consensus_c = match self.sm_state {
+            StateMachine::WaitingConsensus | StateMachine::Synchronizing => {
+                self.consensus_c_syncing
+            }
+            StateMachine::AlmostSynced | StateMachine::Synced => self.consensus_c,
+        };
+
+        consensus_c as usize
+    }
+
+    /// Check whether the node can build new transactions in its current state. This requires the
+    /// node to be `Synced`, or `AlmostSynced` if `build_transactions_while_almost_synced` is set.
+    fn check_can_build_transactions(&self) -> Result<(), ChainManagerError> {
+        match self.sm_state {
+            StateMachine::Synced => Ok(()),
+            StateMachine::AlmostSynced if self.build_transactions_while_almost_synced => {
+                log::warn!("Building a transaction while the node is not completely synced yet");
+
+                Ok(())
+            }
+            current_state => Err(ChainManagerError::NotSynced { current_state }),
+        }
+    }
+
+    /// Highest block number whose outputs are old enough to be used as collateral
+    fn collateral_block_number_limit(&self) -> u32 {
+        let active_wips = ActiveWips {
+            active_wips: self.chain_state.tapi_engine.wip_activation.clone(),
+            block_epoch: self.current_epoch.unwrap(),
+        };
+        let collateral_age = if active_wips.wip0027() {
+            PSEUDO_CONSENSUS_CONSTANTS_WIP0027_COLLATERAL_AGE
+        } else {
+            self.chain_state
+                .chain_info
+                .as_ref()
+                .unwrap()
+                .consensus_constants
+                .collateral_age
+        };
+
+        self.chain_state
+            .block_number()
+            .saturating_sub(collateral_age)
+    }
+
+    /// Whether following a fork would roll back more than `max_reorg_depth` epochs
+    fn fork_too_deep(&self) -> bool {
+        self.max_reorg_depth != 0 && self.reorg_depth() > self.max_reorg_depth
+    }
+
+    /// Handle the node being on a fork by restoring the chain state from the storage, unless that
+    /// would roll back more than `max_reorg_depth` epochs. In that case the node refuses to follow
+    /// the fork and stays in its current chain until the operator intervenes.
+    fn handle_fork(&mut self, ctx: &mut Context<Self>) {
+        if self.fork_too_deep() {
+            log::error!(
+                "[CONSENSUS]: Refusing to follow a fork that would roll back {} epochs, more than the maximum of {}. Operator intervention is needed",
+                self.reorg_depth(),
+                self.max_reorg_depth
+            );
+        } else {
+            self.initialize_from_storage(ctx);
+            log::info!("Restored chain state from storage");
+        }
+    }
+
+    /// Whether the last requested batch of blocks has been waited for longer than the configured
+    /// number of epochs, and therefore it should be requested again
+    fn sync_blocks_batch_timed_out(&self) -> bool {
+        match (self.sync_waiting_for_add_blocks_since, self.current_epoch) {
+            (Some(sync_start_epoch), Some(current_epoch)) => {
+                current_epoch.saturating_sub(sync_start_epoch)
+                    >= self.sync_blocks_batch_timeout_epochs
+            }
+            _ => false,
+        }
+    }
+
     fn request_sync_target_superblock(
         &mut self,
         ctx: &mut Context<Self>,
@@ -2128,6 +2543,10 @@ impl ChainManager {
     ) -> (bool, usize) {
         let mut batch_succeeded = true;
         let mut num_processed_blocks = 0;
+        let chain_beacon = self.get_chain_beacon();
+        let sync_start = *self
+            .sync_progress
+            .get_or_insert_with(|| (Instant::now(), chain_beacon.checkpoint));
 
         for block in blocks.iter() {
             if let Err(e) = self.process_requested_block(ctx, block.clone(), false) {
@@ -2151,6 +2570,7 @@ impl ChainManager {
                 sync_target,
                 self.epoch_constants.unwrap(),
                 self.current_epoch.unwrap(),
+                sync_start,
             );
         }
 
@@ -2282,6 +2702,54 @@ impl ChainManager {
         }
     }
 
+    /// Get a list of (epoch, block_hash, timestamp), where the timestamp is the start of the
+    /// epoch of the block
+    fn get_blocks_epoch_range_with_timestamps(
+        &self,
+        GetBlocksEpochRangeWithTimestamps(msg): GetBlocksEpochRangeWithTimestamps,
+    ) -> Result<Vec<(Epoch, Hash, i64)>, ChainManagerError> {
+        let epoch_constants = self
+            .epoch_constants
+            .ok_or(ChainManagerError::ChainNotReady)?;
+
+        self.get_blocks_epoch_range(msg)
+            .into_iter()
+            .map(|(epoch, hash)| {
+                epoch_constants
+                    .epoch_timestamp(epoch)
+                    .map(|timestamp| (epoch, hash, timestamp))
+                    .map_err(|_| ChainManagerError::EpochTimestampOverflow { epoch })
+            })
+            .collect()
+    }
+
+    /// Get a page of (epoch, block_hash) starting right after the `after` epoch, along with the
+    /// cursor of the next page
+    fn get_blocks_epoch_range_paged(
+        &self,
+        GetBlocksEpochRangePaged { after, limit }: GetBlocksEpochRangePaged,
+    ) -> BlocksEpochRangePage {
+        let limit = if limit == 0 { MAX_BLOCKS_SYNC } else { limit };
+        let start = match after {
+            Some(epoch) => Bound::Excluded(epoch),
+            None => Bound::Unbounded,
+        };
+
+        let mut block_chain_range = self
+            .chain_state
+            .block_chain
+            .range((start, Bound::Unbounded))
+            .map(|(k, v)| (*k, *v));
+        let blocks: Vec<(Epoch, Hash)> = block_chain_range.by_ref().take(limit).collect();
+        // Only return a cursor if there are blocks left after this page
+        let next = match block_chain_range.next() {
+            Some(_) => blocks.last().map(|(epoch, _hash)| *epoch),
+            None => None,
+        };
+
+        BlocksEpochRangePage { blocks, next }
+    }
+
     /// This function takes the transactions included in unconfirmed blocks and it allows them to be
     /// included again in the mempool when the node wold be Synced again
     #[must_use]
@@ -2622,6 +3090,126 @@ impl From<bincode::Error> for ImportError {
     }
 }
 
+/// Value held in the unspent outputs pool
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UtxoSupply {
+    /// Sum of the values of all the unspent outputs
+    pub total: u64,
+    /// Time lock and value of the unspent outputs that have a time lock
+    pub time_locked: Vec<(u64, u64)>,
+}
+
+impl UtxoSupply {
+    /// Sum the values of all the unspent outputs
+    pub fn from_utxo_pool(unspent_outputs_pool: &UnspentOutputsPool) -> Self {
+        let mut supply = UtxoSupply::default();
+        for (_output_pointer, (value_transfer_output, _block_number)) in unspent_outputs_pool.iter()
+        {
+            supply.total += value_transfer_output.value;
+            if value_transfer_output.time_lock > 0 {
+                supply
+                    .time_locked
+                    .push((value_transfer_output.time_lock, value_transfer_output.value));
+            }
+        }
+
+        supply
+    }
+
+    /// Split the supply into unlocked and locked supply at the given timestamp
+    pub fn unlocked_and_locked(&self, current_time: u64) -> (u64, u64) {
+        let locked: u64 = self
+            .time_locked
+            .iter()
+            .filter(|(time_lock, _value)| *time_lock > current_time)
+            .map(|(_time_lock, value)| value)
+            .sum();
+
+        (self.total - locked, locked)
+    }
+}
+
+/// Maximum number of data request reports kept in the `DataRequestReportCache`
+const DATA_REQUEST_REPORT_CACHE_CAPACITY: usize = 1000;
+
+/// Number of attempts to read a data request report from storage before giving up
+const DATA_REQUEST_REPORT_READ_ATTEMPTS: u32 = 3;
+
+/// Time to wait before retrying a failed read of a data request report. It doubles after every
+/// failed attempt
+const DATA_REQUEST_REPORT_READ_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Bounded cache of the data request reports read from storage, so that polling the same resolved
+/// data request does not read the storage on every call.
+#[derive(Debug, Default)]
+pub struct DataRequestReportCache {
+    /// Time after which a cached report expires. Zero disables the cache
+    ttl: Duration,
+    /// Cached reports along with the instant when they were read from storage
+    entries: HashMap<Hash, (DataRequestInfo, Instant)>,
+}
+
+impl DataRequestReportCache {
+    /// Set the time after which the cached reports expire
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+        if ttl == Duration::from_secs(0) {
+            self.entries.clear();
+        }
+    }
+
+    /// Get the report of a data request, unless it has already expired
+    pub fn get(&mut self, dr_pointer: &Hash, now: Instant) -> Option<DataRequestInfo> {
+        let (dr_info, inserted_at) = self.entries.get(dr_pointer)?;
+        if now.saturating_duration_since(*inserted_at) < self.ttl {
+            Some(dr_info.clone())
+        } else {
+            self.entries.remove(dr_pointer);
+
+            None
+        }
+    }
+
+    /// Cache the report of a data request. When the cache is full, the expired reports are
+    /// removed first, and then the oldest one.
+    pub fn insert(&mut self, dr_pointer: Hash, dr_info: DataRequestInfo, now: Instant) {
+        if self.ttl == Duration::from_secs(0) {
+            return;
+        }
+
+        if self.entries.len() >= DATA_REQUEST_REPORT_CACHE_CAPACITY
+            && !self.entries.contains_key(&dr_pointer)
+        {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, (_, inserted_at)| now.saturating_duration_since(*inserted_at) < ttl);
+
+            if self.entries.len() >= DATA_REQUEST_REPORT_CACHE_CAPACITY {
+                if let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(hash, (_, inserted_at))| (*inserted_at, **hash))
+                    .map(|(hash, _)| *hash)
+                {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        self.entries.insert(dr_pointer, (dr_info, now));
+    }
+
+    /// Number of cached reports, including the expired ones that were not removed yet
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no cached reports
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
 /// Helper struct used to persist an old copy of the `ChainState` to the storage
 #[derive(Debug, Default)]
 struct ChainStateSnapshot {
@@ -2752,6 +3340,31 @@ impl ChainStateSnapshot {
     }
 }
 
+/// Filter the transactions restored from a persisted transactions pool, keeping only the value
+/// transfer and data request transactions whose inputs are all still unspent.
+///
+/// The transactions that pass this filter will be fully validated again once they are re-added
+/// into the mempool.
+pub fn filter_restored_transactions(
+    transactions: Vec<Transaction>,
+    utxo_set: &UnspentOutputsPool,
+) -> Vec<Transaction> {
+    transactions
+        .into_iter()
+        .filter(|transaction| {
+            let inputs = match transaction {
+                Transaction::ValueTransfer(vt_tx) => &vt_tx.body.inputs,
+                Transaction::DataRequest(dr_tx) => &dr_tx.body.inputs,
+                _ => return false,
+            };
+
+            inputs
+                .iter()
+                .all(|input| utxo_set.contains_key(input.output_pointer()))
+        })
+        .collect()
+}
+
 /// Block validation process which doesn't use futures
 ///
 /// This uses a `Visitor` that will visit each transaction as well as its fee and weight.
@@ -2879,6 +3492,7 @@ fn update_pools(
     own_pkh: PublicKeyHash,
     own_utxos: &mut OwnUnspentOutputsPool,
     node_stats: &mut NodeStats,
+    resolved_data_requests: &mut ResolvedDataRequests,
     state_machine: StateMachine,
 ) -> ReputationInfo {
     let mut rep_info = ReputationInfo::new();
@@ -2887,9 +3501,15 @@ fn update_pools(
         // Process tally transactions: used to update reputation engine
         rep_info.update(ta_tx, data_request_pool, own_pkh, node_stats);
 
+        let dr_epoch = data_request_pool
+            .data_request_state(&ta_tx.dr_pointer)
+            .map(|dr_state| dr_state.epoch);
+
         // IMPORTANT: Update the data request pool after updating reputation info
         if let Err(e) = data_request_pool.process_tally(ta_tx, &block.hash()) {
             log::error!("Error processing tally transaction:\n{}", e);
+        } else if let Some(dr_epoch) = dr_epoch {
+            resolved_data_requests.insert(dr_epoch, block.block_header.beacon.checkpoint);
         }
 
         transactions_pool.clear_reveals_from_finished_dr(&ta_tx.dr_pointer);
@@ -3137,21 +3757,89 @@ fn update_reputation(
     rep_eng.set_current_alpha(new_alpha);
 }
 
-fn show_tally_info(tally_tx: &TallyTransaction, block_epoch: Epoch) {
-    let result = RadonTypes::try_from(tally_tx.tally.as_slice());
-    let result_str = RadonReport::from_result(result, &ReportContext::default())
-        .into_inner()
-        .to_string();
-    log::info!(
-        "{} {} completed at epoch #{} with result: {}",
-        Yellow.bold().paint("[Data Request]"),
-        Yellow.bold().paint(tally_tx.dr_pointer.to_string()),
-        Yellow.bold().paint(block_epoch.to_string()),
-        Yellow.bold().paint(result_str),
+fn format_tally_info(
+    tally_tx: &TallyTransaction,
+    block_epoch: Epoch,
+    options: DrLogOptions,
+) -> String {
+    let info = format!(
+        "{} {} completed at epoch #{}",
+        options.paint(Yellow.bold(), "[Data Request]"),
+        options.paint(Yellow.bold(), tally_tx.dr_pointer),
+        options.paint(Yellow.bold(), block_epoch),
     );
+
+    if options.results {
+        let result = RadonTypes::try_from(tally_tx.tally.as_slice());
+        let result_str = RadonReport::from_result(result, &ReportContext::default())
+            .into_inner()
+            .to_string();
+
+        format!(
+            "{} with result: {}",
+            info,
+            options.paint(Yellow.bold(), result_str)
+        )
+    } else {
+        info
+    }
+}
+
+/// Build the log message for a transition of the state machine. When transitioning into `Synced`,
+/// the `SYNCED_BANNER` is only included if `show_banners` is set.
+fn state_transition_message(old: StateMachine, new: StateMachine, show_banners: bool) -> String {
+    match (old, new) {
+        (old, new) if old == new => format!("State machine staying in state {:?}", old),
+        (old, StateMachine::Synced) if show_banners => format!(
+            "State machine is transitioning from {:?} into {:?}\n{}",
+            old,
+            StateMachine::Synced,
+            SYNCED_BANNER
+        ),
+        (old, StateMachine::Synced) => format!(
+            "State machine is transitioning from {:?} into {:?}: node synced",
+            old,
+            StateMachine::Synced
+        ),
+        (old, new) => format!(
+            "State machine is transitioning from {:?} into {:?}",
+            old, new
+        ),
+    }
+}
+
+/// Build the notification of a state machine transition, unless the state stays the same
+fn node_state_changed(previous: StateMachine, current: StateMachine) -> Option<NodeStateChanged> {
+    if previous == current {
+        None
+    } else {
+        Some(NodeStateChanged { previous, current })
+    }
+}
+
+/// Build the notifications of the data requests whose tallies are included in a consolidated block
+fn data_requests_resolved(
+    dr_infos: &[DataRequestInfo],
+    block_hash: Hash,
+    block_epoch: Epoch,
+) -> Vec<DataRequestResolved> {
+    dr_infos
+        .iter()
+        .filter_map(|dr_info| dr_info.tally.as_ref())
+        .map(|tally| DataRequestResolved {
+            dr_pointer: tally.dr_pointer,
+            block_hash,
+            block_epoch,
+            tally: tally.tally.clone(),
+        })
+        .collect()
+}
+
+fn show_tally_info(tally_tx: &TallyTransaction, block_epoch: Epoch, options: DrLogOptions) {
+    log::info!("{}", format_tally_info(tally_tx, block_epoch, options));
 }
 
-fn show_info_dr(data_request_pool: &DataRequestPool, block: &Block) {
+fn show_info_dr(data_request_pool: &DataRequestPool, block: &Block, options: DrLogOptions) {
     let block_hash = block.hash();
     let block_epoch = block.block_header.beacon.checkpoint;
 
@@ -3168,8 +3856,8 @@ fn show_info_dr(data_request_pool: &DataRequestPool, block: &Block) {
                 format!(
                     "{}\n\t* {} Stage: {} ({}/{}), Commits: {}, Reveals: {}",
                     acc,
-                    White.bold().paint(k.to_string()),
-                    White.bold().paint(format!("{:?}", v.stage)),
+                    options.paint(White.bold(), k),
+                    options.paint(White.bold(), format!("{:?}", v.stage)),
                     current_round,
                     data_request_pool.extra_rounds + 1,
                     v.info.commits.len(),
@@ -3179,8 +3867,8 @@ fn show_info_dr(data_request_pool: &DataRequestPool, block: &Block) {
                 format!(
                     "{}\n\t* {} Stage: {}, Commits: {}, Reveals: {}",
                     acc,
-                    White.bold().paint(k.to_string()),
-                    White.bold().paint(format!("{:?}", v.stage)),
+                    options.paint(White.bold(), k),
+                    options.paint(White.bold(), format!("{:?}", v.stage)),
                     v.info.commits.len(),
                     v.info.reveals.len()
                 )
@@ -3190,19 +3878,19 @@ fn show_info_dr(data_request_pool: &DataRequestPool, block: &Block) {
     if info.is_empty() {
         log::info!(
             "{} Block {} consolidated for epoch #{} {}",
-            Purple.bold().paint("[Chain]"),
-            Purple.bold().paint(block_hash.to_string()),
-            Purple.bold().paint(block_epoch.to_string()),
-            White.paint("with no data requests".to_string()),
+            options.paint(Purple.bold(), "[Chain]"),
+            options.paint(Purple.bold(), block_hash),
+            options.paint(Purple.bold(), block_epoch),
+            options.paint(White.normal(), "with no data requests"),
         );
     } else {
         log::info!(
             "{} Block {} consolidated for epoch #{}\n{}{}",
-            Purple.bold().paint("[Chain]"),
-            Purple.bold().paint(block_hash.to_string()),
-            Purple.bold().paint(block_epoch.to_string()),
-            White.bold().paint("Data Requests: "),
-            White.bold().paint(info),
+            options.paint(Purple.bold(), "[Chain]"),
+            options.paint(Purple.bold(), block_hash),
+            options.paint(Purple.bold(), block_epoch),
+            options.paint(White.bold(), "Data Requests: "),
+            options.paint(White.bold(), info),
         );
     }
 }
@@ -3212,6 +3900,7 @@ fn show_sync_progress(
     sync_target: &SyncTarget,
     epoch_constants: EpochConstants,
     current_epoch: u32,
+    sync_start: (Instant, Epoch),
 ) {
     let target_checkpoint = sync_target.block.checkpoint;
     // Show progress log
@@ -3230,15 +3919,45 @@ fn show_sync_progress(
         * u32::from(epoch_constants.checkpoints_period);
 
     let human_age = seconds_to_human_string(u64::from(block_age));
+    let human_eta = sync_eta(
+        sync_start,
+        (Instant::now(), beacon.checkpoint),
+        target_checkpoint,
+    )
+    .map(|eta| seconds_to_human_string(eta.as_secs()))
+    .unwrap_or_else(|| "unknown".to_string());
     log::info!(
-        "Synchronization progress: {} ({:>6}/{:>6}). Latest synced block is {} old.",
+        "Synchronization progress: {} ({:>6}/{:>6}). Latest synced block is {} old. ETA: {}.",
         percent_done_string,
         beacon.checkpoint,
         target_checkpoint,
-        human_age
+        human_age,
+        human_eta
     );
 }
 
+/// Estimate the time left to reach `target_epoch`, extrapolating the rate at which epochs have
+/// been processed since the `start` of the synchronization
+fn sync_eta(
+    start: (Instant, Epoch),
+    now: (Instant, Epoch),
+    target_epoch: Epoch,
+) -> Option<Duration> {
+    let (start_instant, start_epoch) = start;
+    let (now_instant, now_epoch) = now;
+    let processed_epochs = now_epoch
+        .checked_sub(start_epoch)
+        .filter(|epochs| *epochs > 0)?;
+    let elapsed_secs = now_instant
+        .saturating_duration_since(start_instant)
+        .as_secs_f64();
+    let remaining_epochs = target_epoch.saturating_sub(now_epoch);
+
+    Some(Duration::from_secs_f64(
+        f64::from(remaining_epochs) * elapsed_secs / f64::from(processed_epochs),
+    ))
+}
+
 fn last_superblock_signed_by_bootstrap(consensus_constants: &ConsensusConstants) -> u32 {
     // This needs to use the old value of collateral_age, because it is calculating a superblock
     // index from the early days of the chain
@@ -3365,6 +4084,7 @@ pub fn run_dr_locally(dr: &DataRequestOutput) -> Result<RadonTypes, failure::Err
 mod tests {
     use std::sync::Arc;
 
+    use futures::StreamExt;
     use witnet_config::{
         config::{consensus_constants_from_partial, Config, StorageBackend},
         defaults::Testnet,
@@ -3381,16 +4101,25 @@ mod tests {
             OutputPointer, PartialConsensusConstants, PublicKey, SecretKey, Signature,
             ValueTransferOutput,
         },
+        fee::Fee,
         transaction::{
-            CommitTransaction, DRTransaction, MintTransaction, RevealTransaction, VTTransaction,
-            VTTransactionBody,
+            CommitTransaction, DRTransaction, DRTransactionBody, MintTransaction,
+            RevealTransaction, VTTransaction, VTTransactionBody,
         },
+        utxo_pool::UtxoSelectionStrategy,
         vrf::BlockEligibilityClaim,
     };
     use witnet_protected::Protected;
     use witnet_validations::validations::block_reward;
 
     use crate::{
+        actors::{
+            json_rpc::Subscriptions,
+            messages::{
+                BuildVtt, DataRequestStats, GetDataRequestInfo, GetDataRequestStats,
+                GetMempoolByPriority, GetSupplyInfo, PersistTransactionsPool,
+            },
+        },
         config_mngr,
         utils::{test_actix_system, ActorFutureToNormalFuture},
     };
@@ -3593,14 +4322,96 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(
-        expected = "Something went wrong as the last consolidated checkpoint is bigger than our current checkpoint 2 > 1"
-    )]
-    fn test_wrong_checkpoints() {
-        current_committee_size_requirement(5, 5, 4, 1, 2, 1, 0, 1);
-    }
-
-    #[test]
+    fn get_blocks_epoch_range_is_bounded_by_limit() {
+        let mut chain_manager = ChainManager::default();
+        for epoch in 0..2000 {
+            chain_manager
+                .chain_state
+                .block_chain
+                .insert(epoch, Hash::default());
+        }
+
+        let res =
+            chain_manager.get_blocks_epoch_range(GetBlocksEpochRange::new_with_limit(0.., 1000));
+        assert_eq!(res.len(), 1000);
+        assert_eq!(res[0].0, 0);
+
+        let res = chain_manager
+            .get_blocks_epoch_range(GetBlocksEpochRange::new_with_limit_from_end(0.., 1000));
+        assert_eq!(res.len(), 1000);
+        assert_eq!(res[0].0, 1000);
+    }
+
+    #[test]
+    fn get_blocks_epoch_range_paged_iterates_whole_chain() {
+        let mut chain_manager = ChainManager::default();
+        // Leave some gaps in the chain, as epochs without blocks are common
+        let expected: Vec<(Epoch, Hash)> = (0..100)
+            .filter(|epoch| epoch % 7 != 3)
+            .map(|epoch| (epoch, Hash::SHA256([u8::try_from(epoch).unwrap(); 32])))
+            .collect();
+        chain_manager
+            .chain_state
+            .block_chain
+            .extend(expected.iter().cloned());
+
+        let mut blocks = vec![];
+        let mut after = None;
+        let mut pages = 0;
+        loop {
+            let page = chain_manager
+                .get_blocks_epoch_range_paged(GetBlocksEpochRangePaged { after, limit: 10 });
+            assert!(page.blocks.len() <= 10);
+            blocks.extend(page.blocks);
+            pages += 1;
+            match page.next {
+                Some(next) => after = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(blocks, expected);
+        assert_eq!(pages, (expected.len() + 9) / 10);
+    }
+
+    #[test]
+    fn get_blocks_epoch_range_with_timestamps() {
+        let mut chain_manager = ChainManager::default();
+        chain_manager
+            .chain_state
+            .block_chain
+            .extend((0..10).map(|epoch| (epoch, Hash::SHA256([epoch as u8; 32]))));
+        let msg = || GetBlocksEpochRangeWithTimestamps(GetBlocksEpochRange::new(3..6));
+
+        // Epoch constants are needed to calculate the timestamps
+        assert_eq!(
+            chain_manager.get_blocks_epoch_range_with_timestamps(msg()),
+            Err(ChainManagerError::ChainNotReady)
+        );
+
+        chain_manager.epoch_constants = Some(EpochConstants {
+            checkpoint_zero_timestamp: 1_000,
+            checkpoints_period: 45,
+        });
+        assert_eq!(
+            chain_manager.get_blocks_epoch_range_with_timestamps(msg()),
+            Ok(vec![
+                (3, Hash::SHA256([3; 32]), 1_135),
+                (4, Hash::SHA256([4; 32]), 1_180),
+                (5, Hash::SHA256([5; 32]), 1_225),
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Something went wrong as the last consolidated checkpoint is bigger than our current checkpoint 2 > 1"
+    )]
+    fn test_wrong_checkpoints() {
+        current_committee_size_requirement(5, 5, 4, 1, 2, 1, 0, 1);
+    }
+
+    #[test]
     fn test_current_committee_size_requirement_sequence() {
         let default_size = 100;
         let decreasing_period = 5;
@@ -4033,6 +4844,920 @@ mod tests {
         Transaction::ValueTransfer(vtt)
     }
 
+    #[test]
+    fn synced_banner_can_be_disabled() {
+        let with_banner =
+            state_transition_message(StateMachine::AlmostSynced, StateMachine::Synced, true);
+        assert!(with_banner.contains(SYNCED_BANNER));
+
+        let without_banner =
+            state_transition_message(StateMachine::AlmostSynced, StateMachine::Synced, false);
+        assert!(!without_banner.contains(SYNCED_BANNER));
+        assert!(!without_banner.contains("This node has finished bootstrapping"));
+        assert!(without_banner.ends_with("node synced"));
+    }
+
+    #[test]
+    fn tip_is_returned_in_any_state() {
+        let mut chain_manager = ChainManager::default();
+        assert!(chain_manager.tip().is_err());
+
+        let highest_block_checkpoint = CheckpointBeacon {
+            checkpoint: 42,
+            hash_prev_block: Hash::SHA256([7; 32]),
+        };
+        chain_manager.chain_state.chain_info = Some(ChainInfo {
+            environment: Environment::default(),
+            consensus_constants: consensus_constants_from_partial(
+                &PartialConsensusConstants::default(),
+                &Testnet,
+            ),
+            highest_block_checkpoint,
+            highest_superblock_checkpoint: CheckpointBeacon::default(),
+            highest_vrf_output: CheckpointVRF::default(),
+        });
+
+        chain_manager.sm_state = StateMachine::Synchronizing;
+        assert_eq!(
+            chain_manager.tip().unwrap(),
+            TipInfo {
+                beacon: highest_block_checkpoint,
+                node_state: StateMachine::Synchronizing,
+                synced: false,
+            }
+        );
+
+        chain_manager.sm_state = StateMachine::Synced;
+        assert_eq!(
+            chain_manager.tip().unwrap(),
+            TipInfo {
+                beacon: highest_block_checkpoint,
+                node_state: StateMachine::Synced,
+                synced: true,
+            }
+        );
+    }
+
+    #[test]
+    fn get_block_from_memory_and_storage() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            // Setup testing: use in-memory database instead of rocksdb
+            let mut config = Config::default();
+            config.storage.backend = StorageBackend::HashMap;
+            let config = Arc::new(config);
+            // Start relevant actors
+            config_mngr::start(config);
+            storage_mngr::start();
+
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+
+            let block_with_epoch = |checkpoint| {
+                let mut block = Block::default();
+                block.block_header.beacon.checkpoint = checkpoint;
+                block
+            };
+
+            // A block candidate is found in memory
+            let candidate = block_with_epoch(1);
+            let candidate_hash = candidate.hash();
+            chain_manager
+                .candidates
+                .insert(candidate_hash, vec![candidate.clone()]);
+            let fut = chain_manager.get_block(candidate_hash);
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert_eq!(res.unwrap(), candidate);
+
+            // A consolidated block is found in the storage
+            let consolidated = block_with_epoch(2);
+            let consolidated_hash = consolidated.hash();
+            let key = match consolidated_hash {
+                Hash::SHA256(x) => x.to_vec(),
+            };
+            storage_mngr::put(&key, &consolidated).await.unwrap();
+            let fut = chain_manager.get_block(consolidated_hash);
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert_eq!(res.unwrap(), consolidated);
+
+            // Unknown blocks are not found
+            let fut = chain_manager.get_block(block_with_epoch(3).hash());
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert_eq!(
+                res.unwrap_err().downcast::<ChainManagerError>().unwrap(),
+                ChainManagerError::BlockDoesNotExist
+            );
+        });
+    }
+
+    #[test]
+    fn build_vtt_dry_run() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+            chain_manager.chain_state.chain_info = Some(ChainInfo {
+                environment: Environment::default(),
+                consensus_constants: consensus_constants_from_partial(
+                    &PartialConsensusConstants::default(),
+                    &Testnet,
+                ),
+                highest_block_checkpoint: CheckpointBeacon::default(),
+                highest_superblock_checkpoint: CheckpointBeacon::default(),
+                highest_vrf_output: CheckpointVRF::default(),
+            });
+            chain_manager.sm_state = StateMachine::Synced;
+            let own_pkh = PublicKeyHash::from_bytes(&[1; 20]).unwrap();
+            chain_manager.own_pkh = Some(own_pkh);
+
+            let utxo = OutputPointer {
+                transaction_id: Hash::SHA256([2; 32]),
+                output_index: 0,
+            };
+            chain_manager.chain_state.unspent_outputs_pool.insert(
+                utxo,
+                ValueTransferOutput {
+                    pkh: own_pkh,
+                    value: 1_000,
+                    time_lock: 0,
+                },
+                0,
+            );
+            chain_manager.chain_state.own_utxos.insert(utxo, 0);
+
+            let msg = BuildVtt {
+                vto: vec![ValueTransferOutput {
+                    pkh: PublicKeyHash::from_bytes(&[3; 20]).unwrap(),
+                    value: 600,
+                    time_lock: 0,
+                }],
+                fee: Fee::absolute_from_nanowits(10),
+                utxo_strategy: UtxoSelectionStrategy::default(),
+                dry_run: true,
+            };
+            let fut = chain_manager.handle(msg, &mut ctx);
+            let vtt = fut
+                .into_normal_future(&mut chain_manager, &mut ctx)
+                .await
+                .unwrap();
+
+            // The transaction is built but not signed
+            assert!(vtt.signatures.is_empty());
+            assert_eq!(vtt.hash(), vtt.body.hash());
+            assert_eq!(vtt.weight(), vtt.body.weight());
+            assert_eq!(vtt.body.inputs, vec![Input::new(utxo)]);
+            // Change output after paying 600 and a fee of 10
+            assert_eq!(vtt.body.outputs[1].value, 390);
+            // The mempool is not modified and the UTXO is not marked as used
+            assert!(chain_manager.transactions_pool.is_empty());
+            assert_eq!(chain_manager.chain_state.own_utxos.get(&utxo), Some(&0));
+        });
+    }
+
+    #[test]
+    fn data_request_report_read_from_storage_once() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            // Setup testing: use in-memory database instead of rocksdb
+            let mut config = Config::default();
+            config.storage.backend = StorageBackend::HashMap;
+            let config = Arc::new(config);
+            // Start relevant actors
+            config_mngr::start(config);
+            storage_mngr::start();
+
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+            chain_manager
+                .dr_report_cache
+                .set_ttl(Duration::from_secs(60));
+
+            let dr_pointer = Hash::SHA256([4; 32]);
+            // Finished data requests have no current stage
+            let dr_info = DataRequestInfo {
+                current_stage: None,
+                block_hash_tally_tx: Some(Hash::SHA256([6; 32])),
+                ..DataRequestInfo::default()
+            };
+            let key = format!("DR-REPORT-{}", dr_pointer);
+            storage_mngr::put(&key, &dr_info).await.unwrap();
+
+            let fut = chain_manager.handle(GetDataRequestInfo { dr_pointer }, &mut ctx);
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert_eq!(res.unwrap(), dr_info);
+            assert_eq!(chain_manager.dr_report_cache.len(), 1);
+
+            // Once the report is cached, the second lookup does not read the storage anymore
+            storage_mngr::delete(&key).await.unwrap();
+            let fut = chain_manager.handle(GetDataRequestInfo { dr_pointer }, &mut ctx);
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert_eq!(res.unwrap(), dr_info);
+        });
+    }
+
+    #[test]
+    fn data_request_report_cache_expiration() {
+        let mut cache = DataRequestReportCache::default();
+        let dr_pointer = Hash::SHA256([5; 32]);
+        let now = Instant::now();
+
+        // The cache is disabled by default
+        cache.insert(dr_pointer, DataRequestInfo::default(), now);
+        assert!(cache.is_empty());
+
+        cache.set_ttl(Duration::from_secs(10));
+        cache.insert(dr_pointer, DataRequestInfo::default(), now);
+        assert_eq!(
+            cache.get(&dr_pointer, now + Duration::from_secs(9)),
+            Some(DataRequestInfo::default())
+        );
+        assert_eq!(cache.get(&dr_pointer, now + Duration::from_secs(10)), None);
+        assert!(cache.is_empty());
+
+        // When the cache is full, the oldest report is evicted
+        let hash_from_index = |i: u64| {
+            let mut bytes = [0; 32];
+            bytes[..8].copy_from_slice(&i.to_be_bytes());
+            Hash::SHA256(bytes)
+        };
+        for i in 0..=u64::try_from(DATA_REQUEST_REPORT_CACHE_CAPACITY).unwrap() {
+            cache.insert(
+                hash_from_index(i),
+                DataRequestInfo::default(),
+                now + Duration::from_millis(i),
+            );
+        }
+        assert_eq!(cache.len(), DATA_REQUEST_REPORT_CACHE_CAPACITY);
+        assert_eq!(cache.get(&hash_from_index(0), now), None);
+        assert!(cache.get(&hash_from_index(1), now).is_some());
+    }
+
+    #[test]
+    fn supply_info_is_cached_until_new_block() {
+        test_actix_system(|| async {
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+            chain_manager.chain_state.chain_info = Some(ChainInfo {
+                environment: Environment::default(),
+                consensus_constants: consensus_constants_from_partial(
+                    &PartialConsensusConstants::default(),
+                    &Testnet,
+                ),
+                highest_block_checkpoint: CheckpointBeacon::default(),
+                highest_superblock_checkpoint: CheckpointBeacon::default(),
+                highest_vrf_output: CheckpointVRF::default(),
+            });
+            chain_manager.sm_state = StateMachine::Synced;
+            chain_manager.current_epoch = Some(1);
+
+            let insert_utxo = |chain_manager: &mut ChainManager, index, value, time_lock| {
+                chain_manager.chain_state.unspent_outputs_pool.insert(
+                    OutputPointer {
+                        transaction_id: Hash::SHA256([7; 32]),
+                        output_index: index,
+                    },
+                    ValueTransferOutput {
+                        pkh: PublicKeyHash::default(),
+                        value,
+                        time_lock,
+                    },
+                    0,
+                );
+            };
+            insert_utxo(&mut chain_manager, 0, 100, 0);
+            insert_utxo(&mut chain_manager, 1, 200, 0);
+            // Locked until the year 2286
+            insert_utxo(&mut chain_manager, 2, 50, 9_999_999_999);
+
+            let supply_info = chain_manager.handle(GetSupplyInfo, &mut ctx).unwrap();
+            assert_eq!(supply_info.current_unlocked_supply, 300);
+            assert_eq!(supply_info.current_locked_supply, 50);
+            assert_eq!(supply_info.locked_wits_by_requests, 0);
+            assert_eq!(
+                chain_manager.utxo_supply_cache,
+                Some((
+                    CheckpointBeacon::default(),
+                    UtxoSupply {
+                        total: 350,
+                        time_locked: vec![(9_999_999_999, 50)],
+                    }
+                ))
+            );
+
+            // The UTXO set is not iterated again while the chain beacon does not change
+            insert_utxo(&mut chain_manager, 3, 1_000, 0);
+            let supply_info = chain_manager.handle(GetSupplyInfo, &mut ctx).unwrap();
+            assert_eq!(supply_info.current_unlocked_supply, 300);
+
+            // A new block invalidates the cached supply
+            chain_manager
+                .chain_state
+                .chain_info
+                .as_mut()
+                .unwrap()
+                .highest_block_checkpoint
+                .checkpoint = 1;
+            let supply_info = chain_manager.handle(GetSupplyInfo, &mut ctx).unwrap();
+            assert_eq!(supply_info.current_unlocked_supply, 1_300);
+            assert_eq!(supply_info.current_locked_supply, 50);
+        });
+    }
+
+    #[test]
+    fn data_requests_resolved_by_consolidated_block() {
+        let dr_pointer = Hash::SHA256([1; 32]);
+        let block_hash = Hash::SHA256([2; 32]);
+        let tally = TallyTransaction::new(dr_pointer, vec![0x18, 0x2A], vec![], vec![], vec![]);
+        let dr_infos = vec![
+            DataRequestInfo {
+                tally: Some(tally),
+                block_hash_tally_tx: Some(block_hash),
+                ..DataRequestInfo::default()
+            },
+            // A data request without tally is not resolved yet
+            DataRequestInfo::default(),
+        ];
+
+        assert_eq!(
+            data_requests_resolved(&dr_infos, block_hash, 10),
+            vec![DataRequestResolved {
+                dr_pointer,
+                block_hash,
+                block_epoch: 10,
+                tally: vec![0x18, 0x2A],
+            }]
+        );
+    }
+
+    #[test]
+    fn consolidate_block_notifies_resolved_data_requests() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            // Setup testing: use in-memory database instead of rocksdb
+            let mut config = Config::default();
+            config.storage.backend = StorageBackend::HashMap;
+            let config = Arc::new(config);
+            // Start relevant actors
+            config_mngr::start(config);
+            storage_mngr::start();
+
+            // Subscribe to the resolution of the data request
+            let mut dr_tx = DRTransaction::default();
+            dr_tx.signatures.push(KeyedSignature::default());
+            let dr_pointer = dr_tx.hash();
+            let (subscriber, _id_receiver, mut notifications) =
+                jsonrpc_pubsub::Subscriber::new_test("dataRequestResolved");
+            let subscription = jsonrpc_pubsub::SubscriptionId::Number(1);
+            let sink = subscriber.assign_id(subscription.clone()).unwrap();
+            let subscriptions = Subscriptions::default();
+            subscriptions.lock().unwrap().insert(
+                "dataRequestResolved".to_string(),
+                vec![(
+                    subscription,
+                    (sink, serde_json::Value::from(dr_pointer.to_string())),
+                )]
+                .into_iter()
+                .collect(),
+            );
+            SystemRegistry::set(JsonRpcServer::with_subscriptions(subscriptions).start());
+
+            // A data request without commits is ready for tally
+            let mut dr_pool = DataRequestPool::default();
+            dr_pool
+                .add_data_request(0, dr_tx, &Hash::default())
+                .unwrap();
+            dr_pool.update_data_request_stages();
+            assert!(dr_pool.get_tally_ready_drs().contains(&dr_pointer));
+
+            // A block with its tally
+            let mut block = Block::default();
+            block.txns.tally_txns.push(TallyTransaction::new(
+                dr_pointer,
+                vec![0x18, 0x2A],
+                vec![],
+                vec![],
+                vec![],
+            ));
+            let block_hash = block.hash();
+
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+            let mut consensus_constants =
+                consensus_constants_from_partial(&PartialConsensusConstants::default(), &Testnet);
+            // Consolidating the genesis block does not update the reputation
+            consensus_constants.genesis_hash = block_hash;
+            chain_manager.chain_state.chain_info = Some(ChainInfo {
+                environment: Environment::default(),
+                consensus_constants,
+                highest_block_checkpoint: CheckpointBeacon::default(),
+                highest_superblock_checkpoint: CheckpointBeacon::default(),
+                highest_vrf_output: CheckpointVRF::default(),
+            });
+            chain_manager.chain_state.reputation_engine = Some(ReputationEngine::new(1000));
+            chain_manager.chain_state.data_request_pool = dr_pool;
+            chain_manager.vrf_ctx = Some(VrfCtx::secp256k1().unwrap());
+            chain_manager.own_pkh = Some(PublicKeyHash::default());
+            chain_manager.sm_state = StateMachine::Synced;
+
+            chain_manager.consolidate_block(
+                &mut ctx,
+                block,
+                Diff::new(),
+                Priorities::default(),
+                true,
+            );
+
+            let notification = tokio::time::timeout(Duration::from_secs(5), notifications.next())
+                .await
+                .expect("the resolved data request should be notified")
+                .unwrap();
+            let notification: serde_json::Value = serde_json::from_str(&notification).unwrap();
+            assert_eq!(
+                notification["params"]["result"],
+                serde_json::to_value(DataRequestResolved {
+                    dr_pointer,
+                    block_hash,
+                    block_epoch: 0,
+                    tally: vec![0x18, 0x2A],
+                })
+                .unwrap()
+            );
+            assert_eq!(notification["params"]["subscription"], 1);
+        });
+    }
+
+    #[test]
+    fn node_state_changed_on_transitions() {
+        let transitions = [
+            StateMachine::WaitingConsensus,
+            StateMachine::WaitingConsensus,
+            StateMachine::AlmostSynced,
+            StateMachine::Synced,
+            StateMachine::Synced,
+        ];
+        let notifications: Vec<_> = transitions
+            .windows(2)
+            .filter_map(|w| node_state_changed(w[0], w[1]))
+            .collect();
+
+        // Staying in the same state is not notified
+        assert_eq!(
+            notifications,
+            vec![
+                NodeStateChanged {
+                    previous: StateMachine::WaitingConsensus,
+                    current: StateMachine::AlmostSynced,
+                },
+                NodeStateChanged {
+                    previous: StateMachine::AlmostSynced,
+                    current: StateMachine::Synced,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_eta_from_sync_start() {
+        // 100 epochs processed in 10 seconds, 800 epochs left
+        let start = Instant::now();
+        let later = start + Duration::from_secs(10);
+        assert_eq!(
+            sync_eta((start, 100), (later, 200), 1000),
+            Some(Duration::from_secs(80))
+        );
+        // No progress since the synchronization started, so the rate is unknown
+        assert_eq!(sync_eta((start, 200), (later, 200), 1000), None);
+    }
+
+    #[test]
+    fn sync_progress_is_reset_when_synchronizing_again() {
+        test_actix_system(|| async {
+            let mut chain_manager = ChainManager::default();
+            let mut ctx = Context::new();
+            chain_manager.sm_state = StateMachine::Synchronizing;
+            chain_manager.sync_progress = Some((Instant::now(), 100));
+
+            // Staying in Synchronizing keeps the progress of the current synchronization
+            chain_manager.update_state_machine(StateMachine::Synchronizing, &mut ctx);
+            assert!(chain_manager.sync_progress.is_some());
+
+            // A new synchronization does not use the progress of the previous one
+            chain_manager.update_state_machine(StateMachine::WaitingConsensus, &mut ctx);
+            chain_manager.update_state_machine(StateMachine::Synchronizing, &mut ctx);
+            assert_eq!(chain_manager.sync_progress, None);
+        });
+    }
+
+    #[test]
+    fn fork_deeper_than_max_reorg_depth_is_refused() {
+        let mut chain_manager = ChainManager::default();
+        let consensus_constants =
+            consensus_constants_from_partial(&PartialConsensusConstants::default(), &Testnet);
+        let superblock_period = u32::from(consensus_constants.superblock_period);
+        let set_beacons = |chain_manager: &mut ChainManager, block_epoch, superblock_index| {
+            chain_manager.chain_state.chain_info = Some(ChainInfo {
+                environment: Environment::default(),
+                consensus_constants: consensus_constants.clone(),
+                highest_block_checkpoint: CheckpointBeacon {
+                    checkpoint: block_epoch,
+                    hash_prev_block: Hash::SHA256([1; 32]),
+                },
+                highest_superblock_checkpoint: CheckpointBeacon {
+                    checkpoint: superblock_index,
+                    hash_prev_block: Hash::SHA256([2; 32]),
+                },
+                highest_vrf_output: CheckpointVRF::default(),
+            });
+        };
+        chain_manager.max_reorg_depth = 2 * superblock_period;
+
+        // Shallow fork: the chain tip is a few epochs after the last consolidated superblock
+        set_beacons(&mut chain_manager, 10 * superblock_period + 3, 10);
+        assert_eq!(chain_manager.reorg_depth(), 3);
+        assert!(!chain_manager.fork_too_deep());
+
+        // Exactly at the limit
+        set_beacons(&mut chain_manager, 12 * superblock_period, 10);
+        assert!(!chain_manager.fork_too_deep());
+
+        // Deep fork: superblocks have not been consolidated for a long time
+        set_beacons(&mut chain_manager, 12 * superblock_period + 1, 10);
+        assert!(chain_manager.fork_too_deep());
+
+        // A limit of 0 follows forks of any depth
+        chain_manager.max_reorg_depth = 0;
+        assert!(!chain_manager.fork_too_deep());
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    #[test]
+    fn build_transactions_while_almost_synced() {
+        let mut chain_manager = ChainManager::default();
+        let not_synced = |current_state| Err(ChainManagerError::NotSynced { current_state });
+
+        // By default, transactions can only be built while synced
+        chain_manager.sm_state = StateMachine::Synced;
+        assert_eq!(chain_manager.check_can_build_transactions(), Ok(()));
+        chain_manager.sm_state = StateMachine::AlmostSynced;
+        assert_eq!(
+            chain_manager.check_can_build_transactions(),
+            not_synced(StateMachine::AlmostSynced)
+        );
+
+        chain_manager.build_transactions_while_almost_synced = true;
+        chain_manager.sm_state = StateMachine::AlmostSynced;
+        assert_eq!(chain_manager.check_can_build_transactions(), Ok(()));
+        chain_manager.sm_state = StateMachine::Synchronizing;
+        assert_eq!(
+            chain_manager.check_can_build_transactions(),
+            not_synced(StateMachine::Synchronizing)
+        );
+        chain_manager.sm_state = StateMachine::WaitingConsensus;
+        assert_eq!(
+            chain_manager.check_can_build_transactions(),
+            not_synced(StateMachine::WaitingConsensus)
+        );
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    #[test]
+    fn consensus_threshold_depends_on_state() {
+        let mut chain_manager = ChainManager::default();
+        chain_manager.consensus_c = 60;
+        chain_manager.consensus_c_syncing = 80;
+
+        chain_manager.sm_state = StateMachine::WaitingConsensus;
+        assert_eq!(chain_manager.consensus_threshold(), 80);
+        chain_manager.sm_state = StateMachine::Synchronizing;
+        assert_eq!(chain_manager.consensus_threshold(), 80);
+        chain_manager.sm_state = StateMachine::AlmostSynced;
+        assert_eq!(chain_manager.consensus_threshold(), 60);
+        chain_manager.sm_state = StateMachine::Synced;
+        assert_eq!(chain_manager.consensus_threshold(), 60);
+    }
+
+    // TODO: cannot use struct update syntax with ChainManager because it implements the
+    // Drop trait, but clippy seems to miss that?
+    #[allow(clippy::field_reassign_with_default)]
+    #[test]
+    fn sync_blocks_batch_timeout() {
+        let mut chain_manager = ChainManager::default();
+        chain_manager.sm_state = StateMachine::Synchronizing;
+        chain_manager.sync_blocks_batch_timeout_epochs = 10;
+        // Not waiting for any batch of blocks
+        chain_manager.current_epoch = Some(100);
+        assert!(!chain_manager.sync_blocks_batch_timed_out());
+
+        // Blocks were requested at epoch 100
+        chain_manager.sync_waiting_for_add_blocks_since = Some(100);
+        chain_manager.current_epoch = Some(105);
+        assert!(!chain_manager.sync_blocks_batch_timed_out());
+        chain_manager.current_epoch = Some(109);
+        assert!(!chain_manager.sync_blocks_batch_timed_out());
+        chain_manager.current_epoch = Some(110);
+        assert!(chain_manager.sync_blocks_batch_timed_out());
+
+        // A shorter timeout requests the blocks again sooner
+        chain_manager.sync_blocks_batch_timeout_epochs = 3;
+        chain_manager.current_epoch = Some(102);
+        assert!(!chain_manager.sync_blocks_batch_timed_out());
+        chain_manager.current_epoch = Some(103);
+        assert!(chain_manager.sync_blocks_batch_timed_out());
+    }
+
+    #[test]
+    fn resolved_data_requests_average() {
+        let mut resolved = ResolvedDataRequests::default();
+        assert_eq!(resolved.average_epochs(), None);
+
+        resolved.insert(10, 13);
+        resolved.insert(20, 24);
+        assert_eq!(resolved.count, 2);
+        assert_eq!(resolved.average_epochs(), Some(3.5));
+    }
+
+    #[test]
+    fn get_mempool_by_priority() {
+        test_actix_system(|| async {
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+
+            let vt = |value| {
+                Transaction::ValueTransfer(VTTransaction::new(
+                    VTTransactionBody::new(
+                        vec![Input::default()],
+                        vec![ValueTransferOutput {
+                            value,
+                            ..Default::default()
+                        }],
+                    ),
+                    vec![],
+                ))
+            };
+            // All the transactions weigh the same, so their priority follows their fee
+            for (value, fee) in [(1, 100), (2, 300), (3, 200)] {
+                assert!(chain_manager
+                    .transactions_pool
+                    .insert(vt(value), fee)
+                    .is_empty());
+            }
+
+            let res = chain_manager
+                .handle(GetMempoolByPriority { limit: 2 }, &mut ctx)
+                .unwrap();
+            let transactions: Vec<_> = res.into_iter().map(|t| t.transaction).collect();
+            assert_eq!(transactions, vec![vt(2), vt(3)]);
+
+            // A limit of 0 returns all the transactions
+            let res = chain_manager
+                .handle(GetMempoolByPriority { limit: 0 }, &mut ctx)
+                .unwrap();
+            assert_eq!(res.len(), 3);
+            assert_eq!(res[2].transaction, vt(1));
+            assert!(res.windows(2).all(|w| w[0].priority > w[1].priority));
+        });
+    }
+
+    #[test]
+    fn get_data_request_stats() {
+        test_actix_system(|| async {
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+
+            // The stats are only available when the node is synced
+            chain_manager.sm_state = StateMachine::WaitingConsensus;
+            let res = chain_manager.handle(GetDataRequestStats, &mut ctx);
+            assert!(matches!(
+                res.unwrap_err().downcast::<ChainManagerError>(),
+                Ok(ChainManagerError::NotSynced { .. })
+            ));
+
+            chain_manager.sm_state = StateMachine::Synced;
+            let res = chain_manager.handle(GetDataRequestStats, &mut ctx);
+            assert_eq!(res.unwrap(), DataRequestStats::default());
+
+            let new_data_request = |witnesses| {
+                let dr_output = DataRequestOutput {
+                    witnesses,
+                    ..DataRequestOutput::default()
+                };
+                DRTransaction::new(
+                    DRTransactionBody::new(vec![Input::default()], vec![], dr_output),
+                    vec![KeyedSignature::default()],
+                )
+            };
+            // A data request without commits moves to tally stage, and a new one starts in
+            // commit stage
+            let dr_pool = &mut chain_manager.chain_state.data_request_pool;
+            dr_pool
+                .add_data_request(10, new_data_request(1), &Hash::default())
+                .unwrap();
+            dr_pool.update_data_request_stages();
+            dr_pool
+                .add_data_request(11, new_data_request(2), &Hash::default())
+                .unwrap();
+            // Two data requests were resolved in 3 and 4 epochs
+            chain_manager.resolved_data_requests.insert(1, 4);
+            chain_manager.resolved_data_requests.insert(2, 6);
+
+            let res = chain_manager.handle(GetDataRequestStats, &mut ctx);
+            assert_eq!(
+                res.unwrap(),
+                DataRequestStats {
+                    commit_stage: 1,
+                    reveal_stage: 0,
+                    tally_stage: 1,
+                    resolved: 2,
+                    average_resolution_epochs: Some(3.5),
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn tally_info_without_colors() {
+        let tally_tx = TallyTransaction::new(
+            Hash::SHA256([1; 32]),
+            // CBOR encoding of the integer 42
+            vec![0x18, 0x2A],
+            vec![],
+            vec![],
+            vec![],
+        );
+
+        let colored = format_tally_info(&tally_tx, 10, DrLogOptions::default());
+        assert!(colored.contains('\x1b'));
+
+        let plain = format_tally_info(
+            &tally_tx,
+            10,
+            DrLogOptions {
+                color: false,
+                results: true,
+            },
+        );
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.contains("completed at epoch #10 with result"));
+
+        let no_results = format_tally_info(
+            &tally_tx,
+            10,
+            DrLogOptions {
+                color: false,
+                results: false,
+            },
+        );
+        assert!(!no_results.contains('\x1b'));
+        assert!(!no_results.contains("with result"));
+    }
+
+    #[test]
+    fn transactions_pool_survives_restart() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            // Setup testing: use in-memory database instead of rocksdb
+            let mut config = Config::default();
+            config.storage.backend = StorageBackend::HashMap;
+            let config = Arc::new(config);
+            // Start relevant actors
+            config_mngr::start(config);
+            storage_mngr::start();
+
+            let mut ctx = Context::new();
+            let mut chain_manager = ChainManager::default();
+
+            // Spends an output that is still in the UTXO set
+            let valid_tx = create_valid_transaction(&mut chain_manager, &PRIV_KEY_1);
+            let valid_pointer = match &valid_tx {
+                Transaction::ValueTransfer(vt_tx) => *vt_tx.body.inputs[0].output_pointer(),
+                _ => unreachable!(),
+            };
+            // Spends an output that does not exist anymore
+            let vti = Input::new(OutputPointer {
+                transaction_id: Hash::SHA256([2; 32]),
+                output_index: 0,
+            });
+            let vt_body = VTTransactionBody::new(vec![vti], vec![]);
+            let signatures = vec![sign_tx(PRIV_KEY_1, &vt_body)];
+            let invalid_tx = Transaction::ValueTransfer(VTTransaction::new(vt_body, signatures));
+
+            chain_manager.transactions_pool.insert(valid_tx.clone(), 1);
+            chain_manager.transactions_pool.insert(invalid_tx, 1);
+            assert_eq!(chain_manager.transactions_pool.vt_len(), 2);
+
+            // The transactions pool is persisted when the node is being closed
+            let fut = chain_manager.handle(PersistTransactionsPool, &mut ctx);
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert!(res.is_ok());
+
+            // Simulate a restart: the new instance only knows about the UTXO set
+            let mut chain_manager = ChainManager::default();
+            chain_manager.chain_state.unspent_outputs_pool.insert(
+                valid_pointer,
+                ValueTransferOutput {
+                    pkh: pkh(&PRIV_KEY_1),
+                    value: 1000,
+                    time_lock: 0,
+                },
+                0,
+            );
+
+            let fut = chain_manager.restore_transactions_pool();
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert!(res.is_ok());
+
+            assert_eq!(
+                chain_manager.temp_vts_and_drs,
+                VecDeque::from(vec![valid_tx])
+            );
+        });
+    }
+
+    #[test]
+    fn sync_target_survives_restart() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        test_actix_system(|| async {
+            // Setup testing: use in-memory database instead of rocksdb
+            let mut config = Config::default();
+            config.storage.backend = StorageBackend::HashMap;
+            let config = Arc::new(config);
+            // Start relevant actors
+            config_mngr::start(config);
+            storage_mngr::start();
+
+            let consensus_constants =
+                consensus_constants_from_partial(&PartialConsensusConstants::default(), &Testnet);
+            let chain_manager_with_superblock = |superblock_index| {
+                let mut chain_manager = ChainManager::default();
+                chain_manager.chain_state.chain_info = Some(ChainInfo {
+                    environment: Environment::default(),
+                    consensus_constants: consensus_constants.clone(),
+                    highest_block_checkpoint: CheckpointBeacon::default(),
+                    highest_superblock_checkpoint: CheckpointBeacon {
+                        checkpoint: superblock_index,
+                        hash_prev_block: Hash::SHA256([1; 32]),
+                    },
+                    highest_vrf_output: CheckpointVRF::default(),
+                });
+
+                chain_manager
+            };
+
+            let mut ctx = Context::new();
+            let mut chain_manager = chain_manager_with_superblock(5);
+            let sync_target = SyncTarget {
+                block: CheckpointBeacon {
+                    checkpoint: 100,
+                    hash_prev_block: Hash::SHA256([2; 32]),
+                },
+                superblock: CheckpointBeacon {
+                    checkpoint: 10,
+                    hash_prev_block: Hash::SHA256([3; 32]),
+                },
+            };
+            chain_manager.sync_target = Some(sync_target);
+
+            let fut = chain_manager.persist_sync_target();
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert!(res.is_ok());
+
+            // Simulate a restart: the persisted target is newer than the consolidated superblock,
+            // so synchronization resumes towards it
+            let mut chain_manager = chain_manager_with_superblock(5);
+            let fut = chain_manager.restore_sync_target();
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert!(res.is_ok());
+            assert_eq!(chain_manager.sync_target, Some(sync_target));
+            // While waiting for a peers consensus, the node synchronizes towards the restored target
+            assert_eq!(
+                chain_manager.restored_sync_target_to_resume(),
+                Some(sync_target)
+            );
+
+            // The first peers consensus replaces the restored target
+            let consensus_target = SyncTarget {
+                block: CheckpointBeacon {
+                    checkpoint: 120,
+                    hash_prev_block: Hash::SHA256([4; 32]),
+                },
+                superblock: CheckpointBeacon {
+                    checkpoint: 12,
+                    hash_prev_block: Hash::SHA256([5; 32]),
+                },
+            };
+            chain_manager.set_sync_target(&mut ctx, consensus_target);
+            assert_eq!(chain_manager.sync_target, Some(consensus_target));
+            assert_eq!(chain_manager.restored_sync_target_to_resume(), None);
+
+            // The consensus has moved past the persisted target, so it is discarded
+            let mut chain_manager = chain_manager_with_superblock(11);
+            let fut = chain_manager.restore_sync_target();
+            let res = fut.into_normal_future(&mut chain_manager, &mut ctx).await;
+            assert!(res.is_ok());
+            assert_eq!(chain_manager.sync_target, None);
+        });
+    }
+
     #[test]
     fn test_add_transaction_malleability() {
         let _ = env_logger::builder().is_test(true).try_init();
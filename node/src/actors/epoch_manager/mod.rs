@@ -9,7 +9,8 @@ use witnet_data_structures::{
     error::EpochCalculationError,
 };
 use witnet_util::timestamp::{
-    duration_between_timestamps, get_timestamp, get_timestamp_nanos, update_global_timestamp,
+    duration_between_timestamps, get_local_timestamp, get_timestamp, get_timestamp_nanos,
+    update_global_timestamp,
 };
 
 use crate::{
@@ -63,6 +64,9 @@ pub struct EpochManager {
 
     /// Last epoch that was checked by the epoch monitor process
     last_checked_epoch: Option<Epoch>,
+
+    /// Maximum drift of the system clock before warning about it (zero disables the check)
+    max_clock_drift: Duration,
 }
 
 impl Drop for EpochManager {
@@ -96,6 +100,38 @@ impl EpochManager {
             checkpoints_period,
         });
     }
+    /// Set the maximum drift of the system clock before warning about it
+    pub fn set_max_clock_drift(&mut self, max_clock_drift: Duration) {
+        self.max_clock_drift = max_clock_drift;
+    }
+    /// Compare the system clock against a reference clock (i.e. NTP) and warn if they differ by
+    /// more than the maximum clock drift, as the epochs of this node would not match those of the
+    /// rest of the network. Returns the drift if it exceeds the maximum.
+    pub fn check_clock_drift(
+        &self,
+        local_timestamp: (i64, u32),
+        reference_timestamp: (i64, u32),
+    ) -> Option<Duration> {
+        if self.max_clock_drift == Duration::from_secs(0) {
+            return None;
+        }
+
+        let drift = duration_between_timestamps(local_timestamp, reference_timestamp)
+            .or_else(|| duration_between_timestamps(reference_timestamp, local_timestamp))
+            .unwrap_or_default();
+        if drift > self.max_clock_drift {
+            log::warn!(
+                "The system clock differs from the NTP time by {}.{:03} seconds, more than the maximum of {} seconds. Please synchronize the system clock, otherwise this node may work with the wrong epochs when NTP is not available",
+                drift.as_secs(),
+                drift.subsec_millis(),
+                self.max_clock_drift.as_secs(),
+            );
+
+            Some(drift)
+        } else {
+            None
+        }
+    }
     /// Calculate the last checkpoint (current epoch) at the supplied timestamp
     pub fn epoch_at(&self, timestamp: i64) -> EpochResult<Epoch> {
         match &self.constants {
@@ -109,6 +145,22 @@ impl EpochManager {
         let now = get_timestamp();
         self.epoch_at(now)
     }
+    /// Calculate how many seconds remain until the next checkpoint at the supplied timestamp
+    pub fn seconds_to_next_epoch_at(&self, timestamp: i64) -> EpochResult<i64> {
+        let next_epoch = self
+            .epoch_at(timestamp)?
+            .checked_add(1)
+            .ok_or(EpochManagerError::Overflow)?;
+        let next_checkpoint = self.epoch_timestamp(next_epoch)?;
+
+        // If the clock just crossed the boundary, the next checkpoint is now
+        Ok(next_checkpoint.saturating_sub(timestamp).max(0))
+    }
+    /// Calculate how many seconds remain until the next checkpoint
+    pub fn seconds_to_next_epoch(&self) -> EpochResult<i64> {
+        let now = get_timestamp();
+        self.seconds_to_next_epoch_at(now)
+    }
     /// Calculate the timestamp for a checkpoint (the start of an epoch)
     pub fn epoch_timestamp(&self, epoch: Epoch) -> EpochResult<i64> {
         match &self.constants {
@@ -117,6 +169,15 @@ impl EpochManager {
             None => Err(EpochManagerError::UnknownEpochConstants),
         }
     }
+    /// Calculate the timestamp window of an epoch: the start is inclusive and the end is
+    /// exclusive, so the end of an epoch is the start of the next one
+    pub fn epoch_range(&self, epoch: Epoch) -> EpochResult<(i64, i64)> {
+        let start = self.epoch_timestamp(epoch)?;
+        let next_epoch = epoch.checked_add(1).ok_or(EpochManagerError::Overflow)?;
+        let end = self.epoch_timestamp(next_epoch)?;
+
+        Ok((start, end))
+    }
     /// Method to process the configuration received from the config manager
     fn process_config(&mut self, ctx: &mut <Self as Actor>::Context) {
         config_mngr::get()
@@ -144,8 +205,11 @@ impl EpochManager {
 
                 // Start ntp update process
                 if config.ntp.enabled {
+                    act.set_max_clock_drift(config.ntp.max_drift);
                     let ntp_addr = config.ntp.servers[0].clone();
-                    update_global_timestamp(ntp_addr.as_str());
+                    if let Some(ntp_timestamp) = update_global_timestamp(ntp_addr.as_str()) {
+                        act.check_clock_drift(get_local_timestamp(), ntp_timestamp);
+                    }
                     act.update_ntp_timestamp(ctx, config.ntp.update_period, ntp_addr);
                 }
 
@@ -289,7 +353,9 @@ impl EpochManager {
 
         // Wait until next checkpoint to execute the periodic function
         ctx.run_later(period, move |act, ctx| {
-            update_global_timestamp(addr.as_str());
+            if let Some(ntp_timestamp) = update_global_timestamp(addr.as_str()) {
+                act.check_clock_drift(get_local_timestamp(), ntp_timestamp);
+            }
 
             // Reschedule update ntp process
             act.update_ntp_timestamp(ctx, period, addr);
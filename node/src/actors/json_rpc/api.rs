@@ -23,37 +23,48 @@ use serde::{Deserialize, Serialize};
 use witnet_crypto::key::KeyPath;
 use witnet_data_structures::{
     chain::{
-        tapi::ActiveWips, Block, DataRequestOutput, Epoch, Hash, Hashable, PublicKeyHash, RADType,
+        address_to_pkh, pkh_to_address, tapi::ActiveWips, Block, ConsensusConstants,
+        DataRequestOutput, Epoch, Hash, Hashable, OutputPointer, PublicKeyHash, RADType,
         StateMachine, SyncStatus,
     },
+    error::{BlockError, TransactionError},
+    get_environment,
     transaction::Transaction,
     vrf::VrfMessage,
 };
+use witnet_p2p::{peers::PeerInfo, sessions::GetConsolidatedPeersResult};
+use witnet_validations::validations::validate_merkle_tree;
 
 use crate::{
     actors::{
         chain_manager::{run_dr_locally, ChainManager, ChainManagerError},
         epoch_manager::{EpochManager, EpochManagerError},
         inventory_manager::{InventoryManager, InventoryManagerError},
-        json_rpc::Subscriptions,
+        json_rpc::{server::parse_dr_pointer, Subscriptions},
         messages::{
             AddCandidates, AddPeers, AddTransaction, BuildDrt, BuildVtt, ClearPeers, DropAllPeers,
-            EstimatePriority, GetBalance, GetBalanceTarget, GetBlocksEpochRange,
-            GetConsolidatedPeers, GetDataRequestInfo, GetEpoch, GetHighestCheckpointBeacon,
-            GetItemBlock, GetItemSuperblock, GetItemTransaction, GetKnownPeers,
-            GetMemoryTransaction, GetMempool, GetNodeStats, GetReputation, GetSignalingInfo,
-            GetState, GetSupplyInfo, GetUtxoInfo, InitializePeers, IsConfirmedBlock, Rewind,
+            EstimatePriority, GetBalance, GetBalanceTarget, GetBlock, GetBlocksEpochRange,
+            GetConsolidatedPeers, GetDataRequestInfo, GetDataRequestStats, GetEpoch,
+            GetHighestCheckpointBeacon, GetItemSuperblock, GetItemTransaction, GetKnownPeers,
+            GetKnownPeersInfo, GetMemoryTransaction, GetMempool, GetMyActiveDataRequests,
+            GetNodeStats, GetOutput, GetReputation, GetSignalingInfo, GetState, GetSupplyInfo,
+            GetTip, GetUtxoInfo, InitializePeers, IsConfirmedBlock, PeersInfoNewTried, Rewind,
             SnapshotExport, SnapshotImport,
         },
         peers_manager::PeersManager,
         sessions_manager::SessionsManager,
     },
-    config_mngr, signature_mngr,
+    signature_mngr,
     utils::Force,
 };
 
+#[cfg(not(test))]
+use crate::config_mngr;
+
 #[cfg(test)]
 use self::mock_actix::SystemService;
+#[cfg(test)]
+use self::mock_config_mngr as config_mngr;
 
 type JsonRpcResult = jsonrpc_core::Result<jsonrpc_core::Value>;
 
@@ -98,7 +109,14 @@ pub fn attach_regular_methods<H>(
     server.add_actix_method(system, "getTransaction", |params: Params| {
         Box::pin(get_transaction(params.parse()))
     });
+    server.add_actix_method(system, "getOutput", |params: Params| {
+        Box::pin(get_output(params.parse()))
+    });
+    server.add_actix_method(system, "validateAddress", |params: Params| {
+        Box::pin(validate_address(params.parse()))
+    });
     server.add_actix_method(system, "syncStatus", |_params: Params| Box::pin(status()));
+    server.add_actix_method(system, "getTip", |_params: Params| Box::pin(get_tip()));
     server.add_actix_method(system, "dataRequestReport", |params: Params| {
         Box::pin(data_request_report(params.parse()))
     });
@@ -111,13 +129,20 @@ pub fn attach_regular_methods<H>(
     server.add_actix_method(system, "getReputationAll", |_params: Params| {
         Box::pin(get_reputation(Ok((PublicKeyHash::default(),)), true))
     });
+    server.add_actix_method(system, "getMyActiveDataRequests", |params: Params| {
+        Box::pin(get_my_active_data_requests(params.parse()))
+    });
     server.add_actix_method(system, "getSupplyInfo", |_params: Params| {
         Box::pin(get_supply_info())
     });
+    server.add_actix_method(system, "getDataRequestStats", |_params: Params| {
+        Box::pin(get_data_request_stats())
+    });
     server.add_actix_method(system, "peers", |_params: Params| Box::pin(peers()));
     server.add_actix_method(system, "knownPeers", |_params: Params| {
         Box::pin(known_peers())
     });
+    server.add_actix_method(system, "getPeers", |_params: Params| Box::pin(get_peers()));
     server.add_actix_method(
         system,
         "nodeStats",
@@ -352,10 +377,23 @@ pub fn attach_subscriptions<H>(
                 Ok((topic, params)) => {
                     // Deal with supported / unsupported subscription methods
                     match topic.as_str() {
-                        "blocks" | "superblocks" | "status" => {
+                        "blocks" | "superblocks" | "status" | "stateChanges" => {
                             // If using a supported topic, register the subscription
                             register(topic, params, subscriber);
                         }
+                        "dataRequestBlocks" | "dataRequestResolved" => {
+                            // These topics need the hash of the data request as their param
+                            if parse_dr_pointer(&params).is_some() {
+                                register(topic, params, subscriber);
+                            } else {
+                                subscriber
+                                    .reject(Error::invalid_params(format!(
+                                        "The {} topic expects a data request hash as its param",
+                                        topic
+                                    )))
+                                    .ok();
+                            }
+                        }
                         other => {
                             // If the topic is unknown, reject the subscription
                             log::error!(
@@ -471,11 +509,24 @@ pub enum InventoryItem {
     Block(Block),
 }
 
+/// Stateless checks run on the blocks received through `inventory` before handing them over to
+/// the ChainManager, which only validates candidates later on and cannot report the result back
+fn validate_inventory_block(block: &Block) -> Result<(), Error> {
+    if !validate_merkle_tree(block) {
+        return Err(Error::invalid_params(
+            BlockError::NotValidMerkleTree.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Make the node process, validate and potentially broadcast a new inventory entry.
 ///
 /// Input: the JSON serialization of a well-formed inventory entry
 ///
-/// Returns a boolean indicating success.
+/// Returns a boolean indicating success, or an error if the entry is rejected. Blocks are only
+/// checked structurally before being added as candidates, while transactions are fully validated.
 /* Test string:
 {"jsonrpc": "2.0","method": "inventory","params": {"block": {"block_header":{"version":1,"beacon":{"checkpoint":2,"hash_prev_block": {"SHA256": [4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4,4]}},"hash_merkle_root":{"SHA256":[3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3,3]}},"proof":{"block_sig": null}"txns":[null]}},"id": 1}
 */
@@ -489,6 +540,8 @@ pub async fn inventory(params: Result<InventoryItem, Error>) -> JsonRpcResult {
         InventoryItem::Block(block) => {
             log::debug!("Got block from JSON-RPC. Sending AnnounceItems message.");
 
+            validate_inventory_block(&block)?;
+
             let chain_manager_addr = ChainManager::from_registry();
             let res = chain_manager_addr
                 .send(AddCandidates {
@@ -536,8 +589,39 @@ pub struct GetBlockChainParams {
     /// Number of block hashes to return.
     /// If negative, return the last n block hashes from this epoch range.
     /// If zero, unlimited.
+    /// In any case, the number of block hashes is capped by the `jsonrpc.max_block_chain_limit`
+    /// setting of the node.
     #[serde(default)] // default to 0
     pub limit: i64,
+    /// Whether to apply the limit from the end of the epoch range: return the last n block hashes.
+    /// Setting this flag is equivalent to using a negative `limit`.
+    #[serde(default)] // default to false
+    pub limit_from_end: bool,
+}
+
+/// Clamp the absolute value of the `limit` param of getBlockChain to `max_limit`, taking into
+/// account that a `limit` of 0 means unlimited. A `max_limit` of 0 disables the cap.
+fn clamp_block_chain_limit(limit: u32, max_limit: u32) -> u32 {
+    if max_limit == 0 {
+        limit
+    } else if limit == 0 {
+        max_limit
+    } else {
+        std::cmp::min(limit, max_limit)
+    }
+}
+
+/// Build the `GetBlocksEpochRange` message used by getBlockChain
+fn block_chain_epoch_range(
+    start_epoch: u32,
+    limit: u32,
+    limit_from_end: bool,
+) -> GetBlocksEpochRange {
+    if limit_from_end {
+        GetBlocksEpochRange::new_with_limit_from_end(start_epoch.., limit as usize)
+    } else {
+        GetBlocksEpochRange::new_with_limit(start_epoch.., limit as usize)
+    }
 }
 
 /// Get the list of all the known block hashes.
@@ -580,14 +664,6 @@ pub async fn get_block_chain(params: Result<Option<GetBlockChainParams>, Error>)
         }
     }
 
-    fn epoch_range(start_epoch: u32, limit: u32, limit_negative: bool) -> GetBlocksEpochRange {
-        if limit_negative {
-            GetBlocksEpochRange::new_with_limit_from_end(start_epoch.., limit as usize)
-        } else {
-            GetBlocksEpochRange::new_with_limit(start_epoch.., limit as usize)
-        }
-    }
-
     fn convert_negative_to_positive_with_negative_flag(x: i64) -> Result<(u32, bool), String> {
         let positive_x = u32::try_from(x.unsigned_abs()).map_err(|_e| {
             format!(
@@ -601,7 +677,11 @@ pub async fn get_block_chain(params: Result<Option<GetBlockChainParams>, Error>)
         Ok((positive_x, x.is_negative()))
     }
 
-    let GetBlockChainParams { epoch, limit } = match params {
+    let GetBlockChainParams {
+        epoch,
+        limit,
+        limit_from_end,
+    } = match params {
         Ok(x) => x.unwrap_or_default(),
         Err(e) => return Err(e),
     };
@@ -622,6 +702,14 @@ pub async fn get_block_chain(params: Result<Option<GetBlockChainParams>, Error>)
         }
     };
 
+    let limit_from_end = limit_from_end || limit_negative;
+
+    // Avoid returning huge responses
+    let limit = match config_mngr::get().await {
+        Ok(config) => clamp_block_chain_limit(limit, config.jsonrpc.max_block_chain_limit),
+        Err(e) => return Err(internal_error(e)),
+    };
+
     let chain_manager_addr = ChainManager::from_registry();
     if epoch_negative {
         // On negative epoch, get blocks from last n epochs
@@ -633,7 +721,7 @@ pub async fn get_block_chain(params: Result<Option<GetBlockChainParams>, Error>)
                 let epoch = current_epoch.saturating_sub(epoch);
 
                 let res = chain_manager_addr
-                    .send(epoch_range(epoch, limit, limit_negative))
+                    .send(block_chain_epoch_range(epoch, limit, limit_from_end))
                     .await;
 
                 process_get_block_chain(res).await
@@ -649,14 +737,27 @@ pub async fn get_block_chain(params: Result<Option<GetBlockChainParams>, Error>)
         }
     } else {
         let res = chain_manager_addr
-            .send(epoch_range(epoch, limit, limit_negative))
+            .send(block_chain_epoch_range(epoch, limit, limit_from_end))
             .await;
         process_get_block_chain(res).await
     }
 }
 
+/// Error returned by getBlock when the block is neither a candidate nor in storage
+fn block_not_found_error(hash: Hash) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(404),
+        message: "Block not found".to_string(),
+        data: Some(serde_json::json!({ "hash": hash })),
+    }
+}
+
 /// Get block by hash
 ///
+/// The block is looked up among the candidates of the current epoch before the consolidated
+/// blocks in storage, so the response can be an unconsolidated candidate block that may never
+/// become part of the chain.
+///
 /// - First argument is the hash of the block that we are querying.
 /// - Second argument is whether we want the response to contain a list of hashes of the
 ///   transactions found in the block.
@@ -669,10 +770,14 @@ pub async fn get_block(params: Params) -> Result<Value, Error> {
     // Handle parameters as an array with a first obligatory hash field plus an optional bool field
     if let Params::Array(params) = params {
         if let Some(Value::String(hash)) = params.first() {
+            // Validate the hash before asking the ChainManager for it
             match hash.parse() {
                 Ok(hash) => block_hash = hash,
                 Err(e) => {
-                    return Err(internal_error(e));
+                    return Err(Error::invalid_params(format!(
+                        "First argument of `get_block` must be a valid block hash: {}",
+                        e
+                    )));
                 }
             }
         } else {
@@ -696,11 +801,9 @@ pub async fn get_block(params: Params) -> Result<Value, Error> {
         ));
     };
 
-    let inventory_manager = InventoryManager::from_registry();
+    let chain_manager = ChainManager::from_registry();
 
-    let res = inventory_manager
-        .send(GetItemBlock { hash: block_hash })
-        .await;
+    let res = chain_manager.send(GetBlock { hash: block_hash }).await;
 
     match res {
         Ok(Ok(mut output)) => {
@@ -899,10 +1002,11 @@ pub async fn get_block(params: Params) -> Result<Value, Error> {
                 }
             }
         }
-        Ok(Err(e)) => {
-            let err = internal_error(e);
-            Err(err)
-        }
+        Ok(Err(e)) => match e.downcast::<ChainManagerError>() {
+            Ok(ChainManagerError::BlockDoesNotExist) => Err(block_not_found_error(block_hash)),
+            Ok(e) => Err(internal_error(e)),
+            Err(e) => Err(internal_error(e)),
+        },
         Err(e) => {
             let err = internal_error(e);
             Err(err)
@@ -928,6 +1032,44 @@ pub struct GetTransactionOutput {
     pub confirmed: bool,
 }
 
+impl GetTransactionOutput {
+    /// Output for a transaction that is still in the mempool
+    pub fn pending(transaction: Transaction) -> Self {
+        Self {
+            weight: transaction.weight(),
+            transaction,
+            block_hash: "pending".to_string(),
+            block_epoch: None,
+            confirmed: false,
+        }
+    }
+
+    /// Output for a transaction that has been included in a block
+    pub fn included(
+        transaction: Transaction,
+        block_hash: Hash,
+        block_epoch: Epoch,
+        confirmed: bool,
+    ) -> Self {
+        Self {
+            weight: transaction.weight(),
+            transaction,
+            block_hash: block_hash.to_string(),
+            block_epoch: Some(block_epoch),
+            confirmed,
+        }
+    }
+}
+
+/// Error returned by getTransaction when the transaction is neither in the mempool nor in storage
+fn transaction_not_found_error(hash: Hash) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(404),
+        message: "Transaction not found".to_string(),
+        data: Some(serde_json::json!({ "hash": hash })),
+    }
+}
+
 /// Get transaction by hash
 pub async fn get_transaction(hash: Result<(Hash,), Error>) -> JsonRpcResult {
     let hash = match hash {
@@ -935,16 +1077,25 @@ pub async fn get_transaction(hash: Result<(Hash,), Error>) -> JsonRpcResult {
         Err(e) => return Err(e),
     };
 
+    // Transactions in the mempool have not been included in any block yet, so look them up first
+    let chain_manager = ChainManager::from_registry();
+    match chain_manager.send(GetMemoryTransaction { hash }).await {
+        Ok(Ok(transaction)) => {
+            return serde_json::to_value(GetTransactionOutput::pending(transaction))
+                .map_err(internal_error);
+        }
+        Ok(Err(())) => {}
+        Err(e) => return Err(internal_error(e)),
+    }
+
     let inventory_manager = InventoryManager::from_registry();
 
     let res = inventory_manager.send(GetItemTransaction { hash }).await;
 
     match res {
         Ok(Ok((transaction, pointer_to_block, block_epoch))) => {
-            let weight = transaction.weight();
             let block_hash = pointer_to_block.block_hash;
             // Check if this block is confirmed by a majority of superblock votes
-            let chain_manager = ChainManager::from_registry();
             let confirmed = match chain_manager
                 .send(IsConfirmedBlock {
                     block_hash,
@@ -964,9 +1115,7 @@ pub async fn get_transaction(hash: Result<(Hash,), Error>) -> JsonRpcResult {
             let new_transaction = match transaction {
                 Transaction::DataRequest(mut dr_txn) => {
                     // Create Active WIPs
-                    let signaling_info = ChainManager::from_registry()
-                        .send(GetSignalingInfo {})
-                        .await;
+                    let signaling_info = chain_manager.send(GetSignalingInfo {}).await;
                     let active_wips = match signaling_info {
                         Ok(Ok(wips)) => ActiveWips {
                             active_wips: wips.active_upgrades,
@@ -994,57 +1143,12 @@ pub async fn get_transaction(hash: Result<(Hash,), Error>) -> JsonRpcResult {
                 _ => transaction,
             };
 
-            let output = GetTransactionOutput {
-                transaction: new_transaction,
-                weight,
-                block_hash: block_hash.to_string(),
-                block_epoch: Some(block_epoch),
-                confirmed,
-            };
-            let value = match serde_json::to_value(output) {
-                Ok(x) => x,
-                Err(e) => {
-                    let err = internal_error(e);
-                    let fut: JsonRpcResult = Err(err);
-                    return fut;
-                }
-            };
-            let fut: JsonRpcResult = Ok(value);
-            fut
-        }
-        Ok(Err(InventoryManagerError::ItemNotFound)) => {
-            let chain_manager = ChainManager::from_registry();
-            let res = chain_manager.send(GetMemoryTransaction { hash }).await;
+            let output =
+                GetTransactionOutput::included(new_transaction, block_hash, block_epoch, confirmed);
 
-            match res {
-                Ok(Ok(transaction)) => {
-                    let weight = transaction.weight();
-                    let output = GetTransactionOutput {
-                        transaction,
-                        weight,
-                        block_hash: "pending".to_string(),
-                        block_epoch: None,
-                        confirmed: false,
-                    };
-                    let value = match serde_json::to_value(output) {
-                        Ok(x) => x,
-                        Err(e) => {
-                            let err = internal_error(e);
-                            return Err(err);
-                        }
-                    };
-                    Ok(value)
-                }
-                Ok(Err(())) => {
-                    let err = internal_error(InventoryManagerError::ItemNotFound);
-                    Err(err)
-                }
-                Err(e) => {
-                    let err = internal_error(e);
-                    Err(err)
-                }
-            }
+            serde_json::to_value(output).map_err(internal_error)
         }
+        Ok(Err(InventoryManagerError::ItemNotFound)) => Err(transaction_not_found_error(hash)),
         Ok(Err(e)) => {
             let err = internal_error(e);
             Err(err)
@@ -1056,48 +1160,76 @@ pub async fn get_transaction(hash: Result<(Hash,), Error>) -> JsonRpcResult {
     }
 }
 
-/*
-/// get output
-pub fn get_output(output_pointer: Result<(String,), Error>) -> JsonRpcResult {
-    let output_pointer = match output_pointer {
-        Ok(x) => match OutputPointer::from_str(&x.0) {
+/// Format of the output of validateAddress
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateAddressOutput {
+    /// Normalized bech32 address
+    pub address: String,
+    /// Public key hash of the address in hex format
+    pub pkh_hex: String,
+}
+
+/// Validate a bech32 address for the environment this node is running in, and return its
+/// normalized form
+pub async fn validate_address(params: Result<(String,), Error>) -> JsonRpcResult {
+    let address = match params {
+        Ok((address,)) => address,
+        Err(e) => return Err(e),
+    };
+
+    let environment = get_environment();
+    let pkh =
+        address_to_pkh(environment, &address).map_err(|e| Error::invalid_params(e.to_string()))?;
+    let output = ValidateAddressOutput {
+        address: pkh_to_address(environment, &pkh),
+        pkh_hex: pkh.to_hex(),
+    };
+
+    serde_json::to_value(output).map_err(internal_error)
+}
+
+/// Error returned by getOutput when the output does not exist or has already been spent
+fn output_not_found_error(output_pointer: &OutputPointer) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(404),
+        message: "Output not found".to_string(),
+        data: Some(serde_json::json!({ "output_pointer": output_pointer })),
+    }
+}
+
+/// Get the unspent output referred to by an output pointer, formatted as
+/// `<transaction id>:<output index>`
+pub async fn get_output(params: Result<(String,), Error>) -> JsonRpcResult {
+    let output_pointer = match params {
+        Ok((output_pointer,)) => match OutputPointer::from_str(&output_pointer) {
             Ok(x) => x,
-            Err(e) => {
-                let err = internal_error(e);
-                return Box::new(Err(err));
-            }
+            Err(e) => return Err(Error::invalid_params(e.to_string())),
         },
-        Err(e) => {
-            return Box::new(Err(e));
-        }
+        Err(e) => return Err(e),
     };
+
     let chain_manager_addr = ChainManager::from_registry();
-    Box::new(
-        chain_manager_addr
-            .send(GetOutput { output_pointer })
-            .then(|res| match res {
-                Ok(Ok(output)) => {
-                    let value = match serde_json::to_value(output) {
-                        Ok(x) => x,
-                        Err(e) => {
-                            let err = internal_error(e);
-                            return Err(err);
-                        }
-                    };
-                    Ok(value)
-                }
-                Ok(Err(e)) => {
-                    let err = internal_error(e);
-                    Err(err)
-                }
-                Err(e) => {
-                    let err = internal_error(e);
-                    Err(err)
-                }
-            }),
-    )
+
+    let res = chain_manager_addr
+        .send(GetOutput {
+            output_pointer: output_pointer.clone(),
+        })
+        .await;
+
+    match res {
+        Ok(Ok(output)) => serde_json::to_value(output).map_err(internal_error_s),
+        Ok(Err(e)) => match e.downcast::<ChainManagerError>() {
+            Ok(ChainManagerError::OutputNotFound { .. }) => {
+                Err(output_not_found_error(&output_pointer))
+            }
+            Ok(e) => Err(internal_error_s(e)),
+            Err(e) => Err(internal_error_s(e)),
+        },
+        Err(e) => Err(internal_error(e)),
+    }
 }
-*/
+
 /// Build data request transaction
 pub async fn send_request(params: Result<BuildDrt, Error>) -> JsonRpcResult {
     log::debug!("Creating data request from JSON-RPC.");
@@ -1158,10 +1290,10 @@ pub async fn send_value(params: Result<BuildVtt, Error>) -> JsonRpcResult {
                             Err(err)
                         }
                     },
-                    Ok(Err(e)) => {
-                        let err = internal_error_s(e);
-                        Err(err)
-                    }
+                    Ok(Err(e)) => match e.downcast::<TransactionError>() {
+                        Ok(e) => Err(build_transaction_error(e)),
+                        Err(e) => Err(internal_error_s(e)),
+                    },
                     Err(e) => {
                         let err = internal_error_s(e);
                         Err(err)
@@ -1173,6 +1305,56 @@ pub async fn send_value(params: Result<BuildVtt, Error>) -> JsonRpcResult {
     }
 }
 
+/// Error returned when a transaction cannot be built because of the requested outputs or the
+/// available funds. The reason and its details are included in the `data` field, so clients do
+/// not need to parse the message.
+fn build_transaction_error(e: TransactionError) -> jsonrpc_core::Error {
+    let message = e.to_string();
+    let data = match e {
+        TransactionError::NoMoney {
+            total_balance,
+            available_balance,
+            transaction_value,
+        } => serde_json::json!({
+            "reason": "InsufficientBalance",
+            "total_balance": total_balance,
+            "available_balance": available_balance,
+            "transaction_value": transaction_value,
+        }),
+        TransactionError::ZeroValueOutputRequested { output_id } => serde_json::json!({
+            "reason": "ZeroValueOutput",
+            "output_id": output_id,
+        }),
+        TransactionError::DustOutputRequested {
+            output_id,
+            value,
+            dust_threshold,
+        } => serde_json::json!({
+            "reason": "DustOutput",
+            "output_id": output_id,
+            "value": value,
+            "dust_threshold": dust_threshold,
+        }),
+        TransactionError::TooManyInputs {
+            inputs,
+            weight,
+            max_weight,
+        } => serde_json::json!({
+            "reason": "TooManyInputs",
+            "inputs": inputs,
+            "weight": weight,
+            "max_weight": max_weight,
+        }),
+        e => return internal_error_s(e),
+    };
+
+    jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(400),
+        message,
+        data: Some(data),
+    }
+}
+
 /// Get node status
 pub async fn status() -> JsonRpcResult {
     let chain_manager = ChainManager::from_registry();
@@ -1231,6 +1413,18 @@ pub async fn status() -> JsonRpcResult {
         .await
 }
 
+/// Get the highest block checkpoint beacon, even if the node is not synced yet
+pub async fn get_tip() -> JsonRpcResult {
+    let chain_manager = ChainManager::from_registry();
+    let res = chain_manager.send(GetTip).await;
+
+    match res {
+        Ok(Ok(tip)) => serde_json::to_value(tip).map_err(internal_error_s),
+        Ok(Err(e)) => Err(internal_error_s(e)),
+        Err(e) => Err(internal_error_s(e)),
+    }
+}
+
 /// Get public key
 pub async fn get_public_key() -> JsonRpcResult {
     signature_mngr::public_key()
@@ -1407,6 +1601,28 @@ pub async fn get_supply_info() -> JsonRpcResult {
         .await
 }
 
+/// Get the number of active data requests in each stage, and statistics about the resolved ones
+pub async fn get_data_request_stats() -> JsonRpcResult {
+    let chain_manager_addr = ChainManager::from_registry();
+
+    chain_manager_addr
+        .send(GetDataRequestStats)
+        .map(|res| {
+            res.map_err(internal_error)
+                .and_then(|dr_stats| match dr_stats {
+                    Ok(x) => match serde_json::to_value(x) {
+                        Ok(x) => Ok(x),
+                        Err(e) => {
+                            let err = internal_error_s(e);
+                            Err(err)
+                        }
+                    },
+                    Err(e) => Err(internal_error_s(e)),
+                })
+        })
+        .await
+}
+
 /// Get utxos
 pub async fn get_utxo_info(params: Result<(PublicKeyHash,), Error>) -> JsonRpcResult {
     let chain_manager_addr = ChainManager::from_registry();
@@ -1460,6 +1676,33 @@ pub async fn get_reputation(params: Result<(PublicKeyHash,), Error>, all: bool)
         .await
 }
 
+/// Get the active data requests in which one pkh has committed or revealed
+pub async fn get_my_active_data_requests(params: Result<(PublicKeyHash,), Error>) -> JsonRpcResult {
+    let pkh = match params {
+        Ok(x) => x.0,
+        Err(e) => return Err(e),
+    };
+
+    let chain_manager_addr = ChainManager::from_registry();
+
+    chain_manager_addr
+        .send(GetMyActiveDataRequests { pkh })
+        .map(|res| {
+            res.map_err(internal_error)
+                .and_then(|active_drs| match active_drs {
+                    Ok(x) => match serde_json::to_value(x) {
+                        Ok(x) => Ok(x),
+                        Err(e) => {
+                            let err = internal_error_s(e);
+                            Err(err)
+                        }
+                    },
+                    Err(e) => Err(internal_error_s(e)),
+                })
+        })
+        .await
+}
+
 /// Export private key associated with the node identity
 pub async fn master_key_export() -> JsonRpcResult {
     signature_mngr::key_pair()
@@ -1584,6 +1827,83 @@ pub async fn known_peers() -> JsonRpcResult {
         .await
 }
 
+/// Known peer address, as returned by the `getPeers` method
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PeerEntry {
+    /// Socket address of the peer
+    pub address: String,
+    /// Timestamp of the last time that this address was tried (tried bucket) or added (new bucket)
+    pub timestamp: i64,
+    /// "inbound" | "outbound" if there is a consolidated session with this peer
+    pub session: Option<String>,
+}
+
+/// Result of the `getPeers` method
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct GetPeersResult {
+    /// Peers in the new addresses bucket
+    pub new: Vec<PeerEntry>,
+    /// Peers in the tried addresses bucket
+    pub tried: Vec<PeerEntry>,
+}
+
+/// Combine the known peers with the consolidated sessions, if available, into a `GetPeersResult`
+fn build_get_peers_result(
+    known_peers: PeersInfoNewTried,
+    consolidated_peers: Option<GetConsolidatedPeersResult>,
+) -> GetPeersResult {
+    let (inbound, outbound) = consolidated_peers
+        .map(|x| (x.inbound, x.outbound))
+        .unwrap_or_default();
+    let entries = |peers: Vec<PeerInfo>| -> Vec<PeerEntry> {
+        peers
+            .into_iter()
+            .sorted_by_key(|p| (p.address.is_ipv6(), p.address.ip(), p.address.port()))
+            .map(|p| {
+                let session = if outbound.contains(&p.address) {
+                    Some("outbound".to_string())
+                } else if inbound.contains(&p.address) {
+                    Some("inbound".to_string())
+                } else {
+                    None
+                };
+
+                PeerEntry {
+                    address: p.address.to_string(),
+                    timestamp: p.timestamp,
+                    session,
+                }
+            })
+            .collect()
+    };
+
+    GetPeersResult {
+        new: entries(known_peers.new),
+        tried: entries(known_peers.tried),
+    }
+}
+
+/// Get list of known peers and their timestamps, marking those with a consolidated session
+pub async fn get_peers() -> JsonRpcResult {
+    let known_peers = match PeersManager::from_registry().send(GetKnownPeersInfo).await {
+        Ok(Ok(x)) => x,
+        Ok(Err(e)) => return Err(internal_error_s(e)),
+        Err(e) => return Err(internal_error(e)),
+    };
+
+    // The sessions are only used to tell inbound and outbound peers apart, so this is not fatal
+    let consolidated_peers = match SessionsManager::from_registry()
+        .send(GetConsolidatedPeers)
+        .await
+    {
+        Ok(Ok(x)) => Some(x),
+        _ => None,
+    };
+
+    serde_json::to_value(build_get_peers_result(known_peers, consolidated_peers))
+        .map_err(internal_error_s)
+}
+
 /// Get the node stats
 pub async fn node_stats() -> JsonRpcResult {
     let chain_manager_addr = ChainManager::from_registry();
@@ -1606,17 +1926,21 @@ pub async fn node_stats() -> JsonRpcResult {
         .await
 }
 
-/// Get all the pending transactions
-pub async fn get_mempool(params: Result<(), Error>) -> JsonRpcResult {
-    match params {
-        Ok(()) => (),
+/// Get all the pending transactions, optionally filtered by type and limited in number
+/* test
+{"jsonrpc": "2.0","method": "getMempool", "id": 1}
+{"jsonrpc": "2.0","method": "getMempool", "params": {"filter": "dataRequest", "limit": 10}, "id": 1}
+*/
+pub async fn get_mempool(params: Result<Option<GetMempool>, Error>) -> JsonRpcResult {
+    let params = match params {
+        Ok(x) => x.unwrap_or_default(),
         Err(e) => return Err(e),
     };
 
     let chain_manager_addr = ChainManager::from_registry();
 
     chain_manager_addr
-        .send(GetMempool)
+        .send(params)
         .map(|res| {
             res.map_err(internal_error)
                 .and_then(|dr_info| match dr_info {
@@ -1722,19 +2046,23 @@ pub async fn get_consensus_constants(params: Result<(), Error>) -> JsonRpcResult
 
     config_mngr::get()
         .map(|res| {
-            res.map_err(internal_error).and_then(|config| {
-                match serde_json::to_value(&config.consensus_constants) {
-                    Ok(x) => Ok(x),
-                    Err(e) => {
-                        let err = internal_error_s(e);
-                        Err(err)
-                    }
-                }
-            })
+            res.map_err(internal_error)
+                .and_then(|config| consensus_constants_to_value(&config.consensus_constants))
         })
         .await
 }
 
+/// Serialize the consensus constants returned by getConsensusConstants
+fn consensus_constants_to_value(consensus_constants: &ConsensusConstants) -> JsonRpcResult {
+    match serde_json::to_value(consensus_constants) {
+        Ok(x) => Ok(x),
+        Err(e) => {
+            let err = internal_error_s(e);
+            Err(err)
+        }
+    }
+}
+
 /// Rewind
 pub async fn rewind(params: Result<(Epoch,), Error>) -> JsonRpcResult {
     let epoch = match params {
@@ -1924,14 +2252,92 @@ pub async fn snapshot_import(params: Result<SnapshotImportParams, Error>) -> Jso
 
 #[cfg(test)]
 mod mock_actix {
+    use std::{
+        any::{Any, TypeId},
+        cell::{Cell, RefCell},
+        task::Poll,
+    };
+
     use actix::{MailboxError, Message};
 
+    thread_local! {
+        /// Messages sent to any actor from this thread
+        static SENT_MESSAGES: RefCell<Vec<Box<dyn Any>>> = RefCell::new(vec![]);
+        /// Responses to return from `send` instead of an error, in order, along with the type of
+        /// the message that they answer
+        static RESPONSES: RefCell<Vec<(TypeId, Box<dyn Any>)>> = RefCell::new(vec![]);
+        /// Number of `send` calls from this thread waiting for their response
+        static IN_FLIGHT: Cell<usize> = Cell::new(0);
+        /// Maximum number of `send` calls that were waiting for their response at the same time
+        static MAX_IN_FLIGHT: Cell<usize> = Cell::new(0);
+    }
+
+    /// Take the maximum number of `send` calls from this thread that were waiting for their
+    /// response at the same time
+    pub fn take_max_in_flight() -> usize {
+        MAX_IN_FLIGHT.with(|max_in_flight| max_in_flight.replace(0))
+    }
+
+    /// Make the next `send` of a message of type `T` from this thread return `response`
+    pub fn set_response<T: Message + 'static>(response: T::Result) {
+        RESPONSES.with(|responses| {
+            responses
+                .borrow_mut()
+                .push((TypeId::of::<T>(), Box::new(response)))
+        });
+    }
+
+    /// Take the messages of type `T` sent to any actor from this thread
+    pub fn take_sent_messages<T: 'static>() -> Vec<T> {
+        SENT_MESSAGES.with(|sent_messages| {
+            sent_messages
+                .borrow_mut()
+                .drain(..)
+                .filter_map(|msg| msg.downcast::<T>().ok())
+                .map(|msg| *msg)
+                .collect()
+        })
+    }
+
     pub struct Addr;
 
     impl Addr {
-        pub async fn send<T: Message>(&self, _msg: T) -> Result<T::Result, MailboxError> {
-            // We cannot test methods which use `send`, so return an error
-            Err(MailboxError::Closed)
+        pub async fn send<T: Message + 'static>(&self, msg: T) -> Result<T::Result, MailboxError> {
+            // We cannot test methods which use `send`, so keep the message to allow inspecting it
+            // and return the response set by the test, or an error if there is none
+            SENT_MESSAGES.with(|sent_messages| sent_messages.borrow_mut().push(Box::new(msg)));
+
+            // Like a real actor, do not answer immediately, so that other futures can make
+            // progress while this one is waiting for the response
+            let in_flight = IN_FLIGHT.with(|in_flight| {
+                in_flight.set(in_flight.get() + 1);
+                in_flight.get()
+            });
+            MAX_IN_FLIGHT
+                .with(|max_in_flight| max_in_flight.set(max_in_flight.get().max(in_flight)));
+            let mut answered = false;
+            futures::future::poll_fn(|cx| {
+                if answered {
+                    Poll::Ready(())
+                } else {
+                    answered = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+            .await;
+            IN_FLIGHT.with(|in_flight| in_flight.set(in_flight.get() - 1));
+
+            RESPONSES.with(|responses| {
+                let mut responses = responses.borrow_mut();
+                match responses
+                    .iter()
+                    .position(|(msg_type, _)| *msg_type == TypeId::of::<T>())
+                {
+                    Some(i) => Ok(*responses.remove(i).1.downcast::<T::Result>().unwrap()),
+                    None => Err(MailboxError::Closed),
+                }
+            })
         }
 
         pub fn do_send<T: Message>(&self, _msg: T) {
@@ -1949,13 +2355,28 @@ mod mock_actix {
     impl<T> SystemService for T {}
 }
 
+#[cfg(test)]
+mod mock_config_mngr {
+    use std::sync::Arc;
+
+    use witnet_config::config::Config;
+
+    /// The ConfigManager actor is not running in tests, so use the default configuration
+    pub async fn get() -> Result<Arc<Config>, failure::Error> {
+        Ok(Arc::new(Config::default()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeSet;
+    use std::{collections::BTreeSet, ops::Bound};
 
     use witty_jsonrpc::prelude::*;
 
-    use witnet_data_structures::{chain::RADRequest, transaction::*};
+    use witnet_data_structures::{
+        chain::{PointerToBlock, RADRequest, TransactionPointer, ValueTransferOutput},
+        transaction::*,
+    };
 
     use super::*;
 
@@ -1972,11 +2393,19 @@ mod tests {
         assert_eq!(response, Some(parse_error));
     }
 
+    /// A block with merkle roots that match its transactions
+    fn structurally_valid_block() -> Block {
+        let mut block = witnet_data_structures::chain::block_example();
+        block.block_header.merkle_roots =
+            witnet_data_structures::chain::BlockMerkleRoots::from_transactions(&block.txns);
+
+        block
+    }
+
     #[test]
     fn inventory_method() {
         // The expected behaviour of the inventory method
-        use witnet_data_structures::chain::*;
-        let block = block_example();
+        let block = structurally_valid_block();
 
         let inv_elem = InventoryItem::Block(block);
         let s = serde_json::to_string(&inv_elem).unwrap();
@@ -2023,8 +2452,7 @@ mod tests {
     #[test]
     fn inventory_block() {
         // Check that the inventory method accepts blocks
-        use witnet_data_structures::chain::*;
-        let block = block_example();
+        let block = structurally_valid_block();
         let inv_elem = InventoryItem::Block(block);
         let msg = format!(
             r#"{{"jsonrpc":"2.0","method":"inventory","params":{},"id":1}}"#,
@@ -2037,6 +2465,23 @@ mod tests {
         assert_eq!(response, Some(expected));
     }
 
+    #[test]
+    fn inventory_block_invalid_merkle_roots() {
+        // Blocks whose merkle roots do not match their transactions are rejected before reaching
+        // the ChainManager
+        let block = witnet_data_structures::chain::block_example();
+        let inv_elem = InventoryItem::Block(block);
+        let msg = format!(
+            r#"{{"jsonrpc":"2.0","method":"inventory","params":{},"id":1}}"#,
+            serde_json::to_string(&inv_elem).unwrap()
+        );
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params: The block has an invalid Merkle Tree"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(&msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
     #[test]
     fn get_block_chain_abs_overflow() {
         // Ensure that the get_block_chain method does not panic when passed i64::MIN as argument
@@ -2067,6 +2512,185 @@ mod tests {
         assert_eq!(response, Some(expected));
     }
 
+    #[test]
+    fn get_block_chain_batch() {
+        // All the calls in a batch are resolved, and answered in the same order as requested.
+        // Actors are not running in tests, so use out of bounds epochs that are rejected before
+        // reaching them
+        let batch_size = 20;
+        let epoch = |id: usize| i64::MIN + i64::try_from(id).unwrap();
+        let batch: Vec<Value> = (0..batch_size)
+            .map(|id| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "getBlockChain",
+                    "params": {"epoch": epoch(id), "limit": 1},
+                    "id": id,
+                })
+            })
+            .collect();
+        let msg = serde_json::to_string(&batch).unwrap();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server
+            .handle_request_sync(&msg, Default::default())
+            .unwrap();
+        let responses: Vec<Value> = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(responses.len(), batch_size);
+        for (id, response) in responses.iter().enumerate() {
+            assert_eq!(response["id"], id);
+            assert_eq!(
+                response["error"]["message"],
+                format!(
+                    "Epoch out of bounds: {} must be between -4294967295 and 4294967295 inclusive",
+                    epoch(id)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn get_block_chain_batch_is_concurrent() {
+        // The calls in a batch wait for the ChainManager at the same time, instead of one after
+        // the other
+        let batch_size = 20;
+        let batch: Vec<Value> = (0..batch_size)
+            .map(|id| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "getBlockChain",
+                    "params": {"epoch": 900 + id, "limit": 1},
+                    "id": id,
+                })
+            })
+            .collect();
+        let msg = serde_json::to_string(&batch).unwrap();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        mock_actix::take_max_in_flight();
+        let response = server
+            .handle_request_sync(&msg, Default::default())
+            .unwrap();
+        let responses: Vec<Value> = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(responses.len(), batch_size);
+        for (id, response) in responses.iter().enumerate() {
+            assert_eq!(response["id"], id);
+            assert_eq!(
+                response["error"]["message"],
+                "MailboxError(Mailbox has closed)"
+            );
+        }
+        let sent = mock_actix::take_sent_messages::<GetBlocksEpochRange>();
+        assert_eq!(sent.len(), batch_size);
+        assert_eq!(mock_actix::take_max_in_flight(), batch_size);
+    }
+
+    #[test]
+    fn get_block_chain_limit_is_capped() {
+        let max_limit = 1000;
+
+        // Requesting more blocks than allowed, or unlimited blocks, is capped
+        assert_eq!(clamp_block_chain_limit(1_000_000, max_limit), max_limit);
+        assert_eq!(clamp_block_chain_limit(0, max_limit), max_limit);
+        // Smaller limits are left untouched
+        assert_eq!(clamp_block_chain_limit(50, max_limit), 50);
+        // A cap of 0 disables this check
+        assert_eq!(clamp_block_chain_limit(1_000_000, 0), 1_000_000);
+        assert_eq!(clamp_block_chain_limit(0, 0), 0);
+    }
+
+    #[test]
+    fn get_block_chain_limit_from_end() {
+        // The flag is optional, for backwards compatibility
+        let params: GetBlockChainParams =
+            serde_json::from_str(r#"{"epoch":-100,"limit":10}"#).unwrap();
+        assert!(!params.limit_from_end);
+
+        // Request the last 10 blocks from the last 100 epochs
+        let params: GetBlockChainParams =
+            serde_json::from_str(r#"{"epoch":-100,"limit":10,"limit_from_end":true}"#).unwrap();
+        assert!(params.limit_from_end);
+        assert_eq!(params.limit, 10);
+
+        let msg = block_chain_epoch_range(900, 10, params.limit_from_end);
+        assert_eq!(msg.range, (Bound::Included(900), Bound::Unbounded));
+        assert_eq!(msg.limit, 10);
+        assert!(msg.limit_from_end);
+
+        let msg = block_chain_epoch_range(900, 10, false);
+        assert!(!msg.limit_from_end);
+    }
+
+    #[test]
+    fn get_block_chain_limit_from_end_is_sent_to_chain_manager() {
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let get_block_chain = |params: &str| {
+            let msg = format!(
+                r#"{{"jsonrpc":"2.0","method":"getBlockChain","params":{},"id":1}}"#,
+                params
+            );
+            server.handle_request_sync(&msg, Default::default());
+            let mut sent = mock_actix::take_sent_messages::<GetBlocksEpochRange>();
+            assert_eq!(sent.len(), 1);
+
+            sent.remove(0)
+        };
+
+        // Both the flag and a negative limit request the last 10 blocks of the epoch range
+        for params in [
+            r#"{"epoch":900,"limit":10,"limit_from_end":true}"#,
+            r#"{"epoch":900,"limit":-10}"#,
+        ] {
+            let msg = get_block_chain(params);
+            assert_eq!(msg.range, (Bound::Included(900), Bound::Unbounded));
+            assert_eq!(msg.limit, 10);
+            assert!(msg.limit_from_end);
+        }
+
+        let msg = get_block_chain(r#"{"epoch":900,"limit":10}"#);
+        assert_eq!(msg.limit, 10);
+        assert!(!msg.limit_from_end);
+    }
+
+    #[test]
+    fn get_peers_result_serialization() {
+        let known_peers = PeersInfoNewTried {
+            new: vec![PeerInfo {
+                address: "127.0.0.1:21339".parse().unwrap(),
+                timestamp: 200,
+            }],
+            tried: vec![
+                PeerInfo {
+                    address: "127.0.0.2:21337".parse().unwrap(),
+                    timestamp: 100,
+                },
+                PeerInfo {
+                    address: "127.0.0.1:21338".parse().unwrap(),
+                    timestamp: 150,
+                },
+            ],
+        };
+        let consolidated_peers = GetConsolidatedPeersResult {
+            inbound: vec!["127.0.0.1:21339".parse().unwrap()],
+            outbound: vec!["127.0.0.2:21337".parse().unwrap()],
+        };
+
+        let result = build_get_peers_result(known_peers, Some(consolidated_peers));
+        let expected = serde_json::json!({
+            "new": [
+                {"address": "127.0.0.1:21339", "timestamp": 200, "session": "inbound"},
+            ],
+            "tried": [
+                {"address": "127.0.0.1:21338", "timestamp": 150, "session": null},
+                {"address": "127.0.0.2:21337", "timestamp": 100, "session": "outbound"},
+            ],
+        });
+        assert_eq!(serde_json::to_value(result).unwrap(), expected);
+    }
+
     #[test]
     fn subscribe_invalid_method() {
         // Try to subscribe to a non-existent subscription?
@@ -2192,6 +2816,337 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn get_transaction_output_mempool_hit() {
+        // A transaction found in the mempool is reported as pending
+        let transaction = Transaction::ValueTransfer(VTTransaction::default());
+        let output = GetTransactionOutput::pending(transaction.clone());
+        let value = serde_json::to_value(output).unwrap();
+
+        assert_eq!(value["blockHash"], "pending");
+        assert_eq!(value["blockEpoch"], Value::Null);
+        assert_eq!(value["confirmed"], false);
+        assert_eq!(value["weight"], transaction.weight());
+    }
+
+    #[test]
+    fn get_transaction_output_storage_hit() {
+        // A transaction found in storage is reported along with the block that includes it
+        let transaction = Transaction::ValueTransfer(VTTransaction::default());
+        let block_hash = Hash::SHA256([3; 32]);
+        let output = GetTransactionOutput::included(transaction, block_hash, 7, true);
+        let value = serde_json::to_value(output).unwrap();
+
+        assert_eq!(value["blockHash"], block_hash.to_string());
+        assert_eq!(value["blockEpoch"], 7);
+        assert_eq!(value["confirmed"], true);
+    }
+
+    #[test]
+    fn get_transaction_not_found() {
+        // An unknown transaction hash results in a structured error
+        let hash = Hash::SHA256([3; 32]);
+        let err = transaction_not_found_error(hash);
+        let response = serde_json::to_string(&err).unwrap();
+        let expected = format!(
+            r#"{{"code":404,"message":"Transaction not found","data":{{"hash":"{}"}}}}"#,
+            hash
+        );
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn send_value_transaction_errors() {
+        // The errors returned by the BuildVtt handler keep the TransactionError, so the reason
+        // can be included in the response
+        let error_data = |e: TransactionError| {
+            let e = failure::Error::from(e)
+                .downcast::<TransactionError>()
+                .unwrap();
+            let err = build_transaction_error(e);
+            assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(400));
+
+            err.data.unwrap()
+        };
+
+        assert_eq!(
+            error_data(TransactionError::NoMoney {
+                total_balance: 1000,
+                available_balance: 800,
+                transaction_value: 900,
+            }),
+            serde_json::json!({
+                "reason": "InsufficientBalance",
+                "total_balance": 1000,
+                "available_balance": 800,
+                "transaction_value": 900,
+            })
+        );
+        assert_eq!(
+            error_data(TransactionError::ZeroValueOutputRequested { output_id: 1 }),
+            serde_json::json!({ "reason": "ZeroValueOutput", "output_id": 1 })
+        );
+        assert_eq!(
+            error_data(TransactionError::DustOutputRequested {
+                output_id: 0,
+                value: 100,
+                dust_threshold: 133,
+            }),
+            serde_json::json!({
+                "reason": "DustOutput",
+                "output_id": 0,
+                "value": 100,
+                "dust_threshold": 133,
+            })
+        );
+        assert_eq!(
+            error_data(TransactionError::TooManyInputs {
+                inputs: 180,
+                weight: 24660,
+                max_weight: 20000,
+            }),
+            serde_json::json!({
+                "reason": "TooManyInputs",
+                "inputs": 180,
+                "weight": 24660,
+                "max_weight": 20000,
+            })
+        );
+
+        // Other errors are still internal errors
+        let err = build_transaction_error(TransactionError::FeeOverflow);
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InternalError);
+        assert_eq!(err.data, None);
+    }
+
+    #[test]
+    fn get_block_valid_hash() {
+        // A well-formed block hash is forwarded to the ChainManager
+        let msg = r#"{"jsonrpc":"2.0","method":"getBlock","params":["c0002c6b25615c0f71069f159dffddf8a0b3e529efb054402f0649e969715bdb"],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"MailboxError(Mailbox has closed)"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn get_block_malformed_hash() {
+        // A malformed block hash is rejected before reaching the ChainManager
+        let msg = r#"{"jsonrpc":"2.0","method":"getBlock","params":["c0ffee"],"id":1}"#;
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default()).unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"]["code"], -32602);
+        assert!(response["error"]["message"].as_str().unwrap().starts_with(
+            "Invalid params: First argument of `get_block` must be a valid block hash"
+        ));
+    }
+
+    #[test]
+    fn get_block_not_found() {
+        // An unknown block hash results in a structured error
+        let hash = Hash::SHA256([3; 32]);
+        let err = block_not_found_error(hash);
+        let response = serde_json::to_string(&err).unwrap();
+        let expected = format!(
+            r#"{{"code":404,"message":"Block not found","data":{{"hash":"{}"}}}}"#,
+            hash
+        );
+
+        assert_eq!(response, expected);
+
+        // The ChainManager reports that the block does not exist
+        mock_actix::set_response::<GetBlock>(Err(ChainManagerError::BlockDoesNotExist.into()));
+        let msg = format!(
+            r#"{{"jsonrpc":"2.0","method":"getBlock","params":["{}"],"id":1}}"#,
+            hash
+        );
+        let expected = format!(
+            r#"{{"jsonrpc":"2.0","error":{{"code":404,"message":"Block not found","data":{{"hash":"{}"}}}},"id":1}}"#,
+            hash
+        );
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(&msg, Default::default());
+        assert_eq!(response, Some(expected));
+
+        let sent = mock_actix::take_sent_messages::<GetBlock>();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].hash, hash);
+    }
+
+    #[test]
+    fn get_transaction_from_mempool() {
+        // A transaction found in the mempool is returned without looking into the storage
+        let transaction = Transaction::ValueTransfer(VTTransaction::default());
+        let hash = transaction.hash();
+        mock_actix::set_response::<GetMemoryTransaction>(Ok(transaction.clone()));
+
+        let response = get_transaction_response(hash);
+        let expected = serde_json::to_value(GetTransactionOutput::pending(transaction)).unwrap();
+        assert_eq!(response["result"], expected);
+
+        let sent = mock_actix::take_sent_messages::<GetMemoryTransaction>();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].hash, hash);
+    }
+
+    #[test]
+    fn get_transaction_from_storage() {
+        // A transaction that is not in the mempool is looked up in the storage, along with the
+        // block that includes it
+        let transaction = Transaction::ValueTransfer(VTTransaction::default());
+        let hash = transaction.hash();
+        let block_hash = Hash::SHA256([3; 32]);
+        mock_actix::set_response::<GetMemoryTransaction>(Err(()));
+        mock_actix::set_response::<GetItemTransaction>(Ok((
+            transaction.clone(),
+            PointerToBlock {
+                block_hash,
+                transaction_index: TransactionPointer::ValueTransfer(0),
+            },
+            7,
+        )));
+        mock_actix::set_response::<IsConfirmedBlock>(Ok(true));
+
+        let response = get_transaction_response(hash);
+        let expected = serde_json::to_value(GetTransactionOutput::included(
+            transaction,
+            block_hash,
+            7,
+            true,
+        ))
+        .unwrap();
+        assert_eq!(response["result"], expected);
+    }
+
+    #[test]
+    fn get_transaction_unknown_hash() {
+        // A transaction that is neither in the mempool nor in the storage is not found
+        let hash = Hash::SHA256([3; 32]);
+        mock_actix::set_response::<GetMemoryTransaction>(Err(()));
+        mock_actix::set_response::<GetItemTransaction>(Err(InventoryManagerError::ItemNotFound));
+
+        let response = get_transaction_response(hash);
+        assert_eq!(
+            response["error"],
+            serde_json::to_value(transaction_not_found_error(hash)).unwrap()
+        );
+    }
+
+    /// Call getTransaction with the given hash and return the parsed response
+    fn get_transaction_response(hash: Hash) -> Value {
+        let msg = format!(
+            r#"{{"jsonrpc":"2.0","method":"getTransaction","params":["{}"],"id":1}}"#,
+            hash
+        );
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server
+            .handle_request_sync(&msg, Default::default())
+            .unwrap();
+
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn get_output_valid_pointer() {
+        // A well-formed output pointer is forwarded to the ChainManager
+        let msg = r#"{"jsonrpc":"2.0","method":"getOutput","params":["0000000000000000000000000000000000000000000000000000000000000000:0"],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"MailboxError(Mailbox has closed)"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn get_output_found_and_not_found() {
+        let output_pointer: OutputPointer =
+            "0000000000000000000000000000000000000000000000000000000000000001:2"
+                .parse()
+                .unwrap();
+        let msg = format!(
+            r#"{{"jsonrpc":"2.0","method":"getOutput","params":["{}"],"id":1}}"#,
+            output_pointer
+        );
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+
+        // An unspent output is returned as is
+        let output = ValueTransferOutput {
+            value: 10,
+            ..Default::default()
+        };
+        mock_actix::set_response::<GetOutput>(Ok(output.clone()));
+        let response = server
+            .handle_request_sync(&msg, Default::default())
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["result"], serde_json::to_value(output).unwrap());
+
+        // A missing or spent output results in a not found error
+        mock_actix::set_response::<GetOutput>(Err(ChainManagerError::OutputNotFound {
+            output_pointer: output_pointer.clone(),
+        }
+        .into()));
+        let response = server.handle_request_sync(&msg, Default::default());
+        let expected = format!(
+            r#"{{"jsonrpc":"2.0","error":{{"code":404,"message":"Output not found","data":{{"output_pointer":"{}"}}}},"id":1}}"#,
+            output_pointer
+        );
+        assert_eq!(response, Some(expected));
+
+        let sent = mock_actix::take_sent_messages::<GetOutput>();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().all(|msg| msg.output_pointer == output_pointer));
+    }
+
+    #[test]
+    fn get_output_malformed_pointer() {
+        // A malformed output pointer is rejected before reaching the ChainManager
+        let msg = r#"{"jsonrpc":"2.0","method":"getOutput","params":["0000000000000000000000000000000000000000000000000000000000000000"],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params: Output pointer has the wrong format, expected '<transaction id>:<output index>'"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn validate_address_valid() {
+        // Valid addresses are returned in their normalized form
+        let msg = r#"{"jsonrpc":"2.0","method":"validateAddress","params":[" WIT1GDM8MQLZ8LXTJ05W05MW63JVECYENVUA7AJDK5 "],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","result":{"address":"wit1gdm8mqlz8lxtj05w05mw63jvecyenvua7ajdk5","pkhHex":"43767d83e23fccb93e8e7d36ed464cce0999b39d"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn validate_address_wrong_checksum() {
+        let msg = r#"{"jsonrpc":"2.0","method":"validateAddress","params":["wit1gdm8mqlz8lxtj05w05mw63jvecyenvua7ajdk4"],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params: Failed to deserialize Bech32: invalid checksum"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn validate_address_wrong_prefix() {
+        let msg = r#"{"jsonrpc":"2.0","method":"validateAddress","params":["twit1gdm8mqlz8lxtj05w05mw63jvecyenvuasgmfk9"],"id":1}"#;
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params: Address is for different environment: prefix \"twit\" is not valid for mainnet"},"id":1}"#.to_string();
+        let mut server = WittyMultiServer::new();
+        attach_api(&mut server, true, Subscriptions::default(), &None);
+        let response = server.handle_request_sync(msg, Default::default());
+        assert_eq!(response, Some(expected));
+    }
+
     #[test]
     fn hash_str_format() {
         use witnet_data_structures::chain::Hash;
@@ -2204,6 +3159,30 @@ mod tests {
         assert_eq!(h, h3);
     }
 
+    #[test]
+    fn get_consensus_constants_fields() {
+        // The consensus constants of the node are returned with all the fields clients rely on
+        let consensus_constants = witnet_config::config::Config::default().consensus_constants;
+        let value = consensus_constants_to_value(&consensus_constants).unwrap();
+
+        assert_eq!(
+            value["checkpoints_period"],
+            consensus_constants.checkpoints_period
+        );
+        assert_eq!(
+            value["checkpoint_zero_timestamp"],
+            consensus_constants.checkpoint_zero_timestamp
+        );
+        assert_eq!(
+            value["genesis_hash"],
+            consensus_constants.genesis_hash.to_string()
+        );
+        assert_eq!(
+            value["collateral_minimum"],
+            consensus_constants.collateral_minimum
+        );
+    }
+
     #[test]
     fn build_drt_example() {
         let build_drt = BuildDrt::default();
@@ -2235,13 +3214,18 @@ mod tests {
                 "getBlock",
                 "getBlockChain",
                 "getConsensusConstants",
+                "getDataRequestStats",
                 "getMempool",
+                "getMyActiveDataRequests",
+                "getOutput",
+                "getPeers",
                 "getPkh",
                 "getPublicKey",
                 "getReputation",
                 "getReputationAll",
                 "getSuperblock",
                 "getSupplyInfo",
+                "getTip",
                 "getTransaction",
                 "getUtxoInfo",
                 "initializePeers",
@@ -2258,6 +3242,7 @@ mod tests {
                 "signalingInfo",
                 "syncStatus",
                 "tryRequest",
+                "validateAddress",
                 "witnet_subscribe",
                 "witnet_unsubscribe",
             ]
@@ -2,10 +2,13 @@ use std::collections::HashMap;
 
 use actix::prelude::*;
 use witnet_config::config::{Config, JsonRPC};
+use witnet_data_structures::chain::{Block, Hash, Hashable};
 use witty_jsonrpc::prelude::*;
 
 use crate::{
-    actors::messages::{BlockNotify, NodeStatusNotify, SuperBlockNotify},
+    actors::messages::{
+        BlockNotify, DataRequestResolved, NodeStateChanged, NodeStatusNotify, SuperBlockNotify,
+    },
     utils::stop_system_if_panicking,
 };
 
@@ -41,6 +44,16 @@ impl JsonRpcServer {
         server
     }
 
+    /// Create a new instance of JsonRpcServer that sends notifications to `subscriptions`,
+    /// without starting any transport.
+    #[cfg(test)]
+    pub fn with_subscriptions(subscriptions: Subscriptions) -> Self {
+        Self {
+            subscriptions,
+            ..Self::default()
+        }
+    }
+
     /// Method to process the configuration received from ConfigManager
     pub fn initialize(mut self, runtime: tokio::runtime::Handle) -> Result<Self, failure::Error> {
         let subscriptions = self.subscriptions.clone();
@@ -129,7 +142,7 @@ impl Handler<BlockNotify> for JsonRpcServer {
 
     fn handle(&mut self, msg: BlockNotify, _ctx: &mut Self::Context) -> Self::Result {
         log::debug!("Got NewBlock message, sending notifications...");
-        let block = serde_json::to_value(msg.block).unwrap();
+        let block = serde_json::to_value(&msg.block).unwrap();
         if let Ok(subs) = self.subscriptions.lock() {
             let empty_map = HashMap::new();
             for (subscription, (sink, _subscription_params)) in
@@ -144,6 +157,25 @@ impl Handler<BlockNotify> for JsonRpcServer {
                     log::error!("Failed to send notification: {:?}", e);
                 }
             }
+            // Subscriptions to the blocks that include a given data request or its tally
+            for (subscription, (sink, subscription_params)) in
+                subs.get("dataRequestBlocks").unwrap_or(&empty_map)
+            {
+                let matches = parse_dr_pointer(subscription_params)
+                    .map(|dr_pointer| block_includes_data_request(&msg.block, dr_pointer))
+                    .unwrap_or(false);
+                if !matches {
+                    continue;
+                }
+                log::debug!("Sending data request block notification!");
+                let notification = jsonrpc_core::Params::from(SubscriptionResult {
+                    result: block.clone(),
+                    subscription: subscription.clone(),
+                });
+                if let Err(e) = sink.notify(notification) {
+                    log::error!("Failed to send notification: {:?}", e);
+                }
+            }
         } else {
             log::error!("Failed to acquire lock in BlockNotify handle");
         }
@@ -207,3 +239,127 @@ impl Handler<NodeStatusNotify> for JsonRpcServer {
         }
     }
 }
+
+impl Handler<NodeStateChanged> for JsonRpcServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: NodeStateChanged, _ctx: &mut Self::Context) -> Self::Result {
+        if let Ok(subs) = self.subscriptions.lock() {
+            let empty_map = HashMap::new();
+            for (subscription, (sink, _subscription_params)) in
+                subs.get("stateChanges").unwrap_or(&empty_map)
+            {
+                log::debug!(
+                    "Sending node state change notification ({:?} -> {:?})",
+                    msg.previous,
+                    msg.current
+                );
+                let notification = jsonrpc_core::Params::from(SubscriptionResult {
+                    result: serde_json::to_value(&msg).unwrap(),
+                    subscription: subscription.clone(),
+                });
+                if let Err(e) = sink.notify(notification) {
+                    log::error!("Failed to send notification: {:?}", e);
+                }
+            }
+        } else {
+            log::error!("Failed to acquire lock in NodeStateChanged handle");
+        }
+    }
+}
+
+impl Handler<DataRequestResolved> for JsonRpcServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: DataRequestResolved, _ctx: &mut Self::Context) -> Self::Result {
+        if let Ok(subs) = self.subscriptions.lock() {
+            let empty_map = HashMap::new();
+            for (subscription, (sink, subscription_params)) in
+                subs.get("dataRequestResolved").unwrap_or(&empty_map)
+            {
+                if parse_dr_pointer(subscription_params) != Some(msg.dr_pointer) {
+                    continue;
+                }
+                log::debug!(
+                    "Sending notification of resolved data request {}",
+                    msg.dr_pointer
+                );
+                let notification = jsonrpc_core::Params::from(SubscriptionResult {
+                    result: serde_json::to_value(&msg).unwrap(),
+                    subscription: subscription.clone(),
+                });
+                if let Err(e) = sink.notify(notification) {
+                    log::error!("Failed to send notification: {:?}", e);
+                }
+            }
+        } else {
+            log::error!("Failed to acquire lock in DataRequestResolved handle");
+        }
+    }
+}
+
+/// Parse the params of a `dataRequestBlocks` or `dataRequestResolved` subscription: the hash of
+/// the data request
+pub fn parse_dr_pointer(params: &jsonrpc_core::Value) -> Option<Hash> {
+    params
+        .as_str()
+        .and_then(|dr_pointer| dr_pointer.parse().ok())
+}
+
+/// Whether a block includes the data request with the given hash, or its tally
+pub fn block_includes_data_request(block: &Block, dr_pointer: Hash) -> bool {
+    block
+        .txns
+        .data_request_txns
+        .iter()
+        .any(|dr_tx| dr_tx.hash() == dr_pointer)
+        || block
+            .txns
+            .tally_txns
+            .iter()
+            .any(|tally_tx| tally_tx.dr_pointer == dr_pointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use witnet_data_structures::{
+        chain::DataRequestOutput,
+        transaction::{DRTransaction, DRTransactionBody, TallyTransaction},
+    };
+
+    use super::*;
+
+    #[test]
+    fn data_request_blocks_filter() {
+        let dr_tx = DRTransaction::new(
+            DRTransactionBody::new(vec![], vec![], DataRequestOutput::default()),
+            vec![],
+        );
+        let dr_pointer = dr_tx.hash();
+        let other_dr_pointer = Hash::SHA256([1; 32]);
+        let params = serde_json::Value::from(dr_pointer.to_string());
+        assert_eq!(parse_dr_pointer(&params), Some(dr_pointer));
+        assert_eq!(parse_dr_pointer(&serde_json::Value::Null), None);
+
+        // A block that includes the data request
+        let mut block = Block::default();
+        block.txns.data_request_txns.push(dr_tx);
+        assert!(block_includes_data_request(&block, dr_pointer));
+        assert!(!block_includes_data_request(&block, other_dr_pointer));
+
+        // A block that includes the tally of the data request
+        let mut block = Block::default();
+        block.txns.tally_txns.push(TallyTransaction::new(
+            dr_pointer,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        ));
+        assert!(block_includes_data_request(&block, dr_pointer));
+        assert!(!block_includes_data_request(&block, other_dr_pointer));
+
+        // A block unrelated to the data request
+        assert!(!block_includes_data_request(&Block::default(), dr_pointer));
+    }
+}
@@ -20,10 +20,10 @@ use witnet_data_structures::{
     chain::{
         priority::PrioritiesEstimate,
         tapi::{ActiveWips, BitVotesCounter},
-        Block, CheckpointBeacon, DataRequestInfo, DataRequestOutput, Epoch, EpochConstants, Hash,
-        InventoryEntry, InventoryItem, NodeStats, PointerToBlock, PublicKeyHash,
-        PublicKeyHashParseError, RADRequest, RADTally, Reputation, StateMachine, SuperBlock,
-        SuperBlockVote, SupplyInfo, ValueTransferOutput,
+        Block, CheckpointBeacon, DataRequestInfo, DataRequestOutput, DataRequestStage, Epoch,
+        EpochConstants, Hash, InventoryEntry, InventoryItem, NodeStats, OutputPointer,
+        PointerToBlock, PublicKeyHash, PublicKeyHashParseError, RADRequest, RADTally, Reputation,
+        StateMachine, SuperBlock, SuperBlockVote, SupplyInfo, ValueTransferOutput,
     },
     fee::{deserialize_fee_backwards_compatible, Fee},
     radon_report::RadonReport,
@@ -32,10 +32,11 @@ use witnet_data_structures::{
     },
     transaction_factory::NodeBalance,
     types::LastBeacon,
-    utxo_pool::{UtxoInfo, UtxoSelectionStrategy},
+    utxo_pool::{BalanceBreakdown, UtxoInfo, UtxoSelectionStrategy},
 };
 use witnet_p2p::{
     error::SessionsError,
+    peers::PeerInfo,
     sessions::{GetConsolidatedPeersResult, SessionStatus, SessionType},
 };
 use witnet_rad::{error::RadError, types::RadonTypes};
@@ -69,6 +70,25 @@ impl Message for GetHighestCheckpointBeacon {
     type Result = Result<CheckpointBeacon, failure::Error>;
 }
 
+/// Message to obtain the highest block checkpoint managed by the `ChainManager` actor, along with
+/// the current state of the node. Unlike other chain queries, it works in any state.
+pub struct GetTip;
+
+impl Message for GetTip {
+    type Result = Result<TipInfo, failure::Error>;
+}
+
+/// Highest block checkpoint known by the node
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TipInfo {
+    /// Checkpoint and hash of the highest block
+    pub beacon: CheckpointBeacon,
+    /// Current state of the node
+    pub node_state: StateMachine,
+    /// Whether the node is synced, so the beacon can be trusted as the tip of the chain
+    pub synced: bool,
+}
+
 /// Message to obtain the last super block votes managed by the `ChainManager`
 /// actor.
 pub struct GetSuperBlockVotes;
@@ -128,10 +148,11 @@ pub struct GetBlock {
 }
 
 impl Message for GetBlock {
-    type Result = Result<Block, ChainManagerError>;
+    type Result = Result<Block, failure::Error>;
 }
 
 /// Message to obtain a vector of block hashes using a range of epochs
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GetBlocksEpochRange {
     /// Range of Epochs (prefer using the new method to create a range)
     pub range: (Bound<Epoch>, Bound<Epoch>),
@@ -187,6 +208,39 @@ impl Message for GetBlocksEpochRange {
     type Result = Result<Vec<(Epoch, Hash)>, ChainManagerError>;
 }
 
+/// Message to obtain the same blocks as `GetBlocksEpochRange`, along with the timestamp of the
+/// start of the epoch of each block, as `(Epoch, Hash, timestamp)`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GetBlocksEpochRangeWithTimestamps(pub GetBlocksEpochRange);
+
+impl Message for GetBlocksEpochRangeWithTimestamps {
+    type Result = Result<Vec<(Epoch, Hash, i64)>, ChainManagerError>;
+}
+
+/// Message to obtain a page of block hashes, so that very large epoch ranges can be iterated
+/// without collecting the whole range at once
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GetBlocksEpochRangePaged {
+    /// Cursor: only return blocks with an epoch greater than this one. `None` starts from the
+    /// first block of the chain
+    pub after: Option<Epoch>,
+    /// Maximum number of blocks in the page. 0 means `MAX_BLOCKS_SYNC`
+    pub limit: usize,
+}
+
+impl Message for GetBlocksEpochRangePaged {
+    type Result = Result<BlocksEpochRangePage, ChainManagerError>;
+}
+
+/// A page of block hashes returned by `GetBlocksEpochRangePaged`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlocksEpochRangePage {
+    /// List of (epoch, block_hash), in epoch order
+    pub blocks: Vec<(Epoch, Hash)>,
+    /// Cursor to use as `after` to get the next page, or `None` if this is the last page
+    pub next: Option<Epoch>,
+}
+
 /// A list of peers and their respective last beacon, used to establish consensus
 pub struct PeersBeacons {
     /// A list of peers and their respective last beacon
@@ -211,7 +265,7 @@ pub struct BuildVtt {
     /// Strategy to sort the unspent outputs pool
     #[serde(default)]
     pub utxo_strategy: UtxoSelectionStrategy,
-    /// Construct the transaction but do not broadcast it
+    /// Construct the transaction but do not sign nor broadcast it
     #[serde(default)]
     pub dry_run: bool,
 }
@@ -228,7 +282,7 @@ pub struct BuildDrt {
     /// Fee
     #[serde(deserialize_with = "deserialize_fee_backwards_compatible")]
     pub fee: Fee,
-    /// Construct the transaction but do not broadcast it
+    /// Construct the transaction but do not sign nor broadcast it
     #[serde(default)]
     pub dry_run: bool,
 }
@@ -364,6 +418,18 @@ impl Message for GetBalance {
     type Result = Result<NodeBalance, failure::Error>;
 }
 
+/// Get the balance of one address split into total, available, time-locked and collateralizable
+/// value
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetBalanceBreakdown {
+    /// Address whose balance will be returned. `GetBalanceTarget::All` is not supported
+    pub target: GetBalanceTarget,
+}
+
+impl Message for GetBalanceBreakdown {
+    type Result = Result<BalanceBreakdown, failure::Error>;
+}
+
 /// Get Supply
 #[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct GetSupplyInfo;
@@ -383,6 +449,54 @@ impl Message for GetUtxoInfo {
     type Result = Result<UtxoInfo, failure::Error>;
 }
 
+/// Get the unspent output referred to by an output pointer
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetOutput {
+    /// Output pointer
+    pub output_pointer: OutputPointer,
+}
+
+impl Message for GetOutput {
+    type Result = Result<ValueTransferOutput, failure::Error>;
+}
+
+/// Get a snapshot of the whole UTXO set at the current chain beacon, for auditing purposes
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetUtxoSnapshot {
+    /// Write the snapshot into this file instead of returning it, because the UTXO set can be
+    /// very large. The file contains one JSON object per line: the first one has the block number
+    /// and the beacon, and each of the following ones is an unspent output.
+    pub path: Option<PathBuf>,
+}
+
+impl Message for GetUtxoSnapshot {
+    type Result = Result<UtxoSnapshot, failure::Error>;
+}
+
+/// Unspent output included in a UTXO set snapshot
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UtxoSnapshotEntry {
+    /// Output pointer
+    pub output_pointer: OutputPointer,
+    /// Value transfer output
+    pub output: ValueTransferOutput,
+    /// Number of the block that included this output
+    pub block_number: u32,
+}
+
+/// Snapshot of the UTXO set
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UtxoSnapshot {
+    /// Number of blocks consolidated when the snapshot was taken
+    pub block_number: u32,
+    /// Chain beacon when the snapshot was taken
+    pub beacon: CheckpointBeacon,
+    /// Number of unspent outputs in the snapshot
+    pub utxos_count: usize,
+    /// The unspent outputs, unless they were written into a file
+    pub utxos: Option<Vec<UtxoSnapshotEntry>>,
+}
+
 /// Reputation info
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ReputationStats {
@@ -417,9 +531,85 @@ impl Message for GetReputation {
     type Result = Result<GetReputationResult, failure::Error>;
 }
 
+/// Check whether an identity belongs to the active reputation set, without looking up its
+/// reputation
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IsActiveIdentity {
+    /// Public key hash
+    pub pkh: PublicKeyHash,
+}
+
+impl Message for IsActiveIdentity {
+    type Result = Result<bool, failure::Error>;
+}
+
+/// GetDataRequestStats result
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DataRequestStats {
+    /// Number of active data requests in commit stage
+    pub commit_stage: usize,
+    /// Number of active data requests in reveal stage
+    pub reveal_stage: usize,
+    /// Number of active data requests in tally stage
+    pub tally_stage: usize,
+    /// Number of data requests resolved in the blocks consolidated since the node started
+    pub resolved: u64,
+    /// Average number of epochs between the epoch in which those data requests could start
+    /// receiving commitments and the epoch of their tally, or `None` if none was resolved
+    pub average_resolution_epochs: Option<f64>,
+}
+
+/// Get the number of active data requests in each stage, and statistics about the resolved ones
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetDataRequestStats;
+
+impl Message for GetDataRequestStats {
+    type Result = Result<DataRequestStats, failure::Error>;
+}
+
+/// Get the active data requests in which an identity has committed or revealed
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetMyActiveDataRequests {
+    /// Public key hash of the witness
+    pub pkh: PublicKeyHash,
+}
+
+impl Message for GetMyActiveDataRequests {
+    type Result = Result<Vec<ActiveDataRequest>, failure::Error>;
+}
+
+/// Active data request in which a witness is participating
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActiveDataRequest {
+    /// `DataRequest` transaction hash
+    pub dr_pointer: Hash,
+    /// Current stage of the data request
+    pub stage: DataRequestStage,
+}
+
 /// Get all the pending transactions
 #[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct GetMempool;
+pub struct GetMempool {
+    /// Type of the pending transactions to return
+    #[serde(default)]
+    pub filter: MempoolFilter,
+    /// Maximum number of transaction hashes to return for each type. A limit of 0 means unlimited
+    #[serde(default)]
+    pub limit: u32,
+}
+
+/// Tells the `getMempool` method which types of pending transactions to return
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MempoolFilter {
+    /// Both value transfer and data request transactions
+    #[default]
+    All,
+    /// Only value transfer transactions
+    ValueTransfer,
+    /// Only data request transactions
+    DataRequest,
+}
 
 impl Message for GetMempool {
     type Result = Result<GetMempoolResult, failure::Error>;
@@ -434,6 +624,27 @@ pub struct GetMempoolResult {
     pub data_request: Vec<Hash>,
 }
 
+/// Get the pending value transfer and data request transactions sorted by priority (fee per weight
+/// unit) in descending order, so that the highest paying transactions can be chosen first
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GetMempoolByPriority {
+    /// Maximum number of transactions to return. A limit of 0 means unlimited
+    pub limit: u32,
+}
+
+impl Message for GetMempoolByPriority {
+    type Result = Result<Vec<PrioritizedTransaction>, failure::Error>;
+}
+
+/// Pending transaction along with its priority
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PrioritizedTransaction {
+    /// Fee per weight unit
+    pub priority: f64,
+    /// Pending transaction
+    pub transaction: Transaction,
+}
+
 /// Try to mine a block: signal the ChainManager to check if it can produce a new block
 #[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TryMineBlock;
@@ -476,6 +687,14 @@ impl Message for AddSuperBlock {
     type Result = ();
 }
 
+/// Persist the transactions pool into storage, so that it can be restored after a restart. This is
+/// sent to the ChainManager when the node is being closed.
+pub struct PersistTransactionsPool;
+
+impl Message for PersistTransactionsPool {
+    type Result = Result<(), ()>;
+}
+
 /// Returns true if the provided block hash is the consolidated block for the provided epoch, and
 /// there exists a superblock with a majority of votes to confirm that.
 pub struct IsConfirmedBlock {
@@ -845,6 +1064,14 @@ impl Message for GetKnownPeers {
     type Result = Result<PeersNewTried, failure::Error>;
 }
 
+/// Message to get all the peer addresses from the new and tried lists, along with their
+/// timestamps
+pub struct GetKnownPeersInfo;
+
+impl Message for GetKnownPeersInfo {
+    type Result = Result<PeersInfoNewTried, failure::Error>;
+}
+
 /// Message to get node stats
 pub struct GetNodeStats;
 
@@ -860,6 +1087,14 @@ pub struct PeersNewTried {
     pub tried: Vec<SocketAddr>,
 }
 
+/// List of known peers and their timestamps sorted by bucket
+pub struct PeersInfoNewTried {
+    /// Peers in new bucket
+    pub new: Vec<PeerInfo>,
+    /// Peers in tried bucket
+    pub tried: Vec<PeerInfo>,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////
 // MESSAGES FROM RAD MANAGER
 ////////////////////////////////////////////////////////////////////////////////////////
@@ -1273,6 +1508,37 @@ impl Message for NodeStatusNotify {
     type Result = ();
 }
 
+/// Notification signaling that the node has transitioned from one state into a different one.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NodeStateChanged {
+    /// The node status before the transition.
+    pub previous: StateMachine,
+    /// The node status after the transition.
+    pub current: StateMachine,
+}
+
+impl Message for NodeStateChanged {
+    type Result = ();
+}
+
+/// Notification signaling that the tally of a data request has been included in a block that has
+/// been consolidated into the chain.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DataRequestResolved {
+    /// Hash of the data request
+    pub dr_pointer: Hash,
+    /// Hash of the block that includes the tally
+    pub block_hash: Hash,
+    /// Epoch of the block that includes the tally
+    pub block_epoch: Epoch,
+    /// Result of the data request, as CBOR bytes
+    pub tally: Vec<u8>,
+}
+
+impl Message for DataRequestResolved {
+    type Result = ();
+}
+
 /// Message for ordering a transaction priority estimation.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct EstimatePriority;
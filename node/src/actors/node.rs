@@ -2,10 +2,11 @@ use std::{
     collections::HashSet,
     path::PathBuf,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 pub use actix::System;
-use actix::{Actor, SystemRegistry};
+use actix::{Actor, SystemRegistry, SystemService};
 use witnet_config::config::Config;
 use witnet_validations::witnessing::validate_witnessing_config;
 
@@ -13,7 +14,8 @@ use crate::{
     actors::{
         chain_manager::ChainManager, connections_manager::ConnectionsManager,
         epoch_manager::EpochManager, inventory_manager::InventoryManager, json_rpc::JsonRpcServer,
-        peers_manager::PeersManager, rad_manager::RadManager, sessions_manager::SessionsManager,
+        messages::PersistTransactionsPool, peers_manager::PeersManager, rad_manager::RadManager,
+        sessions_manager::SessionsManager,
     },
     config_mngr, signature_mngr, storage_mngr,
     utils::Force,
@@ -73,7 +75,10 @@ pub fn run(config: Arc<Config>, ops: NodeOps, callback: fn()) -> Result<(), fail
         SystemRegistry::set(inventory_manager_addr);
 
         // Start RadManager actor
-        let rad_manager_addr = RadManager::from_config(witnessing_config).start();
+        let rad_manager_addr = RadManager::from_config(witnessing_config)
+            .with_max_concurrent_retrievals(config.mining.data_request_max_concurrent_retrievals)
+            .with_aggregation_failure_policy(config.mining.data_request_aggregation_failure_policy)
+            .start();
         SystemRegistry::set(rad_manager_addr);
 
         // Start JSON RPC server
@@ -89,7 +94,19 @@ pub fn run(config: Arc<Config>, ops: NodeOps, callback: fn()) -> Result<(), fail
 pub fn close(system: &System) {
     log::info!("Closing node");
 
-    system.stop();
+    // Persist the transactions pool before stopping, so that it can be restored after a restart.
+    // Stop anyway if that takes too long.
+    system.arbiter().spawn(async {
+        let persist = ChainManager::from_registry().send(PersistTransactionsPool);
+        if tokio::time::timeout(Duration::from_secs(5), persist)
+            .await
+            .is_err()
+        {
+            log::warn!("Timed out while persisting the transactions pool");
+        }
+
+        System::current().stop();
+    });
 }
 
 /// Special operations that the node can execute.
@@ -2,9 +2,9 @@ use actix::{Context, Handler};
 
 use super::PeersManager;
 use crate::actors::messages::{
-    AddConsolidatedPeer, AddPeers, ClearPeers, EpochNotification, GetKnownPeers, GetRandomPeers,
-    InitializePeers, PeersNewTried, PeersSocketAddrResult, PeersSocketAddrsResult,
-    RemoveAddressesFromTried, RequestPeers,
+    AddConsolidatedPeer, AddPeers, ClearPeers, EpochNotification, GetKnownPeers, GetKnownPeersInfo,
+    GetRandomPeers, InitializePeers, PeersInfoNewTried, PeersNewTried, PeersSocketAddrResult,
+    PeersSocketAddrsResult, RemoveAddressesFromTried, RequestPeers,
 };
 use witnet_util::timestamp::get_timestamp;
 
@@ -140,6 +140,18 @@ impl Handler<GetKnownPeers> for PeersManager {
     }
 }
 
+/// Handler for GetKnownPeersInfo message
+impl Handler<GetKnownPeersInfo> for PeersManager {
+    type Result = Result<PeersInfoNewTried, failure::Error>;
+
+    fn handle(&mut self, _msg: GetKnownPeersInfo, _: &mut Context<Self>) -> Self::Result {
+        Ok(PeersInfoNewTried {
+            new: self.peers.get_all_info_from_new()?,
+            tried: self.peers.get_all_info_from_tried()?,
+        })
+    }
+}
+
 /// Handler for EpochNotification message
 impl Handler<EpochNotification<()>> for PeersManager {
     type Result = ();
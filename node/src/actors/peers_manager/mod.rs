@@ -70,6 +70,8 @@ impl PeersManager {
     fn persist_peers(&self, ctx: &mut Context<Self>, storage_peers_period: Duration) {
         // Schedule the discovery_peers with a given period
         ctx.run_later(storage_peers_period, move |act, ctx| {
+            act.peers.crowded_hosts();
+
             storage_mngr::put(&storage_keys::peers_key(act.get_magic()), &act.peers)
                 .into_actor(act)
                 .map(|res, _act, _ctx| match res {
@@ -85,7 +87,13 @@ impl PeersManager {
     }
 
     fn import_peers(&mut self, peers: Peers, known_peers: Vec<SocketAddr>) {
-        self.peers = peers;
+        // Settings coming from the configuration are not persisted along with the peers
+        self.peers = Peers {
+            ice_period: self.peers.ice_period,
+            max_slots_per_host: self.peers.max_slots_per_host,
+            new_bucket_capacity: self.peers.new_bucket_capacity,
+            ..peers
+        };
 
         match self.peers.add_to_new(known_peers, None) {
             Ok(_duplicated_peers) => {}
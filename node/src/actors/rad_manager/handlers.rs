@@ -1,9 +1,10 @@
 //! Message handlers for `RadManager`
 
-use std::time::Duration;
+use std::{future::Future, sync::Arc, time::Duration};
 
 use actix::{Handler, ResponseFuture};
-use futures::FutureExt;
+use tokio::sync::Semaphore;
+use witnet_config::config::ScriptFailurePolicy;
 use witnet_data_structures::radon_report::{RadonReport, ReportContext, RetrievalMetadata, Stage};
 use witnet_rad::{
     conditions::{evaluate_tally_precondition_clause, TallyPreconditionClauseResult},
@@ -21,6 +22,39 @@ use super::RadManager;
 // This value must be lower than half an epoch, and having enough time to broadcasting the commit.
 const MAX_RETRIEVAL_TIMEOUT: Duration = Duration::from_millis(10000);
 
+/// Wait until a permit is available in the retrievals semaphore (if any), and then run `fut` while
+/// holding that permit.
+async fn with_retrieval_permit<F: Future>(semaphore: Option<Arc<Semaphore>>, fut: F) -> F::Output {
+    let _permit = match semaphore {
+        Some(semaphore) => Some(
+            semaphore
+                .acquire_owned()
+                .await
+                .expect("retrievals semaphore is never closed"),
+        ),
+        None => None,
+    };
+
+    fut.await
+}
+
+/// Run a retrieval once a permit is available in the retrievals semaphore (if any). The timeout
+/// also covers the time spent waiting for the permit, because the node must still be able to
+/// commit a `RetrieveTimeout` error in time when the retrievals queue is long.
+async fn run_retrieval_with_timeout<F, T>(
+    semaphore: Option<Arc<Semaphore>>,
+    timeout: Duration,
+    fut: F,
+) -> Result<T, RadError>
+where
+    F: Future<Output = Result<T, RadError>>,
+{
+    tokio::time::timeout(timeout, with_retrieval_permit(semaphore, fut))
+        .await
+        // In case of timeout, set response to "RetrieveTimeout" error
+        .unwrap_or(Err(RadError::RetrieveTimeout))
+}
+
 impl Handler<ResolveRA> for RadManager {
     // This must be ResponseFuture, otherwise the actor dies on panic
     type Result = ResponseFuture<Result<RadonReport<RadonTypes>, RadError>>;
@@ -28,6 +62,8 @@ impl Handler<ResolveRA> for RadManager {
     fn handle(&mut self, msg: ResolveRA, _ctx: &mut Self::Context) -> Self::Result {
         // Fetching these values this early makes lifetimes easier for the fut block below
         let witnessing = self.witnessing.clone();
+        let retrievals_semaphore = self.retrievals_semaphore.clone();
+        let aggregation_failure_policy = self.aggregation_failure_policy;
 
         // The result of the RAD aggregation is computed asynchronously, because the async block
         // returns a future
@@ -62,10 +98,8 @@ impl Handler<ResolveRA> for RadManager {
                     )
                 })
                 .map(|fut| {
-                    tokio::time::timeout(timeout, fut).map(|response| {
-                        // In case of timeout, set response to "RetrieveTimeout" error
-                        response.unwrap_or(Err(RadError::RetrieveTimeout))
-                    })
+                    // Queue the retrieval until the global concurrency limit allows it
+                    run_retrieval_with_timeout(retrievals_semaphore.clone(), timeout, fut)
                 });
 
             // Perform retrievals in parallel for the sake of synchronization between sources
@@ -103,11 +137,25 @@ impl Handler<ResolveRA> for RadManager {
                             &msg.active_wips,
                         );
 
-                    // Convert Err into Ok because returning Err from this handler means that the
-                    // node should not commit the result, and we do want to commit this error.
-                    Ok(aggregation_result.unwrap_or_else(|error| {
-                        RadonReport::from_result(Err(error), &aggregation_context)
-                    }))
+                    match aggregation_result {
+                        Ok(report) => Ok(report),
+                        // Returning Err from this handler means that the node should not commit
+                        // the result
+                        Err(error)
+                            if aggregation_failure_policy == ScriptFailurePolicy::Unresolved =>
+                        {
+                            log::warn!(
+                                "Aggregation script failed, refraining from committing: {}",
+                                error
+                            );
+
+                            Err(error)
+                        }
+                        // Convert Err into Ok because we do want to commit this error
+                        Err(error) => {
+                            Ok(RadonReport::from_result(Err(error), &aggregation_context))
+                        }
+                    }
                 }
                 Ok(TallyPreconditionClauseResult::MajorityOfErrors { errors_mode }) => {
                     Ok(RadonReport::from_result(
@@ -144,11 +192,13 @@ impl Handler<RunTally> for RadManager {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use actix::{Actor, MailboxError, Message};
     use witnet_data_structures::chain::{
         tapi::all_wips_active, RADAggregate, RADRequest, RADRetrieve, RADTally, RADType,
     };
-    use witnet_rad::reducers::RadonReducers;
+    use witnet_rad::{reducers::RadonReducers, types::integer::RadonInteger};
 
     use crate::utils::test_actix_system;
 
@@ -320,4 +370,141 @@ mod tests {
             assert!(matches!(res.into_inner(), RadonTypes::RadonError(..)));
         });
     }
+
+    #[test]
+    fn aggregation_error_unresolved_policy() {
+        test_actix_system(|| async move {
+            let rad_manager = RadManager::default()
+                .with_aggregation_failure_policy(ScriptFailurePolicy::Unresolved)
+                .start();
+            let rad_request = RADRequest {
+                time_lock: 0,
+                retrieve: vec![RADRetrieve {
+                    kind: RADType::Rng,
+                    url: "".to_string(),
+                    script: vec![128],
+                    body: vec![],
+                    headers: vec![],
+                }],
+                aggregate: RADAggregate {
+                    filters: vec![],
+                    // Use invalid reducer to simulate error in aggregation function
+                    reducer: u32::MAX,
+                },
+                tally: RADTally {
+                    filters: vec![],
+                    reducer: RadonReducers::HashConcatenate as u32,
+                },
+            };
+            let active_wips = all_wips_active();
+            let res = rad_manager
+                .send(ResolveRA {
+                    rad_request,
+                    timeout: None,
+                    active_wips,
+                })
+                .await
+                .unwrap();
+
+            // Nothing will be committed
+            assert!(res.is_err(), "expected `Err`, got `{:?}`", res);
+        });
+    }
+
+    #[test]
+    fn tally_error_ignores_aggregation_failure_policy() {
+        // Tally results are part of consensus, so failures always resolve as a `RadonError`
+        test_actix_system(|| async move {
+            for policy in [
+                ScriptFailurePolicy::CommitError,
+                ScriptFailurePolicy::Unresolved,
+            ] {
+                let rad_manager = RadManager::default()
+                    .with_aggregation_failure_policy(policy)
+                    .start();
+                let reports = vec![
+                    RadonReport::from_result(
+                        Ok(RadonTypes::from(RadonInteger::from(1))),
+                        &ReportContext::default(),
+                    );
+                    3
+                ];
+                let res = rad_manager
+                    .send(RunTally {
+                        script: RADTally {
+                            filters: vec![],
+                            // Use invalid reducer to simulate error in tally function
+                            reducer: u32::MAX,
+                        },
+                        reports,
+                        min_consensus_ratio: 0.51,
+                        commits_count: 3,
+                        active_wips: all_wips_active(),
+                    })
+                    .await
+                    .unwrap();
+
+                assert!(matches!(res.into_inner(), RadonTypes::RadonError(..)));
+            }
+        });
+    }
+
+    #[test]
+    fn retrievals_concurrency_limit_is_global() {
+        // Two data requests being resolved at the same time must share the same limit on
+        // concurrent retrievals
+        test_actix_system(|| async move {
+            let max_concurrent_retrievals = 2;
+            let rad_manager =
+                RadManager::default().with_max_concurrent_retrievals(max_concurrent_retrievals);
+            let in_flight = Arc::new(AtomicUsize::new(0));
+            let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+            let request = |num_retrievals: usize| {
+                let retrievals = (0..num_retrievals).map(|_| {
+                    let in_flight = in_flight.clone();
+                    let max_in_flight = max_in_flight.clone();
+                    with_retrieval_permit(rad_manager.retrievals_semaphore.clone(), async move {
+                        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                    })
+                });
+
+                futures::future::join_all(retrievals)
+            };
+
+            futures::future::join(request(4), request(4)).await;
+
+            assert_eq!(
+                max_in_flight.load(Ordering::SeqCst),
+                usize::from(max_concurrent_retrievals)
+            );
+            assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+        });
+    }
+
+    #[test]
+    fn retrieval_timeout_includes_queue_wait() {
+        // A retrieval that waits for a permit for longer than the timeout fails, even if it would
+        // complete immediately once executed
+        test_actix_system(|| async move {
+            let rad_manager = RadManager::default().with_max_concurrent_retrievals(1);
+            let semaphore = rad_manager.retrievals_semaphore;
+
+            let slow_retrieval =
+                run_retrieval_with_timeout(semaphore.clone(), Duration::from_millis(500), async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    Ok(())
+                });
+            let queued_retrieval =
+                run_retrieval_with_timeout(semaphore, Duration::from_millis(20), async { Ok(()) });
+
+            let (slow, queued) = futures::future::join(slow_retrieval, queued_retrieval).await;
+
+            assert_eq!(slow, Ok(()));
+            assert_eq!(queued, Err(RadError::RetrieveTimeout));
+        });
+    }
 }
@@ -6,7 +6,12 @@
 //! [Data Requests]: https://docs.witnet.io/protocol/data-requests/overview/
 //! [RAD Engine]: https://docs.witnet.io/protocol/data-requests/overview/#the-rad-engine
 
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
 use crate::utils::stop_system_if_panicking;
+use witnet_config::config::ScriptFailurePolicy;
 use witnet_data_structures::witnessing::WitnessingConfig;
 
 mod actor;
@@ -17,6 +22,11 @@ mod handlers;
 pub struct RadManager {
     /// Contains configuration for witnessing, namely about transports and the paranoid threshold.
     pub witnessing: WitnessingConfig<witnet_rad::Uri>,
+    /// Limits the number of retrievals executed concurrently across all the data requests being
+    /// resolved. `None` means unlimited.
+    pub retrievals_semaphore: Option<Arc<Semaphore>>,
+    /// What to do when the aggregation script of a data request fails.
+    pub aggregation_failure_policy: ScriptFailurePolicy,
 }
 
 impl RadManager {
@@ -30,7 +40,32 @@ impl RadManager {
 
     /// Construct a `RadManager` from existing witnessing configuration.
     pub fn from_config(config: WitnessingConfig<witnet_rad::Uri>) -> Self {
-        Self { witnessing: config }
+        Self {
+            witnessing: config,
+            retrievals_semaphore: None,
+            aggregation_failure_policy: ScriptFailurePolicy::default(),
+        }
+    }
+
+    /// Limit the number of retrievals that can be executed concurrently across all the data
+    /// requests being resolved. A limit of 0 means unlimited.
+    pub fn with_max_concurrent_retrievals(mut self, max_concurrent_retrievals: u16) -> Self {
+        self.retrievals_semaphore = if max_concurrent_retrievals == 0 {
+            None
+        } else {
+            Some(Arc::new(Semaphore::new(usize::from(
+                max_concurrent_retrievals,
+            ))))
+        };
+
+        self
+    }
+
+    /// Set what to do when the aggregation script of a data request fails.
+    pub fn with_aggregation_failure_policy(mut self, policy: ScriptFailurePolicy) -> Self {
+        self.aggregation_failure_policy = policy;
+
+        self
     }
 }
 
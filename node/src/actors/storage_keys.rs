@@ -15,3 +15,15 @@ pub fn chain_state_key(magic: u16) -> String {
 pub fn peers_key(magic: u16) -> String {
     format!("peers-{}-key", magic)
 }
+
+/// Function to create a synchronization target key for the storage
+#[inline]
+pub fn sync_target_key(magic: u16) -> String {
+    format!("sync-target-{}-key", magic)
+}
+
+/// Function to create a transactions pool key for the storage
+#[inline]
+pub fn transactions_pool_key(magic: u16) -> String {
+    format!("mempool-{}-key", magic)
+}
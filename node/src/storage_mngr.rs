@@ -152,6 +152,53 @@ where
     futures::future::Either::Right(fut)
 }
 
+/// Get value associated to key, retrying the read up to `attempts` times if the storage fails.
+/// A missing key is not an error, so it is returned right away without retrying.
+pub async fn get_with_retries<K, T>(
+    key: &K,
+    attempts: u32,
+    backoff: Duration,
+) -> Result<Option<T>, failure::Error>
+where
+    K: serde::Serialize,
+    T: serde::de::DeserializeOwned + 'static,
+{
+    retry_with_backoff(attempts, backoff, || get(key)).await
+}
+
+/// Run `read` until it succeeds or it has failed `attempts` times, waiting `backoff` after the
+/// first failure and doubling the wait after every further failure. Returns the last error if
+/// all the attempts fail.
+pub async fn retry_with_backoff<F, Fut, T>(
+    attempts: u32,
+    mut backoff: Duration,
+    mut read: F,
+) -> Result<T, failure::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, failure::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match read().await {
+            Ok(x) => return Ok(x),
+            Err(e) if attempt < attempts => {
+                log::warn!(
+                    "Storage read failed (attempt {}/{}), retrying in {:?}: {}",
+                    attempt,
+                    attempts,
+                    backoff,
+                    e
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Get an atomic reference to the storage backend
 pub fn get_backend(
 ) -> impl Future<Output = Result<Arc<dyn NodeStorage + Send + Sync>, failure::Error>> {
@@ -446,3 +493,53 @@ where
         Box::pin(self.storage.send(msg).map(FlattenResult::flatten_result))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::utils::test_actix_system;
+
+    #[test]
+    fn retry_with_backoff_recovers_from_transient_errors() {
+        test_actix_system(|| async {
+            // Storage that fails once and then returns the value
+            let calls = Cell::new(0);
+            let res = retry_with_backoff(3, Duration::from_millis(1), || {
+                calls.set(calls.get() + 1);
+                let res = if calls.get() == 1 {
+                    Err(failure::format_err!("storage stalled"))
+                } else {
+                    Ok(Some(5))
+                };
+                future::ready(res)
+            })
+            .await;
+            assert_eq!(res.unwrap(), Some(5));
+            assert_eq!(calls.get(), 2);
+
+            // A missing key is not retried
+            let calls = Cell::new(0);
+            let res: Result<Option<u32>, _> =
+                retry_with_backoff(3, Duration::from_millis(1), || {
+                    calls.set(calls.get() + 1);
+                    future::ready(Ok(None))
+                })
+                .await;
+            assert_eq!(res.unwrap(), None);
+            assert_eq!(calls.get(), 1);
+
+            // Storage that never recovers: the last error is returned
+            let calls = Cell::new(0);
+            let res: Result<Option<u32>, _> =
+                retry_with_backoff(3, Duration::from_millis(1), || {
+                    calls.set(calls.get() + 1);
+                    future::ready(Err(failure::format_err!("storage down")))
+                })
+                .await;
+            assert_eq!(res.unwrap_err().to_string(), "storage down");
+            assert_eq!(calls.get(), 3);
+        });
+    }
+}
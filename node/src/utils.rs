@@ -15,6 +15,16 @@ use std::{
 
 /// Given a list of elements, return the most common one. In case of tie, return `None`.
 pub fn mode_consensus<I, V>(pb: I, threshold: usize) -> Option<V>
+where
+    I: Iterator<Item = V>,
+    V: Eq + Hash,
+{
+    mode_consensus_with_ratio(pb, threshold).map(|(k, _ratio)| k)
+}
+
+/// Same as `mode_consensus`, but also return the fraction of the elements that are equal to the
+/// most common one, which is useful to know how close a consensus was to the threshold.
+pub fn mode_consensus_with_ratio<I, V>(pb: I, threshold: usize) -> Option<(V, f64)>
 where
     I: Iterator<Item = V>,
     V: Eq + Hash,
@@ -35,7 +45,9 @@ where
         None
     } else {
         // Otherwise, the first element is the most common
-        bpv.into_iter().map(|(k, _count)| k).next()
+        bpv.into_iter()
+            .map(|(k, count)| (k, count as f64 / len_pb as f64))
+            .next()
     }
 }
 
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, time::Duration};
 use witnet_node::actors::epoch_manager::{EpochManager, EpochManagerError};
 
 #[test]
@@ -46,3 +46,85 @@ fn epoch_unknown() {
         Err(EpochManagerError::UnknownEpochConstants)
     );
 }
+
+#[test]
+fn clock_drift_warning_threshold() {
+    let mut em = EpochManager::default();
+    em.set_max_clock_drift(Duration::from_secs(5));
+    let ntp = (1_000_000, 0);
+
+    // Within the threshold, either ahead or behind
+    assert_eq!(em.check_clock_drift((1_000_003, 0), ntp), None);
+    assert_eq!(em.check_clock_drift((999_995, 0), ntp), None);
+
+    // Past the threshold, either ahead or behind
+    assert_eq!(
+        em.check_clock_drift((1_000_005, 500_000_000), ntp),
+        Some(Duration::from_millis(5_500))
+    );
+    assert_eq!(
+        em.check_clock_drift((999_990, 0), ntp),
+        Some(Duration::from_secs(10))
+    );
+
+    // A zero threshold disables the check
+    em.set_max_clock_drift(Duration::from_secs(0));
+    assert_eq!(em.check_clock_drift((999_990, 0), ntp), None);
+}
+
+#[test]
+fn epoch_range_is_contiguous() {
+    let zero = 1000;
+    let period = 90;
+    let mut em = EpochManager::default();
+    em.set_checkpoint_zero_and_period(zero, period);
+
+    assert_eq!(em.epoch_range(0), Ok((1000, 1090)));
+    for epoch in 0..10 {
+        let (start, end) = em.epoch_range(epoch).unwrap();
+        let (next_start, _) = em.epoch_range(epoch + 1).unwrap();
+        assert_eq!(start, em.epoch_timestamp(epoch).unwrap());
+        assert_eq!(end - start, i64::from(period));
+        assert_eq!(end, next_start);
+        // The last timestamp of the window belongs to this epoch, the end belongs to the next
+        assert_eq!(em.epoch_at(end - 1), Ok(epoch));
+        assert_eq!(em.epoch_at(end), Ok(epoch + 1));
+    }
+}
+
+#[test]
+fn epoch_range_overflow() {
+    let period = 90;
+    let zero = i64::MAX - 2 * period;
+    let mut em = EpochManager::default();
+    em.set_checkpoint_zero_and_period(zero, u16::try_from(period).unwrap());
+
+    assert_eq!(em.epoch_range(0), Ok((zero, zero + period)));
+    assert_eq!(em.epoch_range(1), Ok((zero + period, zero + 2 * period)));
+    // The end of epoch 2 would be past i64::MAX
+    assert_eq!(em.epoch_range(2), Err(EpochManagerError::Overflow));
+    assert_eq!(em.epoch_range(u32::MAX), Err(EpochManagerError::Overflow));
+}
+
+#[test]
+fn seconds_to_next_epoch() {
+    let zero = 1000;
+    let period = 90;
+    let mut em = EpochManager::default();
+    em.set_checkpoint_zero_and_period(zero, period);
+
+    // Mid-epoch: epoch 0 ends at 1090
+    assert_eq!(em.seconds_to_next_epoch_at(1045), Ok(45));
+    // Last second of epoch 0
+    assert_eq!(em.seconds_to_next_epoch_at(1089), Ok(1));
+    // At the boundary the whole of epoch 1 remains
+    assert_eq!(em.seconds_to_next_epoch_at(1090), Ok(90));
+    assert_eq!(
+        em.seconds_to_next_epoch_at(999),
+        Err(EpochManagerError::CheckpointZeroInTheFuture(zero))
+    );
+    assert_eq!(
+        EpochManager::default().seconds_to_next_epoch_at(1045),
+        Err(EpochManagerError::UnknownEpochConstants)
+    );
+}
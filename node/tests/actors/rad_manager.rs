@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use witnet_config::config::Witnessing;
 use witnet_node::actors::rad_manager::RadManager;
 use witnet_validations::witnessing::validate_witnessing_config;
@@ -11,6 +13,10 @@ fn from_config_test_success_helper(
         allow_unproxied,
         paranoid_percentage: 51,
         proxies,
+        user_agent: String::from("witnet-rad"),
+        max_response_size: 1024 * 1024,
+        retrieval_attempts: 1,
+        retrieval_retry_delay: Duration::from_millis(500),
     }
     .into_config();
     let config = validate_witnessing_config::<String, witnet_rad::Uri>(&config).unwrap();
@@ -35,6 +41,10 @@ fn from_config_test_error_helper(
             allow_unproxied,
             paranoid_percentage: 51,
             proxies,
+            user_agent: String::from("witnet-rad"),
+            max_response_size: 1024 * 1024,
+            retrieval_attempts: 1,
+            retrieval_retry_delay: Duration::from_millis(500),
         }
         .into_config();
         let config = validate_witnessing_config::<String, witnet_rad::Uri>(&config).unwrap();
@@ -1,4 +1,4 @@
-use witnet_node::utils::mode_consensus;
+use witnet_node::utils::{mode_consensus, mode_consensus_with_ratio};
 
 #[test]
 fn test_mode_consensus() {
@@ -85,3 +85,28 @@ fn test_mode_consensus() {
     let c = mode_consensus(v.iter(), 60);
     assert_eq!(c, None);
 }
+
+#[test]
+fn test_mode_consensus_with_ratio() {
+    // Clear majority: 3 out of 5 elements agree
+    let v = [1, 2, 3, 3, 3];
+    let c = mode_consensus_with_ratio(v.iter(), 51);
+    assert_eq!(c, Some((&3, 0.6)));
+
+    // Bare threshold: 2 out of 3 elements agree, which is just above 66%
+    let v = [1, 1, 2];
+    let c = mode_consensus_with_ratio(v.iter(), 66);
+    assert_eq!(c, Some((&1, 2.0 / 3.0)));
+    let c = mode_consensus_with_ratio(v.iter(), 67);
+    assert_eq!(c, None);
+
+    // Tie: there is no consensus, so there is no ratio either
+    let v = [2, 2, 2, 3, 3, 3];
+    let c = mode_consensus_with_ratio(v.iter(), 51);
+    assert_eq!(c, None);
+
+    // A single element always wins
+    let v = [3, 3, 3];
+    let c = mode_consensus_with_ratio(v.iter(), 51);
+    assert_eq!(c, Some((&3, 1.0)));
+}
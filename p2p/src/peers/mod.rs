@@ -17,7 +17,7 @@ use witnet_crypto::hash::calculate_sha256;
 use witnet_util::timestamp::get_timestamp;
 
 /// Peer information being used while listing available Witnet peers
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PeerInfo {
     /// The socket address for a potential peer
     pub address: SocketAddr,
@@ -32,6 +32,11 @@ pub struct Peers {
     pub bootstrapped: bool,
     /// Bucket for "iced" addresses (will not be tried in a while)
     pub ice_bucket: HashMap<SocketAddr, i64>,
+    /// Maximum number of bucket slots that addresses sharing the same IP (but having different
+    /// ports) should take before a warning is logged. Zero disables the warning.
+    #[serde(skip)]
+    #[serde(default = "max_slots_per_host_default")]
+    pub max_slots_per_host: usize,
     /// Period in seconds for a potential peer address to be kept "iced", i.e. will not be tried
     /// again before that amount of time.
     #[serde(skip)]
@@ -39,6 +44,11 @@ pub struct Peers {
     pub ice_period: Duration,
     /// Bucket for new addresses
     pub new_bucket: HashMap<u16, PeerInfo>,
+    /// Maximum number of addresses that can be kept in the new addresses bucket. Once reached,
+    /// the oldest addresses are evicted to make room for new ones. Zero means unlimited.
+    #[serde(skip)]
+    #[serde(default = "new_bucket_capacity_default")]
+    pub new_bucket_capacity: usize,
     /// Server SocketAddress
     pub server_address: SocketAddr,
     /// Nonce value
@@ -52,8 +62,10 @@ impl Default for Peers {
         Peers {
             bootstrapped: false,
             ice_bucket: Default::default(),
+            max_slots_per_host: max_slots_per_host_default(),
             ice_period: safe_ice_period_default(),
             new_bucket: Default::default(),
+            new_bucket_capacity: new_bucket_capacity_default(),
             server_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
             sk: thread_rng().gen(),
             tried_bucket: Default::default(),
@@ -66,6 +78,8 @@ impl Peers {
     pub fn from_config(config: &Config) -> Self {
         Peers {
             ice_period: config.connections.bucketing_ice_period,
+            new_bucket_capacity: config.connections.bucketing_new_capacity,
+            max_slots_per_host: config.connections.bucketing_max_slots_per_host,
             server_address: config.connections.server_addr,
             ..Default::default()
         }
@@ -186,58 +200,99 @@ impl Peers {
     /// Add multiple peer addresses and save timestamp in the new addresses bucket
     /// If an address did already exist, it gets overwritten
     /// If an address is in the ice bucket, it gets ignored
-    /// Returns all the overwritten addresses
+    /// If the bucket is full, the oldest addresses get evicted
+    /// Returns all the overwritten and evicted addresses
     pub fn add_to_new(
         &mut self,
         addrs: Vec<SocketAddr>,
         src_address: Option<SocketAddr>,
     ) -> Result<Vec<SocketAddr>, failure::Error> {
-        // Insert address
-        // Note: if the peer address exists, the peer info will be overwritten
-        let result = addrs
-            .into_iter()
-            .filter_map(|address| {
-                // Filter out unspecified addresses (aka 0.0.0.0), and the server address
-                if !address.ip().is_unspecified()
-                    && !self.is_server_address(&address)
-                    // Ignore "iced" addresses silently
-                    && !self.ice_bucket_contains(&address)
-                {
-                    let index = self.tried_bucket_index(&address);
-                    let elem = self.tried_bucket.get(&index);
-
-                    // If the index points to the same address that it is already
-                    // in tried, we don't include in new bucket
-                    if elem.is_none() || (elem.unwrap().address != address) {
-                        // If the source address that sent us this peer addresses is None, use the same address
-                        // that we want to add. This will make all the peer addresses that were added using manual methods
-                        // go to the same bucket that if it was announced by that address.
-                        let src_address = src_address.unwrap_or(address);
-                        let index = self.new_bucket_index(&address, &src_address);
-
-                        self.new_bucket
-                            .insert(
-                                index,
-                                PeerInfo {
-                                    address,
-                                    timestamp: get_timestamp(), //msg.timestamp,
-                                },
-                            )
-                            .map(|v| v.address)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
+        self.add_to_new_pure(addrs, src_address, get_timestamp())
+    }
+
+    /// Add multiple peer addresses to the new addresses bucket using the provided timestamp as
+    /// the time at which the addresses were added.
+    /// Returns all the overwritten and evicted addresses
+    pub fn add_to_new_pure(
+        &mut self,
+        addrs: Vec<SocketAddr>,
+        src_address: Option<SocketAddr>,
+        current_timestamp: i64,
+    ) -> Result<Vec<SocketAddr>, failure::Error> {
+        let mut result = vec![];
+
+        for address in addrs {
+            // Filter out unspecified addresses (aka 0.0.0.0), and the server address
+            if address.ip().is_unspecified()
+                || self.is_server_address(&address)
+                // Ignore "iced" addresses silently
+                || self.ice_bucket_contains_pure(&address, current_timestamp)
+            {
+                continue;
+            }
+
+            // If the index points to the same address that it is already
+            // in tried, we don't include in new bucket
+            let index = self.tried_bucket_index(&address);
+            if self.tried_bucket_get_address(index) == Some(address) {
+                continue;
+            }
+
+            // If the source address that sent us this peer addresses is None, use the same address
+            // that we want to add. This will make all the peer addresses that were added using manual methods
+            // go to the same bucket that if it was announced by that address.
+            let src_address = src_address.unwrap_or(address);
+            let index = self.new_bucket_index(&address, &src_address);
+
+            // Overwriting an existing slot does not increase the size of the bucket, so only make
+            // room when the slot is free
+            if !self.new_bucket_contains(index) {
+                result.extend(self.evict_oldest_from_new());
+            }
+
+            // Note: if the peer address exists, the peer info will be overwritten
+            if let Some(overwritten) = self.new_bucket.insert(
+                index,
+                PeerInfo {
+                    address,
+                    timestamp: current_timestamp,
+                },
+            ) {
+                result.push(overwritten.address);
+            }
+        }
 
         log::trace!("Added new peers: \n{}", self);
 
         Ok(result)
     }
 
+    /// Remove the oldest addresses from the new addresses bucket until there is room for one more
+    /// address, according to `new_bucket_capacity`
+    /// Returns the evicted addresses
+    fn evict_oldest_from_new(&mut self) -> Vec<SocketAddr> {
+        let mut evicted = vec![];
+        if self.new_bucket_capacity == 0 {
+            return evicted;
+        }
+
+        while self.new_bucket.len() >= self.new_bucket_capacity {
+            // Ties are broken by index so that eviction is deterministic
+            let oldest_index = self
+                .new_bucket
+                .iter()
+                .min_by_key(|(index, info)| (info.timestamp, **index))
+                .map(|(index, _info)| *index);
+
+            match oldest_index.and_then(|index| self.new_bucket.remove(&index)) {
+                Some(info) => evicted.push(info.address),
+                None => break,
+            }
+        }
+
+        evicted
+    }
+
     /// Add multiple peer addresses and save timestamp in the tried addresses bucket
     /// If an address did already exist, it gets overwritten
     /// If an address is in the ice bucket, it gets ignored
@@ -374,6 +429,53 @@ impl Peers {
         Ok(self.new_bucket.values().map(|v| v.address).collect())
     }
 
+    /// Get all the peers from the tried bucket, along with the last time they were tried
+    pub fn get_all_info_from_tried(&self) -> Result<Vec<PeerInfo>, failure::Error> {
+        Ok(self.tried_bucket.values().cloned().collect())
+    }
+
+    /// Get all the peers from the new bucket, along with the last time they were added
+    pub fn get_all_info_from_new(&self) -> Result<Vec<PeerInfo>, failure::Error> {
+        Ok(self.new_bucket.values().cloned().collect())
+    }
+
+    /// Count how many slots of the new and tried buckets are taken by each IP address, no matter
+    /// the port.
+    pub fn count_by_host(&self) -> HashMap<IpAddr, usize> {
+        let mut counts = HashMap::new();
+        for peer in self.new_bucket.values().chain(self.tried_bucket.values()) {
+            *counts.entry(peer.address.ip()).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Find the IP addresses that take more bucket slots than `max_slots_per_host`, logging a
+    /// warning for each one of them, as they weaken the resistance against eclipse attacks.
+    pub fn crowded_hosts(&self) -> Vec<(IpAddr, usize)> {
+        if self.max_slots_per_host == 0 {
+            return vec![];
+        }
+
+        let mut crowded: Vec<_> = self
+            .count_by_host()
+            .into_iter()
+            .filter(|(_, count)| *count > self.max_slots_per_host)
+            .collect();
+        crowded.sort_unstable();
+
+        for (ip, count) in &crowded {
+            log::warn!(
+                "Host {} takes {} slots in the peer buckets (the limit is {})",
+                ip,
+                count,
+                self.max_slots_per_host
+            );
+        }
+
+        crowded
+    }
+
     /// Clear tried addresses bucket
     pub fn clear_tried_bucket(&mut self) {
         self.tried_bucket.clear();
@@ -466,6 +568,16 @@ fn safe_ice_period_default() -> Duration {
     Duration::from_secs(60)
 }
 
+/// Generate a safe default for the `Peers::new_bucket_capacity` field
+fn new_bucket_capacity_default() -> usize {
+    4096
+}
+
+/// Generate a safe default for the `Peers::max_slots_per_host` field
+fn max_slots_per_host_default() -> usize {
+    4
+}
+
 /// Returns the ip and ip split
 pub fn split_socket_addresses(socket_addr: &SocketAddr) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
     match socket_addr {
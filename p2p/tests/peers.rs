@@ -1,4 +1,7 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+};
 
 use witnet_p2p::peers::*;
 
@@ -215,3 +218,125 @@ fn p2p_add_2_peers_in_collision() {
 
     assert_eq!(peers.get_all_from_tried().unwrap().len(), 1);
 }
+
+#[test]
+fn p2p_peers_add_to_new_evicts_oldest() {
+    // Create peers struct with room for only 2 new addresses
+    let mut peers = Peers {
+        new_bucket_capacity: 2,
+        ..Default::default()
+    };
+    let src_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(168, 0, 0, 12)), 8080);
+
+    // Pick 3 addresses that go to different slots of the new bucket
+    let mut used_indexes = HashSet::new();
+    let addresses: Vec<_> = (1..=255)
+        .map(|i| SocketAddr::new(IpAddr::V4(Ipv4Addr::new(i, i, 0, 1)), 8080))
+        .filter(|address| used_indexes.insert(peers.new_bucket_index(address, &src_address)))
+        .take(3)
+        .collect();
+
+    assert_eq!(
+        peers
+            .add_to_new_pure(vec![addresses[0]], Some(src_address), 1)
+            .unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        peers
+            .add_to_new_pure(vec![addresses[1]], Some(src_address), 2)
+            .unwrap(),
+        vec![]
+    );
+    // The bucket is full, so the oldest address is evicted and returned
+    assert_eq!(
+        peers
+            .add_to_new_pure(vec![addresses[2]], Some(src_address), 3)
+            .unwrap(),
+        vec![addresses[0]]
+    );
+
+    let mut remaining = peers.get_all_from_new().unwrap();
+    remaining.sort();
+    let mut expected = vec![addresses[1], addresses[2]];
+    expected.sort();
+    assert_eq!(remaining, expected);
+}
+
+#[test]
+fn p2p_peers_add_to_new_flood_is_bounded() {
+    let capacity = 10;
+    let mut peers = Peers {
+        new_bucket_capacity: capacity,
+        ..Default::default()
+    };
+    let src_address = Some(SocketAddr::new(
+        IpAddr::V4(Ipv4Addr::new(168, 0, 0, 12)),
+        8080,
+    ));
+
+    // Flood the new bucket with 200 addresses, each one newer than the previous one
+    let addresses: Vec<_> = (1..=200)
+        .map(|i| SocketAddr::new(IpAddr::V4(Ipv4Addr::new(i, i, 0, 1)), 8080))
+        .collect();
+    for (timestamp, address) in (1..).zip(&addresses) {
+        peers
+            .add_to_new_pure(vec![*address], src_address, timestamp)
+            .unwrap();
+        assert!(peers.new_bucket.len() <= capacity);
+    }
+
+    // The oldest addresses have been dropped
+    let remaining = peers.get_all_from_new().unwrap();
+    assert!(!remaining.is_empty());
+    for address in &addresses[..100] {
+        assert!(!remaining.contains(address));
+    }
+
+    // Flooding with a single batch is bounded as well
+    let mut peers = Peers {
+        new_bucket_capacity: capacity,
+        ..Default::default()
+    };
+    peers.add_to_new(addresses, src_address).unwrap();
+    assert!(peers.new_bucket.len() <= capacity);
+}
+
+#[test]
+fn p2p_peers_count_by_host() {
+    let mut peers = Peers {
+        max_slots_per_host: 3,
+        ..Default::default()
+    };
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+    // Pick 5 ports of the same host that go to different slots of the tried bucket
+    let mut used_indexes = HashSet::new();
+    let addresses: Vec<_> = (8000..9000)
+        .map(|port| SocketAddr::new(ip, port))
+        .filter(|address| used_indexes.insert(peers.tried_bucket_index(address)))
+        .take(5)
+        .collect();
+    for address in &addresses[..3] {
+        peers.add_to_tried(*address).unwrap();
+    }
+    let other = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)), 8000);
+    peers.add_to_tried(other).unwrap();
+
+    let counts = peers.count_by_host();
+    assert_eq!(counts[&ip], 3);
+    assert_eq!(counts[&other.ip()], 1);
+    // Reaching the limit is fine
+    assert_eq!(peers.crowded_hosts(), vec![]);
+
+    for address in &addresses[3..] {
+        peers.add_to_tried(*address).unwrap();
+    }
+
+    assert_eq!(peers.count_by_host()[&ip], 5);
+    assert_eq!(peers.crowded_hosts(), vec![(ip, 5)]);
+
+    // A limit of zero disables the warning
+    peers.max_slots_per_host = 0;
+    assert_eq!(peers.crowded_hosts(), vec![]);
+}
@@ -59,6 +59,28 @@ pub enum RadError {
         value
     )]
     ArrayFilterWrongSubscript { value: String },
+    /// The items of the input of an ArrayGroupReduce are not `[key, value]` pairs
+    #[fail(
+        display = "ArrayGroupReduce item was not a [RadonString, value] pair (was `{}`)",
+        value
+    )]
+    ArrayGroupWrongItem { value: String },
+    /// The items of the input of an ArrayJoin are not RadonStrings
+    #[fail(display = "ArrayJoin item was not RadonString (was `{}`)", value)]
+    ArrayJoinWrongItem { value: String },
+    /// A StringSplit was called with an empty separator
+    #[fail(display = "StringSplit separator cannot be empty")]
+    StringSplitEmptySeparator,
+    /// A call in a RADON script cannot be applied to the output type of the previous call
+    #[fail(
+        display = "Type error in call #{} of RADON script: operator {} cannot be applied to {}",
+        index, operator, input_type
+    )]
+    ScriptTypeMismatch {
+        index: usize,
+        operator: String,
+        input_type: String,
+    },
     /// Failed to parse a Value from a buffer
     #[fail(
         display = "Failed to parse a Value from a buffer. Error message: {}",
@@ -169,6 +191,12 @@ pub enum RadError {
         message
     )]
     HttpOther { message: String },
+    /// The body of the HTTP response exceeded the maximum allowed size
+    #[fail(
+        display = "HTTP response body is larger than the maximum allowed size of {} bytes",
+        limit
+    )]
+    ResponseTooLarge { limit: usize },
     /// Failed to convert string to float
     #[fail(
         display = "Failed to convert string to float with error message: {}",
@@ -187,6 +215,12 @@ pub enum RadError {
         message
     )]
     ParseBool { message: String },
+    /// Failed to convert a hexadecimal string to bytes
+    #[fail(
+        display = "Failed to convert hex string to bytes with error message: {}",
+        message
+    )]
+    ParseHex { message: String },
     /// Overflow error
     #[fail(display = "Overflow error")]
     Overflow,
@@ -657,6 +691,14 @@ impl From<std::num::ParseFloatError> for RadError {
     }
 }
 
+impl From<hex::FromHexError> for RadError {
+    fn from(err: hex::FromHexError) -> Self {
+        RadError::ParseHex {
+            message: err.to_string(),
+        }
+    }
+}
+
 impl From<std::num::ParseIntError> for RadError {
     fn from(err: std::num::ParseIntError) -> Self {
         RadError::ParseInt {
@@ -2,6 +2,8 @@
 
 extern crate witnet_data_structures;
 
+use std::{collections::BTreeMap, time::Duration};
+
 use futures::{executor::block_on, future::join_all, AsyncReadExt};
 use serde::Serialize;
 pub use serde_cbor::{to_vec as cbor_to_vec, Value as CborValue};
@@ -25,8 +27,7 @@ use crate::{
         create_radon_script_from_filters_and_reducer, execute_radon_script, unpack_radon_script,
         RadonScriptExecutionSettings,
     },
-    types::{array::RadonArray, bytes::RadonBytes, string::RadonString, RadonTypes},
-    user_agents::UserAgent,
+    types::{array::RadonArray, bytes::RadonBytes, map::RadonMap, string::RadonString, RadonTypes},
 };
 use core::convert::From;
 use witnet_net::client::http::{WitnetHttpBody, WitnetHttpRequest};
@@ -39,11 +40,18 @@ pub mod operators;
 pub mod reducers;
 pub mod script;
 pub mod types;
+#[deprecated(
+    note = "HTTP retrievals send the `witnessing.user_agent` setting, which defaults to `DEFAULT_USER_AGENT`, instead of a random user agent"
+)]
 pub mod user_agents;
 
 pub type Result<T> = std::result::Result<T, RadError>;
 
 /// The return type of any method executing the entire life cycle of a data request.
+///
+/// Every stage has its own report, so when a stage fails the results of the previous stages are
+/// still available. This allows to find out which source produced the data that made the data
+/// request fail.
 #[derive(Debug, Serialize)]
 pub struct RADRequestExecutionReport {
     /// Report about aggregation of data sources.
@@ -173,6 +181,53 @@ fn string_response_with_data_report(
     execute_radon_script(input, &radon_script, context, settings)
 }
 
+/// Handle HTTP-HEAD response with data, and return a `RadonReport`.
+///
+/// The response is a JSON object with the response headers, which is passed to the retrieval
+/// script as a `RadonMap` of `RadonString`.
+fn headers_response_with_data_report(
+    retrieve: &RADRetrieve,
+    response: &str,
+    context: &mut ReportContext<RadonTypes>,
+    settings: RadonScriptExecutionSettings,
+) -> Result<RadonReport<RadonTypes>> {
+    let headers: BTreeMap<String, String> =
+        serde_json::from_str(response).map_err(|err| RadError::JsonParse {
+            description: err.to_string(),
+        })?;
+    let headers: BTreeMap<String, RadonTypes> = headers
+        .into_iter()
+        .map(|(name, value)| (name, RadonTypes::from(RadonString::from(value))))
+        .collect();
+    let input = RadonTypes::from(RadonMap::from(headers));
+    let radon_script = unpack_radon_script(&retrieve.script)?;
+
+    execute_radon_script(input, &radon_script, context, settings)
+}
+
+/// Serialize the headers of an HTTP response as a JSON object, which is the format expected by
+/// `headers_response_with_data_report`.
+///
+/// Header names are lowercase, and the values of repeated headers are joined with ", ". Values
+/// that are not valid UTF-8 are ignored.
+fn response_headers_json(headers: &http::HeaderMap) -> Result<String> {
+    let mut map: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in headers {
+        if let Ok(value) = value.to_str() {
+            map.entry(name.as_str().to_string())
+                .and_modify(|values| {
+                    values.push_str(", ");
+                    values.push_str(value);
+                })
+                .or_insert_with(|| value.to_string());
+        }
+    }
+
+    serde_json::to_string(&map).map_err(|err| RadError::HttpOther {
+        message: err.to_string(),
+    })
+}
+
 /// Handle Rng response with data report
 fn rng_response_with_data_report(
     response: &str,
@@ -197,6 +252,9 @@ pub fn run_retrieval_with_data_report(
         RADType::HttpPost => {
             string_response_with_data_report(retrieve, response, context, settings)
         }
+        RADType::HttpHead => {
+            headers_response_with_data_report(retrieve, response, context, settings)
+        }
         _ => Err(RadError::UnknownRetrieval),
     }
 }
@@ -214,12 +272,13 @@ pub fn run_retrieval_with_data(
         .map(RadonReport::into_inner)
 }
 
-/// Handle generic HTTP (GET/POST) response
+/// Handle generic HTTP (GET/POST/HEAD) response
 async fn http_response(
     retrieve: &RADRetrieve,
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
     client: Option<WitnetHttpClient>,
+    witnessing: &WitnessingConfig<witnet_net::Uri>,
 ) -> Result<RadonReport<RadonTypes>> {
     // Validate URL to make sure that we handle malformed URLs nicely before they hit any library
     if let Err(err) = url::Url::parse(&retrieve.url) {
@@ -245,50 +304,78 @@ async fn http_response(
         }
     };
 
-    let request = WitnetHttpRequest::build(|builder| {
-        // Populate the builder and generate the body for different types of retrievals
-        let (builder, body) = match retrieve.kind {
-            RADType::HttpGet => (
-                builder.method("GET").uri(&retrieve.url),
-                WitnetHttpBody::empty(),
-            ),
-            RADType::HttpPost => {
-                // Using `Vec<u8>` as the body sets the content type header to `application/octet-stream`
-                (
-                    builder.method("POST").uri(&retrieve.url),
-                    WitnetHttpBody::from(retrieve.body.clone()),
-                )
+    // The request is consumed when sent, so it needs to be built again for every attempt
+    let build_request = || {
+        WitnetHttpRequest::build(|builder| {
+            // Populate the builder and generate the body for different types of retrievals
+            let (builder, body) = match retrieve.kind {
+                RADType::HttpGet => (
+                    builder.method("GET").uri(&retrieve.url),
+                    WitnetHttpBody::empty(),
+                ),
+                RADType::HttpPost => {
+                    // Using `Vec<u8>` as the body sets the content type header to `application/octet-stream`
+                    (
+                        builder.method("POST").uri(&retrieve.url),
+                        WitnetHttpBody::from(retrieve.body.clone()),
+                    )
+                }
+                RADType::HttpHead => (
+                    builder.method("HEAD").uri(&retrieve.url),
+                    WitnetHttpBody::empty(),
+                ),
+                _ => panic!(
+                    "Called http_response with invalid retrieval kind {:?}",
+                    retrieve.kind
+                ),
+            };
+
+            // Add the configured user agent, which is the same for every node by default
+            let mut builder = builder.header("User-Agent", &witnessing.user_agent);
+
+            // Add extra_headers from retrieve.headers
+            for (name, value) in &retrieve.headers {
+                // Handle invalid header names and values with a specific and friendly error message
+                validate_header(name, value)?;
+
+                builder = builder.header(name, value);
             }
-            _ => panic!(
-                "Called http_response with invalid retrieval kind {:?}",
-                retrieve.kind
-            ),
-        };
-
-        // Add random user agent
-        let mut builder = builder.header("User-Agent", UserAgent::random());
-
-        // Add extra_headers from retrieve.headers
-        for (name, value) in &retrieve.headers {
-            // Handle invalid header names and values with a specific and friendly error message
-            validate_header(name, value)?;
 
-            builder = builder.header(name, value);
-        }
-
-        // Finally attach the body to complete building the HTTP request
-        builder.body(body).map_err(|e| RadError::HttpOther {
-            message: e.to_string(),
+            // Finally attach the body to complete building the HTTP request
+            builder.body(body).map_err(|e| RadError::HttpOther {
+                message: e.to_string(),
+            })
         })
-    })?;
+    };
 
-    let response = client
-        .send(request)
-        .await
-        .map_err(|x| RadError::HttpOther {
-            message: x.to_string(),
-        })?
-        .inner();
+    // Send the request, retrying it if it fails because of a network error. Responses with HTTP
+    // error status codes are never retried.
+    let attempts = witnessing.retrieval_attempts.max(1);
+    let mut retry_delay = witnessing.retrieval_retry_delay;
+    let mut attempt = 1;
+    let response = loop {
+        match client.send(build_request()?).await {
+            Ok(response) => break response.inner(),
+            Err(err) if attempt < attempts => {
+                log::debug!(
+                    "Attempt {}/{} to retrieve source {} failed, retrying in {:?}: {}",
+                    attempt,
+                    attempts,
+                    retrieve.url,
+                    retry_delay,
+                    err
+                );
+                sleep(retry_delay).await;
+                retry_delay = retry_delay.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(err) => {
+                return Err(RadError::HttpOther {
+                    message: err.to_string(),
+                })
+            }
+        }
+    };
 
     if !response.status().is_success() {
         return Err(RadError::HttpStatus {
@@ -296,18 +383,53 @@ async fn http_response(
         });
     }
 
-    // If at some point we want to support the retrieval of non-UTF8 data (e.g. raw bytes), this is
-    // where we need to decide how to read the response body
-    let (_parts, mut body) = response.into_parts();
-    let mut response_string = String::default();
-    body.read_to_string(&mut response_string)
+    // The retrieval script of HEAD requests gets the response headers, as there is no body to read
+    if retrieve.kind == RADType::HttpHead {
+        let response_string = response_headers_json(response.headers())?;
+
+        return log_source_result(
+            retrieve,
+            run_retrieval_with_data_report(retrieve, &response_string, context, settings),
+        );
+    }
+
+    // Read at most one byte more than the size limit, so that oversized responses are detected
+    // without having to hold the full body in memory
+    let (_parts, body) = response.into_parts();
+    let max_response_size = witnessing.max_response_size;
+    let read_limit = u64::try_from(max_response_size)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    let mut response_bytes = Vec::new();
+    body.take(read_limit)
+        .read_to_end(&mut response_bytes)
         .await
         .map_err(|x| RadError::HttpOther {
             message: x.to_string(),
         })?;
+    if response_bytes.len() > max_response_size {
+        return Err(RadError::ResponseTooLarge {
+            limit: max_response_size,
+        });
+    }
 
-    let result = run_retrieval_with_data_report(retrieve, &response_string, context, settings);
+    // If at some point we want to support the retrieval of non-UTF8 data (e.g. raw bytes), this is
+    // where we need to decide how to read the response body
+    let response_string = String::from_utf8(response_bytes).map_err(|x| RadError::HttpOther {
+        message: x.to_string(),
+    })?;
 
+    log_source_result(
+        retrieve,
+        run_retrieval_with_data_report(retrieve, &response_string, context, settings),
+    )
+}
+
+/// Log the result of running the retrieval script on the response of an HTTP source
+fn log_source_result(
+    retrieve: &RADRetrieve,
+    result: Result<RadonReport<RadonTypes>>,
+) -> Result<RadonReport<RadonTypes>> {
     match &result {
         Ok(report) => {
             log::debug!(
@@ -322,6 +444,17 @@ async fn http_response(
     result
 }
 
+/// Wait for the given duration. Inside a tokio runtime (e.g. in the node) this does not block the
+/// executor. Outside of one (e.g. `try_data_request` in the wallet), retrievals are driven by a
+/// blocking executor, so the current thread is put to sleep instead.
+async fn sleep(duration: Duration) {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::time::sleep(duration).await;
+    } else {
+        std::thread::sleep(duration);
+    }
+}
+
 /// Handle Rng response
 async fn rng_response(
     context: &mut ReportContext<RadonTypes>,
@@ -349,14 +482,16 @@ pub async fn run_retrieval_report(
     settings: RadonScriptExecutionSettings,
     active_wips: ActiveWips,
     client: Option<WitnetHttpClient>,
+    witnessing: &WitnessingConfig<witnet_net::Uri>,
 ) -> Result<RadonReport<RadonTypes>> {
     let context = &mut ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
     context.set_active_wips(active_wips);
 
     match retrieve.kind {
-        RADType::HttpGet => http_response(retrieve, context, settings, client).await,
+        RADType::HttpGet | RADType::HttpPost | RADType::HttpHead => {
+            http_response(retrieve, context, settings, client, witnessing).await
+        }
         RADType::Rng => rng_response(context, settings).await,
-        RADType::HttpPost => http_response(retrieve, context, settings, client).await,
         _ => Err(RadError::UnknownRetrieval),
     }
 }
@@ -369,6 +504,7 @@ pub async fn run_retrieval(retrieve: &RADRetrieve, active_wips: ActiveWips) -> R
         RadonScriptExecutionSettings::disable_all(),
         active_wips,
         None,
+        &WitnessingConfig::default(),
     )
     .await
     .map(RadonReport::into_inner)
@@ -389,7 +525,7 @@ pub async fn run_paranoid_retrieval(
 ) -> Result<RadonReport<RadonTypes>> {
     // We can skip paranoid checks for retrieval types that don't use networking (e.g. RNG)
     if !retrieve.kind.is_http() {
-        return run_retrieval_report(retrieve, settings, active_wips, None).await;
+        return run_retrieval_report(retrieve, settings, active_wips, None, &witnessing).await;
     }
 
     let futures: Result<Vec<_>> = witnessing
@@ -406,7 +542,13 @@ pub async fn run_paranoid_retrieval(
                     message: err.to_string(),
                 })
                 .map(|client| {
-                    run_retrieval_report(retrieve, settings, active_wips.clone(), Some(client))
+                    run_retrieval_report(
+                        retrieve,
+                        settings,
+                        active_wips.clone(),
+                        Some(client),
+                        &witnessing,
+                    )
                 })
         })
         .collect();
@@ -867,6 +1009,332 @@ mod tests {
         }
     }
 
+    /// Spawn a mock HTTP server that accepts one connection for each of the provided responses and
+    /// returns the raw requests that it received. A `None` response means hanging up without
+    /// answering.
+    fn serve(responses: Vec<Option<Vec<u8>>>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/data", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut received = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !received.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    received.extend_from_slice(&buffer[..read]);
+                }
+                if let Some(response) = response {
+                    // The client may hang up before reading the whole response
+                    let _ = stream.write_all(&response);
+                }
+                requests.push(String::from_utf8(received).unwrap());
+            }
+
+            requests
+        });
+
+        (url, server)
+    }
+
+    fn http_response_bytes(status: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+
+        response
+    }
+
+    /// Spawn a mock HTTP server that answers a single request with the provided body
+    fn serve_once(body: Vec<u8>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        serve(vec![Some(http_response_bytes("200 OK", &body))])
+    }
+
+    fn user_agent_header(request: &str) -> Vec<&str> {
+        request
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(name, _)| name.eq_ignore_ascii_case("user-agent"))
+            .map(|(_, value)| value.trim())
+            .collect()
+    }
+
+    #[test]
+    fn test_run_retrieval_default_user_agent() {
+        let (url, server) = serve_once(b"ok".to_vec());
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+
+        let result = block_on(run_retrieval(&retrieve, all_wips_active())).unwrap();
+        assert_eq!(result, RadonTypes::from(RadonString::from("ok")));
+
+        let requests = server.join().unwrap();
+        assert_eq!(user_agent_header(&requests[0]), vec!["witnet-rad"]);
+    }
+
+    #[test]
+    fn test_run_retrieval_http_head() {
+        // The response announces a body, but the responses to HEAD requests never include it
+        let response = b"HTTP/1.1 200 OK\r\n\
+            Last-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\n\
+            X-Custom: a\r\n\
+            X-Custom: b\r\n\
+            Content-Length: 1024\r\n\
+            Connection: close\r\n\r\n";
+        let (url, server) = serve(vec![Some(response.to_vec())]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpHead,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+
+        let result = block_on(run_retrieval(&retrieve, all_wips_active())).unwrap();
+        let expected: BTreeMap<String, RadonTypes> = [
+            ("connection", "close"),
+            ("content-length", "1024"),
+            ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            ("x-custom", "a, b"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), RadonTypes::from(RadonString::from(value))))
+        .collect();
+        assert_eq!(result, RadonTypes::from(RadonMap::from(expected)));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("HEAD /data HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_run_retrieval_with_data_http_head() {
+        let script_r = Value::Array(vec![Value::Array(vec![
+            Value::Integer(RadonOpCodes::MapGetString as i128),
+            Value::Text("last-modified".to_string()),
+        ])]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpHead,
+            url: "https://witnet.io".to_string(),
+            script: serde_cbor::to_vec(&script_r).unwrap(),
+            body: vec![],
+            headers: vec![],
+        };
+        let response =
+            r#"{"content-type":"text/html","last-modified":"Wed, 21 Oct 2015 07:28:00 GMT"}"#;
+
+        let result = run_retrieval_with_data(
+            &retrieve,
+            response,
+            RadonScriptExecutionSettings::disable_all(),
+            current_active_wips(),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            RadonTypes::from(RadonString::from("Wed, 21 Oct 2015 07:28:00 GMT"))
+        );
+
+        // The headers of HTTP-HEAD sources must be a JSON object of strings
+        let err = run_retrieval_with_data(
+            &retrieve,
+            "Wed, 21 Oct 2015 07:28:00 GMT",
+            RadonScriptExecutionSettings::disable_all(),
+            current_active_wips(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, RadError::JsonParse { .. }));
+    }
+
+    #[test]
+    fn test_run_paranoid_retrieval_configured_user_agent() {
+        let (url, server) = serve_once(b"ok".to_vec());
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let witnessing = WitnessingConfig {
+            user_agent: String::from("my-witnet-node"),
+            ..WitnessingConfig::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            RADAggregate::default(),
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+        ))
+        .unwrap();
+        assert_eq!(
+            result.into_inner(),
+            RadonTypes::from(RadonString::from("ok"))
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(user_agent_header(&requests[0]), vec!["my-witnet-node"]);
+    }
+
+    #[test]
+    fn test_run_retrieval_response_too_large() {
+        let (url, server) = serve_once(vec![b'a'; 1024]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let witnessing = WitnessingConfig {
+            max_response_size: 1023,
+            ..WitnessingConfig::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            RADAggregate::default(),
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            RadError::ResponseTooLarge { limit: 1023 }
+        );
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_run_retrieval_response_size_at_limit() {
+        let (url, server) = serve_once(vec![b'a'; 1024]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let witnessing = WitnessingConfig {
+            max_response_size: 1024,
+            ..WitnessingConfig::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            RADAggregate::default(),
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+        ))
+        .unwrap();
+        assert_eq!(
+            result.into_inner(),
+            RadonTypes::from(RadonString::from("a".repeat(1024)))
+        );
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_run_retrieval_retries_network_errors() {
+        let (url, server) = serve(vec![None, None, Some(http_response_bytes("200 OK", b"ok"))]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let witnessing = WitnessingConfig {
+            retrieval_attempts: 3,
+            retrieval_retry_delay: Duration::from_millis(10),
+            ..WitnessingConfig::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            RADAggregate::default(),
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+        ))
+        .unwrap();
+        assert_eq!(
+            result.into_inner(),
+            RadonTypes::from(RadonString::from("ok"))
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[test]
+    fn test_retry_delay_does_not_block_tokio_runtime() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        runtime.block_on(async {
+            // In a single threaded runtime, the spawned task can only run while `sleep` waits
+            tokio::spawn(async move { sender.send(()).unwrap() });
+            sleep(Duration::from_millis(10)).await;
+        });
+        drop(runtime);
+
+        assert_eq!(block_on(receiver), Ok(()));
+    }
+
+    #[test]
+    fn test_run_retrieval_does_not_retry_http_errors() {
+        // If the request was retried, the next attempt would fail to connect to the server
+        let (url, server) = serve(vec![Some(http_response_bytes("404 Not Found", b""))]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let witnessing = WitnessingConfig {
+            retrieval_attempts: 3,
+            retrieval_retry_delay: Duration::from_millis(10),
+            ..WitnessingConfig::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            RADAggregate::default(),
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            RadError::HttpStatus { status_code: 404 }
+        );
+
+        server.join().unwrap();
+    }
+
     #[test]
     fn test_run_consensus_and_aggregation() {
         let f_1 = RadonTypes::Float(RadonFloat::from(1f64));
@@ -1526,6 +1994,60 @@ mod tests {
         }
     }
 
+    /// Test that the report of try_data_request keeps the results of the sources that succeeded
+    /// when other sources and the tally stage fail
+    #[test]
+    fn test_try_data_request_partial_results() {
+        let script_r = Value::Array(vec![Value::Integer(RadonOpCodes::StringAsFloat as i128)]);
+        let packed_script_r = serde_cbor::to_vec(&script_r).unwrap();
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://example.com/".to_string(),
+            script: packed_script_r,
+            body: vec![],
+            headers: vec![],
+        };
+        let request = RADRequest {
+            time_lock: 0,
+            retrieve: vec![retrieve.clone(), retrieve],
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::AverageMean as u32,
+            },
+            tally: RADTally {
+                filters: vec![],
+                // Unknown reducer, the tally stage always fails
+                reducer: 0xFF,
+            },
+        };
+        let report = try_data_request(
+            &request,
+            RadonScriptExecutionSettings::enable_all(),
+            Some(&["84", "not a number"]),
+            None,
+        );
+
+        assert_eq!(report.retrieve.len(), 2);
+        assert_eq!(
+            report.retrieve[0].result,
+            RadonTypes::Float(RadonFloat::from(84))
+        );
+        assert!(matches!(
+            report.retrieve[1].result,
+            RadonTypes::RadonError(_)
+        ));
+        // The partial results of the failing source show where its script failed
+        assert_eq!(
+            report.retrieve[1].partial_results.as_ref().unwrap()[0],
+            RadonTypes::String(RadonString::from("not a number"))
+        );
+        assert_eq!(
+            report.aggregate.result,
+            RadonTypes::Float(RadonFloat::from(84))
+        );
+        assert!(matches!(report.tally.result, RadonTypes::RadonError(_)));
+    }
+
     #[test]
     fn test_try_data_request_http_post_non_ascii_header_key() {
         let script_r = Value::Array(vec![]);
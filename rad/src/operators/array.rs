@@ -1,11 +1,15 @@
 use std::{
     clone::Clone,
+    collections::BTreeMap,
     convert::{TryFrom, TryInto},
     iter,
 };
 
 use serde_cbor::value::{from_value, Value};
-use witnet_data_structures::radon_report::{RadonReport, ReportContext, Stage};
+use witnet_data_structures::{
+    chain::tapi::ActiveWips,
+    radon_report::{RadonReport, ReportContext, Stage},
+};
 
 use crate::{
     error::RadError,
@@ -13,7 +17,10 @@ use crate::{
     operators::{string, RadonOpCodes},
     reducers::{self, RadonReducers},
     script::{execute_radon_script, unpack_subscript, RadonCall, RadonScriptExecutionSettings},
-    types::{array::RadonArray, integer::RadonInteger, string::RadonString, RadonType, RadonTypes},
+    types::{
+        array::RadonArray, integer::RadonInteger, map::RadonMap, string::RadonString, RadonType,
+        RadonTypes,
+    },
 };
 
 pub fn count(input: &RadonArray) -> RadonInteger {
@@ -221,6 +228,12 @@ pub fn filter(
     }
 }
 
+/// Sort the items of an homogeneous array in ascending order, optionally using the output of a
+/// subscript as the sorting key.
+///
+/// Strings and integers can be used as keys. After WIP0028, floats can be used as well: they are
+/// sorted using a total order in which `-0.0` goes before `0.0` and every `NaN` goes after any other
+/// value. The sort is stable, so items with equal keys keep their relative order.
 pub fn sort(
     input: &RadonArray,
     args: &[Value],
@@ -236,6 +249,13 @@ pub fn sort(
         return Err(wrong_args());
     }
 
+    // Sorting floats changes the result of existing scripts, so it needs WIP0028
+    let wip0028 = context
+        .active_wips
+        .as_ref()
+        .map(ActiveWips::wip0028)
+        .unwrap_or(false);
+
     let input_value = input.value();
     let empty_array = [Value::Array(vec![])];
     // Sort can be called with an optional argument.
@@ -287,6 +307,14 @@ pub fn sort(
                 _ => unreachable!(),
             });
         }
+        Some(RadonTypes::Float(_)) if wip0028 => {
+            tuple_array.sort_by(|a, b| match (a.1, b.1) {
+                (RadonTypes::Float(a), RadonTypes::Float(b)) => {
+                    float_total_cmp(a.value(), b.value())
+                }
+                _ => unreachable!(),
+            });
+        }
         _ => {
             return Err(RadError::UnsupportedSortOp {
                 array: input.clone(),
@@ -299,6 +327,95 @@ pub fn sort(
     Ok(RadonArray::from(result).into())
 }
 
+/// Total order for floats used when sorting: `NaN` is greater than any other value regardless of
+/// its sign, and the rest of values are ordered as in `f64::total_cmp`.
+fn float_total_cmp(a: f64, b: f64) -> std::cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => std::cmp::Ordering::Equal,
+        (true, false) => std::cmp::Ordering::Greater,
+        (false, true) => std::cmp::Ordering::Less,
+        (false, false) => a.total_cmp(&b),
+    }
+}
+
+/// Group the `[key, value]` pairs contained in the input array by their key, and reduce the values
+/// of each group using the given reducer. The result is a `RadonMap` from each key to the reduced
+/// value of its group.
+pub fn group_reduce(
+    input: &RadonArray,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "GroupReduce".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let arg = args[0].to_owned();
+    let reducer_integer = from_value::<u8>(arg).map_err(|_| wrong_args())?;
+    let reducer_code = RadonReducers::try_from(reducer_integer).map_err(|_| wrong_args())?;
+
+    let mut groups: BTreeMap<String, Vec<RadonTypes>> = BTreeMap::new();
+    for item in input.value() {
+        let wrong_item = || RadError::ArrayGroupWrongItem {
+            value: item.to_string(),
+        };
+
+        let pair = match &item {
+            RadonTypes::Array(pair) => pair.value(),
+            _ => return Err(wrong_item()),
+        };
+
+        match pair.as_slice() {
+            [RadonTypes::String(key), value] => {
+                groups.entry(key.value()).or_default().push(value.clone())
+            }
+            _ => return Err(wrong_item()),
+        }
+    }
+
+    let mut result = BTreeMap::new();
+    for (key, values) in groups {
+        let reduced = reducers::reduce(&RadonArray::from(values), reducer_code, context)?;
+        result.insert(key, reduced);
+    }
+
+    Ok(RadonMap::from(result).into())
+}
+
+/// Join the strings contained in the input array, placing the separator given as argument between
+/// them.
+pub fn join(input: &RadonArray, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "Join".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+    let separator = from_value::<String>(args[0].to_owned()).map_err(|_| wrong_args())?;
+
+    let items = input
+        .value()
+        .into_iter()
+        .map(|item| match item {
+            RadonTypes::String(string) => Ok(string.value()),
+            other => Err(RadError::ArrayJoinWrongItem {
+                value: other.to_string(),
+            }),
+        })
+        .collect::<Result<Vec<String>, RadError>>()?;
+
+    Ok(RadonString::from(items.join(&separator)))
+}
+
 fn partial_results_extract(
     subscript: &[RadonCall],
     reports: &[RadonReport<RadonTypes>],
@@ -425,6 +542,80 @@ mod tests {
 
     use super::*;
 
+    fn wip0028_context() -> ReportContext<RadonTypes> {
+        ReportContext::from_active_wips(witnet_data_structures::chain::tapi::all_wips_active())
+    }
+
+    #[test]
+    fn test_array_group_reduce_wrong_items() {
+        let args = vec![Value::Integer(RadonReducers::AverageMean as i128)];
+
+        // Items must be arrays
+        let input = RadonArray::from(vec![RadonFloat::from(1.0).into()]);
+        let output = group_reduce(&input, &args, &mut ReportContext::default()).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::ArrayGroupWrongItem {
+                value: RadonTypes::from(RadonFloat::from(1.0)).to_string(),
+            }
+        );
+
+        // Items must be pairs whose first element is a string
+        let item = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonFloat::from(1.0).into(),
+        ]));
+        let input = RadonArray::from(vec![item.clone()]);
+        let output = group_reduce(&input, &args, &mut ReportContext::default()).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::ArrayGroupWrongItem {
+                value: item.to_string(),
+            }
+        );
+
+        // The argument must be a valid reducer
+        let input = RadonArray::from(vec![]);
+        let output = group_reduce(
+            &input,
+            &[Value::Integer(0xFF)],
+            &mut ReportContext::default(),
+        );
+        assert!(matches!(output, Err(RadError::WrongArguments { .. })));
+    }
+
+    #[test]
+    fn test_array_join() {
+        let input = RadonArray::from(vec![
+            RadonString::from("BTC").into(),
+            RadonString::from("USD").into(),
+        ]);
+        let args = vec![Value::Text("/".to_string())];
+        let output = join(&input, &args).unwrap();
+        assert_eq!(output, RadonString::from("BTC/USD"));
+
+        // Joining an empty array results in an empty string
+        let output = join(&RadonArray::from(vec![]), &args).unwrap();
+        assert_eq!(output, RadonString::from(""));
+    }
+
+    #[test]
+    fn test_array_join_wrong_items() {
+        let args = vec![Value::Text(",".to_string())];
+        let item = RadonTypes::from(RadonInteger::from(1));
+        let input = RadonArray::from(vec![RadonString::from("a").into(), item.clone()]);
+        let output = join(&input, &args).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::ArrayJoinWrongItem {
+                value: item.to_string(),
+            }
+        );
+
+        let output = join(&input, &[Value::Integer(1)]).unwrap_err();
+        assert!(matches!(output, RadError::WrongArguments { .. }));
+    }
+
     #[test]
     fn test_array_count() {
         let input = &RadonArray::from(vec![
@@ -1110,9 +1301,17 @@ mod tests {
             Value::Text("key2".to_string()),
         ])])];
         let output = sort(&input, &script, &mut ReportContext::default()).unwrap_err();
-        let expected_err = RadError::UnsupportedSortOp { array: input };
+        let expected_err = RadError::UnsupportedSortOp {
+            array: input.clone(),
+        };
 
         assert_eq!(output, expected_err);
+
+        // After WIP0028, floats can be used as sorting keys
+        let output = sort(&input, &script, &mut wip0028_context()).unwrap();
+        let expected = RadonTypes::from(input);
+
+        assert_eq!(output, expected);
     }
 
     #[test]
@@ -1188,13 +1387,60 @@ mod tests {
     #[test]
     fn test_sort_float_arrays() {
         let input = RadonArray::from(vec![
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(-1.5f64).into(),
+            RadonFloat::from(0f64).into(),
+            RadonFloat::from(-0f64).into(),
+            RadonFloat::from(f64::INFINITY).into(),
+            RadonFloat::from(1f64).into(),
+        ]);
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(-1.5f64).into(),
+            RadonFloat::from(-0f64).into(),
+            RadonFloat::from(0f64).into(),
             RadonFloat::from(1f64).into(),
             RadonFloat::from(2f64).into(),
+            RadonFloat::from(f64::INFINITY).into(),
+        ]));
+        // `RadonTypes` are compared by their encoding, which tells `-0.0` and `0.0` apart
+        let output = sort(&input, &[], &mut wip0028_context()).unwrap();
+        assert_eq!(output, expected);
+
+        // Before WIP0028, floats cannot be sorted
+        let mut context = wip0028_context();
+        if let Some(active_wips) = context.active_wips.as_mut() {
+            active_wips.active_wips.remove("WIP0028");
+        }
+        let output = sort(&input, &[], &mut context).unwrap_err();
+        assert_eq!(output, RadError::UnsupportedSortOp { array: input });
+    }
+
+    #[test]
+    fn test_sort_float_arrays_nan() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(f64::NAN).into(),
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(-f64::NAN).into(),
+            RadonFloat::from(f64::NEG_INFINITY).into(),
+            RadonFloat::from(f64::INFINITY).into(),
         ]);
-        let output = sort(&input, &[], &mut ReportContext::default()).unwrap_err();
-        let expected_err = RadError::UnsupportedSortOp { array: input };
+        let output = sort(&input, &[], &mut wip0028_context()).unwrap();
+        let output: Vec<f64> = match output {
+            RadonTypes::Array(output) => output
+                .value()
+                .iter()
+                .map(|item| match item {
+                    RadonTypes::Float(float) => float.value(),
+                    _ => unreachable!(),
+                })
+                .collect(),
+            _ => panic!("Sort should return an array"),
+        };
 
-        assert_eq!(output, expected_err);
+        // NaNs go last regardless of their sign, keeping their relative order
+        assert_eq!(output[..3], [f64::NEG_INFINITY, 1f64, f64::INFINITY]);
+        assert!(output[3].is_nan() && output[3].is_sign_positive());
+        assert!(output[4].is_nan() && output[4].is_sign_negative());
     }
 
     #[test]
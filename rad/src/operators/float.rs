@@ -95,12 +95,46 @@ pub fn floor(input: &RadonFloat) -> RadonInteger {
     RadonInteger::from(input.value().floor() as i128)
 }
 
+/// Round to the nearest integer. Exact halves are rounded away from zero (`2.5` becomes `3` and
+/// `-2.5` becomes `-3`), which is deterministic across platforms.
+///
+/// Note that this rule is consensus-critical: switching to a different one, like
+/// round-half-to-even, would change the result of existing data requests. Use `round_to_float`
+/// for round-half-to-even.
 // FIXME: Allow for now, wait for https://github.com/rust-lang/rust/issues/67058 to reach stable
 #[allow(clippy::cast_possible_truncation)]
 pub fn round(input: &RadonFloat) -> RadonInteger {
     RadonInteger::from(input.value().round() as i128)
 }
 
+/// Round up to the nearest integer, keeping the result as a float.
+pub fn ceiling_to_float(input: &RadonFloat) -> RadonFloat {
+    RadonFloat::from(input.value().ceil())
+}
+
+/// Round down to the nearest integer, keeping the result as a float.
+pub fn floor_to_float(input: &RadonFloat) -> RadonFloat {
+    RadonFloat::from(input.value().floor())
+}
+
+/// Round to the nearest integer, keeping the result as a float. Exact halves are rounded to the
+/// nearest even integer (`2.5` becomes `2.0`, `3.5` becomes `4.0` and `-2.5` becomes `-2.0`), so
+/// that rounding many values does not introduce a bias. This only relies on exact floating point
+/// operations, so it is deterministic across platforms.
+// Only exact halves must be detected, so the comparison is exact on purpose
+#[allow(clippy::float_cmp)]
+pub fn round_to_float(input: &RadonFloat) -> RadonFloat {
+    let value = input.value();
+    let rounded = if value.fract().abs() == 0.5 {
+        // `value / 2` is exact, and its nearest integer is half of the even neighbour of `value`
+        2.0 * (value / 2.0).round()
+    } else {
+        value.round()
+    };
+
+    RadonFloat::from(rounded)
+}
+
 // No safe cast function from a float to integer yet, but this may just be fine since we are truncating anyway
 #[allow(clippy::cast_possible_truncation)]
 pub fn truncate(input: &RadonFloat) -> RadonInteger {
@@ -247,6 +281,110 @@ fn test_float_round() {
     assert_eq!(round(&float3), RadonInteger::from(11));
 }
 
+#[test]
+fn test_float_round_halves_and_negatives() {
+    // Exact halves are rounded away from zero, never to the nearest even integer
+    assert_eq!(round(&RadonFloat::from(0.5)), RadonInteger::from(1));
+    assert_eq!(round(&RadonFloat::from(1.5)), RadonInteger::from(2));
+    assert_eq!(round(&RadonFloat::from(2.5)), RadonInteger::from(3));
+    assert_eq!(round(&RadonFloat::from(-0.5)), RadonInteger::from(-1));
+    assert_eq!(round(&RadonFloat::from(-2.5)), RadonInteger::from(-3));
+
+    assert_eq!(round(&RadonFloat::from(-10.49)), RadonInteger::from(-10));
+    assert_eq!(round(&RadonFloat::from(-10.51)), RadonInteger::from(-11));
+    assert_eq!(absolute(&RadonFloat::from(-2.5)), RadonFloat::from(2.5));
+    assert_eq!(ceiling(&RadonFloat::from(-2.5)), RadonInteger::from(-2));
+    assert_eq!(floor(&RadonFloat::from(-2.5)), RadonInteger::from(-3));
+}
+
+#[test]
+fn test_float_ceiling_floor_to_float() {
+    assert_eq!(
+        ceiling_to_float(&RadonFloat::from(10.01)),
+        RadonFloat::from(11.0)
+    );
+    assert_eq!(
+        ceiling_to_float(&RadonFloat::from(11.0)),
+        RadonFloat::from(11.0)
+    );
+    assert_eq!(
+        ceiling_to_float(&RadonFloat::from(-2.5)),
+        RadonFloat::from(-2.0)
+    );
+    assert_eq!(
+        floor_to_float(&RadonFloat::from(10.99)),
+        RadonFloat::from(10.0)
+    );
+    assert_eq!(
+        floor_to_float(&RadonFloat::from(11.0)),
+        RadonFloat::from(11.0)
+    );
+    assert_eq!(
+        floor_to_float(&RadonFloat::from(-2.5)),
+        RadonFloat::from(-3.0)
+    );
+    // Values that do not fit into an integer are kept as they are
+    assert_eq!(
+        floor_to_float(&RadonFloat::from(1e300)),
+        RadonFloat::from(1e300)
+    );
+}
+
+#[test]
+fn test_float_round_to_float() {
+    // Exact halves are rounded to the nearest even integer
+    assert_eq!(
+        round_to_float(&RadonFloat::from(0.5)),
+        RadonFloat::from(0.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(1.5)),
+        RadonFloat::from(2.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(2.5)),
+        RadonFloat::from(2.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(3.5)),
+        RadonFloat::from(4.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(-0.5)),
+        RadonFloat::from(0.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(-1.5)),
+        RadonFloat::from(-2.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(-2.5)),
+        RadonFloat::from(-2.0)
+    );
+
+    // Other values are rounded to the nearest integer
+    assert_eq!(
+        round_to_float(&RadonFloat::from(10.49)),
+        RadonFloat::from(10.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(10.51)),
+        RadonFloat::from(11.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(-10.49)),
+        RadonFloat::from(-10.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(-10.51)),
+        RadonFloat::from(-11.0)
+    );
+    assert_eq!(
+        round_to_float(&RadonFloat::from(1e300)),
+        RadonFloat::from(1e300)
+    );
+}
+
 #[test]
 fn test_float_trunc() {
     let float1 = RadonFloat::from(10.0);
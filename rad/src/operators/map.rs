@@ -1,11 +1,14 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 use serde_cbor::value::{from_value, Value};
 
 use crate::{
     error::RadError,
     operators::string,
-    types::{array::RadonArray, map::RadonMap, string::RadonString, RadonType, RadonTypes},
+    types::{
+        array::RadonArray, integer::RadonInteger, map::RadonMap, string::RadonString, RadonType,
+        RadonTypes,
+    },
 };
 
 fn inner_get(input: &RadonMap, args: &[Value]) -> Result<RadonTypes, RadError> {
@@ -67,6 +70,33 @@ fn get_numeric_string(input: &RadonMap, args: &[Value]) -> Result<RadonString, R
     )))
 }
 
+/// Get an entry from the input `RadonMap`, as specified by the first argument, which is used as the
+/// search key. If the key is missing, the second argument is returned instead.
+///
+/// The entry is required to be of the same type as the default value.
+pub fn get_or_default(input: &RadonMap, args: &[Value]) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonMap::radon_type_name(),
+        operator: "GetOrDefault".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 2 {
+        return Err(wrong_args());
+    }
+    let key = from_value::<String>(args[0].clone()).map_err(|_| wrong_args())?;
+    let default = RadonTypes::try_from(args[1].clone())?;
+
+    match input.value().get(&key) {
+        Some(item) if item.discriminant() == default.discriminant() => Ok(item.clone()),
+        Some(item) => Err(RadError::Decode {
+            from: item.radon_type_name(),
+            to: default.radon_type_name(),
+        }),
+        None => Ok(default),
+    }
+}
+
 pub fn keys(input: &RadonMap) -> RadonArray {
     let v: Vec<RadonTypes> = input
         .value()
@@ -76,6 +106,10 @@ pub fn keys(input: &RadonMap) -> RadonArray {
     RadonArray::from(v)
 }
 
+pub fn length(input: &RadonMap) -> RadonInteger {
+    RadonInteger::from(input.value().len() as i128)
+}
+
 pub fn values(input: &RadonMap) -> RadonArray {
     let v: Vec<RadonTypes> = input.value().values().cloned().collect();
     RadonArray::from(v)
@@ -160,6 +194,70 @@ mod tests {
         assert!(not_found_object.is_err());
     }
 
+    #[test]
+    fn test_map_get_or_default() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "price".to_string(),
+            RadonTypes::Float(RadonFloat::from(1.5f64)),
+        );
+        let input = RadonMap::from(map);
+
+        // Present key
+        let args = vec![Value::Text(String::from("price")), Value::Float(0f64)];
+        let output = get_or_default(&input, &args).unwrap();
+        assert_eq!(output, RadonTypes::Float(RadonFloat::from(1.5f64)));
+
+        // Missing key
+        let args = vec![Value::Text(String::from("volume")), Value::Float(0f64)];
+        let output = get_or_default(&input, &args).unwrap();
+        assert_eq!(output, RadonTypes::Float(RadonFloat::from(0f64)));
+
+        // The same applies when called through the operator
+        let call = (
+            RadonOpCodes::MapGetOrDefault,
+            Some(vec![Value::Text(String::from("volume")), Value::Integer(7)]),
+        );
+        let output = input.operate(&call).unwrap();
+        assert_eq!(output, RadonTypes::Integer(RadonInteger::from(7)));
+    }
+
+    #[test]
+    fn test_map_get_or_default_errors() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "price".to_string(),
+            RadonTypes::Float(RadonFloat::from(1.5f64)),
+        );
+        let input = RadonMap::from(map);
+
+        // The type of the entry does not match that of the default value
+        let args = vec![
+            Value::Text(String::from("price")),
+            Value::Text(String::from("N/A")),
+        ];
+        let output = get_or_default(&input, &args).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::Decode {
+                from: "RadonFloat",
+                to: "RadonString",
+            }
+        );
+
+        // The default value is mandatory
+        let args = vec![Value::Text(String::from("price"))];
+        let output = get_or_default(&input, &args).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::WrongArguments {
+                input_type: "RadonMap",
+                operator: "GetOrDefault".to_string(),
+                args,
+            }
+        );
+    }
+
     #[test]
     fn test_map_keys() {
         let key0 = "Zero";
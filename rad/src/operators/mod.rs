@@ -25,6 +25,7 @@ pub enum RadonOpCodes {
     ///////////////////////////////////////////////////////////////////////
     // Multi-type operator codes start at 0x00
     Identity = 0x00,
+    Length = 0x01,
     ///////////////////////////////////////////////////////////////////////
     // Array operator codes (start at 0x10)
     ArrayCount = 0x10,
@@ -42,6 +43,7 @@ pub enum RadonOpCodes {
     //    ArraySome = 0x1C,
     ArraySort = 0x1D,
     //    ArrayTake = 0x1E,
+    ArrayGroupReduce = 0x1F,
     ///////////////////////////////////////////////////////////////////////
     // Boolean operator codes (start at 0x20)
     BooleanAsString = 0x20,
@@ -93,10 +95,11 @@ pub enum RadonOpCodes {
     MapGetString = 0x67,
     MapKeys = 0x68,
     MapValues = 0x69,
+    MapGetOrDefault = 0x6A,
     ///////////////////////////////////////////////////////////////////////
     // String operator codes (start at 0x70)
     StringAsBoolean = 0x70,
-    //    StringAsBytes = 0x71,
+    StringAsBytes = 0x71,
     StringAsFloat = 0x72,
     StringAsInteger = 0x73,
     StringLength = 0x74,
@@ -106,6 +109,16 @@ pub enum RadonOpCodes {
     StringParseXMLMap = 0x78,
     StringToLowerCase = 0x79,
     StringToUpperCase = 0x7A,
+    StringSplit = 0x7B,
+    ///////////////////////////////////////////////////////////////////////
+    // More array operator codes (start at 0x80), as all the codes in the 0x10 range are taken
+    ArrayJoin = 0x80,
+    ///////////////////////////////////////////////////////////////////////
+    // More float operator codes (start at 0x90). Unlike their counterparts in the 0x50 range,
+    // these return a RadonFloat, and rounding sends exact halves to the nearest even integer
+    FloatCeilingToFloat = 0x90,
+    FloatFloorToFloat = 0x91,
+    FloatRoundToFloat = 0x92,
 }
 
 impl fmt::Display for RadonOpCodes {
@@ -540,7 +553,15 @@ mod legacy {
 
 #[cfg(test)]
 mod tests {
-    use crate::{fromx::FromX, types::string::RadonString};
+    use std::collections::BTreeMap;
+
+    use crate::{
+        fromx::FromX,
+        types::{
+            array::RadonArray, float::RadonFloat, integer::RadonInteger, map::RadonMap,
+            string::RadonString,
+        },
+    };
 
     use super::*;
 
@@ -562,4 +583,51 @@ mod tests {
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    pub fn test_length_array() {
+        let input = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(1.0).into(),
+            RadonFloat::from(2.0).into(),
+            RadonFloat::from(3.0).into(),
+        ]));
+        let output = operate(input, &(RadonOpCodes::Length, None)).unwrap();
+
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(3)));
+    }
+
+    #[test]
+    pub fn test_length_string() {
+        // Bytes are counted, not characters, like StringLength does
+        let input = RadonTypes::from(RadonString::from("héllo"));
+        let output = operate(input.clone(), &(RadonOpCodes::Length, None)).unwrap();
+        let string_length = operate(input, &(RadonOpCodes::StringLength, None)).unwrap();
+
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(6)));
+        assert_eq!(output, string_length);
+    }
+
+    #[test]
+    pub fn test_length_map() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), RadonFloat::from(1.0).into());
+        map.insert("b".to_string(), RadonString::from("x").into());
+        let input = RadonTypes::from(RadonMap::from(map));
+        let output = operate(input, &(RadonOpCodes::Length, None)).unwrap();
+
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(2)));
+
+        let empty = RadonTypes::from(RadonMap::from(BTreeMap::new()));
+        let output = operate(empty, &(RadonOpCodes::Length, None)).unwrap();
+
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(0)));
+    }
+
+    #[test]
+    pub fn test_length_scalar() {
+        let input = RadonTypes::from(RadonFloat::from(1.0));
+
+        let output = operate(input, &(RadonOpCodes::Length, None));
+        assert!(matches!(output, Err(RadError::UnsupportedOperator { .. })));
+    }
 }
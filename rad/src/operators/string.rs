@@ -203,6 +203,38 @@ pub fn to_uppercase(input: &RadonString) -> RadonString {
     RadonString::from(input.value().as_str().to_uppercase())
 }
 
+/// Decode the input hexadecimal string into bytes. This is the inverse of `BytesAsString`.
+pub fn as_bytes(input: &RadonString) -> Result<RadonBytes, RadError> {
+    let bytes = hex::decode(input.value())?;
+
+    Ok(RadonBytes::from(bytes))
+}
+
+/// Split the input string on every occurrence of the separator given as argument.
+pub fn split(input: &RadonString, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "Split".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+    let separator = from_value::<String>(args[0].to_owned()).map_err(|_| wrong_args())?;
+    if separator.is_empty() {
+        return Err(RadError::StringSplitEmptySeparator);
+    }
+
+    let items: Vec<RadonTypes> = input
+        .value()
+        .split(separator.as_str())
+        .map(|item| RadonString::from(item).into())
+        .collect();
+
+    Ok(RadonArray::from(items))
+}
+
 pub fn hash(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError> {
     let wrong_args = || RadError::WrongArguments {
         input_type: RadonString::radon_type_name(),
@@ -595,6 +627,78 @@ mod tests {
         assert_eq!(output, expected_err);
     }
 
+    #[test]
+    fn test_string_as_bytes() {
+        let input = RadonString::from("0102abFF");
+        let output = as_bytes(&input).unwrap();
+        assert_eq!(output, RadonBytes::from(vec![0x01, 0x02, 0xAB, 0xFF]));
+
+        // Empty strings are decoded into empty bytes
+        let output = as_bytes(&RadonString::from("")).unwrap();
+        assert_eq!(output, RadonBytes::from(vec![]));
+    }
+
+    #[test]
+    fn test_string_as_bytes_invalid_hex() {
+        let output = as_bytes(&RadonString::from("abc")).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::ParseHex {
+                message: "Odd number of digits".to_string()
+            }
+        );
+
+        let output = as_bytes(&RadonString::from("0x12")).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::ParseHex {
+                message: "Invalid character 'x' at position 1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_string_split() {
+        let input = RadonString::from("BTC,USD,42000.5,,ok");
+        let args = vec![Value::Text(",".to_string())];
+        let output = split(&input, &args).unwrap();
+
+        let expected = RadonArray::from(vec![
+            RadonString::from("BTC").into(),
+            RadonString::from("USD").into(),
+            RadonString::from("42000.5").into(),
+            RadonString::from("").into(),
+            RadonString::from("ok").into(),
+        ]);
+        assert_eq!(output, expected);
+
+        // Multi-character separators are allowed
+        let input = RadonString::from("a::b");
+        let args = vec![Value::Text("::".to_string())];
+        let output = split(&input, &args).unwrap();
+        assert_eq!(
+            output,
+            RadonArray::from(vec![
+                RadonString::from("a").into(),
+                RadonString::from("b").into()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_string_split_wrong_separator() {
+        let input = RadonString::from("a,b");
+
+        let output = split(&input, &[Value::Text(String::new())]).unwrap_err();
+        assert_eq!(output, RadError::StringSplitEmptySeparator);
+
+        let output = split(&input, &[Value::Integer(1)]).unwrap_err();
+        assert!(matches!(output, RadError::WrongArguments { .. }));
+
+        let output = split(&input, &[]).unwrap_err();
+        assert!(matches!(output, RadError::WrongArguments { .. }));
+    }
+
     #[test]
     fn test_hash() {
         let input = RadonString::from("Hello, World!");
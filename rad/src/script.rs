@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 use serde_cbor::{
     self as cbor,
@@ -14,7 +14,10 @@ use crate::{
     filters::RadonFilters,
     operators::{operate, operate_in_context, RadonOpCodes},
     reducers::RadonReducers,
-    types::RadonTypes,
+    types::{
+        array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
+        integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
+    },
 };
 
 pub type RadonCall = (RadonOpCodes, Option<Vec<Value>>);
@@ -207,6 +210,146 @@ fn errorify(kind: RadError) -> RadError {
     kind
 }
 
+/// Static type of the values flowing through a RADON script, as tracked by
+/// `typecheck_radon_script`.
+///
+/// `Any` stands for values whose type can only be known at runtime, such as the items of a parsed
+/// JSON array or the result of a reducer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RadonScriptType {
+    Any,
+    Array,
+    Boolean,
+    Bytes,
+    Float,
+    Integer,
+    Map,
+    String,
+}
+
+impl fmt::Display for RadonScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RadonScriptType::Any => "any type",
+            RadonScriptType::Array => RadonArray::radon_type_name(),
+            RadonScriptType::Boolean => RadonBoolean::radon_type_name(),
+            RadonScriptType::Bytes => RadonBytes::radon_type_name(),
+            RadonScriptType::Float => RadonFloat::radon_type_name(),
+            RadonScriptType::Integer => RadonInteger::radon_type_name(),
+            RadonScriptType::Map => RadonMap::radon_type_name(),
+            RadonScriptType::String => RadonString::radon_type_name(),
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Returns the output type of applying an operator on a value of the given type, or `None` if the
+/// operator is not supported by that type.
+fn operator_output_type(
+    op_code: RadonOpCodes,
+    input_type: RadonScriptType,
+) -> Option<RadonScriptType> {
+    use RadonScriptType::*;
+
+    let (accepted, output): (&[RadonScriptType], RadonScriptType) = match op_code {
+        RadonOpCodes::Fail => (&[], Any),
+        RadonOpCodes::Identity => (
+            &[Array, Boolean, Bytes, Float, Integer, Map, String],
+            input_type,
+        ),
+        RadonOpCodes::Length => (&[Array, Map, String], Integer),
+        RadonOpCodes::ArrayCount => (&[Array], Integer),
+        RadonOpCodes::ArrayFilter | RadonOpCodes::ArrayMap | RadonOpCodes::ArraySort => {
+            (&[Array], Array)
+        }
+        RadonOpCodes::ArrayJoin => (&[Array], String),
+        RadonOpCodes::ArrayGetArray => (&[Array], Array),
+        RadonOpCodes::ArrayGetBoolean => (&[Array], Boolean),
+        RadonOpCodes::ArrayGetBytes => (&[Array], Bytes),
+        RadonOpCodes::ArrayGetFloat => (&[Array], Float),
+        RadonOpCodes::ArrayGetInteger => (&[Array], Integer),
+        RadonOpCodes::ArrayGetMap => (&[Array], Map),
+        RadonOpCodes::ArrayGetString => (&[Array], String),
+        RadonOpCodes::ArrayReduce => (&[Array], Any),
+        RadonOpCodes::ArrayGroupReduce => (&[Array], Map),
+        RadonOpCodes::BooleanAsString => (&[Boolean], String),
+        RadonOpCodes::BooleanNegate => (&[Boolean], Boolean),
+        RadonOpCodes::BytesAsString => (&[Bytes], String),
+        RadonOpCodes::BytesHash => (&[Bytes], Bytes),
+        RadonOpCodes::IntegerAbsolute
+        | RadonOpCodes::IntegerModulo
+        | RadonOpCodes::IntegerMultiply
+        | RadonOpCodes::IntegerNegate
+        | RadonOpCodes::IntegerPower => (&[Integer], Integer),
+        RadonOpCodes::IntegerAsFloat => (&[Integer], Float),
+        RadonOpCodes::IntegerAsString => (&[Integer], String),
+        RadonOpCodes::IntegerGreaterThan | RadonOpCodes::IntegerLessThan => (&[Integer], Boolean),
+        RadonOpCodes::FloatAbsolute
+        | RadonOpCodes::FloatCeilingToFloat
+        | RadonOpCodes::FloatFloorToFloat
+        | RadonOpCodes::FloatRoundToFloat
+        | RadonOpCodes::FloatModulo
+        | RadonOpCodes::FloatMultiply
+        | RadonOpCodes::FloatNegate
+        | RadonOpCodes::FloatPower => (&[Float], Float),
+        RadonOpCodes::FloatAsString => (&[Float], String),
+        RadonOpCodes::FloatCeiling
+        | RadonOpCodes::FloatFloor
+        | RadonOpCodes::FloatRound
+        | RadonOpCodes::FloatTruncate => (&[Float], Integer),
+        RadonOpCodes::FloatGreaterThan | RadonOpCodes::FloatLessThan => (&[Float], Boolean),
+        RadonOpCodes::MapGetArray | RadonOpCodes::MapKeys | RadonOpCodes::MapValues => {
+            (&[Map], Array)
+        }
+        RadonOpCodes::MapGetBoolean => (&[Map], Boolean),
+        RadonOpCodes::MapGetBytes => (&[Map], Bytes),
+        RadonOpCodes::MapGetFloat => (&[Map], Float),
+        RadonOpCodes::MapGetInteger => (&[Map], Integer),
+        RadonOpCodes::MapGetMap => (&[Map], Map),
+        RadonOpCodes::MapGetString => (&[Map], String),
+        RadonOpCodes::MapGetOrDefault => (&[Map], Any),
+        RadonOpCodes::StringAsBoolean => (&[String], Boolean),
+        RadonOpCodes::StringAsBytes => (&[String], Bytes),
+        RadonOpCodes::StringAsFloat => (&[String], Float),
+        RadonOpCodes::StringAsInteger | RadonOpCodes::StringLength => (&[String], Integer),
+        RadonOpCodes::StringMatch => (&[String], Any),
+        RadonOpCodes::StringParseJSONArray | RadonOpCodes::StringSplit => (&[String], Array),
+        RadonOpCodes::StringParseJSONMap | RadonOpCodes::StringParseXMLMap => (&[String], Map),
+        RadonOpCodes::StringToLowerCase | RadonOpCodes::StringToUpperCase => (&[String], String),
+    };
+
+    if input_type == Any || accepted.contains(&input_type) {
+        Some(output)
+    } else {
+        None
+    }
+}
+
+/// Statically check that the operators of a packed RADON script can be chained together when the
+/// script is fed a value of type `input_type`, and return the type of its output.
+///
+/// This does not execute the script nor perform any network request. Once a value of type `Any`
+/// is found, the following call is assumed to be compatible with it. The subscripts passed as
+/// arguments to operators such as `ArrayMap` are not inspected.
+pub fn typecheck_radon_script(
+    input_type: RadonScriptType,
+    script: &[u8],
+) -> Result<RadonScriptType, RadError> {
+    let mut current_type = input_type;
+    for (index, (op_code, _args)) in unpack_radon_script(script)?.iter().enumerate() {
+        current_type = operator_output_type(*op_code, current_type).ok_or_else(|| {
+            RadError::ScriptTypeMismatch {
+                index,
+                operator: op_code.to_string(),
+                input_type: current_type.to_string(),
+            }
+        })?;
+    }
+
+    Ok(current_type)
+}
+
 pub fn create_radon_script_from_filters_and_reducer(
     filters: &[RADFilter],
     reducer: u32,
@@ -554,6 +697,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_execute_radon_script_split_and_join() {
+        use crate::types::{array::RadonArray, string::RadonString};
+
+        let input = RadonTypes::from(RadonString::from("2021-01-01,WIT,0.12"));
+        let split = (
+            RadonOpCodes::StringSplit,
+            Some(vec![Value::Text(String::from(","))]),
+        );
+        let join = (
+            RadonOpCodes::ArrayJoin,
+            Some(vec![Value::Text(String::from(","))]),
+        );
+
+        let output = execute_contextfree_radon_script(input.clone(), &[split.clone()]).unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonString::from("2021-01-01").into(),
+            RadonString::from("WIT").into(),
+            RadonString::from("0.12").into(),
+        ]));
+        assert_eq!(output, expected);
+
+        // Joining the result of splitting with the same separator returns the original string
+        let output = execute_contextfree_radon_script(input.clone(), &[split, join]).unwrap();
+        assert_eq!(output, input);
+    }
+
     #[test]
     fn test_unpack_radon_script() {
         let cbor_vec = Value::Array(vec![
@@ -587,6 +757,78 @@ mod tests {
         assert_eq!(output, expected)
     }
 
+    #[test]
+    fn test_typecheck_radon_script() {
+        let packed = serde_cbor::to_vec(&Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetMap as i128),
+                Value::Text(String::from("main")),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                Value::Text(String::from("temp")),
+            ]),
+            Value::Integer(RadonOpCodes::FloatRound as i128),
+        ]))
+        .unwrap();
+
+        let output = typecheck_radon_script(RadonScriptType::String, &packed);
+        assert_eq!(output, Ok(RadonScriptType::Integer));
+
+        // The same script cannot be applied to other types
+        let output = typecheck_radon_script(RadonScriptType::Bytes, &packed);
+        assert_eq!(
+            output,
+            Err(RadError::ScriptTypeMismatch {
+                index: 0,
+                operator: String::from("StringParseJSONMap"),
+                input_type: String::from("RadonBytes"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_typecheck_radon_script_mismatch() {
+        let packed = serde_cbor::to_vec(&Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                Value::Text(String::from("temp")),
+            ]),
+            Value::Integer(RadonOpCodes::IntegerAsString as i128),
+            Value::Integer(RadonOpCodes::StringLength as i128),
+        ]))
+        .unwrap();
+
+        let output = typecheck_radon_script(RadonScriptType::String, &packed);
+        assert_eq!(
+            output,
+            Err(RadError::ScriptTypeMismatch {
+                index: 2,
+                operator: String::from("IntegerAsString"),
+                input_type: String::from("RadonFloat"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_typecheck_radon_script_any() {
+        // The type of the items of a JSON array is only known at runtime
+        let packed = serde_cbor::to_vec(&Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONArray as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayReduce as i128),
+                Value::Integer(RadonReducers::AverageMean as i128),
+            ]),
+            Value::Integer(RadonOpCodes::FloatRound as i128),
+        ]))
+        .unwrap();
+
+        let output = typecheck_radon_script(RadonScriptType::String, &packed);
+        assert_eq!(output, Ok(RadonScriptType::Integer));
+    }
+
     #[test]
     fn test_create_radon_script() {
         let expected = vec![
@@ -137,6 +137,7 @@ impl Operable for RadonArray {
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(array_operators::count(self).into()),
             (RadonOpCodes::ArrayCount, None) => Ok(array_operators::count(self).into()),
             (RadonOpCodes::ArrayGetArray, Some(args)) => {
                 array_operators::get::<RadonArray, _>(self, args).map(RadonTypes::from)
@@ -169,6 +170,12 @@ impl Operable for RadonArray {
             (RadonOpCodes::ArrayMap, Some(args)) => array_operators::map(self, args, context),
             (RadonOpCodes::ArrayReduce, Some(args)) => array_operators::reduce(self, args, context),
             (RadonOpCodes::ArraySort, Some(args)) => array_operators::sort(self, args, context),
+            (RadonOpCodes::ArrayGroupReduce, Some(args)) => {
+                array_operators::group_reduce(self, args, context)
+            }
+            (RadonOpCodes::ArrayJoin, Some(args)) => {
+                array_operators::join(self, args).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_ARRAY_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
@@ -226,6 +233,37 @@ mod tests {
         assert_eq!(output2, expected2);
     }
 
+    #[test]
+    fn test_operate_group_reduce() {
+        let pair = |key: &str, value: f64| -> RadonTypes {
+            RadonArray::from(vec![
+                RadonString::from(key).into(),
+                RadonFloat::from(value).into(),
+            ])
+            .into()
+        };
+        let input = RadonArray::from(vec![
+            pair("BTC", 1.0),
+            pair("ETH", 10.0),
+            pair("BTC", 3.0),
+            pair("ETH", 20.0),
+            pair("WIT", 5.0),
+        ]);
+        let call = (
+            RadonOpCodes::ArrayGroupReduce,
+            Some(vec![Value::Integer(RadonReducers::AverageMean as i128)]),
+        );
+        let mut expected = BTreeMap::new();
+        expected.insert("BTC".to_string(), RadonFloat::from(2.0).into());
+        expected.insert("ETH".to_string(), RadonFloat::from(15.0).into());
+        expected.insert("WIT".to_string(), RadonFloat::from(5.0).into());
+        let expected = RadonTypes::from(RadonMap::from(expected));
+
+        let output = input.operate(&call).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
     #[test]
     fn test_operate_reduce_average_mean_float() {
         let input = RadonArray::from(vec![
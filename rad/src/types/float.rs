@@ -122,6 +122,9 @@ impl Operable for RadonFloat {
             (RadonOpCodes::FloatCeiling, None) => {
                 Ok(RadonTypes::from(float_operators::ceiling(self)))
             }
+            (RadonOpCodes::FloatCeilingToFloat, None) => {
+                Ok(RadonTypes::from(float_operators::ceiling_to_float(self)))
+            }
             (RadonOpCodes::FloatGreaterThan, Some(args)) => {
                 float_operators::greater_than(self, args).map(Into::into)
             }
@@ -135,6 +138,9 @@ impl Operable for RadonFloat {
                 float_operators::modulo(self, args.as_slice()).map(Into::into)
             }
             (RadonOpCodes::FloatFloor, None) => Ok(RadonTypes::from(float_operators::floor(self))),
+            (RadonOpCodes::FloatFloorToFloat, None) => {
+                Ok(RadonTypes::from(float_operators::floor_to_float(self)))
+            }
 
             (RadonOpCodes::FloatNegate, None) => {
                 Ok(RadonTypes::from(float_operators::negate(self)))
@@ -143,6 +149,9 @@ impl Operable for RadonFloat {
                 float_operators::power(self, args.as_slice()).map(Into::into)
             }
             (RadonOpCodes::FloatRound, None) => Ok(RadonTypes::from(float_operators::round(self))),
+            (RadonOpCodes::FloatRoundToFloat, None) => {
+                Ok(RadonTypes::from(float_operators::round_to_float(self)))
+            }
             (RadonOpCodes::FloatTruncate, None) => {
                 Ok(RadonTypes::from(float_operators::truncate(self)))
             }
@@ -132,6 +132,7 @@ impl Operable for RadonMap {
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(map_operators::length(self).into()),
             (RadonOpCodes::MapGetArray, Some(args)) => {
                 map_operators::get::<RadonArray, _>(self, args.as_slice()).map(RadonTypes::from)
             }
@@ -161,6 +162,9 @@ impl Operable for RadonMap {
             }
             (RadonOpCodes::MapKeys, None) => Ok(RadonTypes::from(map_operators::keys(self))),
             (RadonOpCodes::MapValues, None) => Ok(RadonTypes::from(map_operators::values(self))),
+            (RadonOpCodes::MapGetOrDefault, Some(args)) => {
+                map_operators::get_or_default(self, args.as_slice())
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_MAP_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
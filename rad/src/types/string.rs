@@ -105,6 +105,7 @@ impl Operable for RadonString {
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(string_operators::length(self).into()),
             (RadonOpCodes::StringAsFloat, args) => if wip0024 {
                 string_operators::as_float(self, args)
             } else {
@@ -119,6 +120,9 @@ impl Operable for RadonString {
             }
             .map(RadonTypes::from)
             .map_err(Into::into),
+            (RadonOpCodes::StringAsBytes, None) => {
+                string_operators::as_bytes(self).map(RadonTypes::from)
+            }
             (RadonOpCodes::StringAsBoolean, None) => string_operators::to_bool(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
@@ -143,6 +147,9 @@ impl Operable for RadonString {
             (RadonOpCodes::StringParseXMLMap, None) => string_operators::parse_xml_map(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::StringSplit, Some(args)) => {
+                string_operators::split(self, args).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_STRING_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
@@ -179,6 +186,26 @@ fn test_operate_unimplemented() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_operate_as_bytes_round_trip() {
+    use crate::types::bytes::RadonBytes;
+
+    let input = RadonString::from("deadbeef");
+
+    let bytes = input.operate(&(RadonOpCodes::StringAsBytes, None)).unwrap();
+    assert_eq!(
+        bytes,
+        RadonTypes::from(RadonBytes::from(vec![0xDE, 0xAD, 0xBE, 0xEF]))
+    );
+
+    // BytesAsString encodes the bytes back into the same hex string
+    let output = bytes
+        .as_operable()
+        .operate(&(RadonOpCodes::BytesAsString, None))
+        .unwrap();
+    assert_eq!(output, RadonTypes::from(input));
+}
+
 #[test]
 fn test_serialize_radon_string() {
     use witnet_data_structures::radon_report::TypeLike;
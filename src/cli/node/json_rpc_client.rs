@@ -67,7 +67,11 @@ pub fn raw(addr: SocketAddr) -> Result<(), failure::Error> {
 
 pub fn get_blockchain(addr: SocketAddr, epoch: i64, limit: i64) -> Result<(), failure::Error> {
     let mut stream = start_client(addr)?;
-    let params = GetBlockChainParams { epoch, limit };
+    let params = GetBlockChainParams {
+        epoch,
+        limit,
+        limit_from_end: false,
+    };
     let response = send_request(
         &mut stream,
         &format!(
@@ -85,6 +89,54 @@ pub fn get_blockchain(addr: SocketAddr, epoch: i64, limit: i64) -> Result<(), fa
     Ok(())
 }
 
+/// Get the block hashes starting at `epoch`, up to `limit` blocks, where a `limit` of 0 means all
+/// of them. The node caps the number of blocks returned by a single getBlockChain request, so the
+/// chain is requested in pages until `limit` blocks or the tip of the chain are reached. A
+/// negative `limit` counts from the tip of the chain and is requested at once.
+fn get_block_chain_paged<S: Read + Write>(
+    stream: &mut S,
+    mut epoch: i64,
+    limit: i64,
+) -> Result<Vec<(u32, String)>, failure::Error> {
+    let mut block_chain: Vec<(u32, String)> = vec![];
+    loop {
+        let remaining = if limit > 0 {
+            limit - i64::try_from(block_chain.len())?
+        } else {
+            limit
+        };
+        let params = GetBlockChainParams {
+            epoch,
+            limit: remaining,
+            limit_from_end: false,
+        };
+        let response = send_request(
+            stream,
+            &format!(
+                r#"{{"jsonrpc": "2.0","method": "getBlockChain", "params": {}, "id": 1}}"#,
+                serde_json::to_string(&params).unwrap()
+            ),
+        )?;
+        log::info!("{}", response);
+        let page: ResponseBlockChain<'_> = parse_response(&response)?;
+        let last_epoch = match page.last() {
+            Some((last_epoch, _hash)) => *last_epoch,
+            None => break,
+        };
+        block_chain.extend(
+            page.into_iter()
+                .map(|(epoch, hash)| (epoch, hash.to_string())),
+        );
+
+        if limit < 0 || (limit > 0 && block_chain.len() >= usize::try_from(limit)?) {
+            break;
+        }
+        epoch = i64::from(last_epoch) + 1;
+    }
+
+    Ok(block_chain)
+}
+
 // Get integer part of `nanowits / 10^9`: number of whole wits
 fn whole_wits(nanowits: u64) -> u64 {
     Wit::wits_and_nanowits(Wit::from_nanowits(nanowits)).0
@@ -459,19 +511,7 @@ pub fn get_reputation(
 
 pub fn get_miners(addr: SocketAddr, start: i64, end: i64, csv: bool) -> Result<(), failure::Error> {
     let mut stream = start_client(addr)?;
-    let params = GetBlockChainParams {
-        epoch: start,
-        limit: end,
-    };
-    let response = send_request(
-        &mut stream,
-        &format!(
-            r#"{{"jsonrpc": "2.0","method": "getBlockChain", "params": {}, "id": 1}}"#,
-            serde_json::to_string(&params).unwrap()
-        ),
-    )?;
-    log::info!("{}", response);
-    let block_chain: ResponseBlockChain<'_> = parse_response(&response)?;
+    let block_chain = get_block_chain_paged(&mut stream, start, end)?;
     let mut hm = HashMap::new();
 
     if csv {
@@ -1304,18 +1344,7 @@ pub fn search_requests(
         }
     };
 
-    let params = GetBlockChainParams {
-        epoch: start,
-        limit: end,
-    };
-    let response = send_request(
-        &mut stream,
-        &format!(
-            r#"{{"jsonrpc": "2.0","method": "getBlockChain", "params": {}, "id": 1}}"#,
-            serde_json::to_string(&params).unwrap()
-        ),
-    )?;
-    let block_chain: ResponseBlockChain<'_> = parse_response(&response)?;
+    let block_chain = get_block_chain_paged(&mut stream, start, end)?;
     log::info!("Processing {} blocks", block_chain.len());
 
     for (_epoch, hash) in block_chain {
@@ -1904,6 +1933,78 @@ fn prompt_user_for_priority_selection(
 mod tests {
     use super::*;
 
+    /// Mock of a node whose getBlockChain method returns at most `max_limit` blocks per request
+    struct MockBlockChainNode {
+        epochs: Vec<u32>,
+        max_limit: usize,
+        requests: Vec<GetBlockChainParams>,
+        response: io::Cursor<Vec<u8>>,
+    }
+
+    impl Write for MockBlockChainNode {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            // Ignore the newline that terminates the request
+            if buf.iter().all(u8::is_ascii_whitespace) {
+                return Ok(buf.len());
+            }
+            let request: serde_json::Value = serde_json::from_slice(buf).unwrap();
+            let params: GetBlockChainParams =
+                serde_json::from_value(request["params"].clone()).unwrap();
+            let limit = match usize::try_from(params.limit).unwrap() {
+                0 => self.max_limit,
+                limit => limit.min(self.max_limit),
+            };
+            let block_chain: Vec<(u32, String)> = self
+                .epochs
+                .iter()
+                .filter(|epoch| i64::from(**epoch) >= params.epoch)
+                .take(limit)
+                .map(|epoch| (*epoch, format!("hash{}", epoch)))
+                .collect();
+            self.requests.push(params);
+            let response = serde_json::json!({"jsonrpc": "2.0", "result": block_chain, "id": 1});
+            self.response = io::Cursor::new(format!("{}\n", response).into_bytes());
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Read for MockBlockChainNode {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.response.read(buf)
+        }
+    }
+
+    #[test]
+    fn get_block_chain_in_pages() {
+        let mut node = MockBlockChainNode {
+            epochs: vec![0, 1, 3, 4, 5, 8, 9],
+            max_limit: 3,
+            requests: vec![],
+            response: Default::default(),
+        };
+
+        // A limit of 0 gets the whole chain, even if the node caps the size of the responses
+        let block_chain = get_block_chain_paged(&mut node, 0, 0).unwrap();
+        let epochs: Vec<u32> = block_chain.iter().map(|(epoch, _hash)| *epoch).collect();
+        assert_eq!(epochs, vec![0, 1, 3, 4, 5, 8, 9]);
+        assert_eq!(block_chain[6].1, "hash9");
+        let requested_epochs: Vec<i64> = node.requests.iter().map(|params| params.epoch).collect();
+        assert_eq!(requested_epochs, vec![0, 4, 9, 10]);
+
+        // A positive limit stops after that number of blocks
+        node.requests.clear();
+        let block_chain = get_block_chain_paged(&mut node, 1, 5).unwrap();
+        let epochs: Vec<u32> = block_chain.iter().map(|(epoch, _hash)| *epoch).collect();
+        assert_eq!(epochs, vec![1, 3, 4, 5, 8]);
+        let requested_limits: Vec<i64> = node.requests.iter().map(|params| params.limit).collect();
+        assert_eq!(requested_limits, vec![5, 2]);
+    }
+
     #[test]
     fn parse_invalid() {
         let nothing: Result<(), _> = parse_response("");
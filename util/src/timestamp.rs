@@ -40,8 +40,9 @@ pub fn get_local_timestamp() -> (i64, u32) {
     (utc_secs, utc_subsec_nanos)
 }
 
-/// Update NTP timestamp
-pub fn update_global_timestamp(addr: &str) {
+/// Update NTP timestamp, returning the timestamp obtained from the NTP server if the request
+/// succeeded
+pub fn update_global_timestamp(addr: &str) -> Option<(i64, u32)> {
     match get_timestamp_ntp(addr) {
         Ok(ntp) => {
             let utc = get_local_timestamp();
@@ -65,9 +66,13 @@ pub fn update_global_timestamp(addr: &str) {
                 );
                 ntp_diff.bigger = false;
             }
+
+            Some(ntp)
         }
         Err(e) => {
             log::warn!("NTP request failed: {}", e);
+
+            None
         }
     }
 }
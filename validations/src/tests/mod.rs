@@ -29,11 +29,14 @@ use witnet_data_structures::{
 };
 use witnet_protected::Protected;
 use witnet_rad::{
+    cbor_to_vec,
     conditions::*,
     error::RadError,
     filters::RadonFilters,
+    operators::RadonOpCodes,
     reducers::RadonReducers,
     types::{bytes::RadonBytes, integer::RadonInteger, RadonTypes},
+    CborValue,
 };
 
 use crate::validations::*;
@@ -2078,6 +2081,155 @@ fn data_request_empty_scripts() {
     );
 }
 
+#[test]
+fn data_request_http_head_with_body() {
+    let data_request = RADRequest {
+        time_lock: 0,
+        retrieve: vec![RADRetrieve {
+            kind: RADType::HttpHead,
+            url: "https://blockchain.info/q/latesthash".to_string(),
+            script: vec![0x80],
+            body: vec![0x01],
+            headers: vec![],
+        }],
+        aggregate: RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        },
+        tally: RADTally {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        },
+    };
+
+    // HTTP-HEAD requests may have extra headers, but no body
+    let mut valid_data_request = data_request.clone();
+    valid_data_request.retrieve[0].body = vec![];
+    valid_data_request.retrieve[0].headers = vec![(
+        "If-Modified-Since".to_string(),
+        "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+    )];
+    test_rad_request(valid_data_request).unwrap();
+
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::MalformedRetrieval {
+            kind: RADType::HttpHead,
+            expected_fields: "kind, script, url".to_string(),
+            actual_fields: "body, kind, script, url".to_string(),
+        },
+    );
+}
+
+#[test]
+fn data_request_http_head_before_wip_activation() {
+    let data_request = RADRequest {
+        time_lock: 0,
+        retrieve: vec![RADRetrieve {
+            kind: RADType::HttpHead,
+            url: "https://blockchain.info/q/latesthash".to_string(),
+            script: vec![0x80],
+            body: vec![],
+            headers: vec![],
+        }],
+        aggregate: RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        },
+        tally: RADTally {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        },
+    };
+
+    let mut active_wips = all_wips_active();
+    validate_rad_request(&data_request, &active_wips).unwrap();
+
+    // Disable WIP0028
+    active_wips.active_wips.remove("WIP0028");
+    let x = validate_rad_request(&data_request, &active_wips);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidRadType,
+    );
+
+    // Nodes running with the current mainnet WIPs reject it as well
+    let x = validate_rad_request(&data_request, &current_active_wips());
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidRadType,
+    );
+}
+
+/// Retrieval script consisting of a single call to `op_code`, without arguments
+fn single_call_script(op_code: RadonOpCodes) -> Vec<u8> {
+    let op_code = op_code as u8;
+    // Integers lower than 24 are encoded in the CBOR header
+    if op_code < 24 {
+        vec![0x81, op_code]
+    } else {
+        vec![0x81, 0x18, op_code]
+    }
+}
+
+#[test]
+fn data_request_wip0028_operators_before_wip_activation() {
+    for op_code in [
+        RadonOpCodes::ArrayJoin,
+        RadonOpCodes::StringSplit,
+        RadonOpCodes::ArrayGroupReduce,
+        RadonOpCodes::StringAsBytes,
+        RadonOpCodes::MapGetOrDefault,
+        RadonOpCodes::Length,
+        RadonOpCodes::FloatCeilingToFloat,
+        RadonOpCodes::FloatFloorToFloat,
+        RadonOpCodes::FloatRoundToFloat,
+    ] {
+        let mut data_request = example_data_request();
+        data_request.retrieve[0].script = single_call_script(op_code);
+
+        let mut active_wips = all_wips_active();
+        validate_rad_request(&data_request, &active_wips).unwrap();
+
+        // Disable WIP0028
+        active_wips.active_wips.remove("WIP0028");
+        let x = validate_rad_request(&data_request, &active_wips);
+        assert_eq!(
+            x.unwrap_err().downcast::<RadError>().unwrap(),
+            RadError::UnknownOperator {
+                code: op_code as i128
+            },
+            "{:?}",
+            op_code
+        );
+    }
+}
+
+#[test]
+fn data_request_wip0028_operators_in_subscript_before_wip_activation() {
+    // [ArrayMap, [Length]]: the gated operator is only used inside the subscript
+    let script = CborValue::Array(vec![CborValue::Array(vec![
+        CborValue::Integer(RadonOpCodes::ArrayMap as i128),
+        CborValue::Array(vec![CborValue::Integer(RadonOpCodes::Length as i128)]),
+    ])]);
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].script = cbor_to_vec(&script).unwrap();
+
+    let mut active_wips = all_wips_active();
+    validate_rad_request(&data_request, &active_wips).unwrap();
+
+    // Disable WIP0028
+    active_wips.active_wips.remove("WIP0028");
+    let x = validate_rad_request(&data_request, &active_wips);
+    assert_eq!(
+        x.unwrap_err().downcast::<RadError>().unwrap(),
+        RadError::UnknownOperator {
+            code: RadonOpCodes::Length as i128
+        },
+    );
+}
+
 #[test]
 fn data_request_witnesses_0() {
     // A data request with 0 witnesses is invalid
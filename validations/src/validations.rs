@@ -46,7 +46,10 @@ use witnet_rad::{
     },
     error::RadError,
     operators::RadonOpCodes,
-    script::{create_radon_script_from_filters_and_reducer, unpack_radon_script},
+    script::{
+        create_radon_script_from_filters_and_reducer, unpack_radon_script, unpack_subscript,
+        RadonCall,
+    },
     types::{serial_iter_decode, RadonTypes},
 };
 
@@ -275,6 +278,55 @@ pub fn validate_mint_transaction(
     Ok(())
 }
 
+/// RADON operators introduced by WIP0028. Nodes that do not know an operator reject the whole
+/// script, so these operators are invalid until WIP0028 activates.
+const WIP0028_OPERATORS: &[RadonOpCodes] = &[
+    RadonOpCodes::ArrayJoin,
+    RadonOpCodes::StringSplit,
+    RadonOpCodes::ArrayGroupReduce,
+    RadonOpCodes::StringAsBytes,
+    RadonOpCodes::MapGetOrDefault,
+    RadonOpCodes::Length,
+    RadonOpCodes::FloatCeilingToFloat,
+    RadonOpCodes::FloatFloorToFloat,
+    RadonOpCodes::FloatRoundToFloat,
+];
+
+/// RADON operators whose first argument is a subscript that is executed by the interpreter
+const SUBSCRIPT_OPERATORS: &[RadonOpCodes] = &[
+    RadonOpCodes::ArrayFilter,
+    RadonOpCodes::ArrayMap,
+    RadonOpCodes::ArraySort,
+];
+
+/// Reject retrieval scripts that use any of the operators introduced by WIP0028, including those
+/// nested inside the subscripts of array operators
+fn check_operators_before_wip0028(rad_script: &[RadonCall]) -> Result<(), RadError> {
+    for (op_code, args) in rad_script {
+        if WIP0028_OPERATORS.contains(op_code) {
+            return Err(RadError::UnknownOperator {
+                code: *op_code as i128,
+            });
+        }
+
+        if !SUBSCRIPT_OPERATORS.contains(op_code) {
+            continue;
+        }
+
+        // Filters can also be called with a filter code instead of a subscript, and malformed
+        // subscripts are only detected when the script is executed
+        if let Some(subscript) = args
+            .as_ref()
+            .and_then(|args| args.first())
+            .and_then(|arg| unpack_subscript(arg).ok())
+        {
+            check_operators_before_wip0028(&subscript)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Function to validate a rad request
 pub fn validate_rad_request(
     rad_request: &RADRequest,
@@ -287,9 +339,17 @@ pub fn validate_rad_request(
     }
 
     for path in retrieval_paths {
+        // HTTP-HEAD retrievals are a protocol upgrade, they are invalid until WIP0028 activates
+        if path.kind == RADType::HttpHead && !active_wips.wip0028() {
+            return Err(DataRequestError::InvalidRadType.into());
+        }
+
         if active_wips.wip0020() {
             path.check_fields()?;
-            unpack_radon_script(path.script.as_slice())?;
+            let rad_script = unpack_radon_script(path.script.as_slice())?;
+            if !active_wips.wip0028() {
+                check_operators_before_wip0028(&rad_script)?;
+            }
 
             // Regarding WIP-0019 activation:
             // Before -> Only RADType enum 0 position is valid
@@ -303,6 +363,7 @@ pub fn validate_rad_request(
             // This is before WIP-0020, so any fields introduced since then must be rejected
             path.check_fields_before_wip0020()?;
             let rad_script = unpack_radon_script(path.script.as_slice())?;
+            check_operators_before_wip0028(&rad_script)?;
 
             // Scripts with new operators are invalid before TAPI activation
             for rad_call in rad_script {
@@ -41,6 +41,10 @@ where
     Ok(WitnessingConfig {
         transports: valid,
         paranoid_threshold: config.paranoid_threshold,
+        user_agent: config.user_agent.clone(),
+        max_response_size: config.max_response_size,
+        retrieval_attempts: config.retrieval_attempts,
+        retrieval_retry_delay: config.retrieval_retry_delay,
     })
 }
 
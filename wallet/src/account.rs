@@ -1,5 +1,5 @@
 use crate::{constants, types};
-use witnet_crypto::key::{ExtendedSK, KeyPath};
+use witnet_crypto::key::{ExtendedPK, ExtendedSK, KeyPath};
 
 /// Result type for accounts-related operations that can fail.
 pub type Result<T> = std::result::Result<T, failure::Error>;
@@ -37,8 +37,27 @@ pub fn gen_account(account_index: u32, master_key: &ExtendedSK) -> Result<types:
 
     let account = types::Account {
         index: account_index,
-        external,
-        internal,
+        external: types::Keychain::Secret(external),
+        internal: types::Keychain::Secret(internal),
+    };
+
+    Ok(account)
+}
+
+/// Generate a watch-only account from the extended public key of an account.
+///
+/// Addresses of watch-only accounts can be derived as usual, but they cannot sign transactions.
+pub fn gen_watch_only_account(
+    account_index: u32,
+    account_key: &ExtendedPK,
+) -> Result<types::Account> {
+    let external = account_key.derive(&KeyPath::default().index(0))?;
+    let internal = account_key.derive(&KeyPath::default().index(1))?;
+
+    let account = types::Account {
+        index: account_index,
+        external: types::Keychain::Public(external),
+        internal: types::Keychain::Public(internal),
     };
 
     Ok(account)
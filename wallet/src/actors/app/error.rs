@@ -22,6 +22,10 @@ pub enum Error {
     SessionNotFound,
     #[fail(display = "session(s) are still open")]
     SessionsStillOpen,
+    #[fail(display = "administrative methods are disabled")]
+    AdminMethodsDisabled,
+    #[fail(display = "rate limit exceeded")]
+    RateLimitExceeded,
     #[fail(display = "wallet not found")]
     WalletNotFound,
     #[fail(display = "wallet with id {} already exists", _0)]
@@ -65,6 +69,8 @@ impl Error {
                 )
             }
             Error::SessionsStillOpen => (401, "Unauthorized", None),
+            Error::AdminMethodsDisabled => (403, "Forbidden", None),
+            Error::RateLimitExceeded => (429, "Rate Limit Exceeded", None),
         }
     }
 }
@@ -127,6 +133,19 @@ impl From<actors::worker::Error> for Error {
                 }},
                 "Wallet account has not enough balance",
             )),
+            actors::worker::Error::Repository(e @ repository::Error::AddressLabelTaken { .. }) => {
+                validation_error(field_error("label", e.to_string()))
+            }
+            actors::worker::Error::Repository(e @ repository::Error::TransactionNotPending(_)) => {
+                validation_error(field_error("transaction", e.to_string()))
+            }
+            actors::worker::Error::Repository(
+                e @ repository::Error::ReplacementFeeTooLow { .. }
+                | e @ repository::Error::ReplacementChangeTooLow { .. },
+            ) => validation_error(field_error("fee", e.to_string())),
+            actors::worker::Error::Repository(repository::Error::WatchOnly) => {
+                validation_error(field_error("wallet_id", "Watch-only wallet cannot sign"))
+            }
             actors::worker::Error::JsonRpcTimeout => Error::JsonRpcTimeout,
             _ => internal_error(err),
         }
@@ -0,0 +1,22 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use crate::types;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompactDatabaseRequest {
+    session_id: types::SessionId,
+}
+
+impl Message for CompactDatabaseRequest {
+    type Result = app::Result<()>;
+}
+
+impl Handler<CompactDatabaseRequest> for app::App {
+    type Result = app::ResponseActFuture<()>;
+
+    fn handle(&mut self, msg: CompactDatabaseRequest, _ctx: &mut Self::Context) -> Self::Result {
+        self.compact_database(msg.session_id)
+    }
+}
@@ -2,7 +2,7 @@ use actix::prelude::*;
 use serde::{Deserialize, Serialize};
 use witnet_config::defaults::PSEUDO_CONSENSUS_CONSTANTS_WIP0022_REWARD_COLLATERAL_RATIO;
 use witnet_data_structures::{
-    chain::{tapi::current_active_wips, DataRequestOutput, Hashable},
+    chain::{tapi::current_active_wips, DataRequestOutput, Hashable, RADRequest},
     fee::{deserialize_fee_backwards_compatible, AbsoluteFee, Fee},
     proto::ProtobufConvert,
     transaction::Transaction,
@@ -124,6 +124,7 @@ impl Handler<CreateDataReqRequest> for app::App {
 /// To be valid it must pass these checks:
 /// - value is greater that the sum of `witnesses` times the sum of the fees
 /// - value minus all the fees must divisible by the number of witnesses
+/// - the RAD request must be valid, as checked by the node when the transaction is received
 fn validate(
     request: DataRequestOutput,
     minimum_collateral: u64,
@@ -139,11 +140,86 @@ fn validate(
     )
     .map_err(|err| app::field_error("request", format!("{}", err)));
 
-    let data_request = witnet_validations::validations::validate_rad_request(
-        &req.data_request,
-        &current_active_wips(),
-    )
-    .map_err(|err| app::field_error("dataRequest", format!("{}", err)));
+    let data_request = validate_rad_request(&req.data_request);
 
     app::combine_field_errors(request, data_request, move |_, _| req)
 }
+
+/// Validate the RAD request embedded in a data request.
+///
+/// The scripts of the retrieval sources are checked one by one first, so that the errors point to
+/// the source that cannot be decoded.
+fn validate_rad_request(rad_request: &RADRequest) -> Result<(), app::ValidationErrors> {
+    let script_errors: app::ValidationErrors = rad_request
+        .retrieve
+        .iter()
+        .enumerate()
+        .filter_map(|(i, retrieve)| {
+            witnet_rad::script::unpack_radon_script(&retrieve.script)
+                .err()
+                .map(|err| {
+                    (
+                        format!("dataRequest.retrieve[{}].script", i),
+                        format!("{}", err),
+                    )
+                })
+        })
+        .collect();
+    if !script_errors.is_empty() {
+        return Err(script_errors);
+    }
+
+    witnet_validations::validations::validate_rad_request(rad_request, &current_active_wips())
+        .map_err(|err| app::field_error("dataRequest", format!("{}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use witnet_data_structures::chain::{RADAggregate, RADRetrieve, RADTally, RADType};
+    use witnet_rad::reducers::RadonReducers;
+
+    use super::*;
+
+    fn example_rad_request(script: Vec<u8>) -> RADRequest {
+        RADRequest {
+            time_lock: 0,
+            retrieve: vec![RADRetrieve {
+                kind: RADType::HttpGet,
+                url: "https://blockchain.info/q/latesthash".to_string(),
+                script,
+                body: vec![],
+                headers: vec![],
+            }],
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+            tally: RADTally {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_rad_request() {
+        // An empty array is a valid script
+        assert_eq!(
+            validate_rad_request(&example_rad_request(vec![128])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rad_request_unparseable_script() {
+        let mut rad_request = example_rad_request(vec![128]);
+        rad_request.retrieve.push(RADRetrieve {
+            script: vec![0xFF, 0xFF],
+            ..rad_request.retrieve[0].clone()
+        });
+
+        let errors = validate_rad_request(&rad_request).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "dataRequest.retrieve[1].script");
+    }
+}
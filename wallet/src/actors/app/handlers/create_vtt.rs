@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use actix::prelude::*;
 use serde::{Deserialize, Serialize};
 use witnet_data_structures::{
-    chain::{Environment, Hashable, OutputPointer, PublicKeyHash, ValueTransferOutput},
+    chain::{address_to_pkh, Environment, Hashable, OutputPointer, ValueTransferOutput},
     fee::{deserialize_fee_backwards_compatible, AbsoluteFee, Fee},
     proto::ProtobufConvert,
     transaction::Transaction,
@@ -181,7 +181,7 @@ pub fn validate_output_addresses(
         Environment::Mainnet
     };
     outputs.iter().try_fold(vec![], |mut acc, output| {
-        PublicKeyHash::from_bech32(environment, &output.address)
+        address_to_pkh(environment, &output.address)
             .map(|pkh| {
                 acc.push(ValueTransferOutput {
                     pkh,
@@ -0,0 +1,154 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+use witnet_data_structures::{
+    chain::priority::PrioritiesEstimate, fee::Fee, utxo_pool::UtxoSelectionStrategy,
+};
+
+use crate::{
+    actors::{
+        app::{self, validate_output_addresses, VttOutputParams},
+        worker,
+    },
+    types::{self, from_generic_type_vec, into_generic_type_vec, VttOutputParamsHelper},
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EstimateFeeRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+    #[serde(
+        serialize_with = "into_generic_type_vec::<_, VttOutputParamsHelper, _>",
+        deserialize_with = "from_generic_type_vec::<_, VttOutputParamsHelper, _>"
+    )]
+    outputs: Vec<VttOutputParams>,
+}
+
+/// Recommended absolute fees (in nanowits) for a value transfer transaction, by priority tier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EstimateFeeResponse {
+    /// Weight of the transaction that would be built with the current wallet UTXOs
+    #[serde(
+        serialize_with = "types::u32_to_string",
+        deserialize_with = "types::number_from_string"
+    )]
+    pub weight: u32,
+    #[serde(
+        serialize_with = "types::u64_to_string",
+        deserialize_with = "types::number_from_string"
+    )]
+    pub low: u64,
+    #[serde(
+        serialize_with = "types::u64_to_string",
+        deserialize_with = "types::number_from_string"
+    )]
+    pub medium: u64,
+    #[serde(
+        serialize_with = "types::u64_to_string",
+        deserialize_with = "types::number_from_string"
+    )]
+    pub high: u64,
+}
+
+impl Message for EstimateFeeRequest {
+    type Result = app::Result<EstimateFeeResponse>;
+}
+
+impl Handler<EstimateFeeRequest> for app::App {
+    type Result = app::ResponseActFuture<EstimateFeeResponse>;
+
+    fn handle(&mut self, msg: EstimateFeeRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let testnet = self.params.testnet;
+        let validated =
+            validate_output_addresses(testnet, &msg.outputs).map_err(app::validation_error);
+
+        let f = fut::result(validated)
+            .and_then(move |outputs, act: &mut Self, _ctx| {
+                // Build (but do not broadcast) the transaction to find out its actual weight
+                let params = types::VttParams {
+                    fee: Fee::default(),
+                    outputs,
+                    utxo_strategy: UtxoSelectionStrategy::default(),
+                    selected_utxos: Default::default(),
+                    preview: true,
+                };
+
+                act.create_vtt(&msg.session_id, &msg.wallet_id, params)
+            })
+            .and_then(
+                |worker::CreateVttResponse { transaction, .. }, act: &mut Self, _ctx| {
+                    let weight = transaction.transaction.weight();
+
+                    act.forward("priority".to_string(), jsonrpc_core::Params::None)
+                        .into_actor(act)
+                        .and_then(move |value, _act, _ctx| {
+                            fut::result(
+                                serde_json::from_value::<PrioritiesEstimate>(value)
+                                    .map(|priorities| fee_tiers(&priorities, weight))
+                                    .map_err(app::node_error),
+                            )
+                        })
+                },
+            );
+
+        Box::pin(f)
+    }
+}
+
+/// Compute the recommended fees for a transaction of the given weight, according to the priority
+/// estimates of the value transfer transactions recently seen by the node.
+pub fn fee_tiers(priorities: &PrioritiesEstimate, weight: u32) -> EstimateFeeResponse {
+    let fee = |estimate: &witnet_data_structures::chain::priority::PriorityEstimate| {
+        estimate.priority.derive_fee_wit(weight).nanowits()
+    };
+
+    EstimateFeeResponse {
+        weight,
+        low: fee(&priorities.vtt_low),
+        medium: fee(&priorities.vtt_medium),
+        high: fee(&priorities.vtt_high),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_tiers_from_weight() {
+        // Response of the `priority` method of a node, only the VTT tiers are relevant here
+        let priorities: PrioritiesEstimate = serde_json::from_value(serde_json::json!({
+            "drt_stinky": {"priority": 0.0, "time_to_block": 21600},
+            "drt_low": {"priority": 0.0, "time_to_block": 3600},
+            "drt_medium": {"priority": 0.0, "time_to_block": 900},
+            "drt_high": {"priority": 0.0, "time_to_block": 300},
+            "drt_opulent": {"priority": 0.0, "time_to_block": 60},
+            "vtt_stinky": {"priority": 0.1, "time_to_block": 21600},
+            "vtt_low": {"priority": 0.2, "time_to_block": 3600},
+            "vtt_medium": {"priority": 0.3, "time_to_block": 900},
+            "vtt_high": {"priority": 0.4, "time_to_block": 300},
+            "vtt_opulent": {"priority": 1.5, "time_to_block": 60},
+        }))
+        .unwrap();
+
+        // One input and two outputs
+        let weight = 493;
+        let tiers = fee_tiers(&priorities, weight);
+
+        assert_eq!(
+            tiers,
+            EstimateFeeResponse {
+                weight,
+                low: 98,
+                medium: 147,
+                high: 197,
+            }
+        );
+        assert!(tiers.low <= tiers.medium && tiers.medium <= tiers.high);
+
+        // Heavier transactions need higher fees for the same priority
+        let heavier = fee_tiers(&priorities, 2 * weight);
+        assert!(heavier.low > tiers.low);
+        assert!(heavier.medium > tiers.medium);
+        assert!(heavier.high > tiers.high);
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::{Component, Path, PathBuf};
+
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use crate::types;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportTransactionsCsvRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+    /// File where the CSV is written, relative to the export directory configured in the wallet.
+    /// It is overwritten if it already exists
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportTransactionsCsvResponse {
+    /// File where the CSV was written, with one row per balance movement after the header
+    pub path: PathBuf,
+    /// Number of balance movements written to the file
+    pub movements: u32,
+}
+
+impl Message for ExportTransactionsCsvRequest {
+    type Result = app::Result<ExportTransactionsCsvResponse>;
+}
+
+impl Handler<ExportTransactionsCsvRequest> for app::App {
+    type Result = app::ResponseActFuture<ExportTransactionsCsvResponse>;
+
+    fn handle(
+        &mut self,
+        msg: ExportTransactionsCsvRequest,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let validated = validate_export_path(&msg.path).map_err(app::validation_error);
+
+        let f = fut::result(validated).and_then(move |(), slf: &mut Self, _ctx| {
+            let path = slf.params.export_path.join(&msg.path);
+
+            slf.export_transactions_csv(msg.session_id, msg.wallet_id, path.clone())
+                .map_ok(|movements, _, _| ExportTransactionsCsvResponse { path, movements })
+        });
+
+        Box::pin(f)
+    }
+}
+
+/// Check that the path of an export is a file name relative to the export directory, so that
+/// clients cannot write files anywhere else in the filesystem.
+pub fn validate_export_path(path: &Path) -> Result<(), app::ValidationErrors> {
+    let relative = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+    if !relative || path.file_name().is_none() {
+        return Err(app::field_error(
+            "path",
+            "Path must be a file name relative to the export directory, without `..` components",
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{actors::app, types};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportWalletBackupRequest {
+    pub wallet_id: String,
+    /// Password used to encrypt the master key inside the backup
+    pub password: types::Password,
+    pub session_id: types::SessionId,
+}
+
+#[derive(Serialize)]
+pub struct ExportWalletBackupResponse {
+    backup: String,
+}
+
+impl Message for ExportWalletBackupRequest {
+    type Result = Result<ExportWalletBackupResponse, app::Error>;
+}
+
+impl Handler<ExportWalletBackupRequest> for app::App {
+    type Result = app::ResponseActFuture<ExportWalletBackupResponse>;
+
+    fn handle(&mut self, msg: ExportWalletBackupRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let f = self
+            .export_wallet_backup(msg.session_id, msg.wallet_id, msg.password)
+            .map_ok(|backup, _, _| ExportWalletBackupResponse { backup });
+
+        Box::pin(f)
+    }
+}
@@ -1,10 +1,12 @@
 use actix::prelude::*;
 
-use crate::actors::app;
+use crate::{actors::app, types};
 
 pub struct ForwardRequest {
     pub method: String,
     pub params: jsonrpc_core::Params,
+    /// Session on behalf of which the request is forwarded, used for rate limiting
+    pub session_id: Option<types::SessionId>,
 }
 
 impl Message for ForwardRequest {
@@ -15,8 +17,42 @@ impl Handler<ForwardRequest> for app::App {
     type Result = app::ResponseFuture<serde_json::Value>;
 
     fn handle(&mut self, msg: ForwardRequest, _ctx: &mut Self::Context) -> Self::Result {
+        if let Err(e) = self.check_forward_rate_limit(msg.session_id.as_ref()) {
+            log::warn!(
+                "Rate limit exceeded when forwarding {} to the node",
+                msg.method
+            );
+
+            return Box::pin(futures::future::ready(Err(e)));
+        }
+
         let f = self.forward(msg.method, msg.params);
 
         Box::pin(f)
     }
 }
+
+impl ForwardRequest {
+    /// Build a request to be forwarded to the node. If the params are an object containing a
+    /// `session_id`, it is removed from them, as it is only meaningful to the wallet.
+    pub fn new(method: String, params: jsonrpc_core::Params) -> Self {
+        match params {
+            jsonrpc_core::Params::Map(mut map) => {
+                let session_id = map
+                    .remove("session_id")
+                    .and_then(|session_id| serde_json::from_value(session_id).ok());
+
+                Self {
+                    method,
+                    params: jsonrpc_core::Params::Map(map),
+                    session_id,
+                }
+            }
+            params => Self {
+                method,
+                params,
+                session_id: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use crate::types;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetAddressByLabelRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+    label: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetAddressByLabelResponse {
+    /// Address that has the requested label, if any
+    pub address: Option<String>,
+}
+
+impl Message for GetAddressByLabelRequest {
+    type Result = app::Result<GetAddressByLabelResponse>;
+}
+
+impl Handler<GetAddressByLabelRequest> for app::App {
+    type Result = app::ResponseActFuture<GetAddressByLabelResponse>;
+
+    fn handle(&mut self, msg: GetAddressByLabelRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let f = self
+            .get_address_by_label(msg.session_id, msg.wallet_id, msg.label)
+            .map_ok(|address, _, _| GetAddressByLabelResponse { address });
+
+        Box::pin(f)
+    }
+}
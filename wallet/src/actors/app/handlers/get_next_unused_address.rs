@@ -0,0 +1,38 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{actors::app, model, types};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetNextUnusedAddressRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetNextUnusedAddressResponse {
+    pub address: String,
+    pub path: String,
+}
+
+impl Message for GetNextUnusedAddressRequest {
+    type Result = app::Result<GetNextUnusedAddressResponse>;
+}
+
+impl Handler<GetNextUnusedAddressRequest> for app::App {
+    type Result = app::ResponseActFuture<GetNextUnusedAddressResponse>;
+
+    fn handle(
+        &mut self,
+        msg: GetNextUnusedAddressRequest,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let f =
+            self.get_next_unused_address(msg.session_id, msg.wallet_id)
+                .map_ok(|model::Address { address, path, .. }, _, _| {
+                    GetNextUnusedAddressResponse { address, path }
+                });
+
+        Box::pin(f)
+    }
+}
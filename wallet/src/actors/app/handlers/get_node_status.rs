@@ -0,0 +1,34 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use witnet_data_structures::chain::SyncStatus;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetNodeStatusRequest;
+
+#[derive(Debug, Serialize)]
+pub struct GetNodeStatusResponse {
+    /// Last status received from the node, or `None` if the node has not answered yet
+    pub status: Option<SyncStatus>,
+    /// Seconds elapsed since the status was received
+    pub age_secs: Option<u64>,
+}
+
+impl Message for GetNodeStatusRequest {
+    type Result = app::Result<GetNodeStatusResponse>;
+}
+
+impl Handler<GetNodeStatusRequest> for app::App {
+    type Result = <GetNodeStatusRequest as Message>::Result;
+
+    fn handle(&mut self, _msg: GetNodeStatusRequest, _ctx: &mut Self::Context) -> Self::Result {
+        // The status is refreshed by `periodic_node_request`, so there is no need to ask the node
+        let (status, age_secs) = match self.state.cached_sync_status() {
+            Some((status, age_secs)) => (Some(status), Some(age_secs)),
+            None => (None, None),
+        };
+
+        Ok(GetNodeStatusResponse { status, age_secs })
+    }
+}
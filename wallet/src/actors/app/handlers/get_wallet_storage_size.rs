@@ -0,0 +1,31 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use crate::{model, types};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetWalletStorageSizeRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+}
+
+pub type GetWalletStorageSizeResponse = model::WalletStorageSize;
+
+impl Message for GetWalletStorageSizeRequest {
+    type Result = app::Result<GetWalletStorageSizeResponse>;
+}
+
+impl Handler<GetWalletStorageSizeRequest> for app::App {
+    type Result = app::ResponseActFuture<GetWalletStorageSizeResponse>;
+
+    fn handle(
+        &mut self,
+        msg: GetWalletStorageSizeRequest,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let f = self.get_wallet_storage_size(msg.session_id, msg.wallet_id);
+
+        Box::pin(f)
+    }
+}
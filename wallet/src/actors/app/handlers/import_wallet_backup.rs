@@ -0,0 +1,124 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use witnet_futures_utils::TryFutureExt2;
+
+use crate::{
+    actors::{app, worker},
+    model, types,
+};
+
+/// Import Wallet Backup request, restoring a wallet from a backup created by
+/// `export_wallet_backup`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportWalletBackupRequest {
+    backup: String,
+    /// Password that was used to encrypt the master key inside the backup
+    backup_password: types::Password,
+    /// Password of the restored wallet
+    password: types::Password,
+    overwrite: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct ImportWalletBackupResponse {
+    wallet_id: String,
+    session_id: types::SessionId,
+    name: Option<String>,
+    description: Option<String>,
+    available_accounts: Vec<u32>,
+    current_account: u32,
+    account_balance: model::WalletBalance,
+    session_expiration_secs: u64,
+    birth_date: types::Epoch,
+}
+
+impl Message for ImportWalletBackupRequest {
+    type Result = Result<ImportWalletBackupResponse, app::Error>;
+}
+
+impl Handler<ImportWalletBackupRequest> for app::App {
+    type Result = app::ResponseActFuture<ImportWalletBackupResponse>;
+
+    fn handle(&mut self, req: ImportWalletBackupRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let password = req.password.clone();
+        let mut address_labels = vec![];
+        let validated_params = app::methods::decode_wallet_backup(&req.backup).and_then(|backup| {
+            address_labels = backup.address_labels;
+            app::methods::validate(
+                req.password,
+                backup.master_key.into(),
+                "xprv".to_string(),
+                backup.name,
+                backup.description,
+                req.overwrite,
+                Some(req.backup_password),
+                Some(types::BirthDate::Imported(backup.birth_date)),
+            )
+        });
+
+        let f = fut::result(validated_params)
+            .and_then(|params, slf: &mut Self, _ctx| {
+                slf.create_wallet(
+                    params.password,
+                    params.seed_source,
+                    params.name,
+                    params.description,
+                    params.overwrite,
+                    params.birth_date,
+                )
+                .into_actor(slf)
+            })
+            .and_then(move |wallet_id, slf: &mut Self, _ctx| {
+                // The restored wallet is unlocked right away, under a new session
+                slf.unlock_wallet(wallet_id.clone(), password).map_ok(
+                    move |types::UnlockedWallet { data, session_id }, slf, ctx| {
+                        slf.set_session_to_expire(session_id.clone())
+                            .expect("Session id should exist after wallet unlock")
+                            .spawn(ctx);
+
+                        let response = ImportWalletBackupResponse {
+                            wallet_id: wallet_id.clone(),
+                            session_id,
+                            name: data.name,
+                            description: data.description,
+                            current_account: data.current_account,
+                            available_accounts: data.available_accounts,
+                            account_balance: data.balance,
+                            session_expiration_secs: slf.params.session_expires_in.as_secs(),
+                            birth_date: data.birth_date.checkpoint,
+                        };
+
+                        (response, wallet_id)
+                    },
+                )
+            })
+            .and_then(move |(response, wallet_id), slf: &mut Self, _ctx| {
+                // Restore the address book of the wallet
+                let wallet = slf
+                    .state
+                    .get_wallet_by_session_and_id(&response.session_id, &wallet_id);
+                let worker_addr = slf.params.worker.clone();
+                let f = async move {
+                    let wallet = wallet?;
+                    for model::AddressLabel { address, label } in address_labels {
+                        let res: app::Result<()> = worker_addr
+                            .send(worker::SetAddressLabel {
+                                wallet: wallet.clone(),
+                                address,
+                                label,
+                            })
+                            .flatten_err()
+                            .await;
+                        res?;
+                    }
+
+                    Ok::<_, app::Error>(response)
+                };
+
+                f.into_actor(slf)
+            });
+
+        Box::pin(f)
+    }
+}
@@ -0,0 +1,40 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListSessionsRequest;
+
+#[derive(Debug, Serialize)]
+pub struct SessionInfo {
+    /// Prefix of the session id, enough to tell sessions apart without being usable as one
+    pub session_id: String,
+    /// Ids of the wallets unlocked in the session
+    pub wallet_ids: Vec<String>,
+    /// Seconds left until the session expires, unless it gets extended
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSessionsResponse {
+    pub sessions: Vec<SessionInfo>,
+}
+
+impl Message for ListSessionsRequest {
+    type Result = app::Result<ListSessionsResponse>;
+}
+
+impl Handler<ListSessionsRequest> for app::App {
+    type Result = <ListSessionsRequest as Message>::Result;
+
+    fn handle(&mut self, _msg: ListSessionsRequest, _ctx: &mut Self::Context) -> Self::Result {
+        if !self.params.enable_admin_methods {
+            return Err(app::Error::AdminMethodsDisabled);
+        }
+
+        Ok(ListSessionsResponse {
+            sessions: self.state.list_sessions(),
+        })
+    }
+}
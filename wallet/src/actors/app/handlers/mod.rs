@@ -1,26 +1,38 @@
 mod close_session;
+mod compact_database;
 mod create_data_req;
 mod create_mnemonics;
 mod create_vtt;
 mod create_wallet;
 mod delete_wallet;
+mod estimate_fee;
 mod export_master_key;
+mod export_transactions_csv;
+mod export_wallet_backup;
 mod forward;
 mod generate_address;
 mod get;
+mod get_address_by_label;
 mod get_addresses;
 mod get_balance;
+mod get_next_unused_address;
+mod get_node_status;
 mod get_transactions;
 mod get_utxo_info;
 mod get_wallet_infos;
+mod get_wallet_storage_size;
+mod import_wallet_backup;
+mod list_sessions;
 mod lock_wallet;
 mod next_subscription_id;
 mod node_notification;
 mod refresh_session;
+mod replace_transaction;
 mod resync;
 mod run_rad_req;
 mod send_transaction;
 mod set;
+mod set_address_label;
 mod shutdown;
 mod sign_data;
 mod subscribe;
@@ -28,29 +40,42 @@ mod unlock_wallet;
 mod unsubscribe;
 mod update_wallet;
 mod validate_mnemonics;
+mod verify_data;
 
 pub use close_session::*;
+pub use compact_database::*;
 pub use create_data_req::*;
 pub use create_mnemonics::*;
 pub use create_vtt::*;
 pub use create_wallet::*;
 pub use delete_wallet::*;
+pub use estimate_fee::*;
 pub use export_master_key::*;
+pub use export_transactions_csv::*;
+pub use export_wallet_backup::*;
 pub use forward::*;
 pub use generate_address::*;
 pub use get::*;
+pub use get_address_by_label::*;
 pub use get_addresses::*;
 pub use get_balance::*;
+pub use get_next_unused_address::*;
+pub use get_node_status::*;
 pub use get_transactions::*;
 pub use get_utxo_info::*;
 pub use get_wallet_infos::*;
+pub use get_wallet_storage_size::*;
+pub use import_wallet_backup::*;
+pub use list_sessions::*;
 pub use lock_wallet::*;
 pub use next_subscription_id::*;
 pub use refresh_session::*;
+pub use replace_transaction::*;
 pub use resync::*;
 pub use run_rad_req::*;
 pub use send_transaction::*;
 pub use set::*;
+pub use set_address_label::*;
 pub use shutdown::*;
 pub use sign_data::*;
 pub use subscribe::*;
@@ -58,3 +83,4 @@ pub use unlock_wallet::*;
 pub use unsubscribe::*;
 pub use update_wallet::*;
 pub use validate_mnemonics::*;
+pub use verify_data::*;
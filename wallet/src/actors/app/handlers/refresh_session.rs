@@ -11,6 +11,8 @@ pub struct RefreshSessionRequest {
 #[derive(Serialize)]
 pub struct RefreshSessionResponse {
     pub success: bool,
+    /// Timestamp at which the session will expire unless it is refreshed again
+    pub expires_at: i64,
 }
 
 impl Message for RefreshSessionRequest {
@@ -21,20 +23,11 @@ impl Handler<RefreshSessionRequest> for app::App {
     type Result = <RefreshSessionRequest as Message>::Result;
 
     fn handle(&mut self, msg: RefreshSessionRequest, ctx: &mut Self::Context) -> Self::Result {
-        let session = self
-            .state
-            .sessions
-            .get_mut(&msg.session_id)
-            .ok_or(app::Error::SessionNotFound)?;
+        let expires_at = self.refresh_session(msg.session_id, ctx)?;
 
-        if !session.session_extended {
-            session.session_extended = true;
-            self.set_session_to_expire(msg.session_id.clone())?
-                .spawn(ctx);
-
-            Ok(RefreshSessionResponse { success: true })
-        } else {
-            Ok(RefreshSessionResponse { success: false })
-        }
+        Ok(RefreshSessionResponse {
+            success: true,
+            expires_at,
+        })
     }
 }
@@ -0,0 +1,54 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actors::app,
+    model,
+    types::{self, from_generic_type, into_generic_type, number_from_string, TransactionHelper},
+};
+use witnet_data_structures::{fee::AbsoluteFee, transaction::Transaction};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaceTransactionRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+    /// Pending transaction to be replaced
+    #[serde(
+        serialize_with = "into_generic_type::<_, TransactionHelper, _>",
+        deserialize_with = "from_generic_type::<_, TransactionHelper, _>"
+    )]
+    transaction: Transaction,
+    /// Absolute fee of the replacement, must be higher than the fee of the original transaction
+    #[serde(deserialize_with = "number_from_string")]
+    fee: AbsoluteFee,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplaceTransactionResponse {
+    pub transaction_id: String,
+    #[serde(serialize_with = "into_generic_type::<_, TransactionHelper, _>")]
+    pub transaction: Transaction,
+    pub fee: AbsoluteFee,
+    pub jsonrpc_result: serde_json::Value,
+    pub balance_movement: Option<model::BalanceMovement>,
+}
+
+impl Message for ReplaceTransactionRequest {
+    type Result = app::Result<ReplaceTransactionResponse>;
+}
+
+impl Handler<ReplaceTransactionRequest> for app::App {
+    type Result = app::ResponseActFuture<ReplaceTransactionResponse>;
+
+    fn handle(&mut self, msg: ReplaceTransactionRequest, _ctx: &mut Self::Context) -> Self::Result {
+        match msg.transaction {
+            Transaction::ValueTransfer(original) => {
+                self.replace_transaction(msg.session_id, msg.wallet_id, original, msg.fee)
+            }
+            _ => Box::pin(actix::fut::err(app::validation_error(app::field_error(
+                "transaction",
+                "Only value transfer transactions can be replaced",
+            )))),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+use crate::types;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetAddressLabelRequest {
+    session_id: types::SessionId,
+    wallet_id: String,
+    address: String,
+    label: String,
+}
+
+impl Message for SetAddressLabelRequest {
+    type Result = app::Result<()>;
+}
+
+impl Handler<SetAddressLabelRequest> for app::App {
+    type Result = app::ResponseActFuture<()>;
+
+    fn handle(&mut self, msg: SetAddressLabelRequest, _ctx: &mut Self::Context) -> Self::Result {
+        self.set_address_label(msg.session_id, msg.wallet_id, msg.address, msg.label)
+    }
+}
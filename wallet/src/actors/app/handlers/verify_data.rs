@@ -0,0 +1,33 @@
+use actix::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::app;
+
+/// Request to verify a signature produced by `sign_data`: the `signature` must have been
+/// produced by the secret key of `public_key` over the SHA256 hash of `data`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyDataRequest {
+    // Message that was signed
+    data: String,
+    signature: String,
+    public_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyDataResponse {
+    pub valid: bool,
+}
+
+impl Message for VerifyDataRequest {
+    type Result = app::Result<VerifyDataResponse>;
+}
+
+impl Handler<VerifyDataRequest> for app::App {
+    type Result = <VerifyDataRequest as Message>::Result;
+
+    fn handle(&mut self, msg: VerifyDataRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let valid = self.verify_data(&msg.data, &msg.signature, &msg.public_key)?;
+
+        Ok(VerifyDataResponse { valid })
+    }
+}
@@ -1,21 +1,30 @@
-use std::sync::Arc;
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 use actix::utils::TimerFunc;
 use futures::FutureExt;
 
-use witnet_crypto::mnemonic;
+use witnet_crypto::{
+    key::{ExtendedPK, ExtendedSK},
+    mnemonic,
+    signature::{PublicKey, Signature},
+};
 use witnet_data_structures::{
-    chain::{Block, InventoryItem, RADRequest, StateMachine, SyncStatus},
-    transaction::Transaction,
+    chain::{Block, Hashable, InventoryItem, RADRequest, StateMachine, SyncStatus},
+    fee::AbsoluteFee,
+    transaction::{Transaction, VTTransaction},
 };
 use witnet_rad::RADRequestExecutionReport;
 
 use crate::{
+    account,
     actors::{
-        worker::{HandleBlockRequest, HandleSuperBlockRequest, NodeStatusRequest, NotifyStatus},
+        worker::{
+            HandleBlockRequest, HandleBlocksRequest, HandleSuperBlockRequest, NodeStatusRequest,
+            NotifyStatus,
+        },
         *,
     },
-    crypto, model,
+    constants, crypto, model,
 };
 
 use super::*;
@@ -137,6 +146,28 @@ impl App {
         Box::pin(f)
     }
 
+    /// Get the first external address of a wallet that has not been used yet, only generating a
+    /// new one if all of them have been used.
+    pub fn get_next_unused_address(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+    ) -> ResponseActFuture<model::Address> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::NextUnusedAddress { wallet })
+                .flatten_err()
+                .into_actor(slf)
+        });
+
+        Box::pin(f)
+    }
+
     /// Get a list of addresses generated by a wallet.
     pub fn get_addresses(
         &mut self,
@@ -187,6 +218,98 @@ impl App {
         Box::pin(f)
     }
 
+    pub fn get_wallet_storage_size(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+    ) -> ResponseActFuture<model::WalletStorageSize> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::GetStorageSize { wallet })
+                .flatten_err()
+                .into_actor(slf)
+        });
+
+        Box::pin(f)
+    }
+
+    /// Export all the balance movements of a wallet as CSV into the file at `path`, returning the
+    /// number of exported movements.
+    pub fn export_transactions_csv(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+        path: PathBuf,
+    ) -> ResponseActFuture<u32> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::ExportTransactionsCsv { wallet, path })
+                .flatten_err()
+                .into_actor(slf)
+        });
+
+        Box::pin(f)
+    }
+
+    /// Label an address in the address book of a wallet. Labels are unique within a wallet.
+    pub fn set_address_label(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+        address: String,
+        label: String,
+    ) -> ResponseActFuture<()> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::SetAddressLabel {
+                    wallet,
+                    address,
+                    label,
+                })
+                .flatten_err()
+                .into_actor(slf)
+        });
+
+        Box::pin(f)
+    }
+
+    /// Look up an address in the address book of a wallet by its label.
+    pub fn get_address_by_label(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+        label: String,
+    ) -> ResponseActFuture<Option<String>> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::GetAddressByLabel { wallet, label })
+                .flatten_err()
+                .into_actor(slf)
+        });
+
+        Box::pin(f)
+    }
+
     /// Delete a wallet providing its WalletID and its SessionID
     pub fn delete_wallet(
         &mut self,
@@ -284,6 +407,24 @@ impl App {
         Box::pin(f)
     }
 
+    /// Check that the given session has not exceeded the rate limit of the requests forwarded to
+    /// the node, if enabled.
+    pub fn check_forward_rate_limit(
+        &mut self,
+        session_id: Option<&types::SessionId>,
+    ) -> Result<()> {
+        match self.params.forward_rate_limit {
+            Some((capacity, refill_rate))
+                if !self
+                    .state
+                    .try_take_forward_token(session_id, capacity, refill_rate) =>
+            {
+                Err(Error::RateLimitExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Forward a Json-RPC call to the node.
     pub fn forward(
         &mut self,
@@ -491,12 +632,34 @@ impl App {
         Box::pin(fut)
     }
 
+    /// Reclaim the disk space taken by overwritten chain data. Any open session is allowed to
+    /// request it, as the database is shared by all the wallets.
+    pub fn compact_database(&self, session_id: types::SessionId) -> ResponseActFuture<()> {
+        let f = fut::result(self.state.get_wallets_by_session(&session_id).map(|_| ())).and_then(
+            move |(), slf: &mut Self, _| {
+                slf.params
+                    .worker
+                    .send(worker::CompactDb)
+                    .flatten_err()
+                    .into_actor(slf)
+            },
+        );
+
+        Box::pin(f)
+    }
+
     /// Return a timer function that can be scheduled to expire the session after the configured time.
-    pub fn set_session_to_expire(&self, session_id: types::SessionId) -> Result<TimerFunc<Self>> {
-        if !self.state.sessions.contains_key(&session_id) {
+    pub fn set_session_to_expire(
+        &mut self,
+        session_id: types::SessionId,
+    ) -> Result<TimerFunc<Self>> {
+        if let Err(err) = self
+            .state
+            .schedule_session_expiration(&session_id, self.params.session_expires_in)
+        {
             log::error!("Session {} does not exist.", &session_id,);
 
-            return Err(app::Error::SessionNotFound);
+            return Err(err);
         }
 
         log::debug!(
@@ -530,6 +693,21 @@ impl App {
         ))
     }
 
+    /// Postpone the expiration of a session, so that clients can keep it alive without performing
+    /// any other operation. Returns the timestamp at which the session will expire.
+    pub fn refresh_session(
+        &mut self,
+        session_id: types::SessionId,
+        ctx: &mut <Self as Actor>::Context,
+    ) -> Result<i64> {
+        // An already extended session will be rescheduled when its current timer fires
+        if self.state.extend_session(&session_id)? {
+            self.set_session_to_expire(session_id.clone())?.spawn(ctx);
+        }
+
+        self.state.session_expiration(&session_id)
+    }
+
     /// Remove a session from the list of active sessions.
     pub fn close_session(&mut self, session_id: types::SessionId) -> Result<()> {
         self.state.remove_session(&session_id)
@@ -594,6 +772,7 @@ impl App {
     /// Handle any kind of notifications received from a Witnet node.
     pub fn handle_notification(&mut self, topic: String, value: serde_json::Value) -> Result<()> {
         match topic.as_str() {
+            "blocks" if value.is_array() => self.handle_blocks_notification(value),
             "blocks" => self.handle_block_notification(value),
             "superblocks" => self.handle_superblock_notification(value),
             _ => {
@@ -620,6 +799,25 @@ impl App {
         Ok(())
     }
 
+    /// Handle notifications containing a batch of new blocks received from a Witnet node.
+    pub fn handle_blocks_notification(&mut self, value: serde_json::Value) -> Result<()> {
+        let blocks: Vec<Arc<Block>> = serde_json::from_value::<Vec<Block>>(value)
+            .map_err(node_error)?
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+
+        // This iterator is collected early so as to free the immutable reference to `self`.
+        let wallets: Vec<types::SessionWallet> = self.state.wallets.values().cloned().collect();
+
+        for wallet in &wallets {
+            let sink = self.state.get_sink(&wallet.session_id);
+            self.handle_blocks_in_worker(blocks.clone(), wallet, sink.clone());
+        }
+
+        Ok(())
+    }
+
     /// Handle superblock notifications received from a Witnet node.
     pub fn handle_superblock_notification(&mut self, value: serde_json::Value) -> Result<()> {
         let superblock_notification =
@@ -655,6 +853,21 @@ impl App {
         });
     }
 
+    /// Offload the processing of a batch of blocks into a worker, so that all of them are applied
+    /// by a single `HandleBlocksRequest` instead of one request per block.
+    pub fn handle_blocks_in_worker(
+        &self,
+        blocks: Vec<Arc<Block>>,
+        wallet: &types::SessionWallet,
+        sink: types::DynamicSink,
+    ) {
+        self.params.worker.do_send(HandleBlocksRequest {
+            blocks,
+            wallet: wallet.clone(),
+            sink,
+        });
+    }
+
     /// Offload superblock processing into a worker that operates on a different Arbiter than the main
     /// server thread, so as not to lock the rest of the application.
     pub fn handle_superblock_in_worker(
@@ -742,6 +955,74 @@ impl App {
         Box::pin(f)
     }
 
+    /// Replace a pending value transfer transaction with one that spends the same inputs but pays
+    /// a higher fee, send it to the node and update the local pending balance movement.
+    pub fn replace_transaction(
+        &self,
+        session_id: types::SessionId,
+        wallet_id: String,
+        original: VTTransaction,
+        fee: AbsoluteFee,
+    ) -> ResponseActFuture<ReplaceTransactionResponse> {
+        let original_hash = original.hash();
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::ReplaceVtt {
+                    wallet: wallet.clone(),
+                    original,
+                    fee,
+                })
+                .flatten_err()
+                .into_actor(slf)
+                .and_then(
+                    move |worker::ReplaceVttResponse { fee, transaction }, act, _ctx| {
+                        act.send_inventory_transaction(transaction.transaction.clone())
+                            .and_then(move |jsonrpc_result, act, _ctx| {
+                                match wallet.replace_local_movement(original_hash, &transaction) {
+                                    Ok(balance_movement) => {
+                                        let sink = act.state.get_sink(&session_id);
+                                        if let Some(balance_movement) = balance_movement.clone() {
+                                            // We send a notification to the client
+                                            let events = Some(vec![types::Event::Movement(
+                                                balance_movement,
+                                            )]);
+                                            act.params
+                                                .worker
+                                                .do_send(NotifyStatus(wallet, sink, events));
+                                        }
+                                        let transaction = transaction.transaction;
+                                        actix::fut::ok(ReplaceTransactionResponse {
+                                            transaction_id: hex::encode(
+                                                transaction.hash().as_ref(),
+                                            ),
+                                            transaction,
+                                            fee,
+                                            jsonrpc_result,
+                                            balance_movement,
+                                        })
+                                    }
+                                    Err(e) => {
+                                        log::error!(
+                                            "Error while replacing local pending movement: {}",
+                                            e
+                                        );
+
+                                        actix::fut::err(Error::Internal(failure::Error::from(e)))
+                                    }
+                                }
+                            })
+                    },
+                )
+        });
+
+        Box::pin(f)
+    }
+
     /// Use wallet's master key to sign message data
     pub fn sign_data(
         &self,
@@ -769,6 +1050,17 @@ impl App {
         Box::pin(f)
     }
 
+    /// Verify that a message signature was produced by the given public key, as returned by
+    /// `sign_data`
+    pub fn verify_data(&self, data: &str, signature: &str, public_key: &str) -> Result<bool> {
+        let signature = Signature::from_str(signature)
+            .map_err(|e| app::validation_error(app::field_error("signature", e.to_string())))?;
+        let public_key = PublicKey::from_str(public_key)
+            .map_err(|e| app::validation_error(app::field_error("public_key", e.to_string())))?;
+
+        Ok(crypto::verify_data(data, &signature, &public_key))
+    }
+
     /// Shutdown system if session id is valid or there are no open sessions
     pub fn shutdown_request(
         &mut self,
@@ -860,9 +1152,11 @@ impl App {
                 let status = serde_json::from_value::<SyncStatus>(res);
                 // Notify if the node status is changed
                 if let Ok(status) = status {
-                    if Some(status.node_state) != act.state.node_state {
+                    let node_state = status.node_state;
+                    act.state.set_sync_status(status);
+                    if Some(node_state) != act.state.node_state {
                         // Update node_state
-                        act.state.node_state = Some(status.node_state);
+                        act.state.node_state = Some(node_state);
                         for wallet in &wallets {
                             let sink = act.state.get_sink(&wallet.session_id);
                             act.params
@@ -907,12 +1201,13 @@ impl App {
         // Validate mnemonics source and data
         let f = fut::result(match seed_source.as_ref() {
             "xprv" => validate_xprv(seed_data, backup_password),
+            "xpub" => validate_xpub(seed_data).map_err(Error::Validation),
             "mnemonics" => mnemonic::Mnemonic::from_phrase(seed_data)
                 .map_err(|err| Error::Validation(app::field_error("seed_data", format!("{}", err))))
                 .map(types::SeedSource::Mnemonics),
             _ => Err(Error::Validation(app::field_error(
                 "seed_source",
-                "Seed source has to be mnemonics|xprv.",
+                "Seed source has to be mnemonics|xprv|xpub.",
             ))),
         })
         // Check if seed was already used in wallet
@@ -994,6 +1289,30 @@ impl App {
         Box::pin(f)
     }
 
+    /// Export a backup of the wallet, with its metadata and its master key encrypted with
+    /// `password`, encoded as a single string
+    pub fn export_wallet_backup(
+        &mut self,
+        session_id: types::SessionId,
+        wallet_id: String,
+        password: types::Password,
+    ) -> ResponseActFuture<String> {
+        let f = fut::result(
+            self.state
+                .get_wallet_by_session_and_id(&session_id, &wallet_id),
+        )
+        .and_then(move |wallet, slf: &mut Self, _| {
+            slf.params
+                .worker
+                .send(worker::ExportWalletBackup { wallet, password })
+                .flatten_err()
+                .into_actor(slf)
+        })
+        .and_then(|backup, _: &mut Self, _| fut::result(encode_wallet_backup(&backup)));
+
+        Box::pin(f)
+    }
+
     /// Handle status from sync error
     pub fn handle_sync_error(&mut self, e: &Error) {
         if let Error::JsonRpcTimeout = e {
@@ -1024,12 +1343,13 @@ pub fn validate(
     let source = match seed_source.as_ref() {
         "xprv" => validate_xprv(seed_data, backup_password)
             .map_err(|e| app::field_error("seed_data", e.to_string())),
+        "xpub" => validate_xpub(seed_data),
         "mnemonics" => mnemonic::Mnemonic::from_phrase(seed_data)
             .map_err(|err| app::field_error("seed_data", format!("{}", err)))
             .map(types::SeedSource::Mnemonics),
         _ => Err(app::field_error(
             "seed_source",
-            "Seed source has to be mnemonics|xprv",
+            "Seed source has to be mnemonics|xprv|xpub",
         )),
     };
     let password = if <str>::len(password.as_ref()) < 8 {
@@ -1076,6 +1396,9 @@ pub fn validate_xprv(
             "Invalid seed data prefix",
         )));
     }
+    let malformed_key = || validation_error(app::field_error("seed_data", "Malformed XPRV key"));
+    let wrong_password =
+        || validation_error(app::field_error("backup_password", "Wrong backup password"));
     let decrypted_key_string = bech32::FromBase32::from_base32(&ciphertext)
         .map_err(|_| {
             validation_error(app::field_error(
@@ -1084,27 +1407,92 @@ pub fn validate_xprv(
             ))
         })
         .and_then(|res: Vec<u8>| {
-            crypto::decrypt_cbc(&res, backup_password.as_ref()).map_err(|_| {
-                validation_error(app::field_error("seed_data", "Could not decrypt seed data"))
+            crypto::decrypt_cbc(&res, backup_password.as_ref()).map_err(|err| match err {
+                crypto::Error::InvalidCiphertext(_) => malformed_key(),
+                _ => wrong_password(),
             })
         })
         .and_then(|decrypted: Vec<u8>| {
-            std::str::from_utf8(&decrypted)
-                .map(|str| str.to_string())
-                .map_err(|_| {
-                    validation_error(app::field_error("seed_data", "Could not decrypt seed data"))
-                })
+            // A wrong password yields random bytes that are almost never valid UTF-8
+            String::from_utf8(decrypted).map_err(|_| wrong_password())
         })?;
 
+    // Only accept the decrypted data if it actually contains valid extended keys
+    let is_valid_key = |key: &types::Password| ExtendedSK::from_slip32(key.as_ref()).is_ok();
     if hrp.as_str() == "xprv" {
-        Ok(types::SeedSource::Xprv(decrypted_key_string.into()))
+        let key: types::Password = decrypted_key_string.into();
+        if !is_valid_key(&key) {
+            return Err(malformed_key());
+        }
+
+        Ok(types::SeedSource::Xprv(key))
     } else {
         let (internal, external) = split_xprv_double(decrypted_key_string)?;
+        if !is_valid_key(&internal) || !is_valid_key(&external) {
+            return Err(malformed_key());
+        }
 
         Ok(types::SeedSource::XprvDouble((internal, external)))
     }
 }
 
+/// Encode a wallet backup as a JSON string
+pub fn encode_wallet_backup(backup: &model::WalletBackup) -> Result<String> {
+    serde_json::to_string(backup).map_err(internal_error)
+}
+
+/// Decode a wallet backup created by `encode_wallet_backup`, checking first that the version of
+/// its format is supported
+pub fn decode_wallet_backup(backup: &str) -> Result<model::WalletBackup> {
+    #[derive(serde::Deserialize)]
+    struct Versioned {
+        version: u32,
+    }
+
+    let malformed = |err: serde_json::Error| {
+        validation_error(app::field_error(
+            "backup",
+            format!("Malformed wallet backup: {}", err),
+        ))
+    };
+    let Versioned { version } = serde_json::from_str(backup).map_err(malformed)?;
+    if version != constants::WALLET_BACKUP_VERSION {
+        return Err(validation_error(app::field_error(
+            "backup",
+            format!(
+                "Unsupported wallet backup version {}, expected version {}",
+                version,
+                constants::WALLET_BACKUP_VERSION
+            ),
+        )));
+    }
+
+    serde_json::from_str(backup).map_err(malformed)
+}
+
+/// Validate an extended public key used to create a watch-only wallet. The key needs to be the one
+/// of the default wallet account.
+pub fn validate_xpub(
+    seed_data: types::Password,
+) -> std::result::Result<types::SeedSource, app::ValidationErrors> {
+    let seed_data_string: &str = seed_data.as_ref();
+    let (_key, path) = ExtendedPK::from_slip32(seed_data_string)
+        .map_err(|e| app::field_error("seed_data", e.to_string()))?;
+
+    let expected_path = account::account_keypath(0);
+    if path != expected_path {
+        return Err(app::field_error(
+            "seed_data",
+            format!(
+                "Extended public key has to belong to account {}, not {}",
+                expected_path, path
+            ),
+        ));
+    }
+
+    Ok(types::SeedSource::Xpub(seed_data_string.to_string().into()))
+}
+
 /// Split a double XPRV string into internal and external keys
 pub fn split_xprv_double(xprv_double_key: String) -> Result<(types::Password, types::Password)> {
     let ocurrences: Vec<(usize, &str)> = xprv_double_key.match_indices("xprv").collect();
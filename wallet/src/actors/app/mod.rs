@@ -3,7 +3,7 @@ use actix::prelude::*;
 use witnet_net::client::tcp::jsonrpc;
 use witnet_net::server::ws::Server;
 
-use crate::types;
+use crate::{actors::worker, types};
 
 pub mod error;
 pub mod handlers;
@@ -40,6 +40,12 @@ impl Actor for App {
         self.node_subscribe("superblocks", ctx);
         self.periodic_node_request(ctx);
 
+        if let Some(interval) = self.params.db_compaction_interval {
+            ctx.run_interval(interval, |act, _ctx| {
+                act.params.worker.do_send(worker::CompactDb);
+            });
+        }
+
         let mut handler =
             jsonrpc_pubsub::PubSubHandler::new(jsonrpc_core::MetaIoHandler::default());
         connect_routes(&mut handler, ctx.address(), Arbiter::current());
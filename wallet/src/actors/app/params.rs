@@ -1,4 +1,4 @@
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use witnet_data_structures::chain::ConsensusConstants;
 use witnet_net::client::tcp::jsonrpc::{GetCurrentNodeUrl, JsonRpcClient, Request};
@@ -15,6 +15,15 @@ pub struct Params {
     pub session_expires_in: Duration,
     pub requests_timeout: Duration,
     pub consensus_constants: ConsensusConstants,
+    /// Period between automatic compactions of the database, if enabled.
+    pub db_compaction_interval: Option<Duration>,
+    /// Whether the administrative methods can be called.
+    pub enable_admin_methods: bool,
+    /// Capacity and refill rate (per second) of the token buckets that limit the requests
+    /// forwarded to the node by each session, if enabled.
+    pub forward_rate_limit: Option<(u32, u32)>,
+    /// Directory where the files requested by clients are exported.
+    pub export_path: PathBuf,
 }
 
 pub struct NodeClient {
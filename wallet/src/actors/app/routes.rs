@@ -56,10 +56,7 @@ macro_rules! forwarded_routes {
             let api_addr = $api.clone();
             $io.add_method($method_wallet, move |params: Params| {
                 log::debug!("Forwarding request for method: {}", $method_wallet);
-                let msg = ForwardRequest {
-                    method: $method_node.to_string(),
-                    params
-                };
+                let msg = ForwardRequest::new($method_node.to_string(), params);
                 let fut03 = api_addr.send(msg)
                     .flatten_err()
                     .map(|res: Result<_>| {
@@ -166,6 +163,8 @@ pub fn connect_routes<T, S>(
         handler,
         api,
         ("Get-Wallet-Infos", "get_wallet_infos", WalletInfosRequest),
+        ("Get-Node-Status", "get_node_status", GetNodeStatusRequest),
+        ("List-Sessions", "list_sessions", ListSessionsRequest),
         (
             "Create-Mnemonics",
             "create_mnemonics",
@@ -186,22 +185,53 @@ pub fn connect_routes<T, S>(
         ("Refresh-Session", "refresh_session", RefreshSessionRequest),
         ("Get-Balance", "get_balance", GetBalanceRequest),
         ("Get-Utxo-Info", "get_utxo_info", UtxoInfoRequest),
+        (
+            "Get-Wallet-Storage-Size",
+            "get_wallet_storage_size",
+            GetWalletStorageSizeRequest
+        ),
         (
             "Get-Transactions",
             "get_transactions",
             GetTransactionsRequest
         ),
+        (
+            "Export-Transactions-Csv",
+            "export_transactions_csv",
+            ExportTransactionsCsvRequest
+        ),
+        ("Estimate-Fee", "estimate_fee", EstimateFeeRequest),
         (
             "Send-Transaction",
             "send_transaction",
             SendTransactionRequest
         ),
+        (
+            "Replace-Transaction",
+            "replace_transaction",
+            ReplaceTransactionRequest
+        ),
         (
             "Generate-Address",
             "generate_address",
             GenerateAddressRequest
         ),
         ("Get-Addresses", "get_addresses", GetAddressesRequest),
+        (
+            "Get-Next-Unused-Address",
+            "get_next_unused_address",
+            GetNextUnusedAddressRequest
+        ),
+        (
+            "Set-Address-Label",
+            "set_address_label",
+            SetAddressLabelRequest
+        ),
+        (
+            "Get-Address-By-Label",
+            "get_address_by_label",
+            GetAddressByLabelRequest
+        ),
         (
             "Create-Data-Request",
             "create_data_request",
@@ -212,11 +242,27 @@ pub fn connect_routes<T, S>(
         ("Set", "set", SetRequest),
         ("Get", "get", GetRequest),
         ("Sign-Data", "sign_data", SignDataRequest),
+        ("Verify-Data", "verify_data", VerifyDataRequest),
         (
             "Export-Master-Key",
             "export_master_key",
             ExportMasterKeyRequest
         ),
+        (
+            "Export-Wallet-Backup",
+            "export_wallet_backup",
+            ExportWalletBackupRequest
+        ),
+        (
+            "Import-Wallet-Backup",
+            "import_wallet_backup",
+            ImportWalletBackupRequest
+        ),
+        (
+            "Compact-Database",
+            "compact_database",
+            CompactDatabaseRequest
+        ),
         ("Shutdown", "shutdown", ShutdownRequest),
     );
 }
@@ -2,10 +2,12 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
 };
 
-use witnet_data_structures::chain::StateMachine;
+use witnet_data_structures::chain::{StateMachine, SyncStatus};
 use witnet_net::client::tcp::jsonrpc::Subscribe;
+use witnet_util::timestamp::get_timestamp;
 
 use super::*;
 
@@ -17,12 +19,64 @@ pub struct State {
     pub client_subscriptions: HashMap<types::SessionId, types::DynamicSink>,
     pub sessions: HashMap<types::SessionId, Session>,
     pub wallets: HashMap<String, types::SessionWallet>,
+    /// Last status received from the node, along with the timestamp at which it was received
+    pub sync_status: Option<(SyncStatus, i64)>,
+    /// Rate limiters for the requests forwarded to the node, one for each session, created upon
+    /// the first request of the session. Requests without a session share the `None` bucket.
+    pub forward_rate_limiters: HashMap<Option<types::SessionId>, TokenBucket>,
+}
+
+/// Token bucket rate limiter: up to `capacity` requests can be done in a burst, and then the
+/// tokens are refilled at a rate of `refill_rate` tokens per second.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: u32,
+    refill_rate: u32,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full token bucket.
+    pub fn new(capacity: u32, refill_rate: u32) -> Self {
+        Self {
+            capacity,
+            refill_rate,
+            tokens: f64::from(capacity),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token from the bucket, returning `false` if it is exhausted.
+    pub fn try_take(&mut self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    /// Take a token from the bucket at the given instant, returning `false` if it is exhausted.
+    pub fn try_take_at(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * f64::from(self.refill_rate)).min(f64::from(self.capacity));
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+
+            true
+        } else {
+            false
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct Session {
     wallets: HashMap<String, types::SessionWallet>,
     pub session_extended: bool,
+    /// Timestamp at which the session will expire unless it gets extended.
+    pub expires_at: i64,
 }
 
 impl State {
@@ -93,6 +147,81 @@ impl State {
         Ok(wallet)
     }
 
+    /// Set the timestamp at which a session will expire, counting from now. Returns that timestamp.
+    pub fn schedule_session_expiration(
+        &mut self,
+        session_id: &types::SessionId,
+        expires_in: Duration,
+    ) -> Result<i64> {
+        let session = self
+            .sessions
+            .get_mut(session_id)
+            .ok_or(Error::SessionNotFound)?;
+        let expires_in = i64::try_from(expires_in.as_secs()).unwrap_or(i64::MAX);
+        session.expires_at = get_timestamp().saturating_add(expires_in);
+
+        Ok(session.expires_at)
+    }
+
+    /// Mark a session as extended, so that it is not closed when its current expiration timer
+    /// fires. Returns `false` if the session had already been extended.
+    pub fn extend_session(&mut self, session_id: &types::SessionId) -> Result<bool> {
+        let session = self
+            .sessions
+            .get_mut(session_id)
+            .ok_or(Error::SessionNotFound)?;
+        let newly_extended = !session.session_extended;
+        session.session_extended = true;
+
+        Ok(newly_extended)
+    }
+
+    /// Get the timestamp at which a session will expire.
+    pub fn session_expiration(&self, session_id: &types::SessionId) -> Result<i64> {
+        self.sessions
+            .get(session_id)
+            .map(|session| session.expires_at)
+            .ok_or(Error::SessionNotFound)
+    }
+
+    /// Summarize the open sessions: a truncated session id, the ids of the wallets unlocked in
+    /// the session and the seconds left until it expires.
+    pub fn list_sessions(&self) -> Vec<SessionInfo> {
+        let now = get_timestamp();
+        let mut sessions: Vec<SessionInfo> = self
+            .sessions
+            .iter()
+            .map(|(session_id, session)| {
+                let mut wallet_ids: Vec<String> = session.wallets.keys().cloned().collect();
+                wallet_ids.sort();
+
+                SessionInfo {
+                    // Session ids grant access to the wallets, so only their prefix is exposed
+                    session_id: session_id.to_string(),
+                    wallet_ids,
+                    expires_in_secs: u64::try_from(session.expires_at - now).unwrap_or(0),
+                }
+            })
+            .collect();
+        sessions.sort_by_key(|session| session.expires_in_secs);
+
+        sessions
+    }
+
+    /// Cache the last status received from the node.
+    pub fn set_sync_status(&mut self, status: SyncStatus) {
+        self.sync_status = Some((status, get_timestamp()));
+    }
+
+    /// Get the last status received from the node along with its age in seconds.
+    pub fn cached_sync_status(&self) -> Option<(SyncStatus, u64)> {
+        self.sync_status.as_ref().map(|(status, received_at)| {
+            let age_secs = u64::try_from(get_timestamp() - received_at).unwrap_or(0);
+
+            (status.clone(), age_secs)
+        })
+    }
+
     /// Check if the session is still active.
     pub fn is_session_active(&self, session_id: &types::SessionId) -> bool {
         self.sessions.contains_key(session_id)
@@ -125,10 +254,30 @@ impl State {
         }
     }
 
+    /// Take a token from the rate limiter of the given session, returning `false` if it is
+    /// exhausted. Requests from unknown sessions use the bucket of the requests without session,
+    /// so that clients cannot skip the limit by making up session ids.
+    pub fn try_take_forward_token(
+        &mut self,
+        session_id: Option<&types::SessionId>,
+        capacity: u32,
+        refill_rate: u32,
+    ) -> bool {
+        let key = session_id
+            .filter(|session_id| self.sessions.contains_key(session_id))
+            .cloned();
+
+        self.forward_rate_limiters
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(capacity, refill_rate))
+            .try_take()
+    }
+
     /// Remove a session but keep its wallets.
     pub fn remove_session(&mut self, session_id: &types::SessionId) -> Result<()> {
         let subscription_id = jsonrpc_pubsub::SubscriptionId::from(session_id);
         self.unsubscribe(&subscription_id).map(|_| ())?;
+        self.forward_rate_limiters.remove(&Some(session_id.clone()));
         self.sessions
             .remove(session_id)
             .map(|_| ())
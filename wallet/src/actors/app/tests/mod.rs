@@ -1,7 +1,81 @@
 use crate::actors::app;
 use crate::*;
+use actix::{Context, Handler};
 use bech32::ToBase32;
-use std::string::ToString;
+use std::{
+    io::{BufRead, BufReader},
+    net::TcpListener,
+    path::PathBuf,
+    string::ToString,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use witnet_net::client::tcp::jsonrpc::JsonRpcClient;
+
+/// Start a mock Witnet node that only counts the requests it receives, and return its address
+fn counting_node(requests: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let requests = requests.clone();
+            thread::spawn(move || {
+                for _request in BufReader::new(stream.unwrap()).lines() {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+
+    address
+}
+
+/// Build an `App` connected to the node at `address`, without starting it, so that its handlers
+/// can be called directly. Must be called from inside an actix system.
+fn app(name: &str, address: &str) -> (app::App, PathBuf) {
+    let path =
+        std::env::temp_dir().join(format!("witnet-wallet-app-{}-{}", name, std::process::id()));
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    let db = Arc::new(rocksdb::DB::open(&opts, &path).unwrap());
+
+    let client = Arc::new(app::NodeClient {
+        actor: JsonRpcClient::start(address).unwrap(),
+    });
+    let node = params::NodeParams {
+        client: client.clone(),
+        last_beacon: Default::default(),
+        network: String::from("Mainnet"),
+        requests_timeout: Duration::from_secs(5),
+        subscriptions: Default::default(),
+    };
+    let worker = actors::Worker::start(1, db, node, repository::factories::default_params());
+
+    let app = app::App {
+        server: None,
+        params: app::Params {
+            testnet: false,
+            worker,
+            client,
+            server_addr: "127.0.0.1:0".parse().unwrap(),
+            session_expires_in: Duration::from_secs(3600),
+            requests_timeout: Duration::from_secs(5),
+            consensus_constants: Default::default(),
+            db_compaction_interval: None,
+            enable_admin_methods: false,
+            forward_rate_limit: None,
+            export_path: path.join("exports"),
+        },
+        state: Default::default(),
+    };
+
+    (app, path)
+}
 
 #[test]
 fn test_validate_mnemonics() {
@@ -162,7 +236,7 @@ fn test_invalid_decryption() {
         Err(e) => e.into_parts(),
     };
     let expected =
-        app::validation_error(app::field_error("seed_data", "Could not decrypt seed data"))
+        app::validation_error(app::field_error("backup_password", "Wrong backup password"))
             .into_parts();
 
     assert_eq!(expected, result);
@@ -182,6 +256,29 @@ fn test_valid_xprv_decryption() {
     }
 }
 
+#[test]
+fn test_truncated_xprv_ciphertext() {
+    let seed_data = "xprv1506zvl8u2r23zq8a3ayuzncwjawrx8etu5r2vqgz6r8ncdgt032w6ars0lc6jm47mj9tmcwg6wsg539992vhpqglamzqcpcq23h2ljvexsltv480utty2ma4lzmmuqy6zqfjkprnefr2kcu85lr006u9dmqxs84wx5lecr2c6lpwcg2atwvd60e295eqx245a9h7h72gt5r7gceg6avldxcejpt45ugl9cqe0aqzgsjpssmg23hxrglfu5vmu0f5my0xmqn5kmtq3m3wrgqkatf6uydwnnlp";
+    let (_hrp, data) = bech32::decode(seed_data).unwrap();
+    let mut ciphertext: Vec<u8> = bech32::FromBase32::from_base32(&data).unwrap();
+    let expected =
+        app::validation_error(app::field_error("seed_data", "Malformed XPRV key")).into_parts();
+
+    // Neither a ciphertext missing its last byte nor one shorter than the IV and salt can be
+    // decrypted, no matter the password
+    for len in [ciphertext.len() - 1, 20] {
+        ciphertext.truncate(len);
+        let encoded_data = bech32::encode("xprv", ciphertext.to_base32()).unwrap();
+        let backup_password = Some("password".to_string().into());
+        let result = match app::methods::validate_xprv(encoded_data.into(), backup_password) {
+            Ok(_) => panic!("called `Result::unwrap_err()` on an `Ok` value"),
+            Err(e) => e.into_parts(),
+        };
+
+        assert_eq!(expected, result);
+    }
+}
+
 #[test]
 fn test_valid_xprv_double_decryption() {
     let seed_data: types::Password = "xprvdouble1ae5gfvwm339antauxg9zf7ads86ex6nj00syqghdsw5fmgu4lfx0s4zs6tt2txhznq8g47tzdhwh6pq2xmq2r92qed5cyykh2wesgzhldyzusksclcf6uq54jyzcm86f3p3nu5jmqm0vdhdf7hmac9ylkgjjlhs3fc3vd7tqsn53evszlseslxrztp00lg5vxrsj2l8caskv6xrs5gw8xnnlhzw5pq0j4yd0rvgw422fz6xeteru54n0lwfprmnwu6zl2e7nktarr6dh5n22ztk305veu4eegnxvr7a96dcrgm7cdqde2gmf2jgveppp77hpzkulx4af0kz2mawcmyxe97csqsjm5h2fx9aw8anfgsn3jp40h8gjy5ap5fgddfr808k7ldspf3xvxfkw8elx9rshhlwuyk29cmnsd3sazak27dndnumdwj9hp34kh7g86kgtarzcsr5dzl9".to_string().into();
@@ -212,3 +309,252 @@ fn test_split_xprv_more_than_two_xprv_ocurrences() {
 
     assert_eq!(expected, result);
 }
+
+#[test]
+fn test_refresh_session_postpones_expiration() {
+    let mut state = app::state::State::default();
+    let session_id = types::SessionId::from("session".to_string());
+    let expires_in = std::time::Duration::from_secs(60);
+    state
+        .sessions
+        .insert(session_id.clone(), Default::default());
+
+    // Pretend that the session was scheduled to expire a long time ago
+    state.sessions.get_mut(&session_id).unwrap().expires_at = 0;
+
+    // A new expiration is only scheduled the first time the session is extended
+    assert!(state.extend_session(&session_id).unwrap());
+    assert!(!state.extend_session(&session_id).unwrap());
+
+    let expires_at = state
+        .schedule_session_expiration(&session_id, expires_in)
+        .unwrap();
+    assert!(expires_at >= witnet_util::timestamp::get_timestamp() + 59);
+    assert_eq!(state.session_expiration(&session_id).unwrap(), expires_at);
+
+    let unknown = types::SessionId::from("unknown".to_string());
+    assert!(matches!(
+        state.extend_session(&unknown),
+        Err(app::Error::SessionNotFound)
+    ));
+}
+
+#[test]
+fn test_decode_wallet_backup_version_mismatch() {
+    let backup = model::WalletBackup {
+        version: constants::WALLET_BACKUP_VERSION + 1,
+        name: Some("my wallet".to_string()),
+        description: None,
+        birth_date: 0,
+        master_key: "xprv1".to_string(),
+        address_labels: vec![],
+    };
+    let encoded = app::methods::encode_wallet_backup(&backup).unwrap();
+
+    let result = app::methods::decode_wallet_backup(&encoded)
+        .unwrap_err()
+        .into_parts();
+    let expected = app::validation_error(app::field_error(
+        "backup",
+        format!(
+            "Unsupported wallet backup version {}, expected version {}",
+            constants::WALLET_BACKUP_VERSION + 1,
+            constants::WALLET_BACKUP_VERSION
+        ),
+    ))
+    .into_parts();
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn test_decode_wallet_backup_malformed() {
+    let err = app::methods::decode_wallet_backup("not a backup").unwrap_err();
+    assert!(matches!(err, app::Error::Validation(_)), "{:?}", err);
+}
+
+#[test]
+fn test_get_node_status_returns_cached_status() {
+    let mut state = app::state::State::default();
+    assert!(state.cached_sync_status().is_none());
+
+    // Pretend that the periodic request received this status 30 seconds ago
+    let status = witnet_data_structures::chain::SyncStatus {
+        current_epoch: Some(100),
+        node_state: witnet_data_structures::chain::StateMachine::Synced,
+        ..Default::default()
+    };
+    state.sync_status = Some((status, witnet_util::timestamp::get_timestamp() - 30));
+
+    // The cached status is returned as is, no request is sent to the node
+    let (cached, age_secs) = state.cached_sync_status().unwrap();
+    assert_eq!(cached.current_epoch, Some(100));
+    assert_eq!(
+        cached.node_state,
+        witnet_data_structures::chain::StateMachine::Synced
+    );
+    assert!((30..=31).contains(&age_secs));
+
+    // A new status received by the periodic request replaces the cached one
+    state.set_sync_status(witnet_data_structures::chain::SyncStatus {
+        current_epoch: Some(101),
+        ..Default::default()
+    });
+    let (cached, age_secs) = state.cached_sync_status().unwrap();
+    assert_eq!(cached.current_epoch, Some(101));
+    assert!(age_secs <= 1);
+}
+
+#[test]
+fn test_get_node_status_handler_does_not_call_node() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let address = counting_node(requests.clone());
+
+    let path = actix::System::new().block_on(async move {
+        let (mut app, path) = app("get-node-status", &address);
+        let mut ctx = Context::new();
+
+        // The node has not answered the periodic request yet
+        let response = app.handle(app::GetNodeStatusRequest, &mut ctx).unwrap();
+        assert!(response.status.is_none());
+        assert!(response.age_secs.is_none());
+
+        // Once the periodic request receives a status, that status is returned
+        app.state
+            .set_sync_status(witnet_data_structures::chain::SyncStatus {
+                current_epoch: Some(100),
+                ..Default::default()
+            });
+        let response = app.handle(app::GetNodeStatusRequest, &mut ctx).unwrap();
+        assert_eq!(response.status.unwrap().current_epoch, Some(100));
+        assert!(response.age_secs.unwrap() <= 1);
+
+        // Give the client some time to send any pending request to the node
+        actix::clock::sleep(Duration::from_millis(200)).await;
+
+        path
+    });
+
+    // The status was served from the cache, the node was never asked for it
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn test_list_sessions_returns_expiry_estimates() {
+    let mut state = app::state::State::default();
+    assert!(state.list_sessions().is_empty());
+
+    let first = types::SessionId::from("first-session".to_string());
+    let second = types::SessionId::from("second-session".to_string());
+    for (session_id, expires_in) in [(&first, 60), (&second, 600)] {
+        state
+            .sessions
+            .insert(session_id.clone(), Default::default());
+        state
+            .schedule_session_expiration(session_id, std::time::Duration::from_secs(expires_in))
+            .unwrap();
+    }
+
+    let sessions = state.list_sessions();
+    assert_eq!(sessions.len(), 2);
+
+    // Sessions are sorted by time to expiry, and their ids are truncated
+    assert_eq!(sessions[0].session_id, first.to_string());
+    assert_eq!(sessions[0].session_id, "first...");
+    assert!((59..=60).contains(&sessions[0].expires_in_secs));
+    assert!(sessions[0].wallet_ids.is_empty());
+    assert_eq!(sessions[1].session_id, "secon...");
+    assert!((599..=600).contains(&sessions[1].expires_in_secs));
+}
+
+#[test]
+fn test_token_bucket_rate_limit() {
+    let start = std::time::Instant::now();
+    let mut bucket = app::state::TokenBucket::new(3, 2);
+
+    // A burst can take up to the capacity of the bucket
+    for _ in 0..3 {
+        assert!(bucket.try_take_at(start));
+    }
+    assert!(!bucket.try_take_at(start));
+
+    // Half a second later, a single token has been refilled
+    let later = start + std::time::Duration::from_millis(500);
+    assert!(bucket.try_take_at(later));
+    assert!(!bucket.try_take_at(later));
+
+    // Tokens are never refilled beyond the capacity of the bucket
+    let much_later = later + std::time::Duration::from_secs(60);
+    for _ in 0..3 {
+        assert!(bucket.try_take_at(much_later));
+    }
+    assert!(!bucket.try_take_at(much_later));
+}
+
+#[test]
+fn test_forward_rate_limit_is_per_session() {
+    let mut state = app::state::State::default();
+    let first = types::SessionId::from("first-session".to_string());
+    let second = types::SessionId::from("second-session".to_string());
+    for session_id in [&first, &second] {
+        state
+            .sessions
+            .insert(session_id.clone(), Default::default());
+    }
+
+    // Exhausting the bucket of a session does not affect the other sessions
+    for _ in 0..3 {
+        assert!(state.try_take_forward_token(Some(&first), 3, 1));
+    }
+    assert!(!state.try_take_forward_token(Some(&first), 3, 1));
+    assert!(state.try_take_forward_token(Some(&second), 3, 1));
+    assert!(state.try_take_forward_token(None, 3, 1));
+
+    // Unknown sessions share the bucket of the requests without session
+    let unknown = types::SessionId::from("unknown-session".to_string());
+    for _ in 0..2 {
+        assert!(state.try_take_forward_token(Some(&unknown), 3, 1));
+    }
+    assert!(!state.try_take_forward_token(None, 3, 1));
+
+    // Closing a session drops its bucket
+    state.remove_session(&first).unwrap();
+    state.sessions.insert(first.clone(), Default::default());
+    assert!(state.try_take_forward_token(Some(&first), 3, 1));
+}
+
+#[test]
+fn test_forward_request_takes_session_id_from_params() {
+    let params: jsonrpc_core::Params = serde_json::from_value(
+        serde_json::json!({"epoch": 0, "limit": 10, "session_id": "abcdef"}),
+    )
+    .unwrap();
+    let request = app::ForwardRequest::new("getBlockChain".to_string(), params);
+    assert!(request.session_id == Some(types::SessionId::from("abcdef".to_string())));
+    // The session id is not forwarded to the node
+    assert_eq!(
+        request.params,
+        serde_json::from_value(serde_json::json!({"epoch": 0, "limit": 10})).unwrap()
+    );
+
+    let params: jsonrpc_core::Params = serde_json::from_value(serde_json::json!(["abc"])).unwrap();
+    let request = app::ForwardRequest::new("getBlock".to_string(), params.clone());
+    assert!(request.session_id.is_none());
+    assert_eq!(request.params, params);
+}
+
+#[test]
+fn test_validate_export_path() {
+    use std::path::Path;
+
+    assert!(app::validate_export_path(Path::new("transactions.csv")).is_ok());
+    assert!(app::validate_export_path(Path::new("./transactions.csv")).is_ok());
+    assert!(app::validate_export_path(Path::new("wallets/transactions.csv")).is_ok());
+
+    assert!(app::validate_export_path(Path::new("")).is_err());
+    assert!(app::validate_export_path(Path::new("/tmp/transactions.csv")).is_err());
+    assert!(app::validate_export_path(Path::new("../transactions.csv")).is_err());
+    assert!(app::validate_export_path(Path::new("wallets/../../transactions.csv")).is_err());
+    assert!(app::validate_export_path(Path::new("wallets/..")).is_err());
+}
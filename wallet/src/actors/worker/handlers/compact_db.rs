@@ -0,0 +1,17 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+
+pub struct CompactDb;
+
+impl Message for CompactDb {
+    type Result = worker::Result<()>;
+}
+
+impl Handler<CompactDb> for worker::Worker {
+    type Result = <CompactDb as Message>::Result;
+
+    fn handle(&mut self, _msg: CompactDb, _ctx: &mut Self::Context) -> Self::Result {
+        self.compact_db()
+    }
+}
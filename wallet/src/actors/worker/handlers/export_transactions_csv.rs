@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::types;
+
+pub struct ExportTransactionsCsv {
+    pub wallet: types::SessionWallet,
+    pub path: PathBuf,
+}
+
+impl Message for ExportTransactionsCsv {
+    type Result = worker::Result<u32>;
+}
+
+impl Handler<ExportTransactionsCsv> for worker::Worker {
+    type Result = <ExportTransactionsCsv as Message>::Result;
+
+    fn handle(
+        &mut self,
+        ExportTransactionsCsv { wallet, path }: ExportTransactionsCsv,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.export_transactions_csv(&wallet, &path)
+    }
+}
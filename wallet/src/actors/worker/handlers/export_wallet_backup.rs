@@ -0,0 +1,25 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::{model, types};
+
+pub struct ExportWalletBackup {
+    pub wallet: types::SessionWallet,
+    pub password: types::Password,
+}
+
+impl Message for ExportWalletBackup {
+    type Result = worker::Result<model::WalletBackup>;
+}
+
+impl Handler<ExportWalletBackup> for worker::Worker {
+    type Result = <ExportWalletBackup as Message>::Result;
+
+    fn handle(
+        &mut self,
+        ExportWalletBackup { wallet, password }: ExportWalletBackup,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.export_wallet_backup(&wallet, password)
+    }
+}
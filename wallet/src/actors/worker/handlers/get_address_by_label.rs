@@ -0,0 +1,25 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::types;
+
+pub struct GetAddressByLabel {
+    pub wallet: types::SessionWallet,
+    pub label: String,
+}
+
+impl Message for GetAddressByLabel {
+    type Result = worker::Result<Option<String>>;
+}
+
+impl Handler<GetAddressByLabel> for worker::Worker {
+    type Result = <GetAddressByLabel as Message>::Result;
+
+    fn handle(
+        &mut self,
+        GetAddressByLabel { wallet, label }: GetAddressByLabel,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.get_address_by_label(&wallet, &label)
+    }
+}
@@ -0,0 +1,24 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::{model, types};
+
+pub struct GetStorageSize {
+    pub wallet: types::SessionWallet,
+}
+
+impl Message for GetStorageSize {
+    type Result = worker::Result<model::WalletStorageSize>;
+}
+
+impl Handler<GetStorageSize> for worker::Worker {
+    type Result = <GetStorageSize as Message>::Result;
+
+    fn handle(
+        &mut self,
+        GetStorageSize { wallet }: GetStorageSize,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.storage_size(&wallet)
+    }
+}
@@ -0,0 +1,23 @@
+use actix::{Handler, Message};
+use std::sync::Arc;
+
+use crate::{actors::worker, types};
+use witnet_data_structures::chain::Block;
+
+pub struct HandleBlocksRequest {
+    pub blocks: Vec<Arc<Block>>,
+    pub wallet: types::SessionWallet,
+    pub sink: types::DynamicSink,
+}
+
+impl Message for HandleBlocksRequest {
+    type Result = worker::Result<()>;
+}
+
+impl Handler<HandleBlocksRequest> for worker::Worker {
+    type Result = <HandleBlocksRequest as Message>::Result;
+
+    fn handle(&mut self, msg: HandleBlocksRequest, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_blocks(msg.blocks, false, msg.wallet, msg.sink)
+    }
+}
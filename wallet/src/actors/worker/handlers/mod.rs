@@ -1,25 +1,34 @@
 pub mod check_wallet_seed;
+pub mod compact_db;
 pub mod create_data_req;
 pub mod create_vtt;
 pub mod create_wallet;
 pub mod delete_wallet;
 pub mod export_master_key;
+pub mod export_transactions_csv;
+pub mod export_wallet_backup;
 pub mod flush_db;
 pub mod gen_address;
 pub mod gen_mnemonic;
 pub mod get;
+pub mod get_address_by_label;
 pub mod get_addresses;
 pub mod get_balance;
+pub mod get_storage_size;
 pub mod get_transaction;
 pub mod get_transactions;
 pub mod get_utxo_info;
 pub mod handle_block;
+pub mod handle_blocks;
 pub mod handle_node_status;
 pub mod handle_superblock;
+pub mod next_unused_address;
 pub mod notify_status;
+pub mod replace_vtt;
 pub mod resync;
 pub mod run_rad_request;
 pub mod set;
+pub mod set_address_label;
 pub mod sign_data;
 pub mod sync;
 pub mod unlock_wallet;
@@ -28,26 +37,35 @@ pub mod update_wallet_info;
 pub mod wallet_infos;
 
 pub use check_wallet_seed::*;
+pub use compact_db::*;
 pub use create_data_req::*;
 pub use create_vtt::*;
 pub use create_wallet::*;
 pub use delete_wallet::*;
 pub use export_master_key::*;
+pub use export_transactions_csv::*;
+pub use export_wallet_backup::*;
 pub use flush_db::*;
 pub use gen_address::*;
 pub use gen_mnemonic::*;
 pub use get::*;
+pub use get_address_by_label::*;
 pub use get_addresses::*;
 pub use get_balance::*;
+pub use get_storage_size::*;
 pub use get_transactions::*;
 pub use get_utxo_info::*;
 pub use handle_block::*;
+pub use handle_blocks::*;
 pub use handle_node_status::*;
 pub use handle_superblock::*;
+pub use next_unused_address::*;
 pub use notify_status::*;
+pub use replace_vtt::*;
 pub use resync::*;
 pub use run_rad_request::*;
 pub use set::*;
+pub use set_address_label::*;
 pub use sign_data::*;
 pub use sync::*;
 pub use unlock_wallet::*;
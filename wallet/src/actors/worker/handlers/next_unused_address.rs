@@ -0,0 +1,25 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::{model, types};
+
+pub struct NextUnusedAddress {
+    pub wallet: types::SessionWallet,
+}
+
+impl Message for NextUnusedAddress {
+    type Result = worker::Result<model::Address>;
+}
+
+impl Handler<NextUnusedAddress> for worker::Worker {
+    type Result = <NextUnusedAddress as Message>::Result;
+
+    fn handle(
+        &mut self,
+        NextUnusedAddress { wallet }: NextUnusedAddress,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.next_unused_address(&wallet)
+            .map(|address| (*address).clone())
+    }
+}
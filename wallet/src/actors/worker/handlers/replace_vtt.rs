@@ -0,0 +1,36 @@
+use actix::prelude::*;
+
+use crate::{actors::worker, model::ExtendedTransaction, types};
+use witnet_data_structures::{fee::AbsoluteFee, transaction::VTTransaction};
+
+pub struct ReplaceVtt {
+    pub wallet: types::SessionWallet,
+    pub original: VTTransaction,
+    pub fee: AbsoluteFee,
+}
+
+pub struct ReplaceVttResponse {
+    pub fee: AbsoluteFee,
+    pub transaction: ExtendedTransaction,
+}
+
+impl Message for ReplaceVtt {
+    type Result = worker::Result<ReplaceVttResponse>;
+}
+
+impl Handler<ReplaceVtt> for worker::Worker {
+    type Result = <ReplaceVtt as Message>::Result;
+
+    fn handle(
+        &mut self,
+        ReplaceVtt {
+            wallet,
+            original,
+            fee,
+        }: ReplaceVtt,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.create_replacement_vtt(&wallet, &original, fee)
+            .map(|(transaction, fee)| ReplaceVttResponse { fee, transaction })
+    }
+}
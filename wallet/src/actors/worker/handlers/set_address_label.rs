@@ -0,0 +1,30 @@
+use actix::prelude::*;
+
+use crate::actors::worker;
+use crate::types;
+
+pub struct SetAddressLabel {
+    pub wallet: types::SessionWallet,
+    pub address: String,
+    pub label: String,
+}
+
+impl Message for SetAddressLabel {
+    type Result = worker::Result<()>;
+}
+
+impl Handler<SetAddressLabel> for worker::Worker {
+    type Result = <SetAddressLabel as Message>::Result;
+
+    fn handle(
+        &mut self,
+        SetAddressLabel {
+            wallet,
+            address,
+            label,
+        }: SetAddressLabel,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.set_address_label(&wallet, &address, label)
+    }
+}
@@ -1,4 +1,9 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+};
 
 use jsonrpc_core as rpc;
 use serde_json::{json, Value};
@@ -9,14 +14,17 @@ use crate::{
     model, params,
     types::{ChainEntry, DynamicSink, GetBlockChainParams},
 };
-use witnet_crypto::{key::ExtendedSK, mnemonic};
+use witnet_crypto::{
+    key::{ExtendedPK, ExtendedSK},
+    mnemonic,
+};
 use witnet_data_structures::{
     chain::{
         Block, CheckpointBeacon, DataRequestInfo, Hashable, OutputPointer, RADRequest,
         StateMachine, ValueTransferOutput,
     },
     fee::AbsoluteFee,
-    transaction::Transaction,
+    transaction::{Transaction, VTTransaction},
 };
 use witnet_futures_utils::TryFutureExt2;
 use witnet_net::client::tcp::jsonrpc;
@@ -71,6 +79,14 @@ impl Worker {
         Ok(())
     }
 
+    /// Compact the wallets database. This blocks the worker until the compaction is finished.
+    pub fn compact_db(&self) -> Result<()> {
+        log::debug!("Compacting wallet database");
+        self.wallets.compact_db()?;
+
+        Ok(())
+    }
+
     pub fn wallet_infos(&self) -> Result<Vec<model::Wallet>> {
         let wallets = self.wallets.infos()?;
 
@@ -115,11 +131,26 @@ impl Worker {
                 );
                 let account = types::Account {
                     index: 0,
-                    external: external_key,
-                    internal: internal_key,
+                    external: types::Keychain::Secret(external_key),
+                    internal: types::Keychain::Secret(internal_key),
                 };
                 (id, account, None)
             }
+            types::SeedSource::Xpub(xpub) => {
+                let (account_key, _) = ExtendedPK::from_slip32(xpub.as_ref())
+                    .map_err(|e| Error::KeyGen(crypto::Error::Deserialization(e)))?;
+
+                let id = crypto::gen_watch_only_wallet_id(
+                    &self.params.id_hash_function,
+                    &account_key,
+                    self.params.master_key_salt.as_ref(),
+                    self.params.id_hash_iterations,
+                );
+                let default_account_index = 0;
+                let default_account =
+                    account::gen_watch_only_account(default_account_index, &account_key)?;
+                (id, default_account, None)
+            }
             _ => {
                 let master_key = crypto::gen_master_key(
                     self.params.seed_password.as_ref(),
@@ -210,7 +241,13 @@ impl Worker {
         let iv = crypto::salt(&mut self.rng, self.params.db_iv_length);
         let key = crypto::key_from_password(password, &salt, self.params.db_hash_iterations);
 
-        let wallet_db = db::EncryptedDb::new(self.db.clone(), prefix, key, iv.clone());
+        let wallet_db = db::EncryptedDb::new(
+            self.db.clone(),
+            prefix,
+            key,
+            iv.clone(),
+            self.params.db_encryption_mode,
+        );
         wallet_db.put(
             &constants::ENCRYPTION_CHECK_KEY,
             constants::ENCRYPTION_CHECK_VALUE,
@@ -222,6 +259,8 @@ impl Worker {
                 description,
                 iv,
                 salt,
+                hash_iterations: self.params.db_hash_iterations,
+                encryption_mode: self.params.db_encryption_mode,
                 id: &id,
                 account: &default_account,
                 master_key,
@@ -253,6 +292,17 @@ impl Worker {
                     self.params.id_hash_iterations,
                 )
             }
+            types::SeedSource::Xpub(xpub) => {
+                let (account_key, _) = ExtendedPK::from_slip32(xpub.as_ref())
+                    .map_err(|e| Error::KeyGen(crypto::Error::Deserialization(e)))?;
+
+                crypto::gen_watch_only_wallet_id(
+                    &self.params.id_hash_function,
+                    &account_key,
+                    self.params.master_key_salt.as_ref(),
+                    self.params.id_hash_iterations,
+                )
+            }
             _ => {
                 let master_key = crypto::gen_master_key(
                     self.params.seed_password.as_ref(),
@@ -308,7 +358,14 @@ impl Worker {
                 | repository::Error::WalletNotFound => Error::WalletNotFound,
                 err => Error::Repository(err),
             })?;
-        let key = crypto::key_from_password(password, &salt, self.params.db_hash_iterations);
+        // Wallets created before the iteration count was recorded used the configured one
+        let hash_iterations = self
+            .wallets
+            .wallet_hash_iterations(wallet_id)?
+            .unwrap_or(self.params.db_hash_iterations);
+        // The configured encryption mode only applies to new wallets
+        let encryption_mode = self.wallets.wallet_encryption_mode(wallet_id)?;
+        let key = crypto::key_from_password(password, &salt, hash_iterations);
         let session_id: types::SessionId = From::from(crypto::gen_session_id(
             &mut self.rng,
             &self.params.id_hash_function,
@@ -317,7 +374,7 @@ impl Worker {
             self.params.id_hash_iterations,
         ));
         let prefix = wallet_id.as_bytes().to_vec();
-        let wallet_db = db::EncryptedDb::new(self.db.clone(), prefix, key, iv);
+        let wallet_db = db::EncryptedDb::new(self.db.clone(), prefix, key, iv, encryption_mode);
 
         // Check if password-derived key is able to read the special stored value
         wallet_db
@@ -327,6 +384,27 @@ impl Worker {
                 err => Error::Db(err),
             })?;
 
+        // Now that the password is known to be right, upgrade the key derivation if the
+        // recommended iteration count has increased since the wallet was encrypted
+        let wallet_db = if hash_iterations < self.params.db_hash_iterations {
+            log::info!(
+                "Re-encrypting wallet {} with {} hash iterations (was {})",
+                wallet_id,
+                self.params.db_hash_iterations,
+                hash_iterations
+            );
+            let key = crypto::key_from_password(password, &salt, self.params.db_hash_iterations);
+
+            self.wallets.reencrypt_wallet(
+                wallet_id,
+                &wallet_db,
+                key,
+                self.params.db_hash_iterations,
+            )?
+        } else {
+            wallet_db
+        };
+
         let wallet = Arc::new(repository::Wallet::unlock(
             wallet_id,
             session_id.clone(),
@@ -358,6 +436,12 @@ impl Worker {
         Ok(address)
     }
 
+    pub fn next_unused_address(&mut self, wallet: &types::Wallet) -> Result<Arc<model::Address>> {
+        let address = wallet.next_unused_external_address()?;
+
+        Ok(address)
+    }
+
     pub fn addresses(
         &mut self,
         wallet: &types::Wallet,
@@ -380,6 +464,15 @@ impl Worker {
         Ok(balance)
     }
 
+    pub fn storage_size(&mut self, wallet: &types::Wallet) -> Result<model::WalletStorageSize> {
+        let mut storage_size = wallet.storage_size()?;
+        let unlock_params = self.wallets.wallet_storage_size(&wallet.id)?;
+        storage_size.metadata += unlock_params;
+        storage_size.total += unlock_params;
+
+        Ok(storage_size)
+    }
+
     pub fn get_utxo_info(&mut self, wallet: &types::Wallet) -> Result<model::UtxoSet> {
         let utxo_info = wallet.get_utxo_info()?;
 
@@ -397,6 +490,44 @@ impl Worker {
         Ok(transactions)
     }
 
+    /// Export all the balance movements of a wallet as CSV into the file at `path`, returning
+    /// the number of exported movements.
+    ///
+    /// The rows are written to the file as they are produced, so the export is never held in
+    /// memory as a whole.
+    pub fn export_transactions_csv(&mut self, wallet: &types::Wallet, path: &Path) -> Result<u32> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(repository::Error::from)?;
+        }
+        let file = File::create(path).map_err(repository::Error::from)?;
+        let movements = wallet.export_transactions_csv(BufWriter::new(file))?;
+
+        Ok(movements)
+    }
+
+    /// Label an address, replacing any previous label it had.
+    pub fn set_address_label(
+        &self,
+        wallet: &types::Wallet,
+        address: &str,
+        label: String,
+    ) -> Result<()> {
+        wallet.set_address_label(address, label)?;
+
+        Ok(())
+    }
+
+    /// Get the address that has the given label, if any.
+    pub fn get_address_by_label(
+        &self,
+        wallet: &types::Wallet,
+        label: &str,
+    ) -> Result<Option<String>> {
+        let address = wallet.get_address_by_label(label)?;
+
+        Ok(address)
+    }
+
     pub fn get(&self, wallet: &types::Wallet, key: &str) -> Result<Option<String>> {
         let value = wallet.kv_get(key)?;
 
@@ -494,6 +625,15 @@ impl Worker {
         Ok(wallet.create_vtt(params)?)
     }
 
+    pub fn create_replacement_vtt(
+        &self,
+        wallet: &types::Wallet,
+        original: &VTTransaction,
+        fee: AbsoluteFee,
+    ) -> Result<(model::ExtendedTransaction, AbsoluteFee)> {
+        Ok(wallet.create_replacement_vtt(original, fee)?)
+    }
+
     pub fn get_transaction(
         &self,
         wallet: &types::Wallet,
@@ -709,12 +849,16 @@ impl Worker {
         }
     }
 
-    /// Sync wrapper in order to clear transient addresses in case of errors
+    /// Sync wrapper in order to clear transient addresses in case of errors.
+    ///
+    /// If `resync` is set, `ResyncProgress` events are also sent to the client after each batch
+    /// of blocks.
     pub fn sync(
         &self,
         wallet_id: &str,
         wallet: &types::SessionWallet,
         sink: types::DynamicSink,
+        resync: bool,
     ) -> Result<()> {
         let sync_start = wallet.lock_and_read_state(|state| state.last_sync.checkpoint)?;
 
@@ -724,7 +868,7 @@ impl Worker {
             self.params.sync_address_batch_length,
         )?;
 
-        let sync_result = self.sync_inner(wallet_id, wallet, sink.clone());
+        let sync_result = self.sync_inner(wallet_id, wallet, sink.clone(), resync);
 
         // Clear transient created addresses
         wallet.clear_transient_addresses()?;
@@ -756,6 +900,7 @@ impl Worker {
         wallet_id: &str,
         wallet: &types::SessionWallet,
         sink: types::DynamicSink,
+        resync: bool,
     ) -> Result<()> {
         let limit = i64::from(self.params.node_sync_batch_size);
 
@@ -855,12 +1000,21 @@ impl Worker {
                 latest_beacon = block_arc.block_header.beacon;
             }
 
-            let events = Some(vec![types::Event::SyncProgress(
+            let mut events = vec![types::Event::SyncProgress(
                 first_beacon.checkpoint,
                 latest_beacon.checkpoint,
                 tip.checkpoint,
-            )]);
-            self.notify_client(wallet, sink.clone(), events).ok();
+            )];
+            if resync {
+                // A resynchronization starts from the birth date of the wallet, which is where
+                // `first_beacon` points to after clearing the chain data
+                events.push(types::Event::resync_progress(
+                    first_beacon.checkpoint,
+                    latest_beacon.checkpoint,
+                    tip.checkpoint,
+                ));
+            }
+            self.notify_client(wallet, sink.clone(), Some(events)).ok();
 
             // Keep asking for new batches of blocks until we get less than expected, which signals
             // that there are no more blocks to process.
@@ -976,6 +1130,7 @@ impl Worker {
         let wallet_data = wallet.public_data()?;
         let last_sync = wallet_data.last_sync;
         let last_confirmed = wallet_data.last_confirmed;
+        let mut rollback_to = None;
         let (needs_clear_pending, needs_indexing) = if block_beacon.hash_prev_block
             == last_sync.hash_prev_block
             && (block_beacon.checkpoint == 0 || block_beacon.checkpoint > last_sync.checkpoint)
@@ -986,6 +1141,22 @@ impl Worker {
                 last_sync.checkpoint,
             );
 
+            (false, true)
+        } else if let Some(parent) = wallet
+            .pending_block_beacon(&block_beacon.hash_prev_block)?
+            .filter(|parent| block_beacon.checkpoint > parent.epoch)
+        {
+            log::debug!(
+                "Processing block #{} that builds on top of our pending block #{} instead of our tip of the chain #{} (rolling back orphaned blocks)",
+                block_beacon.checkpoint,
+                parent.epoch,
+                last_sync.checkpoint,
+            );
+
+            // Chain reorganization: pending blocks after the parent of the new block are orphaned,
+            // so their changes should be rolled back before indexing the new block
+            rollback_to = Some(parent.block_hash);
+
             (false, true)
         } else if block_beacon.checkpoint > last_confirmed.checkpoint
             && block_beacon.hash_prev_block == last_confirmed.hash_prev_block
@@ -1029,12 +1200,119 @@ impl Worker {
             wallet.clear_pending_state()?;
         }
 
+        if let Some(block_hash) = rollback_to {
+            let orphaned_blocks = wallet.rollback_to_pending_block(&block_hash)?;
+
+            // Notify orphaning of the blocks that have been rolled back
+            self.notify_client(
+                &wallet,
+                sink.clone(),
+                Some(vec![types::Event::BlocksOrphan(orphaned_blocks)]),
+            )
+            .ok();
+        }
+
         if needs_indexing {
             // Index incoming block and its transactions
             let new_last_sync = self.index_block(block, confirmed, &wallet, sink)?;
 
             // Update wallet state with the last indexed epoch and block hash
             wallet.update_sync_state(new_last_sync, confirmed)?;
+
+            // The new block may give enough confirmations to the blocks already consolidated by
+            // a superblock
+            if !confirmed {
+                wallet.consolidate_buried_blocks()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle a batch of blocks, applying them in epoch order.
+    ///
+    /// If the blocks are a chain building directly on top of the local tip of the chain, they are
+    /// indexed locking the state of the wallet only once for the whole batch. Otherwise, e.g. upon
+    /// chain reorganizations, the blocks are handled one by one, and processing stops at the first
+    /// block that fails, as the following ones would not build on top of the local chain tip
+    /// anyway.
+    pub fn handle_blocks(
+        &self,
+        mut blocks: Vec<Arc<Block>>,
+        confirmed: bool,
+        wallet: types::SessionWallet,
+        sink: types::DynamicSink,
+    ) -> Result<()> {
+        blocks.sort_by_key(|block| block.block_header.beacon.checkpoint);
+
+        let last_sync = wallet.public_data()?.last_sync;
+        let builds_on_local_tip = blocks.first().map_or(false, |first| {
+            let beacon = first.block_header.beacon;
+            beacon.hash_prev_block == last_sync.hash_prev_block
+                && (beacon.checkpoint == 0 || beacon.checkpoint > last_sync.checkpoint)
+        });
+        let is_chain = blocks.windows(2).all(|pair| {
+            pair[1].block_header.beacon.hash_prev_block == pair[0].hash()
+                && pair[1].block_header.beacon.checkpoint > pair[0].block_header.beacon.checkpoint
+        });
+        if !builds_on_local_tip || !is_chain {
+            for block in blocks {
+                self.handle_block(block, confirmed, wallet.clone(), sink.clone())?;
+            }
+
+            return Ok(());
+        }
+
+        log::debug!(
+            "Processing a batch of {} blocks that builds directly on top of our tip of the chain #{}",
+            blocks.len(),
+            last_sync.checkpoint,
+        );
+
+        let blocks_txns: Vec<Vec<Transaction>> = blocks
+            .iter()
+            .map(|block| block_transactions(block).collect())
+            .collect();
+        // If syncing, then re-generate transient addresses if needed
+        wallet._sync_address_generation(blocks_txns.iter().flatten().cloned())?;
+        let filtered_blocks_txns = wallet.filter_wallet_transactions_batch(blocks_txns)?;
+
+        let mut batch = Vec::with_capacity(blocks.len());
+        for (block, filtered_txns) in blocks.iter().zip(filtered_blocks_txns) {
+            let block_info = model::Beacon {
+                block_hash: block.hash(),
+                epoch: block.block_header.beacon.checkpoint,
+            };
+            // Extending transactions with metadata queried from the node
+            let extended_txns = self.extend_transactions_data(filtered_txns)?;
+            batch.push((block_info, extended_txns));
+        }
+
+        let balance_movements = wallet.index_blocks_transactions(&batch, confirmed)?;
+
+        if let Some((block_info, _)) = batch.last() {
+            self.node.update_last_beacon(CheckpointBeacon {
+                checkpoint: block_info.epoch,
+                hash_prev_block: block_info.block_hash,
+            });
+        }
+
+        // Notify about the new blocks and every single balance movement found within.
+        let mut events = vec![];
+        for ((block_info, _), block_balance_movements) in batch.into_iter().zip(balance_movements) {
+            events.push(types::Event::Block(block_info));
+            events.extend(
+                block_balance_movements
+                    .into_iter()
+                    .map(types::Event::Movement),
+            );
+        }
+        self.notify_client(&wallet, sink, Some(events)).ok();
+
+        // The new blocks may give enough confirmations to the blocks already consolidated by a
+        // superblock
+        if !confirmed {
+            wallet.consolidate_buried_blocks()?;
         }
 
         Ok(())
@@ -1101,7 +1379,7 @@ impl Worker {
                 )
                 .ok();
 
-                self.sync(&wallet.id, &wallet, sink)?
+                self.sync(&wallet.id, &wallet, sink, false)?
             }
         }
 
@@ -1121,7 +1399,7 @@ impl Worker {
 
         if status == StateMachine::Synced && !wallet.is_syncing()? {
             wallet.clear_pending_state().ok();
-            self.sync(&wallet.id, &wallet, sink)?;
+            self.sync(&wallet.id, &wallet, sink, false)?;
         }
 
         Ok(())
@@ -1144,32 +1422,7 @@ impl Worker {
         self.node.update_last_beacon(block_own_beacon);
 
         // Block transactions to be indexed.
-        // Note: reveal transactions do not change wallet balances
-        let vtt_txns = block
-            .txns
-            .value_transfer_txns
-            .iter()
-            .cloned()
-            .map(Transaction::from);
-        let dr_txns = block
-            .txns
-            .data_request_txns
-            .iter()
-            .cloned()
-            .map(Transaction::from);
-        let commit_txns = block
-            .txns
-            .commit_txns
-            .iter()
-            .cloned()
-            .map(Transaction::from);
-        let tally_txns = block.txns.tally_txns.iter().cloned().map(Transaction::from);
-
-        let block_txns = vtt_txns
-            .chain(dr_txns)
-            .chain(commit_txns)
-            .chain(tally_txns)
-            .chain(std::iter::once(Transaction::Mint(block.txns.mint.clone())));
+        let block_txns = block_transactions(&block);
 
         let block_info = model::Beacon {
             block_hash,
@@ -1209,7 +1462,14 @@ impl Worker {
         if !wallet.is_syncing()? {
             wallet.clear_chain_data()?;
 
-            self.sync(wallet_id, &wallet, sink).map(|_| true)
+            self.sync(wallet_id, &wallet, sink, true)?;
+
+            // Reclaim the space taken by the chain data that was just overwritten
+            if self.params.db_compact_on_resync {
+                self.compact_db()?;
+            }
+
+            Ok(true)
         } else {
             Ok(false)
         }
@@ -1222,6 +1482,14 @@ impl Worker {
     ) -> Result<String> {
         wallet.export_master_key(password).map_err(Error::from)
     }
+
+    pub fn export_wallet_backup(
+        &self,
+        wallet: &types::Wallet,
+        password: types::Password,
+    ) -> Result<model::WalletBackup> {
+        wallet.export_backup(password).map_err(Error::from)
+    }
 }
 
 fn validate_birth_date(
@@ -1241,3 +1509,33 @@ fn validate_birth_date(
         Ok(())
     }
 }
+
+/// Transactions of a block that need to be indexed.
+/// Note: reveal transactions do not change wallet balances
+fn block_transactions(block: &Block) -> impl Iterator<Item = Transaction> + Clone + '_ {
+    let vtt_txns = block
+        .txns
+        .value_transfer_txns
+        .iter()
+        .cloned()
+        .map(Transaction::from);
+    let dr_txns = block
+        .txns
+        .data_request_txns
+        .iter()
+        .cloned()
+        .map(Transaction::from);
+    let commit_txns = block
+        .txns
+        .commit_txns
+        .iter()
+        .cloned()
+        .map(Transaction::from);
+    let tally_txns = block.txns.tally_txns.iter().cloned().map(Transaction::from);
+
+    vtt_txns
+        .chain(dr_txns)
+        .chain(commit_txns)
+        .chain(tally_txns)
+        .chain(std::iter::once(Transaction::Mint(block.txns.mint.clone())))
+}
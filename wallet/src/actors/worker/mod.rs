@@ -8,6 +8,8 @@ use crate::{db, params, repository, types};
 pub mod error;
 pub mod handlers;
 pub mod methods;
+#[cfg(test)]
+mod tests;
 
 pub use error::*;
 pub use handlers::*;
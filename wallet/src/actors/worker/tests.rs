@@ -0,0 +1,536 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::{mpsc, RwLock},
+    thread,
+    time::Duration,
+};
+
+use futures01::Stream as _;
+use serde_json::{json, Value};
+use witnet_crypto::mnemonic;
+use witnet_data_structures::{
+    chain::{
+        Block, BlockHeader, BlockMerkleRoots, BlockTransactions, CheckpointBeacon, Hash, Hashable,
+        PublicKeyHash, ValueTransferOutput,
+    },
+    transaction::MintTransaction,
+};
+use witnet_net::client::tcp::jsonrpc::JsonRpcClient;
+
+use super::*;
+use crate::{actors::app::NodeClient, constants, repository::factories};
+
+/// Build a chain of empty blocks, one for each epoch, each one building on top of the previous one
+fn chain(epochs: RangeInclusive<u32>) -> Vec<Block> {
+    let mut hash_prev_block = Hash::default();
+
+    epochs
+        .map(|checkpoint| {
+            let block = Block::new(
+                BlockHeader {
+                    beacon: CheckpointBeacon {
+                        checkpoint,
+                        hash_prev_block,
+                    },
+                    ..Default::default()
+                },
+                Default::default(),
+                Default::default(),
+            );
+            hash_prev_block = block.hash();
+
+            block
+        })
+        .collect()
+}
+
+/// Build a block on top of `hash_prev_block` whose mint transaction pays `value` to `pkh`
+fn mint_block(checkpoint: u32, hash_prev_block: Hash, pkh: PublicKeyHash, value: u64) -> Block {
+    let mint = MintTransaction::new(
+        checkpoint,
+        vec![ValueTransferOutput {
+            pkh,
+            value,
+            time_lock: 0,
+        }],
+    );
+
+    Block::new(
+        BlockHeader {
+            beacon: CheckpointBeacon {
+                checkpoint,
+                hash_prev_block,
+            },
+            merkle_roots: BlockMerkleRoots {
+                mint_hash: mint.hash(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        Default::default(),
+        BlockTransactions {
+            mint,
+            ..Default::default()
+        },
+    )
+}
+
+/// Start a mock Witnet node that answers the `getBlockChain` and `getBlock` requests using the
+/// given chain, and return its address
+fn mock_node(blocks: Vec<Block>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let blocks = Arc::new(blocks);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let blocks = blocks.clone();
+            thread::spawn(move || serve_node_requests(stream.unwrap(), &blocks));
+        }
+    });
+
+    address
+}
+
+fn serve_node_requests(stream: TcpStream, blocks: &[Block]) {
+    let mut writer = stream.try_clone().unwrap();
+
+    for line in BufReader::new(stream).lines() {
+        let request: Value = match line.ok().and_then(|line| serde_json::from_str(&line).ok()) {
+            Some(request) => request,
+            None => break,
+        };
+
+        let result = match request["method"].as_str() {
+            Some("getBlockChain") => {
+                let epoch = request["params"]["epoch"].as_i64().unwrap();
+                let limit = request["params"]["limit"].as_i64().unwrap();
+                let selected: Vec<&Block> = if limit < 0 {
+                    // A negative limit counts from the tip of the chain
+                    let from_end = usize::try_from(-limit).unwrap();
+                    blocks[blocks.len().saturating_sub(from_end)..]
+                        .iter()
+                        .collect()
+                } else {
+                    blocks
+                        .iter()
+                        .filter(|block| i64::from(block.block_header.beacon.checkpoint) >= epoch)
+                        .take(if limit == 0 {
+                            usize::MAX
+                        } else {
+                            usize::try_from(limit).unwrap()
+                        })
+                        .collect()
+                };
+
+                selected
+                    .into_iter()
+                    .map(|block| {
+                        json!([
+                            block.block_header.beacon.checkpoint,
+                            block.hash().to_string()
+                        ])
+                    })
+                    .collect()
+            }
+            Some("getBlock") => {
+                let hash = request["params"][0].as_str().unwrap();
+                let block = blocks
+                    .iter()
+                    .find(|block| block.hash().to_string() == hash)
+                    .unwrap();
+                let mut block = serde_json::to_value(block).unwrap();
+                block["confirmed"] = json!(true);
+
+                block
+            }
+            _ => Value::Null,
+        };
+
+        let response = json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] });
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+/// Connect to the node at `address`. The client actor runs in its own system, because the worker
+/// blocks the calling thread while waiting for the responses of the node.
+fn node_params(address: String) -> params::NodeParams {
+    let (client_sender, client_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        actix::System::new().block_on(async move {
+            client_sender
+                .send(JsonRpcClient::start(&address).unwrap())
+                .unwrap();
+            futures::future::pending::<()>().await;
+        })
+    });
+
+    params::NodeParams {
+        client: Arc::new(NodeClient {
+            actor: client_receiver.recv().unwrap(),
+        }),
+        last_beacon: Default::default(),
+        network: String::from("Mainnet"),
+        requests_timeout: Duration::from_secs(5),
+        subscriptions: Default::default(),
+    }
+}
+
+fn worker(name: &str, node: params::NodeParams, params: params::Params) -> (Worker, PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+        "witnet-wallet-worker-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    let db = Arc::new(rocksdb::DB::open(&opts, &path).unwrap());
+
+    let worker = Worker {
+        db: db.clone(),
+        wallets: Arc::new(repository::Wallets::new(db::PlainDb::new(db))),
+        node,
+        params,
+        rng: rand::rngs::OsRng,
+    };
+
+    (worker, path)
+}
+
+/// A wallet created and unlocked in a test worker, along with a sink for its notifications
+struct TestWallet {
+    id: String,
+    wallet: types::SessionWallet,
+    sink: types::DynamicSink,
+    /// Notifications sent through the sink. The iterator ends once every copy of the sink has been
+    /// dropped
+    notifications: Box<dyn Iterator<Item = Value>>,
+}
+
+const TEST_WALLET_NAME: &str = "my wallet";
+const TEST_WALLET_PASSWORD: &[u8] = b"12345678";
+
+/// Create a wallet from a new mnemonic and unlock it
+fn unlocked_test_wallet(worker: &mut Worker, birth_date: Option<types::BirthDate>) -> TestWallet {
+    let source = types::SeedSource::Mnemonics(
+        mnemonic::MnemonicGen::new()
+            .with_len(mnemonic::Length::Words12)
+            .generate(),
+    );
+    let id = worker
+        .create_wallet(
+            Some(TEST_WALLET_NAME.to_string()),
+            None,
+            TEST_WALLET_PASSWORD,
+            &source,
+            false,
+            birth_date,
+        )
+        .unwrap();
+    let wallet = worker
+        .unlock_wallet(&id, TEST_WALLET_PASSWORD)
+        .unwrap()
+        .wallet;
+
+    let (subscriber, _id_receiver, notifications) =
+        jsonrpc_pubsub::Subscriber::new_test("notifications");
+    let sink = subscriber
+        .assign_id(jsonrpc_pubsub::SubscriptionId::Number(1))
+        .unwrap();
+    let sink: types::DynamicSink = Arc::new(RwLock::new(Some(sink)));
+    let notifications = notifications
+        .wait()
+        .map(|notification| serde_json::from_str::<Value>(&notification.unwrap()).unwrap());
+
+    TestWallet {
+        id,
+        wallet,
+        sink,
+        notifications: Box::new(notifications),
+    }
+}
+
+#[test]
+fn resync_progress_starts_at_birth_date() {
+    // The wallet is born at epoch 1000, and the node knows blocks up to epoch 1040
+    let node = node_params(mock_node(chain(1000..=1040)));
+    let mut params = factories::default_params();
+    params.node_sync_batch_size = 10;
+    let (mut worker, path) = worker("resync-progress", node, params);
+    let TestWallet {
+        id,
+        wallet,
+        sink,
+        notifications,
+    } = unlocked_test_wallet(&mut worker, Some(types::BirthDate::Imported(1000)));
+
+    assert!(worker
+        .clear_chain_data_and_resync(&id, wallet.clone(), sink)
+        .unwrap());
+
+    // The sink has been dropped, so the stream of notifications ends after the last one
+    let resync_progress: Vec<Value> = notifications
+        .flat_map(|notification| {
+            notification["params"][0]["events"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter_map(|event| event.get("ResyncProgress").cloned())
+        .collect();
+
+    // The 40 epochs after the birth date are synced in batches of 10 blocks, and the last batch
+    // is empty
+    assert_eq!(
+        resync_progress,
+        vec![
+            json!([1010, 1040, 25]),
+            json!([1020, 1040, 50]),
+            json!([1030, 1040, 75]),
+            json!([1040, 1040, 100]),
+            json!([1040, 1040, 100]),
+        ]
+    );
+    assert_eq!(wallet.public_data().unwrap().last_sync.checkpoint, 1040);
+
+    drop((worker, wallet));
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn handle_batch_of_blocks() {
+    let node = node_params(mock_node(vec![]));
+    let (mut worker, path) = worker("handle-blocks", node, factories::default_params());
+    let TestWallet {
+        wallet,
+        sink,
+        notifications,
+        ..
+    } = unlocked_test_wallet(&mut worker, None);
+    let address = wallet.gen_external_address(None).unwrap();
+
+    // A chain of 3 blocks on top of the tip of the wallet, each one minting some wits to it
+    let last_sync = wallet.public_data().unwrap().last_sync;
+    let mut hash_prev_block = last_sync.hash_prev_block;
+    let mut blocks: Vec<Arc<Block>> = (1..=3)
+        .map(|i| {
+            let block = mint_block(
+                last_sync.checkpoint + i,
+                hash_prev_block,
+                address.pkh,
+                u64::from(i),
+            );
+            hash_prev_block = block.hash();
+
+            Arc::new(block)
+        })
+        .collect();
+    // The blocks of the batch are applied in epoch order
+    blocks.reverse();
+
+    worker
+        .handle_blocks(blocks, false, wallet.clone(), sink)
+        .unwrap();
+
+    // The whole batch results in a single notification, with the balance movements of each block
+    // following the block they belong to
+    let notifications: Vec<Value> = notifications.collect();
+    assert_eq!(notifications.len(), 1);
+    // Numbers are serialized as strings
+    let events: Vec<(&str, &str)> = notifications[0]["params"][0]["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| match (event.get("Block"), event.get("Movement")) {
+            (Some(block), _) => ("Block", block["epoch"].as_str().unwrap()),
+            (_, Some(movement)) => ("Movement", movement["amount"].as_str().unwrap()),
+            _ => panic!("Unexpected event {}", event),
+        })
+        .collect();
+    let epochs: Vec<String> = (1..=3)
+        .map(|i| (last_sync.checkpoint + i).to_string())
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            ("Block", epochs[0].as_str()),
+            ("Movement", "1"),
+            ("Block", epochs[1].as_str()),
+            ("Movement", "2"),
+            ("Block", epochs[2].as_str()),
+            ("Movement", "3"),
+        ]
+    );
+
+    let wallet_data = wallet.public_data().unwrap();
+    assert_eq!(wallet_data.last_sync.checkpoint, last_sync.checkpoint + 3);
+    assert_eq!(wallet_data.last_sync.hash_prev_block, hash_prev_block);
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 6);
+
+    drop((worker, wallet));
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn handle_block_rolls_back_orphaned_blocks() {
+    let node = node_params(mock_node(vec![]));
+    let (mut worker, path) = worker("reorg", node, factories::default_params());
+    let TestWallet {
+        wallet,
+        sink,
+        notifications,
+        ..
+    } = unlocked_test_wallet(&mut worker, None);
+    let pkh = wallet.gen_external_address(None).unwrap().pkh;
+
+    // Two pending blocks are indexed
+    let last_sync = wallet.public_data().unwrap().last_sync;
+    let block_1 = mint_block(last_sync.checkpoint + 1, last_sync.hash_prev_block, pkh, 1);
+    let block_2 = mint_block(last_sync.checkpoint + 2, block_1.hash(), pkh, 2);
+    for block in [&block_1, &block_2] {
+        worker
+            .handle_block(Arc::new(block.clone()), false, wallet.clone(), sink.clone())
+            .unwrap();
+    }
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 3);
+
+    // Then the node reports a different block for the epoch of the second one
+    let block_2_bis = mint_block(last_sync.checkpoint + 2, block_1.hash(), pkh, 5);
+    assert_ne!(block_2_bis.hash(), block_2.hash());
+    worker
+        .handle_block(Arc::new(block_2_bis.clone()), false, wallet.clone(), sink)
+        .unwrap();
+
+    // The movements of the orphaned block are rolled back before applying the new one
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 6);
+    let wallet_data = wallet.public_data().unwrap();
+    assert_eq!(wallet_data.last_sync.hash_prev_block, block_2_bis.hash());
+    let transactions = wallet.transactions(0, 10).unwrap();
+    assert_eq!(transactions.total, 2);
+
+    let orphaned: Vec<Value> = notifications
+        .flat_map(|notification| {
+            notification["params"][0]["events"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter_map(|event| event.get("BlocksOrphan").cloned())
+        .collect();
+    assert_eq!(orphaned, vec![json!([block_2.hash().to_string()])]);
+
+    drop((worker, wallet));
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn export_transactions_csv_to_file() {
+    let node = node_params(mock_node(vec![]));
+    let (mut worker, path) = worker("export-csv", node, factories::default_params());
+    let TestWallet { wallet, .. } = unlocked_test_wallet(&mut worker, None);
+
+    // The export of a wallet without movements only contains the header
+    let csv_path = path.with_extension("csv");
+    assert_eq!(
+        worker.export_transactions_csv(&wallet, &csv_path).unwrap(),
+        0
+    );
+    assert_eq!(
+        std::fs::read_to_string(&csv_path).unwrap(),
+        format!("{}\n", constants::CSV_EXPORT_HEADER)
+    );
+
+    drop((worker, wallet));
+    std::fs::remove_file(csv_path).ok();
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn unlock_wallet_upgrades_hash_iterations() {
+    let node = node_params(mock_node(vec![]));
+    let mut params = factories::default_params();
+    params.db_hash_iterations = 10;
+    let (mut worker, path) = worker("upgrade-hash-iterations", node, params);
+    // Unlocking the wallet with the iteration count it was created with does not re-encrypt it
+    let TestWallet { id: wallet_id, .. } = unlocked_test_wallet(&mut worker, None);
+    assert_eq!(
+        worker.wallets.wallet_hash_iterations(&wallet_id).unwrap(),
+        Some(10)
+    );
+
+    // The recommended iteration count increases
+    worker.params.db_hash_iterations = 1_000;
+
+    // A wrong password does not upgrade the wallet
+    assert!(worker.unlock_wallet(&wallet_id, b"87654321").is_err());
+    assert_eq!(
+        worker.wallets.wallet_hash_iterations(&wallet_id).unwrap(),
+        Some(10)
+    );
+
+    // Unlocking with the right password re-encrypts the wallet with the new iteration count
+    let unlocked = worker
+        .unlock_wallet(&wallet_id, TEST_WALLET_PASSWORD)
+        .unwrap();
+    assert_eq!(unlocked.data.name, Some(TEST_WALLET_NAME.to_string()));
+    assert_eq!(
+        worker.wallets.wallet_hash_iterations(&wallet_id).unwrap(),
+        Some(1_000)
+    );
+
+    // The re-encrypted wallet can be unlocked again, and its data is still there
+    drop(unlocked);
+    let unlocked = worker
+        .unlock_wallet(&wallet_id, TEST_WALLET_PASSWORD)
+        .unwrap();
+    assert_eq!(unlocked.data.name, Some(TEST_WALLET_NAME.to_string()));
+
+    drop((worker, unlocked));
+    std::fs::remove_dir_all(path).ok();
+}
+
+#[test]
+fn unlock_wallet_keeps_encryption_mode() {
+    let node = node_params(mock_node(vec![]));
+    let mut params = factories::default_params();
+    params.db_encryption_mode = db::EncryptionMode::Gcm;
+    let (mut worker, path) = worker("keep-encryption-mode", node, params);
+
+    let password = b"12345678";
+    let source = types::SeedSource::Mnemonics(
+        mnemonic::MnemonicGen::new()
+            .with_len(mnemonic::Length::Words12)
+            .generate(),
+    );
+    let wallet_id = worker
+        .create_wallet(
+            Some("my wallet".to_string()),
+            None,
+            password,
+            &source,
+            false,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        worker.wallets.wallet_encryption_mode(&wallet_id).unwrap(),
+        db::EncryptionMode::Gcm
+    );
+
+    // Changing the configured mode does not affect existing wallets
+    worker.params.db_encryption_mode = db::EncryptionMode::Cbc;
+    let unlocked = worker.unlock_wallet(&wallet_id, password).unwrap();
+    assert_eq!(unlocked.data.name, Some("my wallet".to_string()));
+    assert_eq!(
+        worker.wallets.wallet_encryption_mode(&wallet_id).unwrap(),
+        db::EncryptionMode::Gcm
+    );
+
+    drop((worker, unlocked));
+    std::fs::remove_dir_all(path).ok();
+}
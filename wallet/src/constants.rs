@@ -11,6 +11,12 @@ pub static DEFAULT_PAGINATION_OFFSET: u32 = 0;
 /// Default limit/page-size used when returning paginated results.
 pub static DEFAULT_PAGINATION_LIMIT: u32 = u32::MAX;
 
+/// Number of balance movements read at once when exporting transactions as CSV.
+pub static CSV_EXPORT_PAGE_SIZE: u32 = 100;
+
+/// Header of the CSV export of wallet transactions. Column order is part of the export format.
+pub static CSV_EXPORT_HEADER: &str = "timestamp,tx_hash,direction,amount,fee,confirmed";
+
 /// Purpose section for an account keypath.
 pub static KEYPATH_PURPOSE: u32 = 3;
 
@@ -25,6 +31,9 @@ pub static EXTERNAL_KEYCHAIN: u32 = 0;
 /// be used as a change address.
 pub static INTERNAL_KEYCHAIN: u32 = 1;
 
+/// Version of the format of the wallet backups created by `export_wallet_backup`.
+pub static WALLET_BACKUP_VERSION: u32 = 1;
+
 /// Special key used to check if a decryption key is the correct one
 /// for a wallet.
 pub static ENCRYPTION_CHECK_KEY: Key<&'static str, ()> = Key::new_const("ENC_KEY");
@@ -2,9 +2,10 @@ use failure::Fail;
 
 use witnet_crypto::{
     cipher,
-    hash::HashFunction,
-    key::{ExtendedSK, KeyError, MasterKeyGen, MasterKeyGenError},
+    hash::{calculate_sha256, HashFunction},
+    key::{ExtendedPK, ExtendedSK, KeyError, MasterKeyGen, MasterKeyGenError},
     pbkdf2::pbkdf2_sha256,
+    signature::{self, PublicKey, Signature},
 };
 
 use crate::types;
@@ -12,6 +13,7 @@ use crate::types;
 const IV_LENGTH: usize = 16;
 const SALT_LENGTH: usize = 32;
 const HASH_ITER_COUNT: u32 = 10_000;
+const BLOCK_LENGTH: usize = 16;
 
 /// Generation of master key errors
 #[derive(Debug, Fail)]
@@ -30,6 +32,10 @@ pub enum Error {
     InvalidKeyPath(String),
     #[fail(display = "The AES encryption/decryption failed: {}", _0)]
     Aes(#[cause] cipher::Error),
+    /// The ciphertext is too short or not aligned to the AES block size, so it cannot have been
+    /// produced by `encrypt_cbc`.
+    #[fail(display = "Invalid ciphertext length: {}", _0)]
+    InvalidCiphertext(usize),
 }
 
 /// Result type for cryptographic operations that can fail.
@@ -85,6 +91,24 @@ pub fn gen_wallet_id(
     }
 }
 
+/// Generate a cryptographic wallet id for a watch-only wallet, out of the extended public key of
+/// its account.
+pub fn gen_watch_only_wallet_id(
+    hash: &HashFunction,
+    key: &ExtendedPK,
+    salt: &[u8],
+    iterations: u32,
+) -> String {
+    match hash {
+        HashFunction::Sha256 => {
+            let password = [key.key.serialize().as_ref(), key.chain_code.as_ref()].concat();
+            let id_bytes = pbkdf2_sha256(&password, salt, iterations);
+
+            hex::encode(id_bytes)
+        }
+    }
+}
+
 /// Generate a cryptographic session id.
 pub fn gen_session_id<Rng>(
     rng: &mut Rng,
@@ -134,6 +158,13 @@ pub fn encrypt_cbc(value: &[u8], password: &[u8]) -> Result<Vec<u8>> {
 
 /// AES-CBC decryption of a given u8 given as IV|SALT|CIPHERTEXT slice with the provided password.
 pub fn decrypt_cbc(ciphertext: &[u8], password: &[u8]) -> Result<Vec<u8>> {
+    let len = ciphertext.len();
+    if len < IV_LENGTH + SALT_LENGTH + BLOCK_LENGTH
+        || (len - IV_LENGTH - SALT_LENGTH) % BLOCK_LENGTH != 0
+    {
+        return Err(Error::InvalidCiphertext(len));
+    }
+
     let mut iv = ciphertext.to_vec();
     let mut salt = iv.split_off(IV_LENGTH);
     let true_ciphertext = salt.split_off(SALT_LENGTH);
@@ -142,3 +173,11 @@ pub fn decrypt_cbc(ciphertext: &[u8], password: &[u8]) -> Result<Vec<u8>> {
 
     Ok(plaintext)
 }
+
+/// Verify a signature produced by signing the SHA256 hash of some data, as done by the wallet
+/// when signing messages.
+pub fn verify_data(data: &str, signature: &Signature, public_key: &PublicKey) -> bool {
+    let hashed_data = calculate_sha256(data.as_bytes());
+
+    signature::verify(public_key, hashed_data.as_ref(), signature).is_ok()
+}
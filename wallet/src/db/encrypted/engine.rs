@@ -1,17 +1,31 @@
 use super::*;
 use crate::types;
 
+/// Version byte prepended to values encrypted with AES GCM. Values encrypted with AES CBC carry
+/// no version byte, as they predate the introduction of GCM.
+const GCM_VERSION: u8 = 1;
+/// Length of the AES GCM authentication tag.
+const GCM_TAG_LENGTH: usize = 16;
+
 #[derive(Clone)]
 pub struct CryptoEngine {
     key: types::Secret,
     iv: Vec<u8>,
+    mode: EncryptionMode,
 }
 
 impl CryptoEngine {
-    pub fn new(key: types::Secret, iv: Vec<u8>) -> Self {
-        Self { key, iv }
+    pub fn new(key: types::Secret, iv: Vec<u8>, mode: EncryptionMode) -> Self {
+        Self { key, iv, mode }
+    }
+
+    /// Create an engine using the same IV and mode but a different key.
+    pub fn with_key(&self, key: types::Secret) -> Self {
+        Self::new(key, self.iv.clone(), self.mode)
     }
 
+    /// Encrypt a value using AES CBC with the fixed IV. The result is deterministic, so this is
+    /// used for keys, which need to be looked up by their encrypted form.
     pub fn encrypt<T>(&self, value: &T) -> Result<Vec<u8>>
     where
         T: serde::Serialize + ?Sized,
@@ -31,4 +45,64 @@ impl CryptoEngine {
 
         Ok(value)
     }
+
+    /// Encrypt a value using the encryption mode of this engine.
+    pub fn encrypt_value<T>(&self, value: &T) -> Result<Vec<u8>>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let bytes = bincode::serialize(value)?;
+
+        self.encrypt_value_bytes(&bytes)
+    }
+
+    /// Decrypt a value using the encryption mode of this engine.
+    pub fn decrypt_value<T>(&self, bytes: &[u8]) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let decrypted = self.decrypt_value_bytes(bytes)?;
+        let value = bincode::deserialize(&decrypted)?;
+
+        Ok(value)
+    }
+
+    pub fn encrypt_value_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match self.mode {
+            EncryptionMode::Cbc => {
+                let encrypted = cipher::encrypt_aes_cbc(self.key.as_ref(), bytes, &self.iv)?;
+
+                Ok(encrypted)
+            }
+            EncryptionMode::Gcm => {
+                let nonce = cipher::generate_random(cipher::GCM_NONCE_LENGTH)?;
+                let encrypted = cipher::encrypt_aes_gcm(self.key.as_ref(), bytes, &nonce)?;
+
+                Ok([&[GCM_VERSION], nonce.as_slice(), encrypted.as_slice()].concat())
+            }
+        }
+    }
+
+    pub fn decrypt_value_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match self.mode {
+            EncryptionMode::Cbc => {
+                let decrypted = cipher::decrypt_aes_cbc(self.key.as_ref(), bytes, &self.iv)?;
+
+                Ok(decrypted)
+            }
+            // Values that are not authenticated are never accepted in GCM mode, otherwise an
+            // attacker could replace them with values encrypted in CBC mode
+            EncryptionMode::Gcm => match bytes.split_first() {
+                Some((&GCM_VERSION, rest))
+                    if rest.len() >= cipher::GCM_NONCE_LENGTH + GCM_TAG_LENGTH =>
+                {
+                    let (nonce, ciphertext) = rest.split_at(cipher::GCM_NONCE_LENGTH);
+                    let decrypted = cipher::decrypt_aes_gcm(self.key.as_ref(), ciphertext, nonce)?;
+
+                    Ok(decrypted)
+                }
+                _ => Err(cipher::Error::AuthenticationFailed.into()),
+            },
+        }
+    }
 }
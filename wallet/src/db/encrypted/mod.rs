@@ -7,8 +7,24 @@ use crate::{db::encrypted::write_batch::EncryptedWriteBatch, types};
 
 mod engine;
 mod prefix;
+#[cfg(test)]
+mod tests;
 mod write_batch;
 
+/// Cipher mode used for encrypting the values stored in an `EncryptedDb`. Keys are always
+/// encrypted with AES CBC, as they need to be deterministic in order to be looked up.
+///
+/// The mode is chosen when a wallet is created and stored in the wallets DB next to its salt and
+/// IV, so that all the values of a wallet are always encrypted with the same mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum EncryptionMode {
+    /// AES CBC, with no integrity check.
+    #[default]
+    Cbc,
+    /// AES GCM, which fails to decrypt values that have been corrupted or tampered with.
+    Gcm,
+}
+
 #[derive(Clone)]
 pub struct EncryptedDb {
     engine: engine::CryptoEngine,
@@ -17,8 +33,14 @@ pub struct EncryptedDb {
 }
 
 impl EncryptedDb {
-    pub fn new(db: Arc<rocksdb::DB>, prefix: Vec<u8>, key: types::Secret, iv: Vec<u8>) -> Self {
-        let engine = engine::CryptoEngine::new(key, iv);
+    pub fn new(
+        db: Arc<rocksdb::DB>,
+        prefix: Vec<u8>,
+        key: types::Secret,
+        iv: Vec<u8>,
+        mode: EncryptionMode,
+    ) -> Self {
+        let engine = engine::CryptoEngine::new(key, iv, mode);
 
         Self {
             db,
@@ -26,6 +48,44 @@ impl EncryptedDb {
             prefixer: prefix::Prefixer::new(prefix),
         }
     }
+
+    /// Re-encrypt all the entries of this database using a new key, and return a database that
+    /// uses that key. The writes in `batch` are committed atomically with the re-encryption.
+    ///
+    /// As keys are stored encrypted, the entries cannot be looked up by prefix, so the whole
+    /// RocksDB instance is scanned and only the entries that decrypt into a key with this
+    /// database's prefix are rewritten.
+    pub fn reencrypt<B>(&self, key: types::Secret, batch: B) -> Result<Self>
+    where
+        B: Into<rocksdb::WriteBatch>,
+    {
+        let engine = self.engine.with_key(key);
+        let mut batch = batch.into();
+
+        for entry in self.db.iterator(rocksdb::IteratorMode::Start) {
+            let (enc_key, enc_val) = entry?;
+            let prefix_key: Vec<u8> = match self.engine.decrypt(&enc_key) {
+                Ok(prefix_key) if self.prefixer.is_prefixed(&prefix_key) => prefix_key,
+                // Entries of other wallets or stored in plain
+                _ => continue,
+            };
+            let value = self.engine.decrypt_value_bytes(&enc_val)?;
+
+            batch.delete(&enc_key);
+            batch.put(
+                engine.encrypt(&prefix_key)?,
+                engine.encrypt_value_bytes(&value)?,
+            );
+        }
+
+        self.db.write(batch)?;
+
+        Ok(Self {
+            engine,
+            db: self.db.clone(),
+            prefixer: self.prefixer.clone(),
+        })
+    }
 }
 
 impl AsRef<rocksdb::DB> for EncryptedDb {
@@ -48,7 +108,7 @@ impl Database for EncryptedDb {
 
         match res {
             Some(dbvec) => {
-                let value = self.engine.decrypt(dbvec.as_ref())?;
+                let value = self.engine.decrypt_value(dbvec.as_ref())?;
 
                 Ok(Some(value))
             }
@@ -70,6 +130,17 @@ impl Database for EncryptedDb {
         }
     }
 
+    fn entry_size<K, V>(&self, key: &Key<K, V>) -> Result<Option<u64>>
+    where
+        K: AsRef<[u8]>,
+    {
+        let prefix_key = self.prefixer.prefix(key);
+        let enc_key = self.engine.encrypt(&prefix_key)?;
+        let res = self.as_ref().get(&enc_key)?;
+
+        Ok(res.map(|dbvec| (enc_key.len() + dbvec.len()) as u64))
+    }
+
     fn put<K, V, Vref>(&self, key: &Key<K, V>, value: Vref) -> Result<()>
     where
         K: AsRef<[u8]>,
@@ -78,7 +149,7 @@ impl Database for EncryptedDb {
     {
         let prefix_key = self.prefixer.prefix(key);
         let enc_key = self.engine.encrypt(&prefix_key)?;
-        let enc_val = self.engine.encrypt(value.borrow())?;
+        let enc_val = self.engine.encrypt_value(value.borrow())?;
 
         self.as_ref().put(enc_key, enc_val)?;
 
@@ -97,6 +168,12 @@ impl Database for EncryptedDb {
         Ok(())
     }
 
+    fn compact(&self) -> Result<()> {
+        self.as_ref().compact_range::<&[u8], &[u8]>(None, None);
+
+        Ok(())
+    }
+
     fn batch(&self) -> Self::WriteBatch {
         EncryptedWriteBatch::new(self.prefixer.clone(), self.engine.clone())
     }
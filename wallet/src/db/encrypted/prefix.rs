@@ -14,4 +14,8 @@ impl Prefixer {
     {
         [self.prefix.as_slice(), key.as_ref()].concat()
     }
+
+    pub fn is_prefixed(&self, prefixed_key: &[u8]) -> bool {
+        prefixed_key.starts_with(&self.prefix)
+    }
 }
@@ -0,0 +1,141 @@
+use std::fs;
+
+use super::*;
+use crate::{crypto, repository::keys};
+
+fn engine(mode: EncryptionMode) -> engine::CryptoEngine {
+    let key = crypto::key_from_password(b"password", &[1; 32], 10);
+
+    engine::CryptoEngine::new(key, vec![2; 16], mode)
+}
+
+#[test]
+fn test_gcm_value_round_trip() {
+    let engine = engine(EncryptionMode::Gcm);
+
+    let encrypted = engine.encrypt_value("some value").unwrap();
+    let decrypted: String = engine.decrypt_value(&encrypted).unwrap();
+
+    assert_eq!(decrypted, "some value");
+    // Values are encrypted with random nonces
+    assert_ne!(encrypted, engine.encrypt_value("some value").unwrap());
+}
+
+#[test]
+fn test_gcm_value_tampered() {
+    let engine = engine(EncryptionMode::Gcm);
+
+    let mut encrypted = engine.encrypt_value("some value").unwrap();
+    let last = encrypted.len() - 1;
+    encrypted[last] ^= 1;
+    let err = engine.decrypt_value::<String>(&encrypted).unwrap_err();
+
+    assert!(
+        matches!(err, Error::Cipher(cipher::Error::AuthenticationFailed)),
+        "{:?}",
+        err
+    );
+
+    // With a 3 bytes plaintext, the encrypted value is 1 + 12 + 3 + 16 = 32 bytes long, a
+    // multiple of the AES block size, so it must not be mistaken for a value encrypted with CBC
+    let mut encrypted = engine.encrypt_value_bytes(b"abc").unwrap();
+    assert_eq!(encrypted.len(), 32);
+    encrypted[31] ^= 1;
+    let err = engine.decrypt_value_bytes(&encrypted).unwrap_err();
+
+    assert!(
+        matches!(err, Error::Cipher(cipher::Error::AuthenticationFailed)),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_gcm_engine_rejects_cbc_value() {
+    let encrypted = engine(EncryptionMode::Cbc)
+        .encrypt_value("some value")
+        .unwrap();
+    let err = engine(EncryptionMode::Gcm)
+        .decrypt_value::<String>(&encrypted)
+        .unwrap_err();
+
+    assert!(
+        matches!(err, Error::Cipher(cipher::Error::AuthenticationFailed)),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_reencrypt_with_more_hash_iterations() {
+    let path = std::env::temp_dir().join(format!("witnet-wallet-reencrypt-{}", std::process::id()));
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    let rocksdb = Arc::new(rocksdb::DB::open(&opts, &path).unwrap());
+
+    let password = b"password";
+    let salt = vec![1; 32];
+    let iv = vec![2; 16];
+    let weak_key = crypto::key_from_password(password, &salt, 10);
+    let strong_key = crypto::key_from_password(password, &salt, 10_000);
+    let wallet_db = EncryptedDb::new(
+        rocksdb.clone(),
+        b"wallet".to_vec(),
+        weak_key.clone(),
+        iv.clone(),
+        EncryptionMode::Cbc,
+    );
+    let other_db = EncryptedDb::new(
+        rocksdb.clone(),
+        b"other".to_vec(),
+        weak_key,
+        iv.clone(),
+        EncryptionMode::Cbc,
+    );
+    let plain_db = PlainDb::new(rocksdb.clone());
+
+    wallet_db
+        .put(&keys::wallet_name(), "wallet".to_string())
+        .unwrap();
+    wallet_db
+        .put(&keys::wallet_default_account(), 7u32)
+        .unwrap();
+    other_db
+        .put(&keys::wallet_name(), "other".to_string())
+        .unwrap();
+
+    let mut batch = plain_db.batch();
+    batch
+        .put(&keys::wallet_id_hash_iterations("wallet"), 10_000u32)
+        .unwrap();
+    let upgraded_db = wallet_db.reencrypt(strong_key.clone(), batch).unwrap();
+
+    // The entries can be read with the new key, both from the returned database and after
+    // deriving the key again
+    let reopened_db = EncryptedDb::new(
+        rocksdb.clone(),
+        b"wallet".to_vec(),
+        strong_key,
+        iv,
+        EncryptionMode::Cbc,
+    );
+    for db in [&upgraded_db, &reopened_db] {
+        assert_eq!(db.get(&keys::wallet_name()).unwrap(), "wallet");
+        assert_eq!(db.get(&keys::wallet_default_account()).unwrap(), 7);
+    }
+    // The old key is no longer able to read the entries
+    assert!(wallet_db.get_opt(&keys::wallet_name()).unwrap().is_none());
+    // The new iteration count is written along with the re-encrypted entries
+    assert_eq!(
+        plain_db
+            .get(&keys::wallet_id_hash_iterations("wallet"))
+            .unwrap(),
+        10_000
+    );
+    // Entries from other databases sharing the same RocksDB instance are left untouched
+    assert_eq!(other_db.get(&keys::wallet_name()).unwrap(), "other");
+
+    drop((wallet_db, other_db, plain_db, upgraded_db, reopened_db));
+    drop(rocksdb);
+    fs::remove_dir_all(&path).unwrap();
+}
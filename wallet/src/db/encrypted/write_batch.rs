@@ -25,12 +25,16 @@ impl WriteBatch for EncryptedWriteBatch {
     {
         let prefix_key = self.prefixer.prefix(key.as_ref());
         let enc_key = self.engine.encrypt(&prefix_key)?;
-        let enc_val = self.engine.encrypt(value.borrow())?;
+        let enc_val = self.engine.encrypt_value(value.borrow())?;
 
         self.batch.put(enc_key, enc_val);
 
         Ok(())
     }
+
+    fn len(&self) -> usize {
+        self.batch.len()
+    }
 }
 
 impl From<EncryptedWriteBatch> for rocksdb::WriteBatch {
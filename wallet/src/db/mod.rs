@@ -51,6 +51,12 @@ pub trait Database {
     where
         K: AsRef<[u8]>;
 
+    /// Number of bytes used by the entry stored under `key`, including both the key and the value
+    /// exactly as they are written into the database.
+    fn entry_size<K, V>(&self, key: &Key<K, V>) -> Result<Option<u64>>
+    where
+        K: AsRef<[u8]>;
+
     fn put<K, V, Vref>(&self, key: &Key<K, V>, value: Vref) -> Result<()>
     where
         K: AsRef<[u8]>,
@@ -59,8 +65,39 @@ pub trait Database {
 
     fn write(&self, batch: Self::WriteBatch) -> Result<()>;
 
+    /// Write a batch, returning the number of operations it contained.
+    fn write_counted(&self, batch: Self::WriteBatch) -> Result<usize> {
+        let count = batch.len();
+        self.write(batch)?;
+
+        Ok(count)
+    }
+
+    /// Write all the given key/value pairs in a single batch, returning the number of operations
+    /// that were written.
+    fn put_many<I, K, V, Vref>(&self, items: I) -> Result<usize>
+    where
+        I: IntoIterator<Item = (Key<K, V>, Vref)>,
+        K: AsRef<[u8]>,
+        V: serde::Serialize + ?Sized,
+        Vref: Borrow<V>,
+    {
+        let mut batch = self.batch();
+        for (key, value) in items {
+            batch.put(&key, value)?;
+        }
+
+        self.write_counted(batch)
+    }
+
     fn flush(&self) -> Result<()>;
 
+    /// Reclaim the disk space taken by overwritten and deleted entries.
+    ///
+    /// This compacts the whole key range and blocks until done, which may take a while for large
+    /// databases.
+    fn compact(&self) -> Result<()>;
+
     fn batch(&self) -> Self::WriteBatch;
 }
 
@@ -70,4 +107,11 @@ pub trait WriteBatch {
         K: AsRef<[u8]>,
         V: serde::Serialize + ?Sized,
         Vref: Borrow<V>;
+
+    /// Number of operations in the batch.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
@@ -2,6 +2,8 @@ use std::sync::Arc;
 
 use super::*;
 
+#[cfg(test)]
+mod tests;
 mod write_batch;
 
 pub use write_batch::*;
@@ -52,6 +54,15 @@ impl Database for PlainDb {
         }
     }
 
+    fn entry_size<K, V>(&self, key: &Key<K, V>) -> Result<Option<u64>>
+    where
+        K: AsRef<[u8]>,
+    {
+        let res = self.as_ref().get(key)?;
+
+        Ok(res.map(|dbvec| (key.as_ref().len() + dbvec.len()) as u64))
+    }
+
     fn put<K, V, Vref>(&self, key: &Key<K, V>, value: Vref) -> Result<()>
     where
         K: AsRef<[u8]>,
@@ -77,6 +88,12 @@ impl Database for PlainDb {
         Ok(())
     }
 
+    fn compact(&self) -> Result<()> {
+        self.as_ref().compact_range::<&[u8], &[u8]>(None, None);
+
+        Ok(())
+    }
+
     fn batch(&self) -> Self::WriteBatch {
         PlainWriteBatch::default()
     }
@@ -0,0 +1,94 @@
+use std::{fs, sync::Arc};
+
+use super::*;
+use crate::repository::keys::Key;
+
+fn sst_files_size(db: &rocksdb::DB) -> u64 {
+    db.property_int_value("rocksdb.total-sst-files-size")
+        .unwrap()
+        .unwrap()
+}
+
+fn open_db(name: &str) -> (Arc<rocksdb::DB>, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!("witnet-wallet-{}-{}", name, std::process::id()));
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    opts.set_compression_type(rocksdb::DBCompressionType::None);
+    opts.set_disable_auto_compactions(true);
+
+    (Arc::new(rocksdb::DB::open(&opts, &path).unwrap()), path)
+}
+
+#[test]
+fn test_compact_reclaims_space_after_large_delete() {
+    let (rocksdb, path) = open_db("compact");
+    let db = PlainDb::new(rocksdb.clone());
+
+    let keys: Vec<Key<String, Vec<u8>>> = (0..10_000)
+        .map(|i| Key::new(format!("key-{}", i)))
+        .collect();
+    let mut batch = db.batch();
+    for key in &keys {
+        batch.put(key, vec![0xAB; 1024]).unwrap();
+    }
+    db.write(batch).unwrap();
+    db.flush().unwrap();
+
+    // The wallet never deletes entries through `Database`, so go through RocksDB directly
+    for key in &keys {
+        rocksdb.delete(key).unwrap();
+    }
+    db.flush().unwrap();
+    let size_before = sst_files_size(&rocksdb);
+
+    db.compact().unwrap();
+    let size_after = sst_files_size(&rocksdb);
+
+    drop(db);
+    drop(rocksdb);
+    fs::remove_dir_all(&path).unwrap();
+
+    assert!(
+        size_after < size_before,
+        "size after compaction ({}) should be lower than before ({})",
+        size_after,
+        size_before
+    );
+}
+
+#[test]
+fn test_compact_keeps_remaining_keys_readable() {
+    let (rocksdb, path) = open_db("compact-readable");
+    let db = PlainDb::new(rocksdb.clone());
+
+    let keys: Vec<Key<String, u32>> = (0..10_000)
+        .map(|i| Key::new(format!("key-{}", i)))
+        .collect();
+    let mut batch = db.batch();
+    for (i, key) in keys.iter().enumerate() {
+        batch.put(key, i as u32).unwrap();
+    }
+    db.write(batch).unwrap();
+    db.flush().unwrap();
+
+    // Delete every key but one out of ten
+    for (i, key) in keys.iter().enumerate() {
+        if i % 10 != 0 {
+            rocksdb.delete(key).unwrap();
+        }
+    }
+    db.compact().unwrap();
+
+    for (i, key) in keys.iter().enumerate() {
+        let value = db.get_opt(key).unwrap();
+        if i % 10 == 0 {
+            assert_eq!(value, Some(i as u32));
+        } else {
+            assert_eq!(value, None);
+        }
+    }
+
+    drop(db);
+    drop(rocksdb);
+    fs::remove_dir_all(&path).unwrap();
+}
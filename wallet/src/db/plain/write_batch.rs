@@ -18,6 +18,10 @@ impl WriteBatch for PlainWriteBatch {
 
         Ok(())
     }
+
+    fn len(&self) -> usize {
+        self.batch.len()
+    }
 }
 
 impl From<PlainWriteBatch> for rocksdb::WriteBatch {
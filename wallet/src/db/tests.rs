@@ -72,6 +72,18 @@ impl Database for HashMapDb {
         Ok(res)
     }
 
+    fn entry_size<K, V>(&self, key: &Key<K, V>) -> Result<Option<u64>>
+    where
+        K: AsRef<[u8]>,
+    {
+        let k = key.as_ref();
+        let res = RefCell::borrow(&self.rc)
+            .get(k)
+            .map(|value| (k.len() + value.len()) as u64);
+
+        Ok(res)
+    }
+
     fn put<K, V, Vref>(&self, key: &Key<K, V>, value: Vref) -> Result<()>
     where
         K: AsRef<[u8]>,
@@ -100,6 +112,10 @@ impl Database for HashMapDb {
         Ok(())
     }
 
+    fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+
     fn batch(&self) -> Self::WriteBatch {
         Default::default()
     }
@@ -124,6 +140,10 @@ impl WriteBatch for HashMapWriteBatch {
 
         Ok(())
     }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
 }
 
 type IntoIter = std::collections::hash_map::IntoIter<Bytes, Bytes>;
@@ -169,3 +189,20 @@ fn test_hashmap_writebatch() {
     assert_eq!(value1, db.get(&key1).unwrap());
     assert_eq!(value2, db.get(&key2).unwrap());
 }
+
+#[test]
+fn test_hashmap_put_many() {
+    let db = HashMapDb::default();
+    let items = vec![
+        (Key::new(b"key1"), b"value1".to_vec()),
+        (Key::new(b"key2"), b"value2".to_vec()),
+        (Key::new(b"key3"), b"value3".to_vec()),
+    ];
+
+    let count = db.put_many::<_, _, Vec<u8>, _>(items.clone()).unwrap();
+
+    assert_eq!(count, items.len());
+    for (key, value) in items {
+        assert_eq!(value, db.get(&key).unwrap());
+    }
+}
@@ -45,6 +45,7 @@ pub fn run(conf: Config) -> Result<(), Error> {
     let server_addr = conf.wallet.server_addr;
     let db_path = conf.wallet.db_path;
     let db_file_name = conf.wallet.db_file_name;
+    let export_path = conf.wallet.export_path;
     let node_urls = conf.wallet.node_url;
     let rocksdb_opts = conf.rocksdb.to_rocksdb_options();
     let epoch_constants = EpochConstants {
@@ -58,6 +59,11 @@ pub fn run(conf: Config) -> Result<(), Error> {
     let db_hash_iterations = conf.wallet.db_encrypt_hash_iterations;
     let db_iv_length = conf.wallet.db_encrypt_iv_length;
     let db_salt_length = conf.wallet.db_encrypt_salt_length;
+    let db_encryption_mode = if conf.wallet.db_encrypt_authenticated {
+        db::EncryptionMode::Gcm
+    } else {
+        db::EncryptionMode::Cbc
+    };
 
     // Whether wallet is in testnet mode or not
     let testnet = conf.wallet.testnet;
@@ -90,6 +96,20 @@ pub fn run(conf: Config) -> Result<(), Error> {
 
     let pending_transactions_timeout_seconds = conf.wallet.pending_transactions_timeout_seconds;
 
+    // Database compaction, either periodically or after resynchronizing a wallet
+    let db_compaction_interval = match conf.wallet.db_compaction_interval_seconds {
+        0 => None,
+        seconds => Some(Duration::from_secs(seconds)),
+    };
+    let db_compact_on_resync = conf.wallet.db_compact_on_resync;
+
+    let confirmations_required = conf.wallet.confirmations_required;
+    let enable_admin_methods = conf.wallet.enable_admin_methods;
+    let forward_rate_limit = match conf.wallet.forward_rate_limit_capacity {
+        0 => None,
+        capacity => Some((capacity, conf.wallet.forward_rate_limit_refill_rate)),
+    };
+
     let db = Arc::new(
         ::rocksdb::DB::open(&rocksdb_opts, db_path.join(db_file_name))
             .map_err(|e| failure::format_err!("{}", e))?,
@@ -122,6 +142,7 @@ pub fn run(conf: Config) -> Result<(), Error> {
             db_hash_iterations,
             db_iv_length,
             db_salt_length,
+            db_encryption_mode,
             epoch_constants,
             node_sync_batch_size,
             genesis_hash,
@@ -132,6 +153,8 @@ pub fn run(conf: Config) -> Result<(), Error> {
             consensus_constants: consensus_constants.clone(),
             use_unconfirmed_utxos,
             pending_transactions_timeout_seconds,
+            db_compact_on_resync,
+            confirmations_required,
             witnessing: witnessing_config,
         };
 
@@ -158,6 +181,10 @@ pub fn run(conf: Config) -> Result<(), Error> {
             session_expires_in,
             requests_timeout,
             consensus_constants,
+            db_compaction_interval,
+            enable_admin_methods,
+            forward_rate_limit,
+            export_path,
         });
 
         // Intercept SIGTERM signal to gracefully close the wallet
@@ -9,7 +9,7 @@ use crate::{
     types::{number_from_string, u32_to_string, u64_to_string},
 };
 use witnet_data_structures::{
-    chain::{DataRequestInfo, Hash, OutputPointer, PublicKeyHash, ValueTransferOutput},
+    chain::{DataRequestInfo, Epoch, Hash, OutputPointer, PublicKeyHash, ValueTransferOutput},
     transaction::Transaction,
 };
 use witnet_util::timestamp::get_timestamp;
@@ -18,6 +18,8 @@ use witnet_util::timestamp::get_timestamp;
 pub struct Wallet {
     pub id: String,
     pub name: Option<String>,
+    /// Watch-only wallets can derive addresses but cannot sign
+    pub watch_only: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -77,6 +79,42 @@ pub struct BalanceInfo {
     pub locked: u64,
 }
 
+/// Number of bytes used to store a wallet in the database, by category
+#[derive(Copy, Clone, Debug, Eq, Default, Deserialize, PartialEq, Serialize)]
+pub struct WalletStorageSize {
+    /// Generated addresses, along with their paths, public key hashes and additional information
+    pub addresses: u64,
+    /// Balance movements of the transactions indexed by the wallet
+    pub movements: u64,
+    /// Wallet metadata, keys and account state
+    pub metadata: u64,
+    /// Sum of all the categories
+    pub total: u64,
+}
+
+/// Full backup of a wallet: its metadata along with its encrypted master key
+#[derive(Clone, Debug, Eq, Deserialize, PartialEq, Serialize)]
+pub struct WalletBackup {
+    /// Version of the backup format, see `constants::WALLET_BACKUP_VERSION`
+    pub version: u32,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Epoch from which the wallet needs to be synchronized
+    pub birth_date: Epoch,
+    /// Master key encrypted with the backup password, in the format of `export_master_key`
+    pub master_key: String,
+    /// Address book of the wallet
+    #[serde(default)]
+    pub address_labels: Vec<AddressLabel>,
+}
+
+/// Label given to an address in the address book of a wallet
+#[derive(Clone, Debug, Eq, Deserialize, PartialEq, Serialize)]
+pub struct AddressLabel {
+    pub address: String,
+    pub label: String,
+}
+
 /// List of wallet balances (confirmed, unconfirmed and pending)
 #[derive(Copy, Clone, Debug, Eq, Default, Deserialize, PartialEq, Serialize)]
 pub struct WalletBalance {
@@ -106,6 +144,11 @@ impl Addresses {
     pub fn len(&self) -> usize {
         self.addresses.len()
     }
+
+    /// Whether there are no addresses in the internal buffer.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
 }
 
 impl<I> std::ops::Index<I> for Addresses
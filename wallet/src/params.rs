@@ -11,7 +11,7 @@ use witnet_data_structures::{
 };
 use witnet_net::client::tcp::jsonrpc::Subscribe;
 
-use crate::{actors::app::NodeClient, types};
+use crate::{actors::app::NodeClient, db, types};
 
 /// Initialization parameters that can be specific for each wallet.
 #[derive(Clone)]
@@ -24,6 +24,7 @@ pub struct Params {
     pub db_hash_iterations: u32,
     pub db_iv_length: usize,
     pub db_salt_length: usize,
+    pub db_encryption_mode: db::EncryptionMode,
     pub epoch_constants: EpochConstants,
     pub node_sync_batch_size: u32,
     pub genesis_hash: Hash,
@@ -34,6 +35,9 @@ pub struct Params {
     pub consensus_constants: ConsensusConstants,
     pub use_unconfirmed_utxos: bool,
     pub pending_transactions_timeout_seconds: u64,
+    pub db_compact_on_resync: bool,
+    /// Blocks that need to be built on top of a consolidated block before confirming it
+    pub confirmations_required: u32,
     pub witnessing: WitnessingConfig<witnet_net::Uri>,
 }
 
@@ -51,6 +51,8 @@ pub enum Error {
     WrongMetadataType(String),
     #[fail(display = "block consolidation failed: {}", _0)]
     BlockConsolidation(String),
+    #[fail(display = "block rollback failed: {}", _0)]
+    BlockRollback(String),
     #[fail(display = "hash parsing failed: {}", _0)]
     HashParse(#[cause] HashParseError),
     #[fail(display = "failed creating a transaction: {}", _0)]
@@ -79,8 +81,29 @@ pub enum Error {
     UnknownFeeType,
     #[fail(display = "Wallet not found")]
     WalletNotFound,
+    #[fail(display = "watch-only wallet cannot sign")]
+    WatchOnly,
+    #[fail(display = "label `{}` is already used by address {}", label, address)]
+    AddressLabelTaken { label: String, address: String },
     #[fail(display = "Secp256k1 error: {}", _0)]
     Secp256k1(#[cause] witnet_crypto::secp256k1::Error),
+    #[fail(
+        display = "transaction {} is not pending: it has already been confirmed or it was not sent by this wallet",
+        _0
+    )]
+    TransactionNotPending(String),
+    #[fail(
+        display = "the fee of the replacement ({}) must be higher than the original fee ({})",
+        fee, original_fee
+    )]
+    ReplacementFeeTooLow { original_fee: u64, fee: u64 },
+    #[fail(
+        display = "the change output ({}) cannot pay for the fee increase ({})",
+        change, fee_increase
+    )]
+    ReplacementChangeTooLow { change: u64, fee_increase: u64 },
+    #[fail(display = "I/O error: {}", _0)]
+    Io(#[cause] std::io::Error),
 }
 
 impl From<failure::Error> for Error {
@@ -107,6 +130,12 @@ impl From<db::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
 impl From<KeyDerivationError> for Error {
     fn from(err: KeyDerivationError) -> Self {
         Error::KeyDerivation(err)
@@ -139,7 +168,8 @@ impl From<TransactionError> for Error {
             },
             TransactionError::OutputValueOverflow => Error::TransactionValueOverflow,
             TransactionError::FeeOverflow => Error::FeeTooLarge,
-            TransactionError::ValueTransferWeightLimitExceeded { weight, .. } => {
+            TransactionError::ValueTransferWeightLimitExceeded { weight, .. }
+            | TransactionError::TooManyInputs { weight, .. } => {
                 Error::MaximumVTTWeightReached(u64::from(weight))
             }
             TransactionError::DataRequestWeightLimitExceeded { dr_output, .. } => {
@@ -1,6 +1,6 @@
-use crate::model;
+use crate::{db, model};
 use std::{fmt, marker::PhantomData};
-use witnet_crypto::key::ExtendedSK;
+use witnet_crypto::key::{ExtendedPK, ExtendedSK};
 use witnet_data_structures::{
     chain::{CheckpointBeacon, PublicKeyHash},
     transaction::Transaction,
@@ -114,6 +114,18 @@ pub fn wallet_id_iv(wallet_id: &str) -> Key<String, Vec<u8>> {
     Key::new(format!("{}iv", wallet_id))
 }
 
+/// Number of PBKDF2 iterations used to derive a wallet's encryption key.
+#[inline]
+pub fn wallet_id_hash_iterations(wallet_id: &str) -> Key<String, u32> {
+    Key::new(format!("{}hash_iterations", wallet_id))
+}
+
+/// Cipher mode used to encrypt the values of a wallet's database.
+#[inline]
+pub fn wallet_id_encryption_mode(wallet_id: &str) -> Key<String, db::EncryptionMode> {
+    Key::new(format!("{}encryption_mode", wallet_id))
+}
+
 /// A wallet's generated account indexes.
 #[inline]
 pub fn wallet_accounts() -> Key<&'static str, Vec<u32>> {
@@ -138,6 +150,24 @@ pub fn account_key(account_index: u32, keychain: u32) -> Key<String, ExtendedSK>
     Key::new(format!("account-{}-{}-key", account_index, keychain))
 }
 
+/// An account's extended public key, only stored for watch-only wallets.
+#[inline]
+pub fn account_public_key(account_index: u32, keychain: u32) -> Key<String, ExtendedPK> {
+    Key::new(format!("account-{}-{}-public-key", account_index, keychain))
+}
+
+/// Whether a wallet is watch-only.
+#[inline]
+pub fn wallet_watch_only() -> Key<&'static str, bool> {
+    Key::new("watch-only")
+}
+
+/// Whether a wallet is watch-only, as stored in the public wallets DB.
+#[inline]
+pub fn wallet_id_watch_only(id: &str) -> Key<String, bool> {
+    Key::new(format!("{}watch-only", id))
+}
+
 /// An account's total balance.
 #[inline]
 pub fn account_balance(account_index: u32) -> Key<String, model::BalanceInfo> {
@@ -257,6 +287,24 @@ pub fn transaction_movement(
     ))
 }
 
+/// Addresses that have been given a label, so that the whole address book can be listed.
+#[inline]
+pub fn labeled_addresses() -> Key<&'static str, Vec<String>> {
+    Key::new("labeled-addresses")
+}
+
+/// Address that has been given a label, as part of the address book.
+#[inline]
+pub fn address_label(label: &str) -> Key<String, String> {
+    Key::new(format!("address-label-{}", label))
+}
+
+/// Label given to an address, as part of the address book.
+#[inline]
+pub fn address_label_by_address(address: &str) -> Key<String, String> {
+    Key::new(format!("address-{}-label", address))
+}
+
 /// Protocol epoch in which a wallet was created (won't synchronize blocks prior to this epoch)
 #[inline]
 pub fn birth_date() -> Key<&'static str, CheckpointBeacon> {
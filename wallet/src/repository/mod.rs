@@ -7,4 +7,7 @@ pub use error::Error;
 pub use wallet::Wallet;
 pub use wallets::Wallets;
 
+#[cfg(test)]
+pub(crate) use wallet::tests::factories;
+
 pub type Result<T> = std::result::Result<T, Error>;
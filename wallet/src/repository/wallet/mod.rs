@@ -1,8 +1,9 @@
 use itertools::Itertools;
 use std::{
     cmp::min,
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     convert::TryFrom,
+    io,
     ops::Range,
     str::FromStr,
     sync::{Arc, RwLock, RwLockReadGuard},
@@ -12,7 +13,7 @@ use bech32::ToBase32;
 use state::State;
 use witnet_crypto::{
     hash::calculate_sha256,
-    key::{ExtendedPK, ExtendedSK, KeyPath, PK},
+    key::{ExtendedPK, KeyPath, PK},
     signature,
 };
 use witnet_data_structures::{
@@ -47,7 +48,7 @@ use super::*;
 
 mod state;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 /// Internal structure used to gather state mutations while indexing block transactions
 struct AccountMutation {
@@ -82,7 +83,8 @@ impl<'a> OutputsCollection for WalletUtxos<'a> {
         };
 
         match strategy {
-            UtxoSelectionStrategy::BigFirst { from } => {
+            UtxoSelectionStrategy::BigFirst { from }
+            | UtxoSelectionStrategy::MinimizeChange { from } => {
                 sort_utxo_set(self.utxo_set, true, from.as_ref())
                     .filter_map(filter_utxos)
                     .collect()
@@ -274,6 +276,7 @@ where
         state.pending_blocks.clear();
         state.pending_movements.clear();
         state.pending_transactions.clear();
+        state.blocks_to_consolidate.clear();
         state.pending_addresses_by_path.clear();
         state.pending_addresses_by_block.clear();
         state.local_movements.clear();
@@ -291,6 +294,106 @@ where
         Ok(())
     }
 
+    /// Retrieve the beacon of a block that has been indexed but not yet confirmed.
+    pub fn pending_block_beacon(&self, block_hash: &Hash) -> Result<Option<model::Beacon>> {
+        let state = self.state.read()?;
+
+        Ok(state
+            .pending_blocks
+            .get(&block_hash.to_string())
+            .map(|block_state| block_state.beacon.clone()))
+    }
+
+    /// Roll back the pending state to the snapshot taken right after indexing the pending block
+    /// `block_hash`, discarding all the pending blocks with a higher epoch (e.g. because they
+    /// have been orphaned by a chain reorganization).
+    ///
+    /// Returns the hashes of the discarded blocks.
+    pub fn rollback_to_pending_block(&self, block_hash: &Hash) -> Result<Vec<String>> {
+        let mut state = self.state.write()?;
+
+        let block_state = state
+            .pending_blocks
+            .get(&block_hash.to_string())
+            .cloned()
+            .ok_or_else(|| {
+                Error::BlockRollback(format!("beacon not found for pending block {}", block_hash))
+            })?;
+        let mut orphaned_blocks: Vec<model::Beacon> = state
+            .pending_blocks
+            .values()
+            .filter(|pending| pending.beacon.epoch > block_state.beacon.epoch)
+            .map(|pending| pending.beacon.clone())
+            .collect();
+        orphaned_blocks.sort_by_key(|beacon| beacon.epoch);
+        let orphaned_hashes: Vec<String> = orphaned_blocks
+            .iter()
+            .map(|beacon| beacon.block_hash.to_string())
+            .collect();
+
+        for orphaned_hash in &orphaned_hashes {
+            state.pending_blocks.remove(orphaned_hash);
+            state.db_movements_to_update.remove(orphaned_hash);
+            state.pending_addresses_by_block.remove(orphaned_hash);
+            for movement in state
+                .pending_movements
+                .remove(orphaned_hash)
+                .unwrap_or_default()
+            {
+                state
+                    .pending_dr_movements
+                    .remove(&movement.transaction.hash);
+                if let Ok(hash) = movement.transaction.hash.parse() {
+                    state.pending_transactions.remove(&hash);
+                }
+            }
+        }
+        state
+            .blocks_to_consolidate
+            .retain(|hash| !orphaned_hashes.contains(hash));
+
+        // Rebuild the pending addresses from the remaining blocks, so that addresses updated by the
+        // orphaned blocks get back their previous info
+        let mut remaining_blocks: Vec<(u32, String)> = state
+            .pending_blocks
+            .iter()
+            .map(|(hash, pending)| (pending.beacon.epoch, hash.clone()))
+            .collect();
+        remaining_blocks.sort();
+        let mut pending_addresses_by_path = HashMap::new();
+        for (_, hash) in remaining_blocks {
+            for address in state
+                .pending_addresses_by_block
+                .get(&hash)
+                .into_iter()
+                .flatten()
+            {
+                pending_addresses_by_path.insert(address.path.clone(), address.clone());
+            }
+        }
+        state.pending_addresses_by_path = pending_addresses_by_path;
+
+        // Restore the state as it was after indexing the block
+        state.balance.unconfirmed = block_state.balance;
+        state.transaction_next_id = block_state.transaction_next_id;
+        state.utxo_set = block_state.utxo_set;
+        state.used_outputs = model::clean_used_outputs(&state.used_outputs, &state.utxo_set);
+        state.last_sync = CheckpointBeacon {
+            checkpoint: block_state.beacon.epoch,
+            hash_prev_block: block_state.beacon.block_hash,
+        };
+
+        log::debug!(
+            "Rolled back wallet {} to pending block #{} ({}), discarding blocks {:?}",
+            self.id,
+            block_state.beacon.epoch,
+            block_hash,
+            orphaned_hashes,
+        );
+
+        Ok(orphaned_hashes)
+    }
+
     pub fn unlock(id: &str, session_id: types::SessionId, db: T, params: Params) -> Result<Self> {
         let id = id.to_owned();
         let name = db.get_opt(&keys::wallet_name())?;
@@ -343,12 +446,21 @@ where
 
         let last_confirmed = last_sync;
 
-        let external_key = db.get(&keys::account_key(account, constants::EXTERNAL_KEYCHAIN))?;
+        // Watch-only wallets only store the public side of their keychains
+        let watch_only = db.get_or_default(&keys::wallet_watch_only())?;
+        let get_keychain = |keychain| -> Result<types::Keychain> {
+            Ok(if watch_only {
+                types::Keychain::Public(db.get(&keys::account_public_key(account, keychain))?)
+            } else {
+                types::Keychain::Secret(db.get(&keys::account_key(account, keychain))?)
+            })
+        };
+        let external_key = get_keychain(constants::EXTERNAL_KEYCHAIN)?;
         let next_external_index = db.get_or_default(&keys::account_next_index(
             account,
             constants::EXTERNAL_KEYCHAIN,
         ))?;
-        let internal_key = db.get(&keys::account_key(account, constants::INTERNAL_KEYCHAIN))?;
+        let internal_key = get_keychain(constants::INTERNAL_KEYCHAIN)?;
         let next_internal_index = db.get_or_default(&keys::account_next_index(
             account,
             constants::INTERNAL_KEYCHAIN,
@@ -381,6 +493,7 @@ where
             pending_addresses_by_block: Default::default(),
             pending_addresses_by_path: Default::default(),
             pending_blocks: Default::default(),
+            blocks_to_consolidate: Default::default(),
             pending_dr_movements: Default::default(),
             db_movements_to_update: Default::default(),
             transient_external_addresses: Default::default(),
@@ -424,17 +537,21 @@ where
     pub fn derive_and_persist_address(
         &self,
         label: Option<String>,
-        parent_key: &ExtendedSK,
+        parent_key: &types::Keychain,
         account: u32,
         keychain: u32,
         index: u32,
         persist_db: bool,
     ) -> Result<(Arc<model::Address>, u32)> {
-        let extended_sk = parent_key.derive(&KeyPath::default().index(index))?;
-        let ExtendedPK { key, .. } = ExtendedPK::from_secret_key(&extended_sk);
+        let ExtendedPK { key, .. } = parent_key.derive_public(index)?;
 
         let pkh = witnet_data_structures::chain::PublicKey::from(key).pkh();
         let address = pkh.bech32(get_environment());
+        if persist_db {
+            if let Some(label) = &label {
+                self.check_address_label(&address, label)?;
+            }
+        }
         let path = model::Path {
             account,
             keychain,
@@ -469,6 +586,10 @@ where
 
             batch.put(&keys::account_next_index(account, keychain), next_index)?;
 
+            if let Some(label) = &info.label {
+                self.put_address_label(&mut batch, &address, label)?;
+            }
+
             self.db.write(batch)?;
         }
 
@@ -511,6 +632,29 @@ where
         self._gen_internal_address(&mut state, label, preview)
     }
 
+    /// Return the lowest-index external address that has not received any payment yet, so that it
+    /// can be handed out instead of always generating a new one. A new address is only derived if
+    /// all the scanned addresses have been used.
+    ///
+    /// Only the last `sync_address_batch_length` addresses are scanned, as that is the gap limit
+    /// used for discovering addresses when synchronizing a wallet.
+    pub fn next_unused_external_address(&self) -> Result<Arc<model::Address>> {
+        let mut state = self.state.write()?;
+        let account = state.account;
+        let gap_limit = u32::from(self.params.sync_address_batch_length);
+        let start = state.next_external_index.saturating_sub(gap_limit);
+
+        for index in start..state.next_external_index {
+            let address =
+                self._get_address(&state, account, constants::EXTERNAL_KEYCHAIN, index)?;
+            if address.info.received_payments.is_empty() {
+                return Ok(address);
+            }
+        }
+
+        self._gen_external_address(&mut state, None)
+    }
+
     /// Return a list of the generated external addresses that.
     pub fn external_addresses(&self, offset: u32, limit: u32) -> Result<model::Addresses> {
         self.addresses(constants::EXTERNAL_KEYCHAIN, offset, limit)
@@ -655,6 +799,39 @@ where
         })
     }
 
+    /// Write all the balance movements of the wallet as CSV rows, in the same order as they are
+    /// returned by `transactions`, and return the number of rows written after the header.
+    ///
+    /// Movements are read page by page and each row is written as soon as it is formatted, so
+    /// they are never loaded into memory all at once.
+    pub fn export_transactions_csv<W: io::Write>(&self, mut writer: W) -> Result<u32> {
+        writeln!(writer, "{}", constants::CSV_EXPORT_HEADER)?;
+
+        let page_size = constants::CSV_EXPORT_PAGE_SIZE;
+        let mut offset = 0;
+        let mut written = 0;
+        loop {
+            let model::WalletTransactions {
+                transactions,
+                total,
+            } = self.transactions(offset, page_size)?;
+
+            for movement in &transactions {
+                writeln!(writer, "{}", csv_row(movement))?;
+                written += 1;
+            }
+
+            offset = offset.saturating_add(page_size);
+            if offset >= total {
+                break;
+            }
+        }
+
+        writer.flush()?;
+
+        Ok(written)
+    }
+
     #[cfg(test)]
     /// Get an address if it exists in memory or storage.
     pub fn get_address(
@@ -714,6 +891,93 @@ where
         Ok(self.db.get(&keys::transaction_movement(account, index))?)
     }
 
+    /// Set the label of an address, which can be either one of the wallet addresses or the address
+    /// of an external payee. Labels are unique within a wallet.
+    pub fn set_address_label(&self, address: &str, label: String) -> Result<()> {
+        let pkh = PublicKeyHash::from_bech32(get_environment(), address)?;
+        self.check_address_label(address, &label)?;
+
+        let mut batch = self.db.batch();
+        self.put_address_label(&mut batch, address, &label)?;
+
+        // Keep the label of the wallet's own addresses in sync with the address book
+        if let Some(model::Path {
+            account,
+            keychain,
+            index,
+        }) = self.db.get_opt(&keys::pkh(&pkh))?
+        {
+            let mut info = self.db.get(&keys::address_info(account, keychain, index))?;
+            info.label = Some(label);
+            batch.put(&keys::address_info(account, keychain, index), info)?;
+        }
+
+        self.db.write(batch)?;
+
+        Ok(())
+    }
+
+    /// Add the writes needed to label an address to a write batch.
+    fn put_address_label(
+        &self,
+        batch: &mut T::WriteBatch,
+        address: &str,
+        label: &str,
+    ) -> Result<()> {
+        batch.put(&keys::address_label(label), address.to_string())?;
+        batch.put(&keys::address_label_by_address(address), label.to_string())?;
+
+        let mut labeled_addresses = self.db.get_or_default(&keys::labeled_addresses())?;
+        if !labeled_addresses.iter().any(|labeled| labeled == address) {
+            labeled_addresses.push(address.to_string());
+            batch.put(&keys::labeled_addresses(), labeled_addresses)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the whole address book: every labeled address along with its current label.
+    pub fn address_labels(&self) -> Result<Vec<model::AddressLabel>> {
+        let labeled_addresses = self.db.get_or_default(&keys::labeled_addresses())?;
+
+        labeled_addresses
+            .into_iter()
+            .map(|address| {
+                let label = self.db.get(&keys::address_label_by_address(&address))?;
+
+                Ok(model::AddressLabel { address, label })
+            })
+            .collect()
+    }
+
+    /// Get the address that currently has the given label, if any.
+    pub fn get_address_by_label(&self, label: &str) -> Result<Option<String>> {
+        let address = match self.db.get_opt(&keys::address_label(label))? {
+            Some(address) => address,
+            None => return Ok(None),
+        };
+
+        // The entry is stale if the address was relabeled afterwards
+        let current_label = self.db.get_opt(&keys::address_label_by_address(&address))?;
+
+        Ok(if current_label.as_deref() == Some(label) {
+            Some(address)
+        } else {
+            None
+        })
+    }
+
+    /// Fail if the label is already used by an address other than the given one.
+    fn check_address_label(&self, address: &str, label: &str) -> Result<()> {
+        match self.get_address_by_label(label)? {
+            Some(labeled_address) if labeled_address != address => Err(Error::AddressLabelTaken {
+                label: label.to_string(),
+                address: labeled_address,
+            }),
+            _ => Ok(()),
+        }
+    }
+
     /// Get a previously put serialized value.
     ///
     /// See `kv_set`.
@@ -759,6 +1023,34 @@ where
     ) -> Result<Vec<Transaction>> {
         let state = self.state.read()?;
 
+        Ok(self._filter_wallet_transactions(&state, txns, &mut HashSet::new()))
+    }
+
+    /// Filter the transactions of a batch of consecutive blocks if they belong to wallet accounts.
+    /// As the blocks have not been indexed yet, the transactions spending the outputs of (or
+    /// resolving) the wallet transactions found earlier in the batch belong to the wallet as well.
+    pub fn filter_wallet_transactions_batch(
+        &self,
+        blocks_txns: Vec<Vec<Transaction>>,
+    ) -> Result<Vec<Vec<Transaction>>> {
+        let state = self.state.read()?;
+        let mut batch_txns = HashSet::new();
+
+        Ok(blocks_txns
+            .into_iter()
+            .map(|txns| self._filter_wallet_transactions(&state, txns.into_iter(), &mut batch_txns))
+            .collect())
+    }
+
+    /// Filter the transactions that belong to wallet accounts, taking into account the hashes of
+    /// the wallet transactions that have not been indexed yet (`batch_txns`), which are updated
+    /// with the filtered transactions.
+    fn _filter_wallet_transactions(
+        &self,
+        state: &State,
+        txns: impl Iterator<Item = Transaction>,
+        batch_txns: &mut HashSet<Hash>,
+    ) -> Vec<Transaction> {
         let mut filtered_txns = vec![];
         for txn in txns {
             // Inputs and outputs from different transaction types
@@ -778,11 +1070,13 @@ where
                 if state
                     .pending_dr_movements
                     .contains_key(&tally.dr_pointer.to_string())
+                    || batch_txns.contains(&tally.dr_pointer)
                     || self
                         .db
                         .get(&keys::transactions_index(tally.dr_pointer.as_ref()))
                         .is_ok()
                 {
+                    batch_txns.insert(txn.hash());
                     filtered_txns.push(txn.clone());
                     continue;
                 }
@@ -794,16 +1088,17 @@ where
                     || state.transient_internal_addresses.contains_key(&output.pkh)
             };
             // Check if any input or output is from the wallet (input is an UTXO or output points to any wallet's pkh)
-            if inputs
-                .iter()
-                .any(|input| state.utxo_set.get(&input.output_pointer().into()).is_some())
-                || outputs.iter().any(check_db_and_transient)
+            if inputs.iter().any(|input| {
+                state.utxo_set.get(&input.output_pointer().into()).is_some()
+                    || batch_txns.contains(&input.output_pointer().transaction_id)
+            }) || outputs.iter().any(check_db_and_transient)
             {
+                batch_txns.insert(txn.hash());
                 filtered_txns.push(txn.clone());
             }
         }
 
-        Ok(filtered_txns)
+        filtered_txns
     }
 
     /// Index transactions in a block received from a node.
@@ -814,6 +1109,47 @@ where
         confirmed: bool,
     ) -> Result<Vec<model::BalanceMovement>> {
         let mut state = self.state.write()?;
+
+        self._index_block_transactions(&mut state, block_info, txns, confirmed)
+    }
+
+    /// Index the transactions of a batch of consecutive blocks, in order, and move the tip of the
+    /// chain of the wallet to the last one. The state of the wallet is locked only once for the
+    /// whole batch, so no other request can observe the wallet in between blocks.
+    ///
+    /// Indexing stops at the first block that fails.
+    pub fn index_blocks_transactions(
+        &self,
+        blocks: &[(model::Beacon, Vec<model::ExtendedTransaction>)],
+        confirmed: bool,
+    ) -> Result<Vec<Vec<model::BalanceMovement>>> {
+        let mut state = self.state.write()?;
+
+        let mut balance_movements = Vec::with_capacity(blocks.len());
+        for (block_info, txns) in blocks {
+            balance_movements
+                .push(self._index_block_transactions(&mut state, block_info, txns, confirmed)?);
+
+            let beacon = CheckpointBeacon {
+                checkpoint: block_info.epoch,
+                hash_prev_block: block_info.block_hash,
+            };
+            state.last_sync = beacon;
+            if confirmed {
+                state.last_confirmed = beacon;
+            }
+        }
+
+        Ok(balance_movements)
+    }
+
+    fn _index_block_transactions(
+        &self,
+        state: &mut State,
+        block_info: &model::Beacon,
+        txns: &[model::ExtendedTransaction],
+        confirmed: bool,
+    ) -> Result<Vec<model::BalanceMovement>> {
         let mut addresses = HashMap::new();
         let mut block_balance_movements = Vec::new();
         let mut dr_balance_movements = HashMap::new();
@@ -822,7 +1158,7 @@ where
         // Index all transactions
         for txn in txns {
             // Transactions are only indexed if they do not exist in database, or if resynchronizing.
-            match self._index_transaction(&mut state, &mut addresses, txn, block_info, confirmed) {
+            match self._index_transaction(state, &mut addresses, txn, block_info, confirmed) {
                 Ok(Some(balance_movement)) => {
                     if let Transaction::DataRequest(dr_tx) = &txn.transaction {
                         dr_balance_movements.insert(
@@ -1078,6 +1414,75 @@ where
         Ok(utxo_info)
     }
 
+    /// Compute the number of bytes used to store this wallet in the database, by category.
+    ///
+    /// Wallet keys are encrypted, so they cannot be iterated by prefix. Instead, this visits every
+    /// key that the wallet may have written according to its persisted indexes.
+    pub fn storage_size(&self) -> Result<model::WalletStorageSize> {
+        let state = self.state.read()?;
+        let db = &self.db;
+
+        let mut metadata = entry_size(db, &keys::wallet_name())?
+            + entry_size(db, &keys::wallet_description())?
+            + entry_size(db, &keys::wallet_accounts())?
+            + entry_size(db, &keys::wallet_default_account())?
+            + entry_size(db, &keys::wallet_last_sync())?
+            + entry_size(db, &keys::birth_date())?
+            + entry_size(db, &keys::master_key())?
+            + entry_size(db, &keys::wallet_watch_only())?;
+        let mut addresses = entry_size(db, &keys::labeled_addresses())?;
+        let mut movements = 0;
+
+        for address in db.get_or_default(&keys::labeled_addresses())? {
+            addresses += entry_size(db, &keys::address_label_by_address(&address))?;
+
+            if let Some(label) = db.get_opt(&keys::address_label_by_address(&address))? {
+                addresses += entry_size(db, &keys::address_label(&label))?;
+            }
+        }
+
+        for &account in &state.available_accounts {
+            metadata += entry_size(db, &keys::account_balance(account))?
+                + entry_size(db, &keys::account_utxo_set(account))?
+                + entry_size(db, &keys::transaction_next_id(account))?;
+
+            for keychain in [constants::EXTERNAL_KEYCHAIN, constants::INTERNAL_KEYCHAIN] {
+                metadata += entry_size(db, &keys::account_key(account, keychain))?
+                    + entry_size(db, &keys::account_public_key(account, keychain))?
+                    + entry_size(db, &keys::account_next_index(account, keychain))?;
+
+                let next_index = db.get_or_default(&keys::account_next_index(account, keychain))?;
+                for index in 0..next_index {
+                    addresses += entry_size(db, &keys::address(account, keychain, index))?
+                        + entry_size(db, &keys::address_path(account, keychain, index))?
+                        + entry_size(db, &keys::address_pkh(account, keychain, index))?
+                        + entry_size(db, &keys::address_info(account, keychain, index))?;
+
+                    if let Some(pkh) = db.get_opt(&keys::address_pkh(account, keychain, index))? {
+                        addresses += entry_size(db, &keys::pkh(&pkh))?;
+                    }
+                }
+            }
+
+            let transaction_next_id = db.get_or_default(&keys::transaction_next_id(account))?;
+            for id in 0..transaction_next_id {
+                movements += entry_size(db, &keys::transaction_hash(account, id))?
+                    + entry_size(db, &keys::transaction_movement(account, id))?;
+
+                if let Some(hash) = db.get_opt(&keys::transaction_hash(account, id))? {
+                    movements += entry_size(db, &keys::transactions_index(&hash))?;
+                }
+            }
+        }
+
+        Ok(model::WalletStorageSize {
+            addresses,
+            movements,
+            metadata,
+            total: addresses + movements + metadata,
+        })
+    }
+
     /// Create a new value transfer transaction using available UTXOs.
     pub fn create_vtt(
         &self,
@@ -1157,6 +1562,102 @@ where
         Ok((extended, fee))
     }
 
+    /// Create a value transfer transaction that replaces a pending one sent by this wallet.
+    ///
+    /// The replacement spends the same inputs and pays the same outputs, except for the change
+    /// output, which is reduced so that the transaction pays `fee` instead of the original fee.
+    pub fn create_replacement_vtt(
+        &self,
+        original: &VTTransaction,
+        fee: AbsoluteFee,
+    ) -> Result<(model::ExtendedTransaction, AbsoluteFee)> {
+        let mut state = self.state.write()?;
+        let original_hash = original.hash();
+
+        // Once a transaction is confirmed, its local movement is removed and its inputs are no
+        // longer part of the UTXO set, so it cannot be replaced anymore
+        let resolved_inputs = original
+            .body
+            .inputs
+            .iter()
+            .map(|input| {
+                state
+                    .utxo_set
+                    .get(&input.output_pointer().into())
+                    .map(|output| ValueTransferOutput {
+                        pkh: output.pkh,
+                        value: output.amount,
+                        time_lock: output.time_lock,
+                    })
+            })
+            .collect::<Option<Vec<_>>>();
+        let resolved_inputs = match resolved_inputs {
+            Some(resolved_inputs) if state.local_movements.contains_key(&original_hash) => {
+                resolved_inputs
+            }
+            _ => return Err(Error::TransactionNotPending(original_hash.to_string())),
+        };
+
+        let input_value = resolved_inputs
+            .iter()
+            .try_fold(0u64, |acc, input| acc.checked_add(input.value))
+            .ok_or(Error::TransactionValueOverflow)?;
+        let output_value = original
+            .body
+            .outputs
+            .iter()
+            .try_fold(0u64, |acc, output| acc.checked_add(output.value))
+            .ok_or(Error::TransactionValueOverflow)?;
+        let original_fee = input_value
+            .checked_sub(output_value)
+            .ok_or(Error::TransactionBalanceUnderflow)?;
+        if fee.as_nanowits() <= original_fee {
+            return Err(Error::ReplacementFeeTooLow {
+                original_fee,
+                fee: fee.as_nanowits(),
+            });
+        }
+        let fee_increase = fee.as_nanowits() - original_fee;
+
+        // The fee increase is paid by the change output, the one sent to an internal address
+        let mut outputs = original.body.outputs.clone();
+        let mut change_index = None;
+        for (index, output) in outputs.iter().enumerate() {
+            if let Some(model::Path { keychain, .. }) = self.db.get_opt(&keys::pkh(&output.pkh))? {
+                if keychain == constants::INTERNAL_KEYCHAIN {
+                    change_index = Some(index);
+                    break;
+                }
+            }
+        }
+        let change = change_index.map(|index| outputs[index].value).unwrap_or(0);
+        if change < fee_increase {
+            return Err(Error::ReplacementChangeTooLow {
+                change,
+                fee_increase,
+            });
+        }
+        if let Some(index) = change_index {
+            if change == fee_increase {
+                outputs.remove(index);
+            } else {
+                outputs[index].value = change - fee_increase;
+            }
+        }
+
+        let inputs = original.body.inputs.clone();
+        let body = VTTransactionBody::new(inputs.clone(), outputs);
+        let sign_data = body.hash();
+        let signatures = self.create_signatures_from_inputs(inputs, sign_data, &mut state);
+        let transaction = VTTransaction::new(body, signatures?);
+        let extended = model::ExtendedTransaction {
+            transaction: Transaction::ValueTransfer(transaction),
+            metadata: Some(model::TransactionMetadata::InputValues(resolved_inputs)),
+        };
+
+        Ok((extended, fee))
+    }
+
     /// Create signatures from inputs
     fn create_signatures_from_inputs(
         &self,
@@ -1175,7 +1676,9 @@ where
             let parent_key = state
                 .keychains
                 .get(keychain as usize)
-                .expect("could not get keychain");
+                .expect("could not get keychain")
+                .secret()
+                .ok_or(Error::WatchOnly)?;
 
             let extended_sign_key = parent_key.derive(&KeyPath::default().index(index))?;
 
@@ -1535,6 +2038,32 @@ where
         Ok(None)
     }
 
+    /// Replace the local pending balance movement of a transaction with the one of the
+    /// transaction that replaces it
+    pub fn replace_local_movement(
+        &self,
+        original_hash: Hash,
+        replacement: &model::ExtendedTransaction,
+    ) -> Result<Option<model::BalanceMovement>> {
+        {
+            let mut state = self.state.write()?;
+            if let Some(local_movement) = state.local_movements.remove(&original_hash) {
+                log::debug!(
+                    "Local pending movement removed for transaction id: {}, replaced by {}",
+                    original_hash,
+                    replacement.transaction.hash()
+                );
+                state.balance.local = state
+                    .balance
+                    .local
+                    .checked_sub(local_movement.amount)
+                    .ok_or(Error::TransactionValueOverflow)?;
+            }
+        }
+
+        self.add_local_movement(replacement)
+    }
+
     // During wallet synchronization, generate external and internal addresses
     // if transaction outputs are pointing to transient addresses
     pub fn _sync_address_generation(&self, txns: impl Iterator<Item = Transaction>) -> Result<()> {
@@ -1806,7 +2335,9 @@ where
     ) -> Result<model::ExtendedKeyedSignature> {
         let state = self.state.read()?;
         let keychain = constants::EXTERNAL_KEYCHAIN;
-        let parent_key = &state.keychains[keychain as usize];
+        let parent_key = state.keychains[keychain as usize]
+            .secret()
+            .ok_or(Error::WatchOnly)?;
 
         let chaincode = if extended_pk {
             hex::encode(parent_key.chain_code())
@@ -1859,14 +2390,58 @@ where
             }
         }
 
-        block_hashes.iter().try_for_each(|block_hash| {
-            // Genesis block is always confirmed
-            if block_hash == &self.params.genesis_hash.to_string() {
-                Ok(())
-            } else {
-                self.try_consolidate_block(block_hash)
+        {
+            let mut state = self.state.write()?;
+            let genesis_hash = self.params.genesis_hash.to_string();
+            for block_hash in block_hashes {
+                // Genesis block is always confirmed
+                if block_hash != &genesis_hash && !state.blocks_to_consolidate.contains(block_hash)
+                {
+                    state.blocks_to_consolidate.push_back(block_hash.clone());
+                }
             }
-        })
+        }
+
+        self.consolidate_buried_blocks()
+    }
+
+    /// Consolidate, in order, the blocks confirmed by a superblock that have at least
+    /// `confirmations_required` blocks built on top of them.
+    pub fn consolidate_buried_blocks(&self) -> Result<()> {
+        loop {
+            let block_hash = {
+                let state = self.state.read()?;
+                let block_hash = match state.blocks_to_consolidate.front() {
+                    Some(block_hash) => block_hash.clone(),
+                    None => return Ok(()),
+                };
+                // The pending blocks built on top of this one are the ones with a higher epoch
+                if let Some(block_state) = state.pending_blocks.get(&block_hash) {
+                    let epoch = block_state.beacon.epoch;
+                    let depth = state
+                        .pending_blocks
+                        .values()
+                        .filter(|pending| pending.beacon.epoch > epoch)
+                        .count();
+                    if depth < self.params.confirmations_required as usize {
+                        log::debug!(
+                            "Block #{} ({}) needs {} confirmations to be consolidated, it has {}",
+                            epoch,
+                            block_hash,
+                            self.params.confirmations_required,
+                            depth
+                        );
+
+                        return Ok(());
+                    }
+                }
+
+                block_hash
+            };
+
+            self.try_consolidate_block(&block_hash)?;
+            self.state.write()?.blocks_to_consolidate.pop_front();
+        }
     }
 
     /// Try to consolidate a block by persisting all changes into the database.
@@ -1997,8 +2572,12 @@ where
             };
             ("xprv", master_key_string)
         } else {
-            let internal_parent_key = &state.keychains[constants::INTERNAL_KEYCHAIN as usize];
-            let external_parent_key = &state.keychains[constants::EXTERNAL_KEYCHAIN as usize];
+            let internal_parent_key = state.keychains[constants::INTERNAL_KEYCHAIN as usize]
+                .secret()
+                .ok_or(Error::WatchOnly)?;
+            let external_parent_key = state.keychains[constants::EXTERNAL_KEYCHAIN as usize]
+                .secret()
+                .ok_or(Error::WatchOnly)?;
             let internal_secret_key = internal_parent_key.to_slip32(&KeyPath::default());
             let mut internal_secret_key_hex = match internal_secret_key {
                 Ok(x) => x,
@@ -2018,6 +2597,31 @@ where
             bech32::encode(tag, encrypted_final_key.to_base32()).map_err(Error::Bech32)?;
         Ok(final_key)
     }
+
+    /// Export a backup of the wallet containing its metadata and its master key, which is
+    /// encrypted with `password`.
+    pub fn export_backup(&self, password: types::Password) -> Result<model::WalletBackup> {
+        let master_key = self.export_master_key(password)?;
+        let state = self.state.read()?;
+
+        Ok(model::WalletBackup {
+            version: constants::WALLET_BACKUP_VERSION,
+            name: state.name.clone(),
+            description: state.description.clone(),
+            birth_date: state.birth_date.checkpoint,
+            master_key,
+            address_labels: self.address_labels()?,
+        })
+    }
+}
+
+/// Number of bytes used by the entry stored under `key`, or 0 if the key does not exist
+fn entry_size<T, K, V>(db: &T, key: &keys::Key<K, V>) -> Result<u64>
+where
+    T: Database,
+    K: AsRef<[u8]>,
+{
+    Ok(db.entry_size(key)?.unwrap_or_default())
 }
 
 fn convert_block_epoch_to_timestamp(epoch_constants: EpochConstants, epoch: Epoch) -> u64 {
@@ -2191,6 +2795,26 @@ fn build_updated_dr_transaction_data(
     }))
 }
 
+/// Format a balance movement as a row of the CSV export of wallet transactions.
+///
+/// See `constants::CSV_EXPORT_HEADER` for the meaning of each column.
+fn csv_row(movement: &model::BalanceMovement) -> String {
+    let direction = match movement.kind {
+        model::MovementType::Positive => "in",
+        model::MovementType::Negative => "out",
+    };
+
+    format!(
+        "{},{},{},{},{},{}",
+        movement.transaction.timestamp,
+        movement.transaction.hash,
+        direction,
+        movement.amount,
+        movement.transaction.miner_fee,
+        movement.transaction.confirmed,
+    )
+}
+
 #[allow(clippy::type_complexity)]
 fn calculate_transaction_ranges(
     offset: usize,
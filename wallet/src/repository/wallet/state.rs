@@ -1,6 +1,5 @@
 use super::*;
 use std::sync::Arc;
-use witnet_crypto::key::ExtendedSK;
 use witnet_data_structures::chain::EpochConstants;
 
 /// Wallet state snapshot after indexing a block
@@ -39,7 +38,7 @@ pub struct State {
     /// Epoch constants
     pub epoch_constants: EpochConstants,
     /// Keychains used to derive addresses
-    pub keychains: [ExtendedSK; 2],
+    pub keychains: [types::Keychain; 2],
     /// Beacon of last block confirmed by superblock (or during sync process)
     pub last_confirmed: CheckpointBeacon,
     /// Beacon of the last block received during synchronization
@@ -69,6 +68,9 @@ pub struct State {
     pub pending_movements: HashMap<String, Vec<model::BalanceMovement>>,
     /// Transactions that affect the utxo_set but have not been confirmed with a superblock yet.
     pub pending_transactions: HashSet<Hash>,
+    /// Hashes of the blocks consolidated by a superblock, in order, that are still waiting for
+    /// enough blocks to be built on top of them before being confirmed
+    pub blocks_to_consolidate: VecDeque<String>,
     /// Next transaction identifier of the wallet
     pub transaction_next_id: u32,
     /// Current UTXO set (including pending movements)
@@ -108,6 +110,7 @@ impl State {
         self.pending_dr_movements.clear();
         self.pending_movements.clear();
         self.pending_transactions.clear();
+        self.blocks_to_consolidate.clear();
         self.transaction_next_id = Default::default();
         self.utxo_set.clear();
         self.used_outputs.clear();
@@ -5,25 +5,33 @@ use witnet_data_structures::{
 
 use super::*;
 use crate::db::HashMapDb;
-use witnet_crypto::{hash::HashFunction, mnemonic};
+use witnet_crypto::{
+    hash::HashFunction,
+    key::{ExtendedPK, ExtendedSK},
+    mnemonic,
+};
 
 pub fn wallet(data: Option<HashMapDb>) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
-    wallet_inner(data, true)
+    wallet_inner(data, true, default_params())
 }
 
 pub fn wallet_with_args(
     data: Option<HashMapDb>,
     store_master_key: bool,
 ) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
-    wallet_inner(data, store_master_key)
+    wallet_inner(data, store_master_key, default_params())
+}
+
+pub fn wallet_with_params(params: params::Params) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
+    wallet_inner(None, true, params)
 }
 
 fn wallet_inner(
     data: Option<HashMapDb>,
     store_master_key: bool,
+    params: params::Params,
 ) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
     let id = "example-wallet";
-    let params = default_params();
     let mnemonic = mnemonic::MnemonicGen::new()
         .with_len(mnemonic::Length::Words12)
         .generate();
@@ -56,6 +64,8 @@ fn wallet_inner(
             types::CreateWalletData {
                 iv,
                 salt,
+                hash_iterations: params.db_hash_iterations,
+                encryption_mode: params.db_encryption_mode,
                 id,
                 name: None,
                 description: None,
@@ -75,6 +85,56 @@ fn wallet_inner(
     (wallet, db)
 }
 
+/// Create a watch-only wallet out of the extended public key of the default account of the given
+/// master key.
+pub fn watch_only_wallet(
+    data: Option<HashMapDb>,
+    master_key: &ExtendedSK,
+) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
+    let id = "example-watch-only-wallet";
+    let params = default_params();
+    let default_account_index = 0;
+    let account_key = master_key
+        .derive(&account::account_keypath(default_account_index))
+        .unwrap();
+    let account_key = ExtendedPK::from_secret_key(&account_key);
+    let default_account =
+        account::gen_watch_only_account(default_account_index, &account_key).unwrap();
+
+    let mut rng = rand::rngs::OsRng;
+    let salt = crypto::salt(&mut rng, params.db_salt_length);
+    let iv = crypto::salt(&mut rng, params.db_iv_length);
+
+    let db = data.unwrap_or_default();
+    let wallets = Wallets::new(db.clone());
+
+    wallets
+        .create(
+            &db,
+            types::CreateWalletData {
+                iv,
+                salt,
+                hash_iterations: params.db_hash_iterations,
+                encryption_mode: params.db_encryption_mode,
+                id,
+                name: None,
+                description: None,
+                account: &default_account,
+                master_key: None,
+                birth_date: CheckpointBeacon {
+                    checkpoint: 0,
+                    hash_prev_block: params.genesis_prev_hash,
+                },
+            },
+        )
+        .unwrap();
+
+    let session_id = types::SessionId::from(String::from(id));
+    let wallet = Wallet::unlock(id, session_id, db.clone(), params).unwrap();
+
+    (wallet, db)
+}
+
 pub fn pkh() -> PublicKeyHash {
     let bytes: [u8; 20] = rand::random();
     PublicKeyHash::from_bytes(&bytes).expect("PKH of 20 bytes failed")
@@ -124,6 +184,7 @@ pub fn default_params() -> params::Params {
         db_hash_iterations: 10_000,
         db_iv_length: 16,
         db_salt_length: 32,
+        db_encryption_mode: db::EncryptionMode::Cbc,
         epoch_constants: EpochConstants::default(),
         node_sync_batch_size: 100,
         genesis_hash: Hash::default(),
@@ -160,6 +221,8 @@ pub fn default_params() -> params::Params {
         },
         use_unconfirmed_utxos: true,
         pending_transactions_timeout_seconds: 10 * 45,
+        db_compact_on_resync: false,
+        confirmations_required: 0,
         witnessing: WitnessingConfig::default(),
     }
 }
@@ -1,5 +1,9 @@
-use std::{collections::HashMap, iter::FromIterator as _, mem};
+use std::{collections::HashMap, iter::FromIterator as _, mem, str::FromStr};
 
+use witnet_crypto::{
+    secp256k1::{SecretKey, SECP256K1},
+    signature::{PublicKey, Signature},
+};
 use witnet_data_structures::{
     chain::Hashable, transaction::VTTransaction, transaction_factory::calculate_weight,
 };
@@ -8,7 +12,7 @@ use crate::{db::HashMapDb, repository::wallet::tests::factories::vtt_from_body,
 
 use super::*;
 
-mod factories;
+pub(crate) mod factories;
 
 #[test]
 fn test_wallet_public_data() {
@@ -163,6 +167,35 @@ fn test_list_internal_addresses_paginated() {
     assert_eq!(address, addresses[0]);
 }
 
+#[test]
+fn test_list_external_addresses_paginated() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let addresses: Vec<_> = (0..5)
+        .map(|_| (*wallet.gen_external_address(None).unwrap()).clone())
+        .collect();
+
+    // Addresses are returned newest first
+    let page = wallet.external_addresses(1, 2).unwrap();
+    assert_eq!(5, page.total);
+    assert_eq!(2, page.len());
+    assert_eq!(addresses[3], page[0]);
+    assert_eq!(addresses[2], page[1]);
+
+    // The last page is shorter, but the total is the same
+    let page = wallet.external_addresses(4, 2).unwrap();
+    assert_eq!(5, page.total);
+    assert_eq!(1, page.len());
+    assert_eq!(addresses[0], page[0]);
+
+    let page = wallet.external_addresses(5, 2).unwrap();
+    assert_eq!(5, page.total);
+    assert!(page.is_empty());
+
+    // Internal addresses are counted separately
+    assert_eq!(0, wallet.internal_addresses(0, 2).unwrap().total);
+}
+
 #[test]
 fn test_get_address() {
     let (wallet, _db) = factories::wallet(None);
@@ -181,6 +214,77 @@ fn test_get_address() {
     assert_eq!(&address.address, &res.unwrap().address);
 }
 
+#[test]
+fn test_next_unused_address_all_unused() {
+    let (wallet, _db) = factories::wallet(None);
+
+    // With no addresses at all, a new one is derived
+    let address = wallet.next_unused_external_address().unwrap();
+    assert_eq!(0, address.index);
+
+    wallet.gen_external_address(None).unwrap();
+    wallet.gen_external_address(None).unwrap();
+
+    let address = wallet.next_unused_external_address().unwrap();
+
+    assert_eq!(0, address.index);
+    assert_eq!(3, wallet.external_addresses(0, 10).unwrap().total);
+}
+
+#[test]
+fn test_next_unused_address_partially_used() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let addresses: Vec<_> = (0..3)
+        .map(|_| wallet.gen_external_address(None).unwrap())
+        .collect();
+    let block = factories::BlockInfo::default().create();
+    let outputs = vec![ValueTransferOutput {
+        pkh: addresses[0].pkh,
+        value: 1,
+        time_lock: 0,
+    }];
+    let body = VTTransactionBody::new(vec![Input::default()], outputs);
+    wallet
+        .index_block_transactions(&block, &[vtt_from_body(body)], true)
+        .unwrap();
+
+    let address = wallet.next_unused_external_address().unwrap();
+
+    assert_eq!(1, address.index);
+    assert_eq!(addresses[1].address, address.address);
+    assert_eq!(3, wallet.external_addresses(0, 10).unwrap().total);
+}
+
+#[test]
+fn test_next_unused_address_at_gap_limit() {
+    let (wallet, _db) = factories::wallet(None);
+
+    // The factories use a gap limit of 10 addresses
+    let addresses: Vec<_> = (0..12)
+        .map(|_| wallet.gen_external_address(None).unwrap())
+        .collect();
+    let block = factories::BlockInfo::default().create();
+    let outputs = addresses[2..]
+        .iter()
+        .map(|address| ValueTransferOutput {
+            pkh: address.pkh,
+            value: 1,
+            time_lock: 0,
+        })
+        .collect();
+    let body = VTTransactionBody::new(vec![Input::default()], outputs);
+    wallet
+        .index_block_transactions(&block, &[vtt_from_body(body)], true)
+        .unwrap();
+
+    // Addresses 0 and 1 are unused but fall outside of the gap limit, so a new one is derived
+    let address = wallet.next_unused_external_address().unwrap();
+
+    assert_eq!(12, address.index);
+    assert_eq!(13, wallet.external_addresses(0, 20).unwrap().total);
+}
+
 #[test]
 fn test_gen_internal_address() {
     let (wallet, _db) = factories::wallet(None);
@@ -1272,6 +1376,255 @@ fn test_get_transactions() {
     assert_eq!(x.transactions[0], first_tx);
 }
 
+#[test]
+fn test_get_transactions_paginated() {
+    let (wallet, _db) = factories::wallet(None);
+    let address = wallet.gen_external_address(None).unwrap();
+
+    for epoch in 1..=5u32 {
+        let block = model::Beacon {
+            epoch,
+            block_hash: Hash::SHA256([epoch as u8; 32]),
+        };
+        let body = VTTransactionBody::new(
+            vec![Input::default()],
+            vec![ValueTransferOutput {
+                pkh: address.pkh,
+                value: u64::from(epoch),
+                time_lock: 0,
+            }],
+        );
+        wallet
+            .index_block_transactions(&block, &[vtt_from_body(body)], true)
+            .unwrap();
+    }
+
+    // Transactions are returned newest first
+    let page = wallet.transactions(1, 2).unwrap();
+    assert_eq!(page.total, 5);
+    assert_eq!(
+        page.transactions
+            .iter()
+            .map(|m| m.amount)
+            .collect::<Vec<_>>(),
+        vec![4, 3]
+    );
+
+    // The last page is shorter, but the total is the same
+    let page = wallet.transactions(4, 2).unwrap();
+    assert_eq!(page.total, 5);
+    assert_eq!(
+        page.transactions
+            .iter()
+            .map(|m| m.amount)
+            .collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    let page = wallet.transactions(5, 2).unwrap();
+    assert_eq!(page.total, 5);
+    assert!(page.transactions.is_empty());
+}
+
+#[test]
+fn test_export_transactions_csv() {
+    let movement =
+        |db_key: u32, kind, amount, miner_fee, confirmed, timestamp| model::BalanceMovement {
+            db_key,
+            kind,
+            amount,
+            transaction: model::WalletTransaction {
+                block: None,
+                confirmed,
+                data: model::TransactionData::ValueTransfer(model::VtData {
+                    inputs: vec![],
+                    outputs: vec![],
+                }),
+                hash: format!("{:064x}", db_key),
+                miner_fee,
+                timestamp,
+            },
+        };
+
+    let db = HashMapDb::default();
+    db.put(
+        &keys::transaction_movement(0, 0),
+        movement(0, model::MovementType::Positive, 10, 0, true, 1_600_000_000),
+    )
+    .unwrap();
+    db.put(
+        &keys::transaction_movement(0, 1),
+        movement(1, model::MovementType::Negative, 5, 1, true, 1_600_000_045),
+    )
+    .unwrap();
+    db.put(
+        &keys::transaction_movement(0, 2),
+        movement(2, model::MovementType::Positive, 3, 2, false, 1_600_000_090),
+    )
+    .unwrap();
+    db.put(&keys::transaction_next_id(0), 3).unwrap();
+    let (wallet, _db) = factories::wallet(Some(db));
+
+    let mut csv = Vec::new();
+    assert_eq!(wallet.export_transactions_csv(&mut csv).unwrap(), 3);
+
+    let expected = "\
+timestamp,tx_hash,direction,amount,fee,confirmed
+1600000090,0000000000000000000000000000000000000000000000000000000000000002,in,3,2,false
+1600000045,0000000000000000000000000000000000000000000000000000000000000001,out,5,1,true
+1600000000,0000000000000000000000000000000000000000000000000000000000000000,in,10,0,true
+";
+    assert_eq!(String::from_utf8(csv).unwrap(), expected);
+
+    // An empty wallet only exports the header
+    let (wallet, _db) = factories::wallet(None);
+    let mut csv = Vec::new();
+    assert_eq!(wallet.export_transactions_csv(&mut csv).unwrap(), 0);
+
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "timestamp,tx_hash,direction,amount,fee,confirmed\n"
+    );
+}
+
+#[test]
+fn test_movement_is_confirmed_after_required_confirmations() {
+    let (wallet, _db) = factories::wallet_with_params(params::Params {
+        confirmations_required: 2,
+        ..factories::default_params()
+    });
+    let our_address = wallet.gen_external_address(None).unwrap();
+    let block = |epoch: u32| model::Beacon {
+        epoch,
+        block_hash: Hash::SHA256([epoch as u8; 32]),
+    };
+
+    // Receive funds in a block that is not confirmed yet
+    let (block_1, block_2, block_3) = (block(1), block(2), block(3));
+    wallet
+        .index_block_transactions(
+            &block_1,
+            &[factories::vtt_from_body(VTTransactionBody::new(
+                vec![Input::default()],
+                vec![ValueTransferOutput {
+                    pkh: our_address.pkh,
+                    value: 10,
+                    time_lock: 0,
+                }],
+            ))],
+            false,
+        )
+        .unwrap();
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 10);
+
+    // The block is consolidated by a superblock, but nothing has been built on top of it yet
+    wallet
+        .handle_superblock(&[block_1.block_hash.to_string()])
+        .unwrap();
+    assert_eq!(wallet.balance().unwrap().confirmed.available, 0);
+
+    wallet
+        .index_block_transactions(&block_2, &[], false)
+        .unwrap();
+    wallet.consolidate_buried_blocks().unwrap();
+    assert_eq!(wallet.balance().unwrap().confirmed.available, 0);
+    assert!(wallet
+        .state
+        .read()
+        .unwrap()
+        .pending_blocks
+        .contains_key(&block_1.block_hash.to_string()));
+
+    // Once the block has 2 confirmations, its movement is confirmed
+    wallet
+        .index_block_transactions(&block_3, &[], false)
+        .unwrap();
+    wallet.consolidate_buried_blocks().unwrap();
+    assert_eq!(wallet.balance().unwrap().confirmed.available, 10);
+    let state = wallet.state.read().unwrap();
+    assert!(!state
+        .pending_blocks
+        .contains_key(&block_1.block_hash.to_string()));
+    assert!(state.blocks_to_consolidate.is_empty());
+    assert_eq!(state.last_confirmed.checkpoint, 1);
+}
+
+#[test]
+fn test_rollback_to_pending_block() {
+    let (wallet, _db) = factories::wallet(None);
+    let our_address = wallet.gen_external_address(None).unwrap();
+    let receive = |value: u64| {
+        factories::vtt_from_body(VTTransactionBody::new(
+            vec![Input::default()],
+            vec![ValueTransferOutput {
+                pkh: our_address.pkh,
+                value,
+                time_lock: 0,
+            }],
+        ))
+    };
+    let block_1 = model::Beacon {
+        epoch: 1,
+        block_hash: Hash::SHA256([1; 32]),
+    };
+    let block_2 = model::Beacon {
+        epoch: 2,
+        block_hash: Hash::SHA256([2; 32]),
+    };
+    let conflicting_block_2 = model::Beacon {
+        epoch: 2,
+        block_hash: Hash::SHA256([3; 32]),
+    };
+
+    wallet
+        .index_block_transactions(&block_1, &[receive(10)], false)
+        .unwrap();
+    wallet
+        .index_block_transactions(&block_2, &[receive(5)], false)
+        .unwrap();
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 15);
+    assert_eq!(
+        wallet.pending_block_beacon(&block_1.block_hash).unwrap(),
+        Some(block_1.clone())
+    );
+
+    // A block at an already seen epoch builds on top of block 1, so block 2 is orphaned
+    let orphaned = wallet
+        .rollback_to_pending_block(&block_1.block_hash)
+        .unwrap();
+    assert_eq!(orphaned, vec![block_2.block_hash.to_string()]);
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 10);
+    {
+        let state = wallet.state.read().unwrap();
+        assert_eq!(state.last_sync.checkpoint, 1);
+        assert_eq!(state.last_sync.hash_prev_block, block_1.block_hash);
+        assert!(!state
+            .pending_movements
+            .contains_key(&block_2.block_hash.to_string()));
+        assert_eq!(state.utxo_set.len(), 1);
+    }
+
+    // The conflicting block is then applied on top of the rolled back state
+    wallet
+        .index_block_transactions(&conflicting_block_2, &[receive(7)], false)
+        .unwrap();
+    assert_eq!(wallet.balance().unwrap().unconfirmed.available, 17);
+    assert_eq!(wallet.state.read().unwrap().utxo_set.len(), 2);
+}
+
+#[test]
+fn test_rollback_to_unknown_block() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let err = wallet
+        .rollback_to_pending_block(&Hash::SHA256([1; 32]))
+        .unwrap_err();
+    assert_eq!(
+        mem::discriminant(&repository::Error::BlockRollback(String::new())),
+        mem::discriminant(&err)
+    );
+}
+
 #[test]
 fn test_create_vtt_with_locked_balance() {
     let (wallet, _db) = factories::wallet(None);
@@ -1328,6 +1681,101 @@ fn test_create_vtt_with_locked_balance() {
     );
 }
 
+#[test]
+fn test_replace_pending_vtt() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let a_block = factories::BlockInfo::default().create();
+    let our_address = wallet.gen_external_address(None).unwrap();
+    let their_pkh = factories::pkh();
+
+    // index transaction to receive funds
+    wallet
+        .index_block_transactions(
+            &a_block,
+            &[factories::vtt_from_body(VTTransactionBody::new(
+                vec![Input::default()],
+                vec![ValueTransferOutput {
+                    pkh: our_address.pkh,
+                    value: 100,
+                    time_lock: 0,
+                }],
+            ))],
+            true,
+        )
+        .unwrap();
+
+    let (extended, fee) = wallet
+        .create_vtt(types::VttParams {
+            fee: Fee::absolute_from_nanowits(1),
+            outputs: vec![ValueTransferOutput {
+                pkh: their_pkh,
+                value: 10,
+                time_lock: 0,
+            }],
+            utxo_strategy: UtxoSelectionStrategy::Random { from: None },
+            selected_utxos: HashSet::default(),
+            preview: false,
+        })
+        .unwrap();
+    assert_eq!(fee.as_nanowits(), 1);
+    wallet.add_local_movement(&extended).unwrap();
+    let original = match extended.transaction {
+        Transaction::ValueTransfer(vtt) => vtt,
+        _ => panic!("the extended transaction should contain a value transfer transaction"),
+    };
+
+    // The replacement must pay a strictly higher fee
+    let err = wallet
+        .create_replacement_vtt(&original, AbsoluteFee::from_nanowits(1))
+        .unwrap_err();
+    assert!(
+        matches!(err, repository::Error::ReplacementFeeTooLow { .. }),
+        "{:?}",
+        err
+    );
+
+    let (replacement, fee) = wallet
+        .create_replacement_vtt(&original, AbsoluteFee::from_nanowits(5))
+        .unwrap();
+    assert_eq!(fee.as_nanowits(), 5);
+    let replacement_vtt = match &replacement.transaction {
+        Transaction::ValueTransfer(vtt) => vtt.clone(),
+        _ => panic!("the replacement should be a value transfer transaction"),
+    };
+
+    // The replacement spends the same inputs and pays a strictly higher fee
+    assert_eq!(replacement_vtt.body.inputs, original.body.inputs);
+    assert_eq!(replacement_vtt.signatures.len(), original.signatures.len());
+    let output_value = |vtt: &VTTransaction| vtt.body.outputs.iter().map(|o| o.value).sum::<u64>();
+    assert_eq!(100 - output_value(&original), 1);
+    assert_eq!(100 - output_value(&replacement_vtt), 5);
+    assert!(replacement_vtt.body.outputs.contains(&ValueTransferOutput {
+        pkh: their_pkh,
+        value: 10,
+        time_lock: 0,
+    }));
+
+    wallet
+        .replace_local_movement(original.hash(), &replacement)
+        .unwrap();
+    {
+        let state = wallet.state.read().unwrap();
+        assert!(!state.local_movements.contains_key(&original.hash()));
+        assert!(state.local_movements.contains_key(&replacement_vtt.hash()));
+    }
+
+    // The original transaction is no longer pending, so it cannot be replaced again
+    let err = wallet
+        .create_replacement_vtt(&original, AbsoluteFee::from_nanowits(10))
+        .unwrap_err();
+    assert!(
+        matches!(err, repository::Error::TransactionNotPending(_)),
+        "{:?}",
+        err
+    );
+}
+
 #[test]
 fn test_create_vtt_with_multiple_outputs() {
     let (wallet, _db) = factories::wallet(None);
@@ -1415,6 +1863,79 @@ fn test_export_xprv_key() {
         .starts_with("xprvdouble"));
 }
 
+#[test]
+fn test_export_and_import_wallet_backup() {
+    let (wallet, db) = factories::wallet(None);
+    wallet
+        .update(Some("my wallet".to_string()), Some("savings".to_string()))
+        .unwrap();
+    let address = wallet
+        .gen_external_address(Some("mine".to_string()))
+        .unwrap();
+    let payee = PublicKeyHash::default().bech32(get_environment());
+    wallet
+        .set_address_label(&payee, "payee".to_string())
+        .unwrap();
+
+    let backup_password: types::Password = "backup password".to_string().into();
+    let backup = wallet.export_backup(backup_password.clone()).unwrap();
+    assert_eq!(backup.version, constants::WALLET_BACKUP_VERSION);
+    let encoded = actors::app::methods::encode_wallet_backup(&backup).unwrap();
+
+    let decoded = actors::app::methods::decode_wallet_backup(&encoded).unwrap();
+    assert_eq!(decoded, backup);
+
+    // The address book is part of the backup, and can be restored into another wallet
+    assert_eq!(
+        decoded.address_labels,
+        vec![
+            model::AddressLabel {
+                address: address.address.clone(),
+                label: "mine".to_string(),
+            },
+            model::AddressLabel {
+                address: payee.clone(),
+                label: "payee".to_string(),
+            },
+        ]
+    );
+    let (other_wallet, _other_db) = factories::wallet(None);
+    for model::AddressLabel { address, label } in decoded.address_labels.clone() {
+        other_wallet.set_address_label(&address, label).unwrap();
+    }
+    assert_eq!(
+        other_wallet.address_labels().unwrap(),
+        decoded.address_labels
+    );
+    assert_eq!(
+        other_wallet.get_address_by_label("payee").unwrap(),
+        Some(payee)
+    );
+
+    // The backup contains everything needed to create the wallet again
+    let restored = actors::app::methods::validate(
+        "new password".to_string().into(),
+        decoded.master_key.into(),
+        "xprv".to_string(),
+        decoded.name,
+        decoded.description,
+        None,
+        Some(backup_password.clone()),
+        Some(types::BirthDate::Imported(decoded.birth_date)),
+    )
+    .unwrap();
+    assert_eq!(restored.name, Some("my wallet".to_string()));
+    assert_eq!(restored.description, Some("savings".to_string()));
+    match restored.seed_source {
+        types::SeedSource::Xprv(key) => {
+            let key: &str = key.as_ref();
+            let master_key = db.get(&keys::master_key()).unwrap();
+            assert_eq!(key, master_key.to_slip32(&KeyPath::default()).unwrap());
+        }
+        _ => panic!("the backup should restore the wallet from its master key"),
+    }
+}
+
 #[test]
 fn test_export_xprvdouble_key() {
     // Create a wallet that does not store the master key.
@@ -3003,6 +3524,121 @@ fn test_create_transaction_components_does_not_use_unconfirmed_utxos_and_selecti
     );
 }
 
+#[test]
+fn test_watch_only_wallet_derives_same_addresses() {
+    let (wallet, db) = factories::wallet(None);
+    let master_key = db.get(&keys::master_key()).unwrap();
+    let (watch_only_wallet, watch_only_db) = factories::watch_only_wallet(None, &master_key);
+
+    for _ in 0..3 {
+        assert_eq!(
+            wallet.gen_external_address(None).unwrap(),
+            watch_only_wallet.gen_external_address(None).unwrap()
+        );
+        assert_eq!(
+            wallet.gen_internal_address(None, false).unwrap(),
+            watch_only_wallet.gen_internal_address(None, false).unwrap()
+        );
+    }
+
+    let infos = Wallets::new(watch_only_db).infos().unwrap();
+    assert_eq!(infos.len(), 1);
+    assert!(infos[0].watch_only);
+    assert!(!Wallets::new(db).infos().unwrap()[0].watch_only);
+}
+
+#[test]
+fn test_verify_data_valid_signature() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let signed = wallet.sign_data("data", false).unwrap();
+    let signature = Signature::from_str(&signed.signature).unwrap();
+    let public_key = PublicKey::from_str(&signed.public_key).unwrap();
+
+    assert!(crypto::verify_data("data", &signature, &public_key));
+}
+
+#[test]
+fn test_verify_data_tampered_message() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let signed = wallet.sign_data("data", false).unwrap();
+    let signature = Signature::from_str(&signed.signature).unwrap();
+    let public_key = PublicKey::from_str(&signed.public_key).unwrap();
+
+    assert!(!crypto::verify_data("date", &signature, &public_key));
+}
+
+#[test]
+fn test_verify_data_wrong_public_key() {
+    let (wallet, _db) = factories::wallet(None);
+
+    let signed = wallet.sign_data("data", false).unwrap();
+    let signature = Signature::from_str(&signed.signature).unwrap();
+    let secret_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+    let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key);
+
+    assert!(!crypto::verify_data("data", &signature, &public_key));
+}
+
+#[test]
+fn test_watch_only_wallet_cannot_sign() {
+    let pkh = factories::pkh();
+    let out_pointer = model::OutPtr {
+        txn_hash: vec![0; 32],
+        output_index: 0,
+    };
+    let utxo_set: HashMap<model::OutPtr, model::OutputInfo> = HashMap::from_iter(vec![(
+        out_pointer,
+        model::OutputInfo {
+            pkh,
+            amount: 1,
+            time_lock: 0,
+        },
+    )]);
+    let path = model::Path {
+        account: 0,
+        keychain: constants::EXTERNAL_KEYCHAIN,
+        index: 0,
+    };
+    let new_balance = model::BalanceInfo {
+        available: 1u64,
+        locked: 0u64,
+    };
+
+    let (_wallet, full_db) = factories::wallet(None);
+    let master_key = full_db.get(&keys::master_key()).unwrap();
+
+    let db = HashMapDb::default();
+    db.put(&keys::account_utxo_set(0), utxo_set).unwrap();
+    db.put(&keys::account_balance(0), new_balance).unwrap();
+    db.put(&keys::pkh(&pkh), path).unwrap();
+    let (wallet, _db) = factories::watch_only_wallet(Some(db), &master_key);
+
+    let err = wallet.sign_data("data", false).unwrap_err();
+    assert!(matches!(err, repository::Error::WatchOnly), "{:?}", err);
+
+    let err = wallet
+        .create_vtt(types::VttParams {
+            fee: Fee::default(),
+            outputs: vec![ValueTransferOutput {
+                pkh: factories::pkh(),
+                value: 1,
+                time_lock: 0,
+            }],
+            utxo_strategy: UtxoSelectionStrategy::Random { from: None },
+            selected_utxos: HashSet::default(),
+            preview: false,
+        })
+        .unwrap_err();
+    assert!(matches!(err, repository::Error::WatchOnly), "{:?}", err);
+
+    let err = wallet
+        .export_master_key("password".to_string().into())
+        .unwrap_err();
+    assert!(matches!(err, repository::Error::WatchOnly), "{:?}", err);
+}
+
 #[test]
 fn test_unlock_wallet_backwards_compatible() {
     // db created using:
@@ -3015,3 +3651,198 @@ fn test_unlock_wallet_backwards_compatible() {
     let params = factories::default_params();
     let _wallet = Wallet::unlock(id, session_id, db, params).unwrap();
 }
+
+#[test]
+fn test_storage_size_grows_after_indexing_movements() {
+    let (wallet, _db) = factories::wallet(None);
+    let address = wallet.gen_external_address(None).unwrap();
+
+    let size_before = wallet.storage_size().unwrap();
+    assert_eq!(0, size_before.movements);
+    assert!(size_before.addresses > 0);
+    assert_eq!(
+        size_before.total,
+        size_before.addresses + size_before.movements + size_before.metadata
+    );
+
+    let block = factories::BlockInfo::default().create();
+    let body = VTTransactionBody::new(
+        vec![Input::default()],
+        vec![ValueTransferOutput {
+            pkh: address.pkh,
+            value: 1,
+            time_lock: 0,
+        }],
+    );
+    wallet
+        .index_block_transactions(&block, &[vtt_from_body(body)], true)
+        .unwrap();
+
+    let size_after = wallet.storage_size().unwrap();
+    assert!(size_after.movements > 0);
+    assert!(size_after.total > size_before.total);
+    assert_eq!(
+        size_after.total,
+        size_after.addresses + size_after.movements + size_after.metadata
+    );
+}
+
+#[test]
+fn test_index_batch_of_blocks_produces_ordered_movements() {
+    let (wallet, _db) = factories::wallet(None);
+    let address = wallet.gen_external_address(None).unwrap();
+
+    let blocks: Vec<(model::Beacon, Vec<model::ExtendedTransaction>)> = (1..=3u32)
+        .map(|epoch| {
+            let block = model::Beacon {
+                epoch,
+                block_hash: Hash::SHA256([epoch as u8; 32]),
+            };
+            let body = VTTransactionBody::new(
+                vec![Input::default()],
+                vec![ValueTransferOutput {
+                    pkh: address.pkh,
+                    value: u64::from(epoch),
+                    time_lock: 0,
+                }],
+            );
+
+            (block, vec![vtt_from_body(body)])
+        })
+        .collect();
+
+    let movements: Vec<model::BalanceMovement> = wallet
+        .index_blocks_transactions(&blocks, true)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    assert_eq!(
+        movements.iter().map(|m| m.db_key).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    assert_eq!(
+        movements.iter().map(|m| m.amount).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        movements
+            .iter()
+            .map(|m| m.transaction.block.as_ref().unwrap().epoch)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(wallet.balance().unwrap().confirmed.available, 6);
+    // The tip of the chain is moved to the last block of the batch
+    assert_eq!(
+        wallet.public_data().unwrap().last_sync,
+        CheckpointBeacon {
+            checkpoint: 3,
+            hash_prev_block: Hash::SHA256([3; 32]),
+        }
+    );
+}
+
+#[test]
+fn test_storage_size_counts_address_book() {
+    let (wallet, _db) = factories::wallet(None);
+    let payee = PublicKeyHash::default().bech32(get_environment());
+
+    let size_before = wallet.storage_size().unwrap();
+    wallet
+        .set_address_label(&payee, "payee".to_string())
+        .unwrap();
+    let size_after = wallet.storage_size().unwrap();
+
+    assert!(size_after.addresses > size_before.addresses);
+    assert_eq!(size_after.metadata, size_before.metadata);
+    assert_eq!(
+        size_after.total,
+        size_after.addresses + size_after.movements + size_after.metadata
+    );
+}
+
+#[test]
+fn test_set_address_label() {
+    let (wallet, db) = factories::wallet(None);
+    let address = wallet.gen_external_address(None).unwrap();
+    let payee = PublicKeyHash::default().bech32(get_environment());
+
+    assert_eq!(None, wallet.get_address_by_label("mine").unwrap());
+
+    wallet
+        .set_address_label(&address.address, "mine".to_string())
+        .unwrap();
+    wallet
+        .set_address_label(&payee, "payee".to_string())
+        .unwrap();
+
+    assert_eq!(
+        Some(address.address.clone()),
+        wallet.get_address_by_label("mine").unwrap()
+    );
+    assert_eq!(Some(payee), wallet.get_address_by_label("payee").unwrap());
+
+    // Labeling one of the wallet addresses also updates its address info
+    let address_info: model::AddressInfo = db
+        .get(&keys::address_info(0, constants::EXTERNAL_KEYCHAIN, 0))
+        .unwrap();
+    assert_eq!(Some("mine".to_string()), address_info.label);
+}
+
+#[test]
+fn test_set_address_label_overwrites_previous_label() {
+    let (wallet, _db) = factories::wallet(None);
+    let address = wallet
+        .gen_external_address(Some("old".to_string()))
+        .unwrap();
+
+    assert_eq!(
+        Some(address.address.clone()),
+        wallet.get_address_by_label("old").unwrap()
+    );
+
+    wallet
+        .set_address_label(&address.address, "new".to_string())
+        .unwrap();
+
+    assert_eq!(None, wallet.get_address_by_label("old").unwrap());
+    assert_eq!(
+        Some(address.address.clone()),
+        wallet.get_address_by_label("new").unwrap()
+    );
+
+    // The old label is free to be used by another address
+    let other = wallet.gen_external_address(None).unwrap();
+    wallet
+        .set_address_label(&other.address, "old".to_string())
+        .unwrap();
+
+    assert_eq!(
+        Some(other.address.clone()),
+        wallet.get_address_by_label("old").unwrap()
+    );
+}
+
+#[test]
+fn test_set_address_label_fails_if_label_is_taken() {
+    let (wallet, _db) = factories::wallet(None);
+    let address = wallet
+        .gen_external_address(Some("label".to_string()))
+        .unwrap();
+    let other = wallet.gen_external_address(None).unwrap();
+
+    let err = wallet
+        .set_address_label(&other.address, "label".to_string())
+        .unwrap_err();
+
+    assert!(matches!(err, repository::Error::AddressLabelTaken { .. }));
+    assert_eq!(
+        Some(address.address.clone()),
+        wallet.get_address_by_label("label").unwrap()
+    );
+    assert!(wallet
+        .gen_external_address(Some("label".to_string()))
+        .is_err());
+}
@@ -3,7 +3,7 @@ use std::sync::Mutex;
 use super::*;
 use crate::{
     constants,
-    db::{Database, WriteBatch as _},
+    db::{self, Database, WriteBatch as _},
     model, types,
 };
 
@@ -29,6 +29,12 @@ impl<T: Database> Wallets<T> {
         Ok(())
     }
 
+    pub fn compact_db(&self) -> Result<()> {
+        self.db.compact()?;
+
+        Ok(())
+    }
+
     /// Retrieve public information of wallets stored in the wallets DB
     pub fn infos(&self) -> Result<Vec<model::Wallet>> {
         let ids: Vec<String> = self.db.get_or_default(&keys::wallet_ids())?;
@@ -36,8 +42,13 @@ impl<T: Database> Wallets<T> {
 
         for id in ids {
             let name = self.db.get_opt(&keys::wallet_id_name(&id))?;
+            let watch_only = self.db.get_or_default(&keys::wallet_id_watch_only(&id))?;
 
-            wallets.push(model::Wallet { id, name })
+            wallets.push(model::Wallet {
+                id,
+                name,
+                watch_only,
+            })
         }
 
         Ok(wallets)
@@ -69,6 +80,8 @@ impl<T: Database> Wallets<T> {
             description,
             iv,
             salt,
+            hash_iterations,
+            encryption_mode,
             account,
             master_key,
             birth_date,
@@ -91,14 +104,24 @@ impl<T: Database> Wallets<T> {
         }
 
         wbatch.put(&keys::wallet_default_account(), account.index)?;
-        wbatch.put(
-            &keys::account_key(account.index, constants::EXTERNAL_KEYCHAIN),
-            &account.external,
-        )?;
-        wbatch.put(
-            &keys::account_key(account.index, constants::INTERNAL_KEYCHAIN),
-            &account.internal,
-        )?;
+        for (keychain, key) in [
+            (constants::EXTERNAL_KEYCHAIN, &account.external),
+            (constants::INTERNAL_KEYCHAIN, &account.internal),
+        ] {
+            match key {
+                types::Keychain::Secret(key) => {
+                    wbatch.put(&keys::account_key(account.index, keychain), key)?
+                }
+                types::Keychain::Public(key) => {
+                    wbatch.put(&keys::account_public_key(account.index, keychain), key)?
+                }
+            }
+        }
+
+        if account.is_watch_only() {
+            wbatch.put(&keys::wallet_watch_only(), true)?;
+            batch.put(&keys::wallet_id_watch_only(id), true)?;
+        }
 
         wbatch.put(&keys::birth_date(), birth_date)?;
         wbatch.put(&keys::wallet_last_sync(), birth_date)?;
@@ -107,6 +130,8 @@ impl<T: Database> Wallets<T> {
 
         batch.put(&keys::wallet_id_salt(id), &salt)?;
         batch.put(&keys::wallet_id_iv(id), &iv)?;
+        batch.put(&keys::wallet_id_hash_iterations(id), hash_iterations)?;
+        batch.put(&keys::wallet_id_encryption_mode(id), encryption_mode)?;
 
         // FIXME: Use merge operator or a transaction when available in rocksdb crate
         let wallet_id = id.to_string();
@@ -152,4 +177,73 @@ impl<T: Database> Wallets<T> {
             Ok((salt, iv))
         }
     }
+
+    /// Get the number of PBKDF2 iterations used to derive a wallet's encryption key, if it was
+    /// recorded when creating the wallet.
+    pub fn wallet_hash_iterations(&self, id: &str) -> Result<Option<u32>> {
+        let iterations = self.db.get_opt(&keys::wallet_id_hash_iterations(id))?;
+
+        Ok(iterations)
+    }
+
+    /// Compute the number of bytes used by a wallet's entries in the database of wallets, which
+    /// hold the parameters needed to unlock it.
+    pub fn wallet_storage_size(&self, id: &str) -> Result<u64> {
+        let mut size = 0;
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_name(id))?
+            .unwrap_or_default();
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_salt(id))?
+            .unwrap_or_default();
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_iv(id))?
+            .unwrap_or_default();
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_hash_iterations(id))?
+            .unwrap_or_default();
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_encryption_mode(id))?
+            .unwrap_or_default();
+        size += self
+            .db
+            .entry_size(&keys::wallet_id_watch_only(id))?
+            .unwrap_or_default();
+
+        Ok(size)
+    }
+
+    /// Get the cipher mode used to encrypt the values of a wallet's database. Wallets created
+    /// before the mode was recorded are encrypted with AES CBC.
+    pub fn wallet_encryption_mode(&self, id: &str) -> Result<db::EncryptionMode> {
+        let mode = self.db.get_opt(&keys::wallet_id_encryption_mode(id))?;
+
+        Ok(mode.unwrap_or(db::EncryptionMode::Cbc))
+    }
+
+    /// Re-encrypt a wallet's database with a key derived using a new number of PBKDF2 iterations.
+    /// The new iteration count is recorded in the same write, so that the wallet can never end up
+    /// encrypted with a key that cannot be derived again.
+    pub fn reencrypt_wallet(
+        &self,
+        id: &str,
+        wallet_db: &db::EncryptedDb,
+        key: types::Secret,
+        iterations: u32,
+    ) -> Result<db::EncryptedDb>
+    where
+        T::WriteBatch: Into<rocksdb::WriteBatch>,
+    {
+        let mut batch = self.db.batch();
+        batch.put(&keys::wallet_id_hash_iterations(id), iterations)?;
+
+        let wallet_db = wallet_db.reencrypt(key, batch)?;
+
+        Ok(wallet_db)
+    }
 }
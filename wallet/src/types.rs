@@ -10,7 +10,7 @@ use core::fmt::Display;
 use failure::Fail;
 use serde::{Deserialize, Deserializer, Serialize};
 use witnet_crypto::{
-    key::{ExtendedSK, SK},
+    key::{ExtendedPK, ExtendedSK, KeyDerivationError, KeyPath, SK},
     mnemonic,
 };
 pub use witnet_data_structures::chain::Epoch;
@@ -102,6 +102,7 @@ pub enum SeedSource {
     Mnemonics(mnemonic::Mnemonic),
     Xprv(ProtectedString),
     XprvDouble((ProtectedString, ProtectedString)),
+    Xpub(ProtectedString),
 }
 
 pub struct UnlockedSessionWallet {
@@ -117,8 +118,47 @@ pub struct UnlockedWallet {
 
 pub struct Account {
     pub index: u32,
-    pub external: ExtendedSK,
-    pub internal: ExtendedSK,
+    pub external: Keychain,
+    pub internal: Keychain,
+}
+
+impl Account {
+    /// Whether this account only knows the public keys of its keychains.
+    pub fn is_watch_only(&self) -> bool {
+        self.external.secret().is_none()
+    }
+}
+
+/// Parent key of an account keychain.
+///
+/// Watch-only wallets only know the extended public keys of their keychains, which is enough for
+/// deriving addresses but not for signing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Keychain {
+    Secret(ExtendedSK),
+    Public(ExtendedPK),
+}
+
+impl Keychain {
+    /// Derive the extended public key found at the given index of this keychain.
+    pub fn derive_public(&self, index: u32) -> Result<ExtendedPK, KeyDerivationError> {
+        let path = KeyPath::default().index(index);
+
+        match self {
+            Keychain::Secret(key) => key
+                .derive(&path)
+                .map(|key| ExtendedPK::from_secret_key(&key)),
+            Keychain::Public(key) => key.derive(&path),
+        }
+    }
+
+    /// Get the extended secret key of this keychain, unless it belongs to a watch-only wallet.
+    pub fn secret(&self) -> Option<&ExtendedSK> {
+        match self {
+            Keychain::Secret(key) => Some(key),
+            Keychain::Public(_) => None,
+        }
+    }
 }
 
 pub struct WalletData {
@@ -139,6 +179,8 @@ pub struct CreateWalletData<'a> {
     pub description: Option<String>,
     pub iv: Vec<u8>,
     pub salt: Vec<u8>,
+    pub hash_iterations: u32,
+    pub encryption_mode: db::EncryptionMode,
     pub account: &'a Account,
     pub master_key: Option<ExtendedSK>,
     pub birth_date: CheckpointBeacon,
@@ -254,6 +296,23 @@ pub enum Event {
     SyncStart(u32, u32),
     /// An error occurred during the synchronization.
     SyncError(u32, u32),
+    /// An update on the progress of a resynchronization: the last synced epoch, the target epoch
+    /// and the percentage of the target epoch that has been reached.
+    ResyncProgress(u32, u32, u8),
+}
+
+impl Event {
+    /// Create a `ResyncProgress` event. Resynchronizations start from the birth date of the
+    /// wallet, so the percentage is computed against the range of epochs from `start` to `target`.
+    pub fn resync_progress(start: u32, current: u32, target: u32) -> Self {
+        let percentage = if target <= start {
+            100
+        } else {
+            u64::from(current.clamp(start, target) - start) * 100 / u64::from(target - start)
+        };
+
+        Event::ResyncProgress(current, target, u8::try_from(percentage).unwrap_or(100))
+    }
 }
 
 /// Format of the output of getTransaction
@@ -736,6 +795,36 @@ mod tests {
         let _e1: SendTransactionRequest = serde_json::from_str(r#"{"wallet_id":"87575c9031c01cf84dffc33fe2d28474d620dacd673f06990dc0318079ddfde7","session_id":"079b703d4f8935789772651b79326150d1014c92a95e2d02266df1f575abb1fb","transaction":{"DataRequest":{"body":{"dr_output":{"collateral":"1000000000","commit_and_reveal_fee":"1","data_request":{"aggregate":{"filters":[],"reducer":2},"retrieve":[{"kind":"HTTP-GET","script":[128],"url":"https://blockchain.info/q/latesthash"},{"kind":"HTTP-GET","script":[130,24,119,130,24,103,100,104,97,115,104],"url":"https://api-r.bitcoinchain.com/v1/status"},{"kind":"HTTP-GET","script":[131,24,119,130,24,102,100,100,97,116,97,130,24,103,111,98,101,115,116,95,98,108,111,99,107,95,104,97,115,104],"url":"https://api.blockchair.com/bitcoin/stats"}],"tally":{"filters":[{"args":[],"op":8}],"reducer":2},"time_lock":0},"min_consensus_percentage":"51","witness_reward":"1","witnesses":"3"},"inputs":[{"output_pointer":"7db2cb25996c606f3a13e8f581b6112a09acc0d13dc1f444fa36cf645c798c34:0"},{"output_pointer":"b864fb1c00a3a9217c9a90cf9e570a46544356e39b4abe2b73e929c23934d723:0"},{"output_pointer":"2517e3982ee9a16db1c86277ec47d61173943a84933c6b9d1be47ce1dbddcbca:0"},{"output_pointer":"0f56d5a2bdc1c17554f8475b1655aad32e6880a532171fa33b12422d84fb7397:0"}],"outputs":[{"pkh":"wit1dm0rm5hc2uqa5japlpc0n2adfu0tmyx95h3nec","time_lock":0,"value":"7997215"}]},"signatures":[{"public_key":{"bytes":[158,105,89,114,189,234,134,228,92,27,237,221,97,16,29,100,92,144,175,183,160,252,39,134,177,232,245,186,200,119,248,142],"compressed":2},"signature":{"Secp256k1":{"der":[48,68,2,32,123,12,164,83,77,20,246,10,112,206,115,253,207,67,219,85,199,73,193,86,30,107,231,126,226,132,233,14,41,151,251,105,2,32,121,156,174,185,68,84,207,229,52,236,215,106,103,168,15,135,216,103,95,99,57,219,206,212,155,141,129,49,251,40,222,50]}}},{"public_key":{"bytes":[254,74,47,133,149,114,254,214,7,111,206,182,110,168,245,109,170,200,137,97,108,114,229,194,205,26,222,90,7,132,251,47],"compressed":2},"signature":{"Secp256k1":{"der":[48,68,2,32,59,135,250,203,96,245,190,112,13,157,133,31,133,76,245,86,35,90,68,166,61,189,248,31,57,3,120,97,59,143,148,235,2,32,69,92,89,8,155,115,42,93,218,119,1,27,83,69,122,89,28,221,105,203,207,141,218,79,95,70,93,100,76,1,45,170]}}},{"public_key":{"bytes":[247,45,147,229,219,226,79,197,240,181,99,81,110,214,64,98,255,127,136,63,33,105,192,75,58,202,61,19,254,231,83,142],"compressed":2},"signature":{"Secp256k1":{"der":[48,69,2,33,0,198,213,109,66,182,106,42,88,138,190,143,92,121,69,54,152,77,205,38,23,181,113,6,154,250,79,188,190,192,169,88,109,2,32,126,192,235,140,147,31,197,86,172,142,242,224,56,190,60,231,156,159,243,227,160,74,150,207,48,220,244,195,55,184,147,190]}}},{"public_key":{"bytes":[254,74,47,133,149,114,254,214,7,111,206,182,110,168,245,109,170,200,137,97,108,114,229,194,205,26,222,90,7,132,251,47],"compressed":2},"signature":{"Secp256k1":{"der":[48,68,2,32,59,135,250,203,96,245,190,112,13,157,133,31,133,76,245,86,35,90,68,166,61,189,248,31,57,3,120,97,59,143,148,235,2,32,69,92,89,8,155,115,42,93,218,119,1,27,83,69,122,89,28,221,105,203,207,141,218,79,95,70,93,100,76,1,45,170]}}}]}}}"#).unwrap();
     }
 
+    #[test]
+    fn test_resync_progress_increases_monotonically() {
+        // Simulate a resynchronization of a wallet born at epoch 50 up to epoch 1050 in batches
+        // of 100 epochs
+        let start = 50;
+        let target = 1050;
+        let mut epochs: Vec<u32> = (150..target).step_by(100).collect();
+        epochs.push(target);
+
+        let progress: Vec<(u32, u8)> = epochs
+            .into_iter()
+            .map(
+                |current| match Event::resync_progress(start, current, target) {
+                    Event::ResyncProgress(current, event_target, percentage) => {
+                        assert_eq!(event_target, target);
+                        (current, percentage)
+                    }
+                    event => panic!("Unexpected event {:?}", event),
+                },
+            )
+            .collect();
+
+        assert_eq!(progress.first(), Some(&(150, 10)));
+        assert_eq!(progress.last(), Some(&(target, 100)));
+        for window in progress.windows(2) {
+            assert!(window[0].0 < window[1].0);
+            assert!(window[0].1 <= window[1].1);
+        }
+    }
+
     #[test]
     fn test_fee_type_backwards_compatibility() {
         let fee = fee_compat(Fee::absolute_from_nanowits(123456), None);